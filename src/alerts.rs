@@ -0,0 +1,199 @@
+use chrono::Utc;
+use once_cell::sync::OnceCell;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+
+/// Critical events posted to `alert_webhook_url`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AlertKind {
+    CircuitBreakerOpen,
+    AllVendorsFailing,
+    LowBalance,
+    RepeatedReconnects,
+    MemoryLeak,
+    KillSwitch,
+    MapOverflow,
+    StaleStream,
+    Bottleneck,
+}
+
+impl AlertKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            AlertKind::CircuitBreakerOpen => "circuit_breaker_open",
+            AlertKind::AllVendorsFailing => "all_vendors_failing",
+            AlertKind::LowBalance => "low_balance",
+            AlertKind::RepeatedReconnects => "repeated_reconnects",
+            AlertKind::MemoryLeak => "memory_leak",
+            AlertKind::KillSwitch => "kill_switch",
+            AlertKind::MapOverflow => "map_overflow",
+            AlertKind::StaleStream => "stale_stream",
+            AlertKind::Bottleneck => "bottleneck",
+        }
+    }
+}
+
+/// Request body for `alert_webhook_format`: "discord", "slack", or anything else for generic JSON
+pub fn alert_payload(format: &str, kind: AlertKind, message: &str) -> serde_json::Value {
+    let text = format!("[copy_rust] {}: {}", kind.name(), message);
+    match format {
+        "discord" => json!({ "content": text }),
+        "slack" => json!({ "text": text }),
+        _ => json!({ "event": kind.name(), "message": message, "timestamp": Utc::now().to_rfc3339() }),
+    }
+}
+
+/// At most one alert per kind per `min_interval`; repeats inside it are dropped and counted
+pub struct AlertLimiter {
+    last_sent: Mutex<HashMap<AlertKind, Instant>>,
+    min_interval: Duration,
+    suppressed: AtomicUsize,
+}
+
+impl AlertLimiter {
+    pub fn new(min_interval: Duration) -> Self {
+        AlertLimiter { last_sent: Mutex::new(HashMap::new()), min_interval, suppressed: AtomicUsize::new(0) }
+    }
+
+    /// Whether a `kind` alert may go out at `now`; if so, it starts a new interval
+    pub fn allow(&self, kind: AlertKind, now: Instant) -> bool {
+        let mut last_sent = self.last_sent.lock().unwrap_or_else(|e| e.into_inner());
+        match last_sent.get(&kind) {
+            Some(last) if now.duration_since(*last) < self.min_interval => {
+                self.suppressed.fetch_add(1, Ordering::Relaxed);
+                false
+            }
+            _ => {
+                last_sent.insert(kind, now);
+                true
+            }
+        }
+    }
+
+    pub fn suppressed(&self) -> usize {
+        self.suppressed.load(Ordering::Relaxed)
+    }
+}
+
+/// POST one alert to `url`
+pub async fn post_alert(client: &reqwest::Client, url: &str, format: &str, kind: AlertKind, message: &str) -> Result<(), String> {
+    let response = client
+        .post(url)
+        .json(&alert_payload(format, kind, message))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("webhook returned {}", response.status()));
+    }
+    Ok(())
+}
+
+static ALERT_SENDER: OnceCell<UnboundedSender<(AlertKind, String)>> = OnceCell::new();
+static ALERT_LIMITER: OnceCell<AlertLimiter> = OnceCell::new();
+static ALERTS_SENT: AtomicUsize = AtomicUsize::new(0);
+static ALERTS_FAILED: AtomicUsize = AtomicUsize::new(0);
+
+/// (alerts posted, alerts that failed to post, alerts rate-limited)
+pub fn get_alert_stats() -> (usize, usize, usize) {
+    (
+        ALERTS_SENT.load(Ordering::Relaxed),
+        ALERTS_FAILED.load(Ordering::Relaxed),
+        ALERT_LIMITER.get().map_or(0, |limiter| limiter.suppressed()),
+    )
+}
+
+/// Raise a critical alert from any thread; a no-op unless the webhook is running, and rate limited per kind
+pub fn alert(kind: AlertKind, message: String) {
+    let (sender, limiter) = match (ALERT_SENDER.get(), ALERT_LIMITER.get()) {
+        (Some(sender), Some(limiter)) => (sender, limiter),
+        _ => return,
+    };
+    if limiter.allow(kind, Instant::now()) {
+        let _ = sender.send((kind, message));
+    }
+}
+
+/// Post alerts raised through `alert` to `url` until shutdown
+pub async fn run_alert_webhook(url: String, format: String, min_interval: Duration) {
+    let (sender, mut receiver) = unbounded_channel();
+    if ALERT_SENDER.set(sender).is_err() || ALERT_LIMITER.set(AlertLimiter::new(min_interval)).is_err() {
+        return;
+    }
+    println!("[{}] - [ALERTS] Posting critical alerts to a {} webhook (at most one per kind every {:?})",
+        Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), format, min_interval);
+    let client = reqwest::Client::new();
+    while let Some((kind, message)) = receiver.recv().await {
+        match post_alert(&client, &url, &format, kind, &message).await {
+            Ok(()) => {
+                ALERTS_SENT.fetch_add(1, Ordering::Relaxed);
+            }
+            Err(e) => {
+                ALERTS_FAILED.fetch_add(1, Ordering::Relaxed);
+                eprintln!("[{}] - [ALERTS] Failed to post {} alert: {}",
+                    Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), kind.name(), e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Accepts one request, answers 200 and returns its body
+    async fn mock_webhook() -> (String, tokio::task::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let handle = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            loop {
+                let n = stream.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request).to_string();
+                if let Some(header_end) = text.find("\r\n\r\n") {
+                    let length = text[..header_end]
+                        .lines()
+                        .find_map(|line| line.to_ascii_lowercase().strip_prefix("content-length:").map(|v| v.trim().parse::<usize>().unwrap()))
+                        .unwrap_or(0);
+                    if request.len() >= header_end + 4 + length {
+                        stream.write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n").await.unwrap();
+                        return text[header_end + 4..].to_string();
+                    }
+                }
+            }
+        });
+        (url, handle)
+    }
+
+    #[tokio::test]
+    async fn test_critical_alert_posted_and_repeats_limited() {
+        let (url, server) = mock_webhook().await;
+        let limiter = AlertLimiter::new(Duration::from_secs(300));
+        let now = Instant::now();
+
+        // Circuit breaker opens: posted
+        assert!(limiter.allow(AlertKind::CircuitBreakerOpen, now));
+        post_alert(&reqwest::Client::new(), &url, "discord", AlertKind::CircuitBreakerOpen, "zeroslot disabled").await.unwrap();
+        let body: serde_json::Value = serde_json::from_str(&server.await.unwrap()).unwrap();
+        assert_eq!(body["content"], "[copy_rust] circuit_breaker_open: zeroslot disabled");
+
+        // Same event again within the interval: dropped; other kinds unaffected
+        assert!(!limiter.allow(AlertKind::CircuitBreakerOpen, now + Duration::from_secs(10)));
+        assert!(limiter.allow(AlertKind::KillSwitch, now + Duration::from_secs(10)));
+        assert_eq!(limiter.suppressed(), 1);
+        assert!(limiter.allow(AlertKind::CircuitBreakerOpen, now + Duration::from_secs(300)));
+
+        assert_eq!(alert_payload("slack", AlertKind::LowBalance, "0.1 SOL")["text"], "[copy_rust] low_balance: 0.1 SOL");
+        assert_eq!(alert_payload("generic", AlertKind::LowBalance, "0.1 SOL")["event"], "low_balance");
+        assert_eq!(alert_payload("generic", AlertKind::StaleStream, "ARPC stream silent for 60s")["event"], "stale_stream");
+    }
+}
@@ -0,0 +1,212 @@
+// dex_probe.rs
+// Find which DEX a mint trades on from chain state alone, for positions whose original trade (and
+// with it the tx_type and accounts) is gone: startup reconciliation and sells with a lost tx_type.
+
+use crate::build_tx::pump_fun::{get_creator_fee_vault, user_volume_accumulator_pda, BondingCurve, PumpFunAccounts};
+use crate::build_tx::pump_swap::{get_pump_swap_accounts_rpc, pump_swap_constants::PUMP_SWAP_PROGRAM_ID, PumpAmmAccounts};
+use crate::build_tx::ray_cpmm::{get_ray_cpmm_accounts_rpc, RayCpmmSwapAccounts};
+use crate::build_tx::utils::get_pool_accounts;
+use crate::constants::pump_fun::PUMP_FUN_PROGRAM_ID_PUBKEY;
+use crate::constants::raydium_cpmm::RAYDIUM_CPMM_PROGRAM_ID_PUBKEY;
+use crate::grpc::arpc_worker::{ProgramType, TxWithPubkey};
+use crate::init::initialize::GLOBAL_RPC_CLIENT;
+use crate::init::wallet_loader::get_wallet_keypair;
+use borsh::BorshDeserialize;
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+
+// Mint offsets in the pool account data: PumpSwap base/quote mint, Raydium CPMM token_1/token_0 mint
+const PUMP_SWAP_MINT_OFFSETS: [u64; 2] = [43, 75];
+const RAY_CPMM_MINT_OFFSETS: [u64; 2] = [200, 168];
+
+/// Accounts needed to sell on the detected DEX
+#[derive(Debug, Clone)]
+pub enum DexAccounts {
+    PumpFun(PumpFunAccounts),
+    PumpSwap(PumpAmmAccounts),
+    RayCpmm(RayCpmmSwapAccounts),
+}
+
+impl DexAccounts {
+    /// Set `tx`'s tx_type and accounts for a sell on this DEX
+    pub fn apply_to(self, tx: &mut TxWithPubkey) {
+        match self {
+            DexAccounts::PumpFun(accounts) => {
+                tx.tx_type = "pumpfun".to_string();
+                tx.bonding_curve = accounts.bonding_curve_pda;
+                tx.pump_fun_accounts = Some(accounts);
+            }
+            DexAccounts::PumpSwap(accounts) => {
+                tx.tx_type = "pump_swap".to_string();
+                tx.pump_swap_accounts = Some(accounts);
+            }
+            DexAccounts::RayCpmm(accounts) => {
+                tx.tx_type = "ray_cpmm".to_string();
+                tx.raydium_cpmm_accounts = Some(accounts);
+            }
+        }
+    }
+}
+
+/// Chain reads the probe needs (RPC in production)
+pub trait PoolLookup {
+    /// An account of `program` holding `mint` at byte `offset` of its data
+    fn find_pool(&self, program: &Pubkey, offset: u64, mint: &Pubkey) -> Option<Pubkey>;
+    /// Data of `account`, None if it doesn't exist
+    fn account_data(&self, account: &Pubkey) -> Option<Vec<u8>>;
+}
+
+impl PoolLookup for RpcClient {
+    fn find_pool(&self, program: &Pubkey, offset: u64, mint: &Pubkey) -> Option<Pubkey> {
+        get_pool_accounts(*mint, self, [offset], *program)
+    }
+
+    fn account_data(&self, account: &Pubkey) -> Option<Vec<u8>> {
+        self.get_account_data(account).ok()
+    }
+}
+
+pub fn bonding_curve_pda(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"bonding-curve", mint.as_ref()], &PUMP_FUN_PROGRAM_ID_PUBKEY).0
+}
+
+fn active_bonding_curve(lookup: &impl PoolLookup, mint: &Pubkey) -> Option<(Pubkey, BondingCurve)> {
+    let pda = bonding_curve_pda(mint);
+    let data = lookup.account_data(&pda)?;
+    let curve = BondingCurve::deserialize(&mut data.get(8..)?).ok()?;
+    (!curve.complete).then_some((pda, curve))
+}
+
+/// Which DEX `mint` trades on, trying `order` ("pumpfun", "pump_swap", "ray_cpmm"), with the
+/// bonding curve or pool account found. A completed bonding curve doesn't count (the token migrated).
+pub fn probe_dex(lookup: &impl PoolLookup, mint: &Pubkey, order: &[String]) -> Option<(ProgramType, Pubkey)> {
+    order.iter().find_map(|dex| match dex.as_str() {
+        "pumpfun" => active_bonding_curve(lookup, mint).map(|(pda, _)| (ProgramType::AxiomPumpFun, pda)),
+        "pump_swap" => PUMP_SWAP_MINT_OFFSETS.iter()
+            .find_map(|&offset| lookup.find_pool(&PUMP_SWAP_PROGRAM_ID, offset, mint))
+            .map(|pool| (ProgramType::AxiomPumpSwap, pool)),
+        "ray_cpmm" => RAY_CPMM_MINT_OFFSETS.iter()
+            .find_map(|&offset| lookup.find_pool(&RAYDIUM_CPMM_PROGRAM_ID_PUBKEY, offset, mint))
+            .map(|pool| (ProgramType::RaydiumCpmm, pool)),
+        _ => None,
+    })
+}
+
+fn pump_fun_accounts(mint: Pubkey, bonding_curve: Pubkey, curve: &BondingCurve) -> PumpFunAccounts {
+    let user = get_wallet_keypair().pubkey();
+    PumpFunAccounts {
+        mint,
+        bonding_curve_pda: bonding_curve,
+        bonding_curve_ata: spl_associated_token_account::get_associated_token_address(&bonding_curve, &mint),
+        user_ata: spl_associated_token_account::get_associated_token_address(&user, &mint),
+        user,
+        creator_fee_vault: get_creator_fee_vault(&curve.creator),
+        user_volume_accumulator: user_volume_accumulator_pda(&user),
+        ..PumpFunAccounts::default()
+    }
+}
+
+// Detected DEXes by mint; misses aren't cached, a pool may appear later. Bonding-curve hits
+// aren't cached either: the curve can complete and migrate at any time.
+static DEX_CACHE: Lazy<DashMap<Pubkey, (ProgramType, DexAccounts)>> = Lazy::new(DashMap::new);
+
+/// Detect the DEX `mint` trades on (per `dex_probe_order`) and resolve the accounts to sell it there
+pub fn detect_dex_for_mint(mint: Pubkey) -> Option<(ProgramType, DexAccounts)> {
+    if let Some(cached) = DEX_CACHE.get(&mint) {
+        return Some(cached.value().clone());
+    }
+    let rpc = GLOBAL_RPC_CLIENT.get()?;
+    let order = crate::config_load::GLOBAL_CONFIG.get().map(|c| c.dex_probe_order.clone()).unwrap_or_default();
+    let (program, account) = probe_dex(rpc, &mint, &order)?;
+    let accounts = match program {
+        ProgramType::AxiomPumpFun => {
+            let (_, curve) = active_bonding_curve(rpc, &mint)?;
+            DexAccounts::PumpFun(pump_fun_accounts(mint, account, &curve))
+        }
+        ProgramType::AxiomPumpSwap => DexAccounts::PumpSwap(get_pump_swap_accounts_rpc(mint).ok()?),
+        ProgramType::RaydiumCpmm => DexAccounts::RayCpmm(get_ray_cpmm_accounts_rpc(mint).ok()?),
+        ProgramType::RaydiumLaunchpad => return None,
+    };
+    if should_cache(program) {
+        DEX_CACHE.insert(mint, (program, accounts.clone()));
+    }
+    Some((program, accounts))
+}
+
+fn should_cache(program: ProgramType) -> bool {
+    !matches!(program, ProgramType::AxiomPumpFun)
+}
+
+/// Forget the cached DEX for `mint`; called when monitoring sees it migrate
+pub fn invalidate_dex(mint: &Pubkey) {
+    DEX_CACHE.remove(mint);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use borsh::BorshSerialize;
+    use std::collections::HashMap;
+
+    #[derive(Default)]
+    struct MockChain {
+        pools: HashMap<(Pubkey, u64, Pubkey), Pubkey>,
+        accounts: HashMap<Pubkey, Vec<u8>>,
+    }
+
+    impl PoolLookup for MockChain {
+        fn find_pool(&self, program: &Pubkey, offset: u64, mint: &Pubkey) -> Option<Pubkey> {
+            self.pools.get(&(*program, offset, *mint)).copied()
+        }
+
+        fn account_data(&self, account: &Pubkey) -> Option<Vec<u8>> {
+            self.accounts.get(account).cloned()
+        }
+    }
+
+    fn curve_data(complete: bool) -> Vec<u8> {
+        let mut data = vec![0u8; 8];
+        BondingCurve { complete, ..BondingCurve::default() }.serialize(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn test_probe_identifies_dex() {
+        let order: Vec<String> = ["pumpfun", "pump_swap", "ray_cpmm"].iter().map(|s| s.to_string()).collect();
+        let (mint, pool) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        // Only a CPMM pool holds the mint (as token_0)
+        let mut chain = MockChain::default();
+        chain.pools.insert((RAYDIUM_CPMM_PROGRAM_ID_PUBKEY, 168, mint), pool);
+        assert_eq!(probe_dex(&chain, &mint, &order), Some((ProgramType::RaydiumCpmm, pool)));
+
+        // Migrated pump.fun token: completed curve skipped, its PumpSwap pool found
+        chain.accounts.insert(bonding_curve_pda(&mint), curve_data(true));
+        chain.pools.insert((PUMP_SWAP_PROGRAM_ID, 43, mint), pool);
+        assert_eq!(probe_dex(&chain, &mint, &order), Some((ProgramType::AxiomPumpSwap, pool)));
+
+        // Still on the curve
+        chain.accounts.insert(bonding_curve_pda(&mint), curve_data(false));
+        assert_eq!(probe_dex(&chain, &mint, &order), Some((ProgramType::AxiomPumpFun, bonding_curve_pda(&mint))));
+
+        // Order decides; unknown names and unlisted DEXes are skipped
+        let cpmm_only = vec!["raydium".to_string(), "ray_cpmm".to_string()];
+        assert_eq!(probe_dex(&chain, &mint, &cpmm_only), Some((ProgramType::RaydiumCpmm, pool)));
+        assert_eq!(probe_dex(&chain, &Pubkey::new_unique(), &order), None);
+    }
+
+    #[test]
+    fn test_curve_hits_not_cached_and_migration_invalidates() {
+        assert!(!should_cache(ProgramType::AxiomPumpFun));
+        assert!(should_cache(ProgramType::AxiomPumpSwap));
+
+        let mint = Pubkey::new_unique();
+        DEX_CACHE.insert(mint, (ProgramType::AxiomPumpFun, DexAccounts::PumpFun(PumpFunAccounts::default())));
+        assert!(DEX_CACHE.contains_key(&mint));
+        invalidate_dex(&mint);
+        assert!(!DEX_CACHE.contains_key(&mint));
+    }
+}
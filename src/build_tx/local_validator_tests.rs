@@ -0,0 +1,141 @@
+// local_validator_tests.rs
+// End-to-end test against a real solana-test-validator: nonce flow, signing and RPC send.
+// Opt-in (needs the `solana-test-validator` binary on PATH), see LOCAL_VALIDATOR_TEST.md:
+//   cargo test local_validator -- --ignored --nocapture
+
+use crate::build_tx::tx_builder::build_and_sign_transaction_basic;
+use crate::init::wallet_loader::{set_nonce_accounts_global, set_wallet_keypair_global, get_wallet_keypair};
+use solana_client::rpc_client::RpcClient;
+use solana_program::program_pack::Pack;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::hash::Hash;
+use solana_sdk::nonce::state::{State, Versions};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::Transaction;
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+const RPC_PORT: u16 = 18899;
+const FAUCET_PORT: u16 = 19900;
+
+/// Kills the validator and removes its ledger when the test ends (pass or fail)
+struct TestValidator {
+    child: Child,
+    ledger: std::path::PathBuf,
+}
+
+impl TestValidator {
+    fn start() -> Self {
+        let ledger = std::env::temp_dir().join(format!("copy_rust-test-ledger-{}", std::process::id()));
+        let child = Command::new("solana-test-validator")
+            .args(["--reset", "--quiet", "--ledger"])
+            .arg(&ledger)
+            .args(["--rpc-port", &RPC_PORT.to_string(), "--faucet-port", &FAUCET_PORT.to_string()])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("Failed to start solana-test-validator (is it on PATH?)");
+        TestValidator { child, ledger }
+    }
+
+    fn url(&self) -> String {
+        format!("http://127.0.0.1:{}", RPC_PORT)
+    }
+}
+
+impl Drop for TestValidator {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        let _ = std::fs::remove_dir_all(&self.ledger);
+    }
+}
+
+fn wait_until<F: FnMut() -> bool>(what: &str, timeout: Duration, mut ready: F) {
+    let start = Instant::now();
+    while !ready() {
+        assert!(start.elapsed() < timeout, "Timed out waiting for {}", what);
+        std::thread::sleep(Duration::from_millis(250));
+    }
+}
+
+fn nonce_blockhash(rpc: &RpcClient, nonce: &Pubkey) -> Hash {
+    let data = rpc.get_account_data(nonce).expect("nonce account");
+    match bincode::deserialize::<Versions>(&data).expect("nonce state") {
+        Versions::Current(state) => match *state {
+            State::Initialized(ref data) => data.blockhash(),
+            _ => panic!("nonce account not initialized"),
+        },
+        _ => panic!("unsupported nonce version"),
+    }
+}
+
+fn send_setup(rpc: &RpcClient, instructions: &[solana_program::instruction::Instruction], signers: &[&Keypair]) {
+    let blockhash = rpc.get_latest_blockhash().expect("blockhash");
+    let tx = Transaction::new_signed_with_payer(instructions, Some(&signers[0].pubkey()), signers, blockhash);
+    rpc.send_and_confirm_transaction(&tx).expect("setup transaction");
+}
+
+#[test]
+#[ignore = "requires solana-test-validator on PATH"]
+fn test_local_validator_nonce_send_confirms() {
+    let validator = TestValidator::start();
+    let rpc = RpcClient::new_with_commitment(validator.url(), CommitmentConfig::confirmed());
+    wait_until("validator RPC", Duration::from_secs(60), || rpc.get_health().is_ok());
+
+    // Fund a fresh wallet and install it (plus a nonce account) as the bot's globals
+    let wallet = Keypair::new();
+    rpc.request_airdrop(&wallet.pubkey(), 10_000_000_000).expect("airdrop");
+    wait_until("airdrop", Duration::from_secs(30), || rpc.get_balance(&wallet.pubkey()).unwrap_or(0) > 0);
+    set_wallet_keypair_global(wallet.insecure_clone()).expect("wallet already set");
+    let wallet = get_wallet_keypair();
+
+    let nonce = Keypair::new();
+    let nonce_rent = rpc.get_minimum_balance_for_rent_exemption(State::size()).expect("rent");
+    send_setup(
+        &rpc,
+        &system_instruction::create_nonce_account(&wallet.pubkey(), &nonce.pubkey(), &wallet.pubkey(), nonce_rent),
+        &[wallet, &nonce],
+    );
+    let nonce_pubkey = nonce.pubkey();
+    set_nonce_accounts_global(vec![nonce]).expect("nonce accounts already set");
+
+    // Simple SPL token: mint with the wallet as authority
+    let mint = Keypair::new();
+    let mint_rent = rpc.get_minimum_balance_for_rent_exemption(spl_token::state::Mint::LEN).expect("rent");
+    send_setup(
+        &rpc,
+        &[
+            system_instruction::create_account(&wallet.pubkey(), &mint.pubkey(), mint_rent, spl_token::state::Mint::LEN as u64, &spl_token::id()),
+            spl_token::instruction::initialize_mint2(&spl_token::id(), &mint.pubkey(), &wallet.pubkey(), None, 6).unwrap(),
+        ],
+        &[wallet, &mint],
+    );
+
+    // Let the nonce's stored blockhash age so the advance below produces a new one
+    std::thread::sleep(Duration::from_secs(1));
+    let nonce_before = nonce_blockhash(&rpc, &nonce_pubkey);
+
+    // Same shape as a bot transaction: advance nonce first, then the payload
+    let ata = spl_associated_token_account::get_associated_token_address(&wallet.pubkey(), &mint.pubkey());
+    let instructions = vec![
+        system_instruction::advance_nonce_account(&nonce_pubkey, &wallet.pubkey()),
+        spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+            &wallet.pubkey(), &wallet.pubkey(), &mint.pubkey(), &spl_token::id(),
+        ),
+        spl_token::instruction::mint_to(&spl_token::id(), &mint.pubkey(), &ata, &wallet.pubkey(), &[], 1_000_000).unwrap(),
+    ];
+    let tx = build_and_sign_transaction_basic(&rpc, &instructions, wallet).expect("build and sign");
+    assert_eq!(tx.message.recent_blockhash, nonce_before, "transaction must use the nonce blockhash");
+
+    let signature = rpc.send_and_confirm_transaction(&tx).expect("nonce transaction should confirm");
+    println!("Confirmed nonce transaction {}", signature);
+
+    assert_eq!(rpc.get_token_account_balance(&ata).expect("ata balance").amount, "1000000");
+    assert_ne!(nonce_blockhash(&rpc, &nonce_pubkey), nonce_before, "nonce must advance");
+
+    // Replaying the same signed transaction must fail now that the nonce advanced
+    assert!(rpc.send_and_confirm_transaction(&tx).is_err());
+}
@@ -1,6 +1,10 @@
+pub mod dex_probe;
 pub mod pump_fun;
 pub mod tx_builder;
 pub mod pump_swap;
 pub mod ray_launch;
 pub mod ray_cpmm;
-pub mod utils;
\ No newline at end of file
+pub mod utils;
+pub mod simulation;
+#[cfg(test)]
+mod local_validator_tests;
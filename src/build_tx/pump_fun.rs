@@ -7,6 +7,7 @@ use solana_program::{
 };
 use solana_sdk::pubkey::Pubkey;
 use crate::build_tx::utils::get_account;
+use crate::build_tx::utils::with_rpc_retry;
 use crate::constants::pump_fun::{GLOBAL_ACCOUNT, FEE_RECIPIENT, MINT_AUTHORITY, PUMP_FUN_PROGRAM_ID_PUBKEY};
 use crate::init::wallet_loader::get_wallet_keypair;
 use solana_sdk::signature::Signer;
@@ -288,7 +289,7 @@ pub fn get_instruction_accounts(
 
 pub fn get_bonding_curve_state(pump_fun_accounts: &PumpFunAccounts) -> BondingCurve {
     let client = GLOBAL_RPC_CLIENT.get().expect("RPC client not initialized");
-    let account_data = client.get_account_data(&pump_fun_accounts.bonding_curve_pda).expect("Failed to get account data");
+    let account_data = with_rpc_retry(|| client.get_account_data(&pump_fun_accounts.bonding_curve_pda)).expect("Failed to get account data");
     
     let bonding_curve_state = BondingCurve::deserialize(&mut &account_data[8..]).expect("Failed to deserialize bonding curve state");
     
@@ -17,7 +17,8 @@ use solana_client::rpc_config::RpcProgramAccountsConfig;
 use solana_client::rpc_config::RpcAccountInfoConfig;
 use solana_client::rpc_client::RpcClient;
 use borsh::{BorshDeserialize, BorshSerialize};
-use crate::build_tx::utils::get_account;
+use crate::build_tx::utils::{get_account, validate_account_data, with_rpc_retry, BuildError};
+use crate::send_tx::rate_guard::throttle_rpc_call;
 use std::str::FromStr;
 
 /// Enum for swap direction
@@ -27,6 +28,9 @@ pub enum SwapDirection {
     Sell,
 }
 
+/// Minimum pool account size: 8-byte discriminator + PoolAccountInfo
+const POOL_ACCOUNT_MIN_LEN: usize = 8 + 1 + 2 + 32 * 6 + 8 + 32;
+
 #[derive(Debug, Clone, BorshDeserialize, BorshSerialize)]
 pub struct PoolAccountInfo {
     pub pool_bump: u8,
@@ -225,54 +229,37 @@ pub fn build_pump_sell_instruction_raw(
     amount: u64,
     slippage_basis_points: u64,
     mint: Pubkey,
-) -> Instruction {
-    let rpc_client = GLOBAL_RPC_CLIENT.get().expect("RPC client not initialized");
-
+) -> Result<Instruction, BuildError> {
     let slippage_factor = 1.0-slippage_basis_points as f64 /10000.0;
-    let instruction: Instruction = Instruction{
-        program_id: Pubkey::new_unique(),
-        accounts: vec![],
-        data: vec![],
-    };
-
-
-    let pool_ac = get_pool_accounts(mint, rpc_client);
-
-    let account_data = match rpc_client.get_account_data(&pool_ac.unwrap()) {
-        Ok(data) => data,
-        Err(e) => {
-            eprintln!("!!!!!!RPC ERROR: Failed to get account data for pool: {:?}", e);
-            eprintln!("!!!!!!Pool account: {:?}", pool_ac.unwrap());
-            return instruction; // Return default instruction on error
-        }
-    };
-    if account_data.iter().all(|&b| b == 0) {
-        println!("account_data is all zeros!");
-        return instruction; // or return an error, or handle as needed
-    }
-    let pool_ac_detail = match PoolAccountInfo::deserialize(&mut &account_data[8..]) {
-        Ok(detail) => detail,
-        Err(e) => {
-            eprintln!("!!!!!!RPC ERROR: Failed to deserialize pool account info: {:?}", e);
-            eprintln!("!!!!!!Account data length: {}", account_data.len());
-            return instruction; // Return default instruction on error
-        }
-    };
-    println!("pool_ac_detail: {:?}", pool_ac_detail);
 
+    let accounts = get_pump_swap_accounts_rpc(mint)?;
 
     let limit_quote_amount = get_pump_swap_amount(
         SwapDirection::Sell,
-        pool_ac_detail.pool_base_token_account,
-        pool_ac_detail.pool_quote_token_account,
+        accounts.pool_base_token_account,
+        accounts.pool_quote_token_account,
         amount,
         0,
         0,
-    ).expect("Failed to calculate sell limit_quote_amount");
+    ).map_err(|e| BuildError::AccountNotFound(format!("pool vaults for mint {}: {}", mint, e)))?;
+
+    Ok(build_pump_swap_instruction(&accounts, SwapDirection::Sell,  (limit_quote_amount as f64*slippage_factor) as u64, amount))
+}
 
-    let accounts = get_instruction_accounts_rpc(mint, pool_ac.unwrap(), pool_ac_detail.pool_base_token_account, pool_ac_detail.pool_quote_token_account, pool_ac_detail.coin_creator);
+/// Derive the PumpSwap accounts for `mint` from chain state (pool lookup + pool account data)
+pub fn get_pump_swap_accounts_rpc(mint: Pubkey) -> Result<PumpAmmAccounts, BuildError> {
+    let rpc_client = GLOBAL_RPC_CLIENT.get().expect("RPC client not initialized");
 
-    return build_pump_swap_instruction(&accounts, SwapDirection::Sell,  (limit_quote_amount as f64*slippage_factor) as u64, amount);
+    let pool_ac = get_pool_accounts(mint, rpc_client)
+        .ok_or_else(|| BuildError::AccountNotFound(format!("pool for mint {}", mint)))?;
+
+    let account_data = with_rpc_retry(|| rpc_client.get_account_data(&pool_ac))
+        .map_err(|e| BuildError::AccountNotFound(format!("pool {}: {}", pool_ac, e)))?;
+    let account_data = validate_account_data(&account_data, POOL_ACCOUNT_MIN_LEN)?;
+    let pool_ac_detail = PoolAccountInfo::deserialize(&mut &account_data[8..])
+        .map_err(|e| BuildError::Deserialize(format!("pool account info: {}", e)))?;
+
+    Ok(get_instruction_accounts_rpc(mint, pool_ac, pool_ac_detail.pool_base_token_account, pool_ac_detail.pool_quote_token_account, pool_ac_detail.coin_creator))
 }
 
 /// Calculates the expected output amount for a buy or sell swap.
@@ -297,13 +284,19 @@ pub fn get_pump_swap_amount(
     target_sol_buy: u64,
     target_token_buy: u64,
 ) -> Result<u64, Box<dyn Error>> {
+    let (base_amount, quote_amount) = get_vault_reserves(base_vault, quote_vault)?;
+    calculate_pump_swap_amount(direction, base_amount, quote_amount, swap_amount, target_sol_buy, target_token_buy)
+}
+
+/// Current token balances of a pool's (base, quote) vaults, read at processed commitment
+pub fn get_vault_reserves(base_vault: Pubkey, quote_vault: Pubkey) -> Result<(u64, u64), Box<dyn Error>> {
     let keys = vec![base_vault, quote_vault];
     let rpc_client = GLOBAL_RPC_CLIENT.get().expect("RPC client not initialized");
 
-    let res = match rpc_client.get_multiple_accounts_with_commitment(&keys, CommitmentConfig::processed()) {
+    let res = match with_rpc_retry(|| rpc_client.get_multiple_accounts_with_commitment(&keys, CommitmentConfig::processed())) {
         Ok(response) => response,
         Err(e) => {
-            eprintln!("!!!!!!RPC ERROR: Failed to get multiple accounts in get_pump_swap_amount: {:?}", e);
+            eprintln!("!!!!!!RPC ERROR: Failed to get multiple accounts in get_vault_reserves: {:?}", e);
             eprintln!("!!!!!!Keys being requested: {:?}", keys);
             return Err(format!("RPC call failed: {:?}", e).into());
         }
@@ -318,15 +311,40 @@ pub fn get_pump_swap_amount(
     }
     let base_amount = u64::from_le_bytes(base_data[64..72].try_into().unwrap());
     let quote_amount = u64::from_le_bytes(quote_data[64..72].try_into().unwrap());
+    Ok((base_amount, quote_amount))
+}
+
+/// Constant-product output for a swap against the given vault reserves.
+///
+/// `target_sol_buy`/`target_token_buy` adjust the reserves for a leader trade landing before ours.
+/// Returns an error if the adjustment would drain the output-side reserve.
+pub fn calculate_pump_swap_amount(
+    direction: SwapDirection,
+    base_amount: u64,
+    quote_amount: u64,
+    swap_amount: u64,
+    target_sol_buy: u64,
+    target_token_buy: u64,
+) -> Result<u64, Box<dyn Error>> {
     if base_amount == 0 {
         return Err("zero base amount".into());
     }
     let adjusted_price = match direction {
-        SwapDirection::Buy => ((base_amount-target_token_buy) as f64 * swap_amount as f64) / ((quote_amount + target_sol_buy) as f64 + swap_amount as f64) ,
-        SwapDirection::Sell => ((quote_amount-target_sol_buy) as f64 * swap_amount as f64) / ((base_amount + target_token_buy) as f64 + swap_amount as f64) ,
+        SwapDirection::Buy => {
+            let base_remaining = base_amount.checked_sub(target_token_buy).ok_or_else(|| {
+                format!("target_token_buy {} exceeds base reserve {}", target_token_buy, base_amount)
+            })?;
+            (base_remaining as f64 * swap_amount as f64) / (quote_amount as f64 + target_sol_buy as f64 + swap_amount as f64)
+        }
+        SwapDirection::Sell => {
+            let quote_remaining = quote_amount.checked_sub(target_sol_buy).ok_or_else(|| {
+                format!("target_sol_buy {} exceeds quote reserve {}", target_sol_buy, quote_amount)
+            })?;
+            (quote_remaining as f64 * swap_amount as f64) / (base_amount as f64 + target_token_buy as f64 + swap_amount as f64)
+        }
     };
     Ok(adjusted_price as u64)
-} 
+}
 
 pub fn get_instruction_accounts(
     account_keys: &[Vec<u8>],
@@ -465,10 +483,12 @@ pub fn derive_creator_vault_authority(creator: &Pubkey) -> (Pubkey, u8) {
 }
 
 
-pub fn get_instruction_accounts_migrate_pump(
+/// Async: the pool lookup goes through the shared RPC rate limiter and runs on the
+/// blocking pool, so real-time threads never sleep here
+pub async fn get_instruction_accounts_migrate_pump(
     account_keys: &[Vec<u8>],
     accounts: &[u8],
-) -> PumpAmmAccounts {
+) -> Result<PumpAmmAccounts, BuildError> {
 
     let mint = get_account(account_keys, accounts, 2);
     let base_ata = spl_associated_token_account::get_associated_token_address(&get_wallet_keypair().pubkey(), &mint);
@@ -476,34 +496,19 @@ pub fn get_instruction_accounts_migrate_pump(
  
     let rpc_client = GLOBAL_RPC_CLIENT.get().expect("RPC client not initialized");
     
-    // Add 1-second delay before RPC call to prevent rate limiting
-    println!("[PUMP_SWAP] Waiting 1 second before RPC call to prevent rate limiting...");
-    std::thread::sleep(std::time::Duration::from_secs(1));
-    
-    let account_data = match rpc_client.get_account_data(&get_account(account_keys, accounts, 9)) {
-        Ok(data) => data,
-        Err(e) => {
-            eprintln!("!!!!!!RPC ERROR: Failed to get account data in get_instruction_accounts_migrate_pump: {:?}", e);
-            eprintln!("!!!!!!Account being requested: {:?}", get_account(account_keys, accounts, 9));
-            // Return default accounts on error
-            return PumpAmmAccounts::default();
-        }
-    };
-    if account_data.iter().all(|&b| b == 0) {
-        println!("account_data is all zeros!");
-    }
-    let pool_ac_detail = match PoolAccountInfo::deserialize(&mut &account_data[8..]) {
-        Ok(detail) => detail,
-        Err(e) => {
-            eprintln!("!!!!!!RPC ERROR: Failed to deserialize pool account info in migrate: {:?}", e);
-            eprintln!("!!!!!!Account data length: {}", account_data.len());
-            return PumpAmmAccounts::default(); // Return default accounts on error
-        }
-    };
+    let pool = get_account(account_keys, accounts, 9);
+    throttle_rpc_call().await;
+    let account_data = tokio::task::spawn_blocking(move || with_rpc_retry(|| rpc_client.get_account_data(&pool)))
+        .await
+        .map_err(|e| BuildError::AccountNotFound(format!("pool {}: {}", pool, e)))?
+        .map_err(|e| BuildError::AccountNotFound(format!("pool {}: {}", pool, e)))?;
+    let account_data = validate_account_data(&account_data, POOL_ACCOUNT_MIN_LEN)?;
+    let pool_ac_detail = PoolAccountInfo::deserialize(&mut &account_data[8..])
+        .map_err(|e| BuildError::Deserialize(format!("pool account info in migrate: {}", e)))?;
     let (creator_vault_authority, _) = derive_creator_vault_authority(&pool_ac_detail.coin_creator);
     let creator_vault_ata = spl_associated_token_account::get_associated_token_address(&creator_vault_authority, &pump_swap_constants::WSOL);
     
-    PumpAmmAccounts {
+    Ok(PumpAmmAccounts {
         pool: get_account(account_keys, accounts, 9),
         user: get_wallet_keypair().pubkey(),
         global_config: pump_swap_constants::PUMP_SWAP_GLOBAL_CONFIG,
@@ -525,7 +530,7 @@ pub fn get_instruction_accounts_migrate_pump(
         coin_creator_vault_authority: creator_vault_authority,
         global_volume_accumulator: global_volume_accumulator_pda(),
         user_volume_accumulator: user_volume_accumulator_pda(&get_wallet_keypair().pubkey()),
-    }
+    })
     // TODO: Map the correct indices for each field as per the actual instruction layout
 }
 
@@ -545,3 +550,49 @@ pub fn user_volume_accumulator_pda(user: &Pubkey) -> Pubkey {
     );
     user_volume_accumulator
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: u64 = 1_000_000_000_000; // 1M tokens (6 decimals)
+    const QUOTE: u64 = 100_000_000_000; // 100 SOL
+
+    #[test]
+    fn test_buy_amount() {
+        // 1 SOL in: 1e12 * 1e9 / (1e11 + 1e9) = 9_900_990_099
+        let out = calculate_pump_swap_amount(SwapDirection::Buy, BASE, QUOTE, 1_000_000_000, 0, 0).unwrap();
+        assert_eq!(out, 9_900_990_099);
+    }
+
+    #[test]
+    fn test_sell_amount() {
+        // 10k tokens in: 1e11 * 1e10 / (1e12 + 1e10) = 990_099_009
+        let out = calculate_pump_swap_amount(SwapDirection::Sell, BASE, QUOTE, 10_000_000_000, 0, 0).unwrap();
+        assert_eq!(out, 990_099_009);
+    }
+
+    #[test]
+    fn test_buy_after_leader_trade() {
+        // Leader buys 10 SOL / 90_909 tokens first: reserves become (BASE - t, QUOTE + s)
+        let target_sol = 10_000_000_000;
+        let target_token = 90_909_090_909;
+        let adjusted = calculate_pump_swap_amount(SwapDirection::Buy, BASE, QUOTE, 1_000_000_000, target_sol, target_token).unwrap();
+        let plain = calculate_pump_swap_amount(SwapDirection::Buy, BASE, QUOTE, 1_000_000_000, 0, 0).unwrap();
+        assert!(adjusted < plain);
+        assert_eq!(adjusted, 8_190_008_190);
+    }
+
+    #[test]
+    fn test_target_exceeds_reserve() {
+        assert!(calculate_pump_swap_amount(SwapDirection::Buy, BASE, QUOTE, 1_000_000_000, 0, BASE + 1).is_err());
+        assert!(calculate_pump_swap_amount(SwapDirection::Sell, BASE, QUOTE, 1_000_000_000, QUOTE + 1, 0).is_err());
+        // Draining exactly to zero is not an error, output is zero
+        assert_eq!(calculate_pump_swap_amount(SwapDirection::Buy, BASE, QUOTE, 1_000_000_000, 0, BASE).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_zero_base_reserve() {
+        assert!(calculate_pump_swap_amount(SwapDirection::Buy, 0, QUOTE, 1_000_000_000, 0, 0).is_err());
+    }
+}
@@ -15,8 +15,10 @@ use crate::build_tx::utils::get_pool_accounts;
 use borsh::{BorshDeserialize, BorshSerialize};
 use crate::build_tx::utils::get_constant_product_swap_amount;
 use crate::build_tx::utils::get_pool_vault_amount;
+use crate::build_tx::utils::with_rpc_retry;
 use crate::build_tx::utils::SwapDirection;
 use crate::build_tx::utils::get_account;
+use crate::build_tx::utils::{validate_account_data, BuildError};
 use crate::constants::raydium_cpmm::RAYDIUM_CPMM_AUTHORITY;
 use crate::constants::raydium_cpmm::RAYDIUM_CPMM_AMM_CONFIG;
 use crate::constants::consts::WSOL;
@@ -476,7 +478,7 @@ pub fn get_pool_state(ray_cpmm_accounts: &RayCpmmSwapAccounts) -> RaydiumCpmmPoo
         }
     };
     
-    let account_data = match client.get_account_data(&ray_cpmm_accounts.pool_state) {
+    let account_data = match with_rpc_retry(|| client.get_account_data(&ray_cpmm_accounts.pool_state)) {
         Ok(data) => data,
         Err(e) => {
             eprintln!("!!!!!!RPC ERROR: Failed to get account data for pool state: {:?}", e);
@@ -497,6 +499,37 @@ pub fn get_pool_state(ray_cpmm_accounts: &RayCpmmSwapAccounts) -> RaydiumCpmmPoo
     pool_state
 }
 
+/// Pool state account size: 8-byte discriminator + RaydiumCpmmPoolState
+pub const POOL_STATE_MIN_LEN: usize = 8 + 32 * 10 + 5 + 8 * 7 + 8 * 31;
+
+/// Derive the CPMM swap accounts for `mint` from chain state, for migrations the monitoring feed missed.
+/// The pool is located by token_1_mint (offset 200), then token_0_mint (offset 168); accounts are
+/// normalized so token_1 is always the mint, matching the sell account layout.
+pub fn get_ray_cpmm_accounts_rpc(mint: Pubkey) -> Result<RayCpmmSwapAccounts, BuildError> {
+    let rpc_client = GLOBAL_RPC_CLIENT.get().expect("RPC client not initialized");
+
+    let pool_state = get_pool_accounts(mint, rpc_client, [200u64], RAYDIUM_CPMM_PROGRAM_ID_PUBKEY)
+        .or_else(|| get_pool_accounts(mint, rpc_client, [168u64], RAYDIUM_CPMM_PROGRAM_ID_PUBKEY))
+        .ok_or_else(|| BuildError::AccountNotFound(format!("cpmm pool for mint {}", mint)))?;
+
+    let account_data = with_rpc_retry(|| rpc_client.get_account_data(&pool_state))
+        .map_err(|e| BuildError::AccountNotFound(format!("cpmm pool {}: {}", pool_state, e)))?;
+    let account_data = validate_account_data(&account_data, POOL_STATE_MIN_LEN)?;
+    let mut pool_detail = RaydiumCpmmPoolState::deserialize(&mut &account_data[8..])
+        .map_err(|e| BuildError::Deserialize(format!("cpmm pool state: {}", e)))?;
+
+    if pool_detail.token_0_mint == mint {
+        std::mem::swap(&mut pool_detail.token_0_mint, &mut pool_detail.token_1_mint);
+        std::mem::swap(&mut pool_detail.token_0_vault, &mut pool_detail.token_1_vault);
+        std::mem::swap(&mut pool_detail.token_0_program, &mut pool_detail.token_1_program);
+    }
+
+    let wallet = get_wallet_keypair().pubkey();
+    let base_ata = spl_associated_token_account::get_associated_token_address(&wallet, &mint);
+    let quote_ata = spl_associated_token_account::get_associated_token_address(&wallet, &WSOL);
+    Ok(RayCpmmSwapAccounts::from_pool_state(pool_state, &pool_detail, wallet, base_ata, quote_ata))
+}
+
 /// Dummy function to create RayCpmmSwapAccounts for migrate instruction
 /// Based on get_instruction_accounts from ray_cpmm.rs
 pub fn get_instruction_accounts_migrate(
@@ -9,6 +9,7 @@ use std::error::Error;
 use crate::build_tx::utils::SwapDirection;
 use crate::init::wallet_loader::get_wallet_keypair;
 use crate::build_tx::utils::get_account;
+use crate::build_tx::utils::with_rpc_retry;
 use solana_sdk::signature::Signer;
 use solana_program::instruction::{AccountMeta, Instruction};
 use num_bigint::BigUint;
@@ -381,7 +382,7 @@ pub fn get_instruction_accounts(
 }
 pub fn get_pool_state(ray_launch_accounts: &RayLaunchAccounts) -> RaydiumPoolState {
     let client = GLOBAL_RPC_CLIENT.get().expect("RPC client not initialized");
-    let account_data = client.get_account_data(&ray_launch_accounts.pool_state).expect("Failed to get account data");
+    let account_data = with_rpc_retry(|| client.get_account_data(&ray_launch_accounts.pool_state)).expect("Failed to get account data");
     println!("pool_state: {:?}", ray_launch_accounts.pool_state);
     let pool_state = RaydiumPoolState::deserialize(&mut &account_data[8..]).expect("Failed to deserialize bonding curve state");
     
@@ -0,0 +1,197 @@
+// simulation.rs
+// Concurrency gate and timeout for the CU simulation RPC calls on the buy path
+
+use crate::config_load::Config;
+use chrono::Utc;
+use once_cell::sync::{Lazy, OnceCell};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::transaction::{Transaction, TransactionError};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+// In-flight simulations, guarded for blocking waits from the builder threads
+static SIM_IN_FLIGHT: Lazy<(Mutex<usize>, Condvar)> = Lazy::new(|| (Mutex::new(0), Condvar::new()));
+
+static SIM_CALLS: AtomicUsize = AtomicUsize::new(0);
+static SIM_WAITED: AtomicUsize = AtomicUsize::new(0);
+static SIM_WAIT_MICROS: AtomicU64 = AtomicU64::new(0);
+static SIM_QUEUE_TIMEOUTS: AtomicUsize = AtomicUsize::new(0);
+static SIM_RPC_TIMEOUTS: AtomicUsize = AtomicUsize::new(0);
+static SEND_NODE_SIMS: AtomicUsize = AtomicUsize::new(0);
+static SEND_NODE_DISCREPANCIES: AtomicUsize = AtomicUsize::new(0);
+
+// Dedicated client with a request timeout, so a slow simulation falls back to config.cu_limit
+static SIM_RPC_CLIENT: OnceCell<RpcClient> = OnceCell::new();
+// Timeout-bound client on the first sendRPC endpoint, for simulate_on_send_rpc
+static SEND_NODE_SIM_CLIENT: OnceCell<Option<RpcClient>> = OnceCell::new();
+
+/// Held while a simulation runs; releases the slot on drop
+pub struct SimulationPermit;
+
+impl Drop for SimulationPermit {
+    fn drop(&mut self) {
+        let (lock, cvar) = &*SIM_IN_FLIGHT;
+        if let Ok(mut in_flight) = lock.lock() {
+            *in_flight = in_flight.saturating_sub(1);
+        }
+        cvar.notify_one();
+    }
+}
+
+/// Wait for a simulation slot (`limit` 0 = unlimited). Returns None if no slot frees up within
+/// `timeout`, in which case the caller skips simulation and uses config.cu_limit.
+pub fn acquire_simulation_permit(limit: usize, timeout: Option<Duration>) -> Option<SimulationPermit> {
+    SIM_CALLS.fetch_add(1, Ordering::Relaxed);
+    let (lock, cvar) = &*SIM_IN_FLIGHT;
+    let mut in_flight = lock.lock().ok()?;
+
+    if limit > 0 && *in_flight >= limit {
+        let wait_start = Instant::now();
+        SIM_WAITED.fetch_add(1, Ordering::Relaxed);
+        while *in_flight >= limit {
+            match timeout {
+                Some(timeout) => {
+                    let remaining = match timeout.checked_sub(wait_start.elapsed()) {
+                        Some(remaining) if !remaining.is_zero() => remaining,
+                        _ => {
+                            SIM_WAIT_MICROS.fetch_add(wait_start.elapsed().as_micros() as u64, Ordering::Relaxed);
+                            SIM_QUEUE_TIMEOUTS.fetch_add(1, Ordering::Relaxed);
+                            return None;
+                        }
+                    };
+                    in_flight = cvar.wait_timeout(in_flight, remaining).ok()?.0;
+                }
+                None => in_flight = cvar.wait(in_flight).ok()?,
+            }
+        }
+        SIM_WAIT_MICROS.fetch_add(wait_start.elapsed().as_micros() as u64, Ordering::Relaxed);
+    }
+
+    *in_flight += 1;
+    Some(SimulationPermit)
+}
+
+/// RPC client for simulations: a timeout-bound client when `timeout_ms` > 0, otherwise `default`
+pub fn simulation_rpc_client<'a>(default: &'a RpcClient, rpc_endpoint: &str, timeout_ms: u64) -> &'a RpcClient {
+    if timeout_ms == 0 {
+        return default;
+    }
+    SIM_RPC_CLIENT.get_or_init(|| {
+        RpcClient::new_with_timeout_and_commitment(
+            rpc_endpoint.to_string(),
+            Duration::from_millis(timeout_ms),
+            CommitmentConfig::processed(),
+        )
+    })
+}
+
+pub fn record_simulation_rpc_timeout() {
+    SIM_RPC_TIMEOUTS.fetch_add(1, Ordering::Relaxed);
+}
+
+/// How a send-node simulation compared with the read node's
+#[derive(Debug, Clone, PartialEq)]
+pub enum SendNodeCheck {
+    Agree,
+    Diverged(TransactionError), // OK on the read node, failed on the send node
+    Unavailable,                // no send node, or it errored / timed out
+}
+
+/// Compare the read node's simulation error with the send node's result
+pub fn compare_send_node(read_err: Option<&TransactionError>, send: Result<Option<TransactionError>, String>) -> SendNodeCheck {
+    match (read_err, send) {
+        (_, Err(_)) => SendNodeCheck::Unavailable,
+        (None, Ok(Some(send_err))) => SendNodeCheck::Diverged(send_err),
+        _ => SendNodeCheck::Agree,
+    }
+}
+
+/// Re-simulate `tx` on the first sendRPC endpoint (bounded by `send_rpc_simulation_timeout_ms`) and
+/// report it if the send node fails a transaction the read node accepted
+pub fn check_on_send_node(tx: &Transaction, read_err: Option<&TransactionError>, config: &Config, sig_str: &str) -> SendNodeCheck {
+    let client = SEND_NODE_SIM_CLIENT.get_or_init(|| {
+        config.send_rpc.iter().find(|url| !url.trim().is_empty()).map(|url| {
+            RpcClient::new_with_timeout_and_commitment(
+                url.clone(),
+                Duration::from_millis(config.send_rpc_simulation_timeout_ms),
+                CommitmentConfig::processed(),
+            )
+        })
+    });
+    let send = match client {
+        Some(client) => {
+            SEND_NODE_SIMS.fetch_add(1, Ordering::Relaxed);
+            client.simulate_transaction(tx).map(|result| result.value.err).map_err(|e| e.to_string())
+        }
+        None => Err("no sendRPC endpoint".to_string()),
+    };
+    let check = compare_send_node(read_err, send);
+    if let SendNodeCheck::Diverged(err) = &check {
+        SEND_NODE_DISCREPANCIES.fetch_add(1, Ordering::Relaxed);
+        eprintln!(
+            "[{}] - [SIMULATION] Send node diverged for sig {}: read node OK, send node {} failed with {:?}",
+            Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+            sig_str,
+            client.as_ref().map_or("?".to_string(), |c| c.url()),
+            err
+        );
+    }
+    check
+}
+
+/// (send-node simulations, discrepancies)
+pub fn get_send_node_stats() -> (usize, usize) {
+    (SEND_NODE_SIMS.load(Ordering::Relaxed), SEND_NODE_DISCREPANCIES.load(Ordering::Relaxed))
+}
+
+/// (calls, waited, avg queue wait us, queue timeouts, rpc timeouts)
+pub fn get_simulation_stats() -> (usize, usize, u64, usize, usize) {
+    let waited = SIM_WAITED.load(Ordering::Relaxed);
+    let avg_wait = if waited > 0 { SIM_WAIT_MICROS.load(Ordering::Relaxed) / waited as u64 } else { 0 };
+    (
+        SIM_CALLS.load(Ordering::Relaxed),
+        waited,
+        avg_wait,
+        SIM_QUEUE_TIMEOUTS.load(Ordering::Relaxed),
+        SIM_RPC_TIMEOUTS.load(Ordering::Relaxed),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simulation_permit_limit_and_timeout() {
+        let first = acquire_simulation_permit(2, Some(Duration::from_millis(10)));
+        let second = acquire_simulation_permit(2, Some(Duration::from_millis(10)));
+        assert!(first.is_some() && second.is_some());
+
+        // Limit reached: times out instead of blocking the builder
+        let start = Instant::now();
+        assert!(acquire_simulation_permit(2, Some(Duration::from_millis(20))).is_none());
+        assert!(start.elapsed() >= Duration::from_millis(20));
+
+        // Releasing a permit lets the next simulation in
+        drop(first);
+        assert!(acquire_simulation_permit(2, Some(Duration::from_millis(20))).is_some());
+        drop(second);
+        assert!(get_simulation_stats().3 >= 1);
+    }
+
+    #[test]
+    fn test_send_node_divergence() {
+        use solana_sdk::instruction::InstructionError;
+        let failure = TransactionError::InstructionError(2, InstructionError::Custom(6004));
+        // Read node OK, send node fails: reported
+        assert_eq!(compare_send_node(None, Ok(Some(failure.clone()))), SendNodeCheck::Diverged(failure.clone()));
+        // Both OK, or both failing: nothing node-specific
+        assert_eq!(compare_send_node(None, Ok(None)), SendNodeCheck::Agree);
+        assert_eq!(compare_send_node(Some(&failure), Ok(Some(failure.clone()))), SendNodeCheck::Agree);
+        assert_eq!(compare_send_node(Some(&failure), Ok(None)), SendNodeCheck::Agree);
+        // Send node timed out
+        assert_eq!(compare_send_node(None, Err("timeout".to_string())), SendNodeCheck::Unavailable);
+    }
+}
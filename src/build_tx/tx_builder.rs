@@ -6,8 +6,8 @@ use solana_sdk::signer::Signer;
 use solana_sdk::transaction::Transaction;
 use solana_sdk::compute_budget;
 use solana_sdk::pubkey::Pubkey;
-use crate::init::wallet_loader::{get_wallet_keypair, get_nonce_account, get_next_nonce_account_keypair, get_next_nonce_account_atomic};
-use crate::utils::ata::create_ata;
+use crate::init::wallet_loader::{get_wallet_keypair, get_fee_payer_keypair, get_nonce_account, get_next_nonce_account_keypair, get_next_nonce_account_atomic};
+use crate::utils::ata::{create_ata, get_cached_ata, skip_ata_creation};
 use solana_program::instruction::Instruction;
 use solana_sdk::nonce::state::State;
 use solana_sdk::nonce::state::Versions;
@@ -15,7 +15,6 @@ use bincode::deserialize;
 use std::time::Instant;
 use chrono::Utc;
 use once_cell::sync::OnceCell;
-use tokio::runtime::Runtime;
 use crate::config_load::GLOBAL_CONFIG;
 use crate::send_tx::zero_slot::create_instruction_zeroslot;
 use rayon::prelude::*;
@@ -26,71 +25,13 @@ use crate::send_tx::flashblock::create_instruction_flashblock;
 use crate::send_tx::astralane::create_instruction_astralane;
 use crate::send_tx::temporal::create_instruction_temporal;
 use crate::init::tip_stream::get_tip_percentile;
-
-// Thread-local runtime storage for better concurrency
-use std::cell::RefCell;
-use std::sync::Arc;
-use tokio::sync::Mutex;
-
-thread_local! {
-    static THREAD_RUNTIME: RefCell<Option<Runtime>> = RefCell::new(None);
-}
-
-// Alternative: Runtime pool for high-concurrency scenarios
-static RUNTIME_POOL: OnceCell<Arc<Mutex<Vec<Runtime>>>> = OnceCell::new();
-static POOL_SIZE: usize = 4; // Number of runtimes in the pool
-
-/// Initialize runtime pool for high-concurrency scenarios
-pub fn init_runtime_pool() {
-    if RUNTIME_POOL.get().is_none() {
-        let mut runtimes = Vec::with_capacity(POOL_SIZE);
-        for _ in 0..POOL_SIZE {
-            runtimes.push(Runtime::new().expect("Failed to create tokio runtime"));
-        }
-        let _ = RUNTIME_POOL.set(Arc::new(Mutex::new(runtimes)));
-    }
-}
-
-/// Get a runtime from the pool (round-robin)
-async fn get_runtime_from_pool() -> Runtime {
-    let pool = RUNTIME_POOL.get().expect("Runtime pool not initialized");
-    let mut pool_guard = pool.lock().await;
-    
-    // Round-robin selection
-    if let Some(runtime) = pool_guard.pop() {
-        runtime
-    } else {
-        // Pool exhausted, create new runtime
-        Runtime::new().expect("Failed to create tokio runtime")
-    }
-}
-
-/// Return runtime to pool
-async fn return_runtime_to_pool(runtime: Runtime) {
-    let pool = RUNTIME_POOL.get().expect("Runtime pool not initialized");
-    let mut pool_guard = pool.lock().await;
-    
-    if pool_guard.len() < POOL_SIZE {
-        pool_guard.push(runtime);
-    }
-    // If pool is full, drop the runtime
-}
+use crate::send_tx::cu_price::split_bid;
+use crate::grpc::compute_budget::match_leader_cu_price;
+use crate::send_tx::chase::escalate_bid;
 
 // We'll use the existing global blockhash cache from send_tx::rpc
 // No need for our own cache since the global one is already optimized
 
-/// Get or create a thread-local runtime
-fn get_thread_runtime() -> Runtime {
-    THREAD_RUNTIME.with(|runtime_cell| {
-        let mut runtime_opt = runtime_cell.borrow_mut();
-        if runtime_opt.is_none() {
-            *runtime_opt = Some(Runtime::new().expect("Failed to create tokio runtime"));
-        }
-        // Take ownership of the runtime
-        runtime_opt.take().unwrap()
-    })
-}
-
 /// Get cached blockhash directly from the global cache
 fn get_cached_blockhash_sync() -> solana_sdk::hash::Hash {
     // Use the existing global blockhash cache directly
@@ -132,6 +73,10 @@ fn get_nonce_blockhash_sync(rpc_client: &RpcClient, nonce_account: &Pubkey) -> R
         Versions::Current(boxed_state) => {
             match *boxed_state {
                 State::Initialized(ref data) => {
+                    if data.authority != get_wallet_keypair().pubkey() {
+                        crate::init::wallet_loader::record_mismatched_nonce(nonce_account, &data.authority);
+                        return Err(format!("Nonce account {} authority {} is not the wallet", nonce_account, data.authority).into());
+                    }
                     println!("[TX_BUILDER] Nonce account state - blockhash: {}", data.blockhash());
                     data.blockhash()
                 }
@@ -148,14 +93,43 @@ fn get_nonce_blockhash_sync(rpc_client: &RpcClient, nonce_account: &Pubkey) -> R
     Ok(nonce_blockhash)
 }
 
+thread_local! {
+    // Nonce account and value the builds on this thread must reuse (set by `with_nonce`)
+    static NONCE_OVERRIDE: std::cell::Cell<Option<(Pubkey, solana_sdk::hash::Hash)>> = std::cell::Cell::new(None);
+}
+
+/// Run `build` with the transactions it builds on this thread pinned to nonce account `nonce` at value
+/// `blockhash` (taken from an earlier transaction), so at most one of them and that transaction can land
+pub fn with_nonce<T>(nonce: Pubkey, blockhash: solana_sdk::hash::Hash, build: impl FnOnce() -> T) -> T {
+    NONCE_OVERRIDE.with(|pinned| pinned.set(Some((nonce, blockhash))));
+    let _unpin = NonceOverrideGuard;
+    build()
+}
+
+/// Clears the `with_nonce` pin when the build ends, however it ends (a panicking build included)
+struct NonceOverrideGuard;
+
+impl Drop for NonceOverrideGuard {
+    fn drop(&mut self) {
+        NONCE_OVERRIDE.with(|pinned| pinned.set(None));
+    }
+}
+
 /// Get the next nonce account and its blockhash atomically (prevents race conditions)
 fn get_next_nonce_account_and_blockhash(rpc_client: &RpcClient) -> Result<(&'static Keypair, &'static Pubkey, solana_sdk::hash::Hash), Box<dyn std::error::Error + Send + Sync>> {
     use crate::init::wallet_loader::get_next_nonce_account_atomic;
     
-    let (keypair, pubkey) = get_next_nonce_account_atomic();
-    let blockhash = get_nonce_blockhash_sync(rpc_client, pubkey)?;
+    use crate::init::wallet_loader::is_nonce_excluded;
     
-    Ok((keypair, pubkey, blockhash))
+    // An account found mismatched here is excluded, so the next pick moves on to another one
+    loop {
+        let (keypair, pubkey) = get_next_nonce_account_atomic();
+        match get_nonce_blockhash_sync(rpc_client, pubkey) {
+            Ok(blockhash) => return Ok((keypair, pubkey, blockhash)),
+            Err(_) if is_nonce_excluded(pubkey) => continue,
+            Err(e) => return Err(e),
+        }
+    }
 }
 
 /// Build and sign transaction with a specific blockhash (for vendor transactions using same nonce)
@@ -165,21 +139,36 @@ fn build_and_sign_transaction_with_specific_blockhash(
     signer: &Keypair,
     blockhash: solana_sdk::hash::Hash,
 ) -> Result<Transaction, Box<dyn std::error::Error + Send + Sync>> {
-    // Build the message with the specific blockhash
-    let message = solana_sdk::message::Message::new_with_blockhash(instructions, Some(&signer.pubkey()), &blockhash);
+    // Build the message with the specific blockhash, paid by the fee payer
+    let fee_payer = get_fee_payer_keypair();
+    let message = solana_sdk::message::Message::new_with_blockhash(instructions, Some(&fee_payer.pubkey()), &blockhash);
     
-    // Convert to zebra signing key for faster signing
-    let zebra_signing_key = get_or_create_signing_key(signer);
-    
-    // Serialize and sign
+    // Sign with the fee payer and the authority (zebra for faster signing)
+    sign_message(message, &[fee_payer, signer], get_or_create_signing_key)
+}
+
+/// Sign every required signer of `message` from `signers` (duplicates are fine, e.g. when the
+/// fee payer is the wallet), producing signatures in the message's signer order
+fn sign_message(
+    message: solana_sdk::message::Message,
+    signers: &[&Keypair],
+    signing_key: fn(&Keypair) -> SigningKey,
+) -> Result<Transaction, Box<dyn std::error::Error + Send + Sync>> {
     let message_bytes = message.serialize();
-    let zebra_signature = zebra_signing_key.sign(&message_bytes);
-    let solana_signature = solana_sdk::signature::Signature::from(zebra_signature.to_bytes());
+    let required = message.header.num_required_signatures as usize;
+    let mut signatures = Vec::with_capacity(required);
+    for key in &message.account_keys[..required] {
+        let signer = signers
+            .iter()
+            .find(|kp| kp.pubkey() == *key)
+            .ok_or_else(|| format!("Missing signer for {}", key))?;
+        let zebra_signature = signing_key(signer).sign(&message_bytes);
+        signatures.push(solana_sdk::signature::Signature::from(zebra_signature.to_bytes()));
+    }
     
     // Build the transaction
     let mut tx = Transaction::new_unsigned(message);
-    tx.signatures = vec![solana_signature];
-    
+    tx.signatures = signatures;
     Ok(tx)
 }
 
@@ -202,7 +191,6 @@ pub fn init_signing_key_cache() {
 pub fn init_tx_builder_optimizations() {
     println!("[TX_BUILDER] Initializing optimizations...");
     init_signing_key_cache();
-    init_runtime_pool();
     println!("[TX_BUILDER] Optimizations initialized successfully");
 }
 
@@ -217,7 +205,11 @@ fn get_or_create_signing_key(signer: &Keypair) -> SigningKey {
             .clone();
     }
     
-    // For other keypairs, convert on-demand (rare case)
+    // For other keypairs (e.g. a separate fee payer), convert on-demand
+    signing_key_from_keypair(signer)
+}
+
+fn signing_key_from_keypair(signer: &Keypair) -> SigningKey {
     let keypair_bytes = signer.to_bytes();
     let private_key: [u8; 32] = keypair_bytes[..32].try_into().unwrap();
     SigningKey::from(private_key)
@@ -267,14 +259,10 @@ pub fn build_and_sign_transaction_basic(
     // Use nonce blockhash for actual transaction submission (atomic to prevent race conditions)
     let (_, _, recent_blockhash) = get_next_nonce_account_and_blockhash(rpc_client)?;
     
-    // Build and sign the transaction
-    let tx = Transaction::new_signed_with_payer(
-        instructions,
-        Some(&signer.pubkey()),
-        &[signer],
-        recent_blockhash,
-    );
-    Ok(tx)
+    // Build and sign the transaction (fee payer + authority)
+    let fee_payer = get_fee_payer_keypair();
+    let message = solana_sdk::message::Message::new_with_blockhash(instructions, Some(&fee_payer.pubkey()), &recent_blockhash);
+    sign_message(message, &[fee_payer, signer], signing_key_from_keypair)
 }
 
 /// Build and sign a Solana transaction using ed25519-zebra for faster signing.
@@ -340,11 +328,12 @@ fn build_and_sign_transaction_with_timing_and_blockhash(
         regular_hash
     };
     let actual_signer = signer; // Always use main wallet for signing
+    let fee_payer = get_fee_payer_keypair();
     let blockhash_fetch_time = blockhash_start.elapsed();
     
     // Time the message building
     let message_start = Instant::now();
-    let message = solana_sdk::message::Message::new_with_blockhash(instructions, Some(&actual_signer.pubkey()), &recent_blockhash);
+    let message = solana_sdk::message::Message::new_with_blockhash(instructions, Some(&fee_payer.pubkey()), &recent_blockhash);
     let message_build_time = message_start.elapsed();
     
     // Time the keypair conversion (now cached)
@@ -369,8 +358,14 @@ fn build_and_sign_transaction_with_timing_and_blockhash(
 
     // Time the transaction building
     let tx_build_start = Instant::now();
-    let mut tx = Transaction::new_unsigned(message);
-    tx.signatures = vec![solana_signature];
+    let tx = if fee_payer.pubkey() == actual_signer.pubkey() {
+        let mut tx = Transaction::new_unsigned(message);
+        tx.signatures = vec![solana_signature];
+        tx
+    } else {
+        // Separate fee payer: it signs first, the authority's signature follows
+        sign_message(message, &[fee_payer, actual_signer], get_or_create_signing_key).map_err(|e| e.to_string())?
+    };
     let tx_build_time = tx_build_start.elapsed();
     
     let total_time = total_start.elapsed();
@@ -414,27 +409,156 @@ pub fn create_instruction(
     mint: Pubkey,
     instructions: Vec<Instruction>,
 ) -> Vec<Instruction> {
-    let keypair: &'static Keypair = get_wallet_keypair();
+    create_instruction_with_heap_frame(cu_limit, mint, instructions, None)
+}
 
-    
-    // // Log the random addition for debugging
-    // println!("[TX_BUILDER] Original CU price: {}, Random addition: {}, Adjusted CU price: {}", 
-    //     cu_price, random_addition, adjusted_cu_price);
+/// Same as `create_instruction`, optionally requesting a larger heap frame for account-heavy swaps
+pub fn create_instruction_with_heap_frame(
+    cu_limit: u32,
+    mint: Pubkey,
+    instructions: Vec<Instruction>,
+    heap_frame_bytes: Option<u32>,
+) -> Vec<Instruction> {
+    SwapComponents::new(mint, instructions, heap_frame_bytes).with_cu_limit(cu_limit)
+}
+
+/// The vendor-independent parts of a swap transaction: swap instructions, create-ATA and memo
+/// instructions, heap frame. Built once per trade and shared by the simulation and every vendor
+/// build (`reuse_swap_components`); only the CU limit differs between them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwapComponents {
+    payer: Pubkey,
+    omit_cu_limit: bool,
+    heap_frame_bytes: Option<u32>,
+    memo_ix: Option<Instruction>,
+    ata_ix: Option<Instruction>,
+    instructions: Vec<Instruction>,
+}
+
+impl SwapComponents {
+    pub fn new(mint: Pubkey, instructions: Vec<Instruction>, heap_frame_bytes: Option<u32>) -> Self {
+        let keypair: &'static Keypair = get_wallet_keypair();
+        // Skip-listed, or known to exist because our buy of this mint landed
+        let ata_exists = get_cached_ata(&keypair.pubkey(), &mint).map_or(false, |ata| ata.exists);
+        let ata_ix = if skip_ata_creation(&mint) || ata_exists {
+            None
+        } else {
+            // Rent for the ATA comes from the fee payer; the wallet still owns it
+            Some(create_ata(get_fee_payer_keypair(), &keypair.pubkey(), &mint))
+        };
+        let config = GLOBAL_CONFIG.get();
+        SwapComponents {
+            payer: get_fee_payer_keypair().pubkey(),
+            // With omit_cu_limit the runtime default (and max CU cost) applies
+            omit_cu_limit: config.map_or(false, |config| config.omit_cu_limit),
+            heap_frame_bytes,
+            memo_ix: config.filter(|config| config.include_memo).map(|config| memo_instruction(&config.memo_tag)),
+            ata_ix,
+            instructions,
+        }
+    }
 
-    let limit_ix = compute_budget::ComputeBudgetInstruction::set_compute_unit_limit(cu_limit);
+    /// Compute budget prefix at `cu_limit`, then memo (if it fits), create-ATA and swap instructions
+    pub fn with_cu_limit(&self, cu_limit: u32) -> Vec<Instruction> {
+        let cu_limit = (!self.omit_cu_limit).then_some(cu_limit);
+        let with_memo = self.memo_ix.as_ref().map(|memo| {
+            assemble_instructions(cu_limit.map(|limit| limit + MEMO_CU), self.heap_frame_bytes, Some(memo.clone()), self.ata_ix.clone(), self.instructions.clone())
+        });
+        match with_memo {
+            Some(ixs) if fits_packet(&ixs, &self.payer) => ixs,
+            _ => assemble_instructions(cu_limit, self.heap_frame_bytes, None, self.ata_ix.clone(), self.instructions.clone()),
+        }
+    }
+}
 
-    let ata_ix = create_ata(&keypair, &keypair.pubkey(), &mint);
+/// Base instructions at a given CU limit, for the simulation and every vendor build. Swap, create-ATA
+/// and memo instructions are identical across them: with `reuse` (reuse_swap_components) they are
+/// built once here, otherwise per call.
+fn base_instruction_builder(
+    reuse: bool,
+    mint: Pubkey,
+    swap_instructions: Vec<Instruction>,
+    heap_frame_bytes: Option<u32>,
+) -> impl Fn(u32) -> Vec<Instruction> + Sync {
+    let components = reuse.then(|| SwapComponents::new(mint, swap_instructions.clone(), heap_frame_bytes));
+    move |cu_limit| match &components {
+        Some(components) => components.with_cu_limit(cu_limit),
+        None => create_instruction_with_heap_frame(cu_limit, mint, swap_instructions.clone(), heap_frame_bytes),
+    }
+}
 
-    let mut result = vec![limit_ix, ata_ix];
+/// Compute budget prefix, then the memo and create-ATA instructions (if any), then the swap instructions
+fn assemble_instructions(
+    cu_limit: Option<u32>,
+    heap_frame_bytes: Option<u32>,
+    memo_ix: Option<Instruction>,
+    ata_ix: Option<Instruction>,
+    instructions: Vec<Instruction>,
+) -> Vec<Instruction> {
+    let mut result = compute_budget_instructions(cu_limit, heap_frame_bytes);
+    result.extend(memo_ix);
+    result.extend(ata_ix);
     result.extend(instructions);
     result
 }
 
+// SPL Memo v2; a signer-less memo of a short tag costs well under this
+const MEMO_PROGRAM_ID: Pubkey = solana_sdk::pubkey!("MemoSq4gqABAXKb96qAdyfXJv2JvXgzBRp85sG65ZCw");
+const MEMO_CU: u32 = 5_000;
+// Room left for what vendors append later (nonce advance, CU price, tip transfer)
+const VENDOR_SIZE_RESERVE: usize = 200;
+
+/// Memo instruction carrying `tag`, so our transactions are searchable on-chain
+fn memo_instruction(tag: &str) -> Instruction {
+    Instruction::new_with_bytes(MEMO_PROGRAM_ID, tag.as_bytes(), vec![])
+}
+
+/// The memo is dropped rather than pushing a transaction over the packet size limit
+pub(crate) fn fits_packet(instructions: &[Instruction], payer: &Pubkey) -> bool {
+    let tx = Transaction::new_with_payer(instructions, Some(payer));
+    bincode::serialized_size(&tx).map_or(false, |size| {
+        size as usize + VENDOR_SIZE_RESERVE <= solana_sdk::packet::PACKET_DATA_SIZE
+    })
+}
+
+/// Compute budget prefix: CU limit (unless omitted), followed by a heap frame request when set
+fn compute_budget_instructions(cu_limit: Option<u32>, heap_frame_bytes: Option<u32>) -> Vec<Instruction> {
+    let mut result: Vec<Instruction> = cu_limit
+        .map(compute_budget::ComputeBudgetInstruction::set_compute_unit_limit)
+        .into_iter()
+        .collect();
+    if let Some(bytes) = heap_frame_bytes {
+        result.push(compute_budget::ComputeBudgetInstruction::request_heap_frame(bytes));
+    }
+    result
+}
+
+// Heap frame must be a multiple of 1KB between 32KB and 256KB
+const MIN_HEAP_FRAME_BYTES: u32 = 32 * 1024;
+const MAX_HEAP_FRAME_BYTES: u32 = 256 * 1024;
+
+/// Heap frame size to request for a tx_type, if enabled in config
+pub fn heap_frame_for_tx_type(config: &crate::config_load::Config, tx_type: &str) -> Option<u32> {
+    if !config.heap_frame_tx_types.iter().any(|t| t == tx_type) {
+        return None;
+    }
+    let bytes = (config.heap_frame_bytes / 1024) * 1024;
+    Some(bytes.clamp(MIN_HEAP_FRAME_BYTES, MAX_HEAP_FRAME_BYTES))
+}
+
 /// Simulate a transaction to get compute units used and performance metrics
 pub fn simulate_transaction(
     rpc_client: &RpcClient,
     transaction: &Transaction,
 ) -> Result<Option<u64>, Box<dyn std::error::Error>> {
+    simulate_transaction_result(rpc_client, transaction).map(|result| result.units_consumed)
+}
+
+/// `simulate_transaction`, also returning the simulation's transaction error
+pub fn simulate_transaction_result(
+    rpc_client: &RpcClient,
+    transaction: &Transaction,
+) -> Result<SimulationResult, Box<dyn std::error::Error>> {
     let simulation_start = Instant::now();
     
     match rpc_client.simulate_transaction(transaction) {
@@ -469,7 +593,7 @@ pub fn simulate_transaction(
                 );
             }
             
-            Ok(units_consumed)
+            Ok(SimulationResult { units_consumed, error, simulation_time })
         }
         Err(e) => {
             let simulation_time = simulation_start.elapsed();
@@ -656,26 +780,66 @@ pub fn build_vendor_specific_transactions_parallel(
     mint: Pubkey,
     target_token_buy: u64,
     sig_str: &str,
-) -> Result<Vec<(String, Transaction)>, Box<dyn std::error::Error + Send + Sync>> {
+    tx_type: &str,
+) -> Result<(Vec<(String, Transaction)>, VendorBuildFailures), Box<dyn std::error::Error + Send + Sync>> {
+    build_vendor_specific_transactions_escalated(buy_instruction, mint, target_token_buy, sig_str, tx_type, 1.0, None)
+}
+
+/// `build_vendor_specific_transactions_parallel` with every vendor's tip and CU price scaled by
+/// `bid_multiplier` (buy chase resends), and CU prices raised to at least `leader_cu_price` times
+/// `leader_cu_price_multiplier` when given (match_leader_cu_price)
+pub fn build_vendor_specific_transactions_escalated(
+    buy_instruction: Instruction,
+    mint: Pubkey,
+    target_token_buy: u64,
+    sig_str: &str,
+    tx_type: &str,
+    bid_multiplier: f64,
+    leader_cu_price: Option<u64>,
+) -> Result<(Vec<(String, Transaction)>, VendorBuildFailures), Box<dyn std::error::Error + Send + Sync>> {
+    build_vendor_specific_transactions_multi(vec![buy_instruction], mint, target_token_buy, sig_str, tx_type, bid_multiplier, leader_cu_price)
+}
+
+/// `build_vendor_specific_transactions_escalated` for several swap instructions sharing one
+/// transaction (and so one nonce and tip), e.g. batched sells; `mint` is the one whose ATA is created
+pub fn build_vendor_specific_transactions_multi(
+    swap_instructions: Vec<Instruction>,
+    mint: Pubkey,
+    _target_token_buy: u64,
+    sig_str: &str,
+    tx_type: &str,
+    bid_multiplier: f64,
+    leader_cu_price: Option<u64>,
+) -> Result<(Vec<(String, Transaction)>, VendorBuildFailures), Box<dyn std::error::Error + Send + Sync>> {
     let build_start = Instant::now();
     let config = GLOBAL_CONFIG.get().expect("Config not initialized");
     let rpc = crate::init::initialize::GLOBAL_RPC_CLIENT.get().expect("RPC client not initialized");
+    let heap_frame_bytes = heap_frame_for_tx_type(config, tx_type);
+
+    let base_instructions = base_instruction_builder(config.reuse_swap_components, mint, swap_instructions, heap_frame_bytes);
     
     // First, get optimized compute units (same as before)
     let cu_start = Instant::now();
-    let cu_limit = get_optimized_compute_units(&buy_instruction, mint, sig_str, rpc, config)?;
+    let cu_limit = get_optimized_compute_units(base_instructions(config.cu_limit), sig_str, rpc, config)?;
     let cu_time = cu_start.elapsed();
     println!("[PROFILE][{}] Compute units optimization: {:.2?}", sig_str, cu_time);
     // let cu_limit = config.cu_limit;
     
     // Define vendor configurations for parallel building
-    let vendor_configs = vec![
+    let mut vendor_configs: Vec<(&str, VendorConfig)> = vec![
         ("rpc", VendorConfig {
             name: "rpc",
             tip_amount: (config.zeroslot_buy_tip * 1_000_000_000.0) as u64,
             cu_price: config.rpc_cu_price,
             use_jito: false,
         }),
+        // Direct-to-leader QUIC send, built like the rpc vendor's tx
+        ("quic", VendorConfig {
+            name: "quic",
+            tip_amount: (config.zeroslot_buy_tip * 1_000_000_000.0) as u64,
+            cu_price: config.rpc_cu_price,
+            use_jito: false,
+        }),
         ("zeroslot", VendorConfig {
             name: "zeroslot", 
             tip_amount: if config.zeroslot_dynamic_buy_tip {
@@ -742,28 +906,65 @@ pub fn build_vendor_specific_transactions_parallel(
             cu_price: config.temporal_cu_price,
             use_jito: false,
         }),
-    ];
+    ]
+    .into_iter()
+    .filter(|(name, _)| vendor_enabled(name, &config.send_rpc))
+    .filter(|(name, _)| crate::config_load::network::network().allows_vendor(name))
+    .filter(|(name, _)| *name != "quic" || config.quic_send)
+    .collect();
+
+    // One total bid, split between priority fee and tip by bid_tip_ratio, replaces the per-vendor values
+    if config.total_bid_sol > 0.0 {
+        let total_lamports = (config.total_bid_sol * 1_000_000_000.0) as u64;
+        for (_, vendor) in vendor_configs.iter_mut() {
+            let vendor_cu = vendor_cu_limit(cu_limit, vendor.name, config.vendor_cu_overhead);
+            let (cu_price, tip) = split_bid(total_lamports, config.bid_tip_ratio, vendor_cu, config.max_cuprice);
+            vendor.cu_price = cu_price;
+            vendor.tip_amount = if vendor.name == "rpc" || vendor.name == "quic" {
+                // No tip transfer: create_instruction_rpc prices these from tip_amount over the CU limit
+                cu_price.saturating_mul(vendor_cu as u64) / 1_000_000
+            } else {
+                tip
+            };
+        }
+    }
+    if leader_cu_price.is_some() {
+        for (_, vendor) in vendor_configs.iter_mut() {
+            vendor.cu_price = match_leader_cu_price(vendor.cu_price, leader_cu_price, config.leader_cu_price_multiplier);
+        }
+    }
+    if bid_multiplier != 1.0 {
+        for (_, vendor) in vendor_configs.iter_mut() {
+            vendor.tip_amount = escalate_bid(vendor.tip_amount, bid_multiplier);
+            vendor.cu_price = escalate_bid(vendor.cu_price, bid_multiplier);
+        }
+    }
     
     // Get the same nonce account and blockhash for all vendor transactions (prevents multiple advances)
     let nonce_start = Instant::now();
-    let (nonce_keypair, nonce_pubkey, nonce_blockhash) = get_next_nonce_account_and_blockhash(rpc)?;
+    let (nonce_pubkey, nonce_blockhash) = match NONCE_OVERRIDE.with(|pinned| pinned.get()) {
+        Some(pinned) => pinned,
+        None => {
+            let (_, pubkey, blockhash) = get_next_nonce_account_and_blockhash(rpc)?;
+            (*pubkey, blockhash)
+        }
+    };
+    let nonce_pubkey = &nonce_pubkey;
     let nonce_time = nonce_start.elapsed();
     println!("[PROFILE][{}] Nonce account setup: {:.2?}", sig_str, nonce_time);
     println!("[TX_BUILDER] Using nonce account {} for all {} vendor transactions", nonce_pubkey, vendor_configs.len());
     
     // Build all vendor versions in parallel using rayon
+    let add_cu_overhead = config.vendor_cu_overhead;
     let parallel_start = Instant::now();
-    let results: Vec<Result<(String, Transaction), Box<dyn std::error::Error + Send + Sync>>> = vendor_configs
+    let results: Vec<Result<(String, Transaction), (String, String)>> = vendor_configs
         .into_par_iter()
         .map(|(vendor_name, config)| {
             let start_time = Instant::now();
+            let cu_limit = vendor_cu_limit(cu_limit, config.name, add_cu_overhead);
             
             // Build base instruction with optimized compute units
-            let mut instructions = create_instruction(
-                cu_limit,
-                mint,
-                vec![buy_instruction.clone()],
-            );
+            let mut instructions = base_instructions(cu_limit);
             
             // Add vendor-specific tip instructions
             if config.name == "zeroslot" {
@@ -814,7 +1015,7 @@ pub fn build_vendor_specific_transactions_parallel(
                     nonce_pubkey,
                 );
             }
-            if config.name == "rpc" {
+            if config.name == "rpc" || config.name == "quic" {
                 instructions = create_instruction_rpc(
                     cu_limit,
                     config.cu_price,
@@ -837,12 +1038,7 @@ pub fn build_vendor_specific_transactions_parallel(
                 nonce_blockhash,
             ) {
                 Ok(tx) => tx,
-                Err(e) => {
-                    return Err(Box::new(std::io::Error::new(
-                        std::io::ErrorKind::Other,
-                        format!("Failed to build transaction: {}", e)
-                    )) as Box<dyn std::error::Error + Send + Sync>);
-                }
+                Err(e) => return Err((vendor_name.to_string(), e.to_string())),
             };
             
             let build_time = start_time.elapsed();
@@ -865,11 +1061,13 @@ pub fn build_vendor_specific_transactions_parallel(
     // Collect successful results
     let collect_start = Instant::now();
     let mut successful_results = Vec::new();
+    let mut failures = VendorBuildFailures::default();
     for result in results {
         match result {
             Ok((vendor, tx)) => successful_results.push((vendor, tx)),
-            Err(e) => {
-                eprintln!("[TX_BUILDER] Failed to build vendor transaction: {}", e);
+            Err((vendor, reason)) => {
+                eprintln!("[TX_BUILDER] Failed to build {} transaction: {}", vendor, reason);
+                failures.0.push((vendor, reason));
             }
         }
     }
@@ -884,13 +1082,31 @@ pub fn build_vendor_specific_transactions_parallel(
         total_time
     );
     
-    Ok(successful_results)
+    Ok((successful_results, failures))
+}
+
+/// Per-vendor reasons a vendor transaction could not be built, in vendor order
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VendorBuildFailures(pub Vec<(String, String)>);
+
+impl VendorBuildFailures {
+    /// One-line diagnosis, e.g. `zeroslot: <reason>; nextblock: <reason>`
+    pub fn summary(&self) -> String {
+        if self.0.is_empty() {
+            return "no vendors enabled in send_rpc".to_string();
+        }
+        self.0
+            .iter()
+            .map(|(vendor, reason)| format!("{}: {}", vendor, reason))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
 }
 
 /// Helper function to get optimized compute units (extracted from build_optimized_transaction)
+/// `base_instructions` are the swap transaction's instructions at `config.cu_limit`, before any vendor's
 fn get_optimized_compute_units(
-    buy_instruction: &Instruction,
-    mint: Pubkey,
+    base_instructions: Vec<Instruction>,
     sig_str: &str,
     rpc: &RpcClient,
     config: &crate::config_load::Config,
@@ -899,11 +1115,7 @@ fn get_optimized_compute_units(
     
     // Build initial transaction for simulation
     let build_sim_start = Instant::now();
-    let mut initial_instructions = create_instruction(
-        config.cu_limit,
-        mint,
-        vec![buy_instruction.clone()],
-    );
+    let mut initial_instructions = base_instructions;
     
     initial_instructions = create_instruction_zeroslot(
         initial_instructions,
@@ -929,17 +1141,39 @@ fn get_optimized_compute_units(
     let build_sim_time = build_sim_start.elapsed();
     println!("[PROFILE][{}] Simulation transaction build: {:.2?}", sig_str, build_sim_time);
     
-    // Simulate to get compute units
+    // Simulate to get compute units (bounded concurrency; on queue or RPC timeout fall back to config.cu_limit)
     let sim_start = Instant::now();
-    let simulated_units = match simulate_transaction(rpc, &tx) {
-        Ok(Some(units)) => Some(units),
-        Ok(None) => None,
-        Err(e) => {
+    let sim_timeout = (config.simulation_timeout_ms > 0).then(|| std::time::Duration::from_millis(config.simulation_timeout_ms));
+    let simulated_units = match crate::build_tx::simulation::acquire_simulation_permit(config.simulation_concurrency, sim_timeout) {
+        Some(_permit) => {
+            let sim_rpc = crate::build_tx::simulation::simulation_rpc_client(rpc, &config.rpc_endpoint, config.simulation_timeout_ms);
+            match simulate_transaction_result(sim_rpc, &tx) {
+                Ok(result) => {
+                    if config.simulate_on_send_rpc {
+                        crate::build_tx::simulation::check_on_send_node(&tx, result.error.as_ref(), config, sig_str);
+                    }
+                    result.units_consumed
+                }
+                Err(e) => {
+                    if sim_timeout.map_or(false, |timeout| sim_start.elapsed() >= timeout) {
+                        crate::build_tx::simulation::record_simulation_rpc_timeout();
+                    }
+                    println!(
+                        "[{}] - [TX_BUILDER] Simulation failed for sig {}: {}",
+                        Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+                        sig_str,
+                        e
+                    );
+                    None
+                }
+            }
+        }
+        None => {
             println!(
-                "[{}] - [TX_BUILDER] Simulation failed for sig {}: {}",
+                "[{}] - [TX_BUILDER] Simulation queue full for sig {} after {:.2?}, skipping simulation",
                 Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"),
                 sig_str,
-                e
+                sim_start.elapsed()
             );
             None
         }
@@ -977,6 +1211,38 @@ fn get_optimized_compute_units(
     Ok(final_cu)
 }
 
+/// Whether a vendor can actually send; the rpc vendor needs at least one `sendRPC` endpoint,
+/// otherwise it would join every race without sending anything
+pub fn vendor_enabled(vendor_name: &str, send_rpc: &[String]) -> bool {
+    match vendor_name {
+        "rpc" => send_rpc.iter().any(|url| !url.trim().is_empty()),
+        _ => true,
+    }
+}
+
+/// Approximate CU cost of the extra instructions each vendor prepends
+const CU_OVERHEAD_ADVANCE_NONCE: u32 = 150;
+const CU_OVERHEAD_TIP_TRANSFER: u32 = 150;
+const CU_OVERHEAD_COMPUTE_BUDGET: u32 = 150;
+
+/// Extra compute units needed by a vendor's own instructions (nonce advance, tip transfer, CU price)
+fn vendor_cu_overhead(vendor_name: &str) -> u32 {
+    match vendor_name {
+        // rpc and quic (built like rpc) pay via CU price only, no tip transfer
+        "rpc" | "quic" => CU_OVERHEAD_ADVANCE_NONCE + CU_OVERHEAD_COMPUTE_BUDGET,
+        _ => CU_OVERHEAD_ADVANCE_NONCE + CU_OVERHEAD_TIP_TRANSFER + CU_OVERHEAD_COMPUTE_BUDGET,
+    }
+}
+
+/// CU limit for a specific vendor, adding its instruction overhead when enabled
+fn vendor_cu_limit(cu_limit: u32, vendor_name: &str, add_overhead: bool) -> u32 {
+    if add_overhead {
+        cu_limit.saturating_add(vendor_cu_overhead(vendor_name))
+    } else {
+        cu_limit
+    }
+}
+
 /// Configuration for building vendor-specific transactions
 #[derive(Clone)]
 struct VendorConfig {
@@ -992,10 +1258,203 @@ pub async fn build_vendor_specific_transactions(
     mint: Pubkey,
     target_token_buy: u64,
     sig_str: &str,
+    tx_type: &str,
 ) -> Result<Vec<(String, Transaction)>, Box<dyn std::error::Error + Send + Sync>> {
     // Use the parallel version instead
-    build_vendor_specific_transactions_parallel(buy_instruction, mint, target_token_buy, sig_str)
+    build_vendor_specific_transactions_parallel(buy_instruction, mint, target_token_buy, sig_str, tx_type)
+        .map(|(transactions, _)| transactions)
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_nonce_pins_only_inside_the_build() {
+        let (nonce, blockhash) = (Pubkey::new_unique(), solana_sdk::hash::Hash::new_unique());
+        let seen = with_nonce(nonce, blockhash, || NONCE_OVERRIDE.with(|pinned| pinned.get()));
+        assert_eq!(seen, Some((nonce, blockhash)));
+        assert_eq!(NONCE_OVERRIDE.with(|pinned| pinned.get()), None);
+
+        // A panicking build must not leave later builds on this thread pinned
+        let panicked = std::panic::catch_unwind(|| with_nonce(nonce, blockhash, || panic!("build failed")));
+        assert!(panicked.is_err());
+        assert_eq!(NONCE_OVERRIDE.with(|pinned| pinned.get()), None);
+    }
+
+    #[test]
+    fn test_vendor_cu_limit_overhead() {
+        let base = 100_000;
+        let zeroslot = vendor_cu_limit(base, "zeroslot", true);
+        let rpc = vendor_cu_limit(base, "rpc", true);
+        assert_eq!(zeroslot - base, vendor_cu_overhead("zeroslot"));
+        assert_eq!(rpc - base, vendor_cu_overhead("rpc"));
+        // rpc has no tip transfer, so it differs from tipped vendors by exactly that overhead
+        assert_eq!(zeroslot - rpc, CU_OVERHEAD_TIP_TRANSFER);
+        assert_eq!(vendor_cu_limit(base, "nextblock", true), zeroslot);
+        // quic sends the rpc-style transaction, so it has the same overhead
+        assert_eq!(vendor_cu_limit(base, "quic", true), rpc);
+    }
+
+    #[test]
+    fn test_heap_frame_instruction_toggle() {
+        let heap_ix = compute_budget::ComputeBudgetInstruction::request_heap_frame(256 * 1024);
+
+        let without = compute_budget_instructions(Some(200_000), None);
+        assert_eq!(without.len(), 1);
+        assert!(!without.contains(&heap_ix));
+
+        let with = compute_budget_instructions(Some(200_000), Some(256 * 1024));
+        assert_eq!(with.len(), 2);
+        assert_eq!(with[1], heap_ix);
+
+        // Vendors prepend [advance_nonce, tip, price]; nonce advance must stay first
+        let nonce = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let advance_nonce_ix = solana_program::system_instruction::advance_nonce_account(&nonce, &authority);
+        let mut wrapped = vec![advance_nonce_ix.clone()];
+        wrapped.extend(with);
+        assert_eq!(wrapped[0], advance_nonce_ix);
+        assert_eq!(wrapped[2], heap_ix);
+    }
+
+    #[test]
+    fn test_vendor_cu_limit_disabled() {
+        assert_eq!(vendor_cu_limit(100_000, "zeroslot", false), 100_000);
+        assert_eq!(vendor_cu_limit(u32::MAX, "zeroslot", true), u32::MAX);
+    }
+
+    #[test]
+    fn test_empty_send_rpc_disables_rpc_vendor() {
+        assert!(!vendor_enabled("rpc", &[]));
+        assert!(!vendor_enabled("rpc", &["  ".to_string()]));
+        assert!(vendor_enabled("rpc", &["http://127.0.0.1:8899".to_string()]));
+        // Other vendors are unaffected by sendRPC
+        assert!(vendor_enabled("zeroslot", &[]));
+
+        let vendors = ["rpc", "zeroslot", "temporal"];
+        let enabled: Vec<&str> = vendors.iter().copied().filter(|v| vendor_enabled(v, &[])).collect();
+        assert_eq!(enabled, vec!["zeroslot", "temporal"]);
+    }
+
+    #[test]
+    fn test_sign_message_with_separate_fee_payer() {
+        let fee_payer = Keypair::new();
+        let authority = Keypair::new();
+        let ix = solana_program::system_instruction::transfer(&authority.pubkey(), &Pubkey::new_unique(), 1);
+        let blockhash = solana_sdk::hash::Hash::new_unique();
 
+        let message = solana_sdk::message::Message::new_with_blockhash(&[ix.clone()], Some(&fee_payer.pubkey()), &blockhash);
+        let tx = sign_message(message, &[&fee_payer, &authority], signing_key_from_keypair).unwrap();
+        assert_eq!(tx.signatures.len(), 2);
+        assert_eq!(tx.message.account_keys[0], fee_payer.pubkey());
+        assert!(tx.verify().is_ok());
 
+        // Same key as payer and authority signs once
+        let message = solana_sdk::message::Message::new_with_blockhash(&[ix.clone()], Some(&authority.pubkey()), &blockhash);
+        let tx = sign_message(message, &[&authority, &authority], signing_key_from_keypair).unwrap();
+        assert_eq!(tx.signatures.len(), 1);
+        assert!(tx.verify().is_ok());
+
+        // Missing authority is an error, not a half-signed transaction
+        let message = solana_sdk::message::Message::new_with_blockhash(&[ix], Some(&fee_payer.pubkey()), &blockhash);
+        assert!(sign_message(message, &[&fee_payer], signing_key_from_keypair).is_err());
+    }
+
+    #[test]
+    fn test_skip_ata_creation_shrinks_transaction() {
+        let payer = Keypair::new();
+        let mint = Pubkey::new_unique();
+        let swap_ix = solana_program::system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1);
+        let ata_ix = create_ata(&payer, &payer.pubkey(), &mint);
+
+        let with_ata = assemble_instructions(Some(200_000), None, None, Some(ata_ix.clone()), vec![swap_ix.clone()]);
+        let without_ata = assemble_instructions(Some(200_000), None, None, None, vec![swap_ix.clone()]);
+        assert_eq!(with_ata.len(), without_ata.len() + 1);
+        assert!(with_ata.contains(&ata_ix));
+        assert!(!without_ata.contains(&ata_ix));
+        assert_eq!(without_ata.last(), Some(&swap_ix));
+
+        let size = |ixs: &[Instruction]| {
+            let tx = Transaction::new_with_payer(ixs, Some(&payer.pubkey()));
+            bincode::serialize(&tx).unwrap().len()
+        };
+        assert!(size(&without_ata) < size(&with_ata));
+
+        // Nothing skip-listed by default
+        assert!(!skip_ata_creation(&mint));
+    }
+
+    #[test]
+    fn test_memo_instruction_carries_tag() {
+        let payer = Keypair::new();
+        let swap_ix = solana_program::system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1);
+        let memo = memo_instruction("copyrust");
+        assert_eq!(memo.program_id, MEMO_PROGRAM_ID);
+        assert_eq!(memo.data, b"copyrust");
+
+        let ixs = assemble_instructions(Some(200_000), None, Some(memo.clone()), None, vec![swap_ix.clone()]);
+        assert_eq!(ixs.iter().filter(|ix| ix.program_id == MEMO_PROGRAM_ID).count(), 1);
+        assert!(ixs.contains(&memo));
+        assert_eq!(ixs.last(), Some(&swap_ix));
+        assert!(fits_packet(&ixs, &payer.pubkey()));
+
+        // An oversized transaction fails the size guard, so the memo would be dropped
+        let huge = memo_instruction(&"x".repeat(1200));
+        assert!(!fits_packet(&[huge, swap_ix], &payer.pubkey()));
+    }
+
+    #[test]
+    fn test_omit_cu_limit_keeps_ordering() {
+        let payer = Keypair::new();
+        let mint = Pubkey::new_unique();
+        let swap_ix = solana_program::system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1);
+        let ata_ix = create_ata(&payer, &payer.pubkey(), &mint);
+        let limit_ix = compute_budget::ComputeBudgetInstruction::set_compute_unit_limit(200_000);
+        let heap_ix = compute_budget::ComputeBudgetInstruction::request_heap_frame(64 * 1024);
+
+        let with = assemble_instructions(Some(200_000), Some(64 * 1024), None, Some(ata_ix.clone()), vec![swap_ix.clone()]);
+        assert_eq!(with, vec![limit_ix.clone(), heap_ix.clone(), ata_ix.clone(), swap_ix.clone()]);
+
+        let omitted = assemble_instructions(None, Some(64 * 1024), None, Some(ata_ix.clone()), vec![swap_ix.clone()]);
+        assert_eq!(omitted, vec![heap_ix, ata_ix, swap_ix]);
+        assert!(!omitted.contains(&limit_ix));
+        assert!(compute_budget_instructions(None, None).is_empty());
+    }
+
+    #[test]
+    fn test_reused_components_build_identical_transactions() {
+        let _ = crate::init::wallet_loader::set_wallet_keypair_global(Keypair::new());
+        let wallet = get_wallet_keypair();
+        let mint = Pubkey::new_unique();
+        let swap_ix = solana_program::system_instruction::transfer(&wallet.pubkey(), &Pubkey::new_unique(), 1);
+
+        // reuse_swap_components on and off build the same transaction at every vendor's CU limit
+        let reused = base_instruction_builder(true, mint, vec![swap_ix.clone()], Some(64 * 1024));
+        let rebuilt = base_instruction_builder(false, mint, vec![swap_ix.clone()], Some(64 * 1024));
+        let blockhash = solana_sdk::hash::Hash::new_unique();
+        let tx = |ixs: &[Instruction]| Transaction::new_signed_with_payer(ixs, Some(&wallet.pubkey()), &[wallet], blockhash);
+        for vendor in ["rpc", "zeroslot", "nextblock"] {
+            let cu_limit = vendor_cu_limit(200_000, vendor, true);
+            assert_eq!(reused(cu_limit), rebuilt(cu_limit));
+            assert_eq!(tx(&reused(cu_limit)), tx(&rebuilt(cu_limit)));
+            assert!(reused(cu_limit).contains(&swap_ix));
+        }
+        // Repeated builds from the shared components don't drift
+        assert_eq!(reused(300_000), reused(300_000));
+    }
+
+    #[test]
+    fn test_vendor_build_failures_summary() {
+        let failures = VendorBuildFailures(vec![
+            ("zeroslot".to_string(), "nonce account has no blockhash".to_string()),
+            ("nextblock".to_string(), "transaction too large: 1290 > 1232".to_string()),
+            ("rpc".to_string(), "simulation aborted".to_string()),
+        ]);
+        assert_eq!(
+            failures.summary(),
+            "zeroslot: nonce account has no blockhash; nextblock: transaction too large: 1290 > 1232; rpc: simulation aborted"
+        );
+        assert_eq!(VendorBuildFailures::default().summary(), "no vendors enabled in send_rpc");
+    }
+}
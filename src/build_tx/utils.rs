@@ -9,10 +9,98 @@ use solana_client::rpc_filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType};
 use solana_client::rpc_config::RpcProgramAccountsConfig;
 use solana_client::rpc_config::RpcAccountInfoConfig;
 use solana_client::rpc_client::RpcClient;
+use solana_client::client_error::{ClientError, ClientErrorKind};
 use solana_sdk::commitment_config::CommitmentConfig;
 use crate::init::initialize::GLOBAL_RPC_CLIENT;
 use std::error::Error;
 use std::convert::TryInto;
+use std::time::{Duration, Instant};
+
+/// Errors raised while building instructions from on-chain account data
+#[derive(Debug, PartialEq)]
+pub enum BuildError {
+    AccountNotFound(String),
+    AllZeros,
+    TooShort { len: usize, min_len: usize },
+    Deserialize(String),
+}
+
+impl std::fmt::Display for BuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BuildError::AccountNotFound(msg) => write!(f, "Account not found: {}", msg),
+            BuildError::AllZeros => write!(f, "Account data is all zeros (account missing or closed)"),
+            BuildError::TooShort { len, min_len } => write!(f, "Account data too short: {} bytes (need {})", len, min_len),
+            BuildError::Deserialize(msg) => write!(f, "Failed to deserialize account data: {}", msg),
+        }
+    }
+}
+
+impl Error for BuildError {}
+
+/// Reject account data that is too short or all zeros (account doesn't exist or was closed)
+pub fn validate_account_data(data: &[u8], min_len: usize) -> Result<&[u8], BuildError> {
+    if data.len() < min_len {
+        return Err(BuildError::TooShort { len: data.len(), min_len });
+    }
+    if data.iter().all(|&b| b == 0) {
+        return Err(BuildError::AllZeros);
+    }
+    Ok(data)
+}
+
+// Backoff before retry n is n * this; kept tiny so retries fit inside the opportunity window
+const RPC_RETRY_BACKOFF: Duration = Duration::from_millis(10);
+
+/// Run `read` up to `attempts` times while its error `is_transient`, stopping early when the next
+/// backoff would overrun `budget`. Without `backoff` (async runtime threads) retries follow at once.
+fn retry_within<T, E>(
+    attempts: u32,
+    budget: Duration,
+    backoff: bool,
+    is_transient: impl Fn(&E) -> bool,
+    mut read: impl FnMut() -> Result<T, E>,
+) -> Result<T, E> {
+    let start = Instant::now();
+    let mut attempt = 1;
+    loop {
+        match read() {
+            Ok(value) => return Ok(value),
+            Err(e) => {
+                let delay = if backoff { RPC_RETRY_BACKOFF * attempt } else { Duration::ZERO };
+                if !is_transient(&e) || attempt >= attempts || start.elapsed() + delay > budget {
+                    return Err(e);
+                }
+                if !delay.is_zero() {
+                    std::thread::sleep(delay);
+                }
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// Transport failures and timeouts; RPC answers such as AccountNotFound would only repeat
+pub fn is_transient_rpc_error(error: &ClientError) -> bool {
+    match error.kind() {
+        ClientErrorKind::Io(_) => true,
+        ClientErrorKind::Reqwest(e) => {
+            e.is_timeout() || e.is_connect() || e.status().map_or(false, |s| s.is_server_error() || s.as_u16() == 429)
+        }
+        _ => false,
+    }
+}
+
+/// Retry a transiently failed RPC account read (rpc_read_attempts / rpc_read_budget_ms from config).
+/// On a tokio runtime thread the retries don't sleep, so the runtime's other tasks aren't stalled.
+pub fn with_rpc_retry<T>(read: impl FnMut() -> Result<T, ClientError>) -> Result<T, ClientError> {
+    let (attempts, budget_ms) = crate::config_load::GLOBAL_CONFIG
+        .get()
+        .map(|c| (c.rpc_read_attempts, c.rpc_read_budget_ms))
+        .unwrap_or((crate::config_load::default_rpc_read_attempts(), crate::config_load::default_rpc_read_budget_ms()));
+    let backoff = tokio::runtime::Handle::try_current().is_err();
+    retry_within(attempts.max(1), Duration::from_millis(budget_ms), backoff, is_transient_rpc_error, read)
+}
 
 #[derive(PartialEq, Copy, Clone)]
 pub enum SwapDirection {
@@ -147,7 +235,7 @@ pub fn get_pool_vault_amount(
     let rpc_client = GLOBAL_RPC_CLIENT.get().expect("RPC client not initialized");
 
     // Add proper error handling to see what's going wrong
-    let res = match rpc_client.get_multiple_accounts_with_commitment(&keys, CommitmentConfig::processed()) {
+    let res = match with_rpc_retry(|| rpc_client.get_multiple_accounts_with_commitment(&keys, CommitmentConfig::processed())) {
         Ok(response) => response,
         Err(e) => {
             eprintln!("!!!!!!RPC ERROR: Failed to get multiple accounts: {:?}", e);
@@ -198,3 +286,71 @@ pub fn get_pool_vault_amount(
 
     Ok((base_amount, quote_amount))
 } 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_account_data_all_zeros() {
+        let data = vec![0u8; 64];
+        assert_eq!(validate_account_data(&data, 16), Err(BuildError::AllZeros));
+    }
+
+    #[test]
+    fn test_validate_account_data_too_short() {
+        let data = vec![1u8; 8];
+        assert_eq!(validate_account_data(&data, 16), Err(BuildError::TooShort { len: 8, min_len: 16 }));
+        // Too short takes precedence over all zeros
+        assert_eq!(validate_account_data(&[], 1), Err(BuildError::TooShort { len: 0, min_len: 1 }));
+    }
+
+    #[test]
+    fn test_validate_account_data_ok() {
+        let mut data = vec![0u8; 32];
+        data[5] = 7;
+        assert_eq!(validate_account_data(&data, 32).unwrap().len(), 32);
+    }
+
+    #[test]
+    fn test_retry_within_recovers_from_transient_failure() {
+        let mut calls = 0;
+        let result: Result<u32, &str> = retry_within(2, Duration::from_millis(100), true, |_| true, || {
+            calls += 1;
+            if calls == 1 { Err("connection reset") } else { Ok(42) }
+        });
+        assert_eq!(result, Ok(42));
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn test_deterministic_rpc_errors_not_retried() {
+        let timeout = ClientError::from(std::io::Error::new(std::io::ErrorKind::TimedOut, "timed out"));
+        let not_found = ClientError::from(ClientErrorKind::RpcError(
+            solana_client::rpc_request::RpcError::ForUser("AccountNotFound: pubkey=11111111111111111111111111111111".to_string()),
+        ));
+        assert!(is_transient_rpc_error(&timeout));
+        assert!(!is_transient_rpc_error(&not_found));
+
+        let mut calls = 0;
+        let result: Result<u32, &str> = retry_within(3, Duration::from_millis(100), false, |e| *e != "not found", || {
+            calls += 1;
+            Err("not found")
+        });
+        assert_eq!(result, Err("not found"));
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn test_retry_within_respects_attempts_and_budget() {
+        let mut calls = 0;
+        let result: Result<u32, &str> = retry_within(3, Duration::from_millis(100), true, |_| true, || { calls += 1; Err("down") });
+        assert_eq!(result, Err("down"));
+        assert_eq!(calls, 3);
+
+        // No budget left for even the first backoff: fail fast after one try
+        let mut calls = 0;
+        let _: Result<u32, &str> = retry_within(3, Duration::ZERO, true, |_| true, || { calls += 1; Err("down") });
+        assert_eq!(calls, 1);
+    }
+}
@@ -1,3 +1,5 @@
+pub mod network;
+
 use once_cell::sync::OnceCell;
 use serde::Deserialize;
 use std::fs;
@@ -11,7 +13,8 @@ pub struct Config {
     pub grpc_endpoint2: String,
     #[serde(rename = "arpcEndpoint")]
     pub arpc_endpoint: String,
-    #[serde(rename = "rpcEndpoint")]
+    // Empty uses the public RPC of `network`
+    #[serde(rename = "rpcEndpoint", default)]
     pub rpc_endpoint: String,
     #[serde(rename = "sendRPC")]
     pub send_rpc: Vec<String>,
@@ -41,6 +44,8 @@ pub struct Config {
     pub mints_monitor: Vec<String>,
     #[serde(rename = "nonceAc")]
     pub nonce_ac: Vec<String>,
+    // DEX toggles, 0 = off / nonzero = on: pumpMkts gates pump.fun + PumpSwap copies, rayCpmm gates
+    // Raydium CPMM copies; the meteora toggles have no copy path to gate yet
     #[serde(rename = "meteoraMkts")]
     pub meteora_mkts: u8,
     #[serde(rename = "pumpMkts")]
@@ -53,7 +58,9 @@ pub struct Config {
     pub cu_price_percentile: f64,
     #[serde(rename = "cuLimit")]
     pub cu_limit: u32,
-    #[serde(rename = "maxCUPrice")]
+    // Ceiling for every vendor's CU price, rpc/quic included (0 = none, the default). Unused before
+    // min_cu_price was added, so a value kept from older configs now caps bids
+    #[serde(rename = "maxCUPrice", default)]
     pub max_cuprice: u64,
     #[serde(rename = "totalVolumeFilter")]
     pub total_volume_filter: u64,
@@ -113,6 +120,9 @@ pub struct Config {
     pub nextblock_dynamic_buy_tip: bool,
     #[serde(rename = "waitTime")]
     pub wait_time: f64,
+    // Randomize the sell wait within ±this percent of waitTime (0 = exact)
+    #[serde(rename = "wait_time_jitter_pct", default)]
+    pub wait_time_jitter_pct: f64,
     // BlockRazor configuration
     #[serde(rename = "blockrazor_url")]
     pub blockrazor_url: String,
@@ -165,6 +175,567 @@ pub struct Config {
     pub tip_stream: String,
     #[serde(rename = "dynamic_tip_percentile")]
     pub dynamic_tip_percentile: u8,
+    // Compute unit configuration
+    #[serde(rename = "vendor_cu_overhead", default)]
+    pub vendor_cu_overhead: bool,
+    // Send runtime configuration
+    #[serde(rename = "send_runtime_threads", default = "default_send_runtime_threads")]
+    pub send_runtime_threads: usize,
+    #[serde(rename = "send_runtime_cores", default)]
+    pub send_runtime_cores: Vec<usize>,
+    // Core set per thread pool (arpc_workers, triton_workers, send_runtime); overrides the built-in
+    // 5-7 / 2-4 / unpinned sets and send_runtime_cores. Keep send_runtime apart from the worker cores
+    #[serde(rename = "core_affinity", default)]
+    pub core_affinity: std::collections::HashMap<String, Vec<usize>>,
+    // Drop transactions signed by our own wallet from the buy-trigger path
+    #[serde(rename = "filter_self_transactions", default = "default_true")]
+    pub filter_self_transactions: bool,
+    // Expire pending GLOBAL_TX_MAP entries this many slots after send (0 disables)
+    #[serde(rename = "max_position_slots", default)]
+    pub max_position_slots: u64,
+    // Request a larger heap frame for these tx_types (e.g. ["ray_cpmm"])
+    #[serde(rename = "heap_frame_tx_types", default)]
+    pub heap_frame_tx_types: Vec<String>,
+    #[serde(rename = "heap_frame_bytes", default = "default_heap_frame_bytes")]
+    pub heap_frame_bytes: u32,
+    // Control interface (pause/resume pipelines), empty disables
+    #[serde(rename = "control_addr", default)]
+    pub control_addr: String,
+    // gRPC response compression for subscriptions: none/gzip/zstd
+    #[serde(rename = "grpc_compression", default)]
+    pub grpc_compression: String,
+    // Triton feed dedup window and capacity (oldest entries evicted beyond capacity)
+    #[serde(rename = "feed_dedup_ttl_secs", default = "default_feed_dedup_ttl_secs")]
+    pub feed_dedup_ttl_secs: u64,
+    #[serde(rename = "feed_dedup_capacity", default = "default_feed_dedup_capacity")]
+    pub feed_dedup_capacity: usize,
+    // Minimum time between sends per vendor (ms, 0/absent disables); excess sends "skip" or "queue"
+    #[serde(rename = "vendor_min_send_interval_ms", default)]
+    pub vendor_min_send_interval_ms: std::collections::HashMap<String, u64>,
+    #[serde(rename = "vendor_throttle_mode", default)]
+    pub vendor_throttle_mode: String,
+    #[serde(rename = "vendor_throttle_max_wait_ms", default = "default_vendor_throttle_max_wait_ms")]
+    pub vendor_throttle_max_wait_ms: u64,
+    // Connections per host for each HTTP vendor client (flashblock, temporal, zeroslot, astralane)
+    #[serde(rename = "http_pool_size", default = "default_http_pool_size")]
+    pub http_pool_size: usize,
+    // Vendor launch order for parallel sends (unlisted vendors after), stagger between launches (0 = all at once)
+    #[serde(rename = "vendor_launch_order", default)]
+    pub vendor_launch_order: Vec<String>,
+    #[serde(rename = "vendor_launch_stagger_ms", default)]
+    pub vendor_launch_stagger_ms: u64,
+    #[serde(rename = "vendor_launch_skip_after_accept", default)]
+    pub vendor_launch_skip_after_accept: bool,
+    // Per-vendor tip account overrides (vendor -> pubkeys); vendors not listed use the built-in lists
+    #[serde(rename = "vendor_tip_accounts", default)]
+    pub vendor_tip_accounts: std::collections::HashMap<String, Vec<String>>,
+    // Per-vendor minimum tip in lamports (vendor -> floor); vendors not listed use the published minimums
+    #[serde(rename = "vendor_min_tips", default)]
+    pub vendor_min_tips: std::collections::HashMap<String, u64>,
+    // Per-step timing on the vendor send path (blockrazor/flashblock); off = no clock reads
+    #[serde(rename = "send_profiling", default)]
+    pub send_profiling: bool,
+    // Max concurrent CU simulations (0 = unlimited) and timeout for queue wait + RPC call (0 = none)
+    #[serde(rename = "simulation_concurrency", default)]
+    pub simulation_concurrency: usize,
+    #[serde(rename = "simulation_timeout_ms", default)]
+    pub simulation_timeout_ms: u64,
+    // Create+initialize missing nonce accounts (from nonce-account.json) during the startup check
+    #[serde(rename = "create_missing_nonce_accounts", default)]
+    pub create_missing_nonce_accounts: bool,
+    // Omit the create-ATA instruction for all mints, or only for listed mints (their ATAs are verified at startup)
+    #[serde(rename = "skip_ata_creation", default)]
+    pub skip_ata_creation: bool,
+    #[serde(rename = "skip_ata_mints", default)]
+    pub skip_ata_mints: Vec<String>,
+    // Skip buys whose expected token output (raw units) is below this (0 disables)
+    #[serde(rename = "min_token_out", default)]
+    pub min_token_out: u64,
+    // Birdeye prices (needs birdEyeApi): cache TTL, min gap between requests per the plan's rate limit,
+    // and skip buys whose expected output is worth less than this many USD (0 disables)
+    #[serde(rename = "birdeye_price_ttl_secs", default = "default_birdeye_price_ttl_secs")]
+    pub birdeye_price_ttl_secs: u64,
+    #[serde(rename = "birdeye_min_request_interval_ms", default = "default_birdeye_min_request_interval_ms")]
+    pub birdeye_min_request_interval_ms: u64,
+    #[serde(rename = "min_token_out_usd", default)]
+    pub min_token_out_usd: f64,
+    // Sanity caps: refuse any buy or sell whose token amount (raw units) or Birdeye USD value
+    // exceeds these; guards against parse bugs reading garbage as amounts (0 disables)
+    #[serde(rename = "max_token_amount", default)]
+    pub max_token_amount: u64,
+    #[serde(rename = "max_trade_usd", default)]
+    pub max_trade_usd: f64,
+    // Max instructions the arpc worker scans per transaction looking for a buy trigger (0 = no cap)
+    #[serde(rename = "max_instructions_to_scan", default = "default_max_instructions_to_scan")]
+    pub max_instructions_to_scan: usize,
+    // Skip sending non-nonce transactions whose blockhash is past its lastValidBlockHeight
+    #[serde(rename = "skip_expired_blockhash_sends", default = "default_true")]
+    pub skip_expired_blockhash_sends: bool,
+    // Optional fee-payer keypair file (JSON byte array); pays fees/ATA rent while the wallet signs the swap
+    #[serde(rename = "fee_payer_key", default)]
+    pub fee_payer_key: String,
+    // Age after which stale GLOBAL_TX_MAP entries are purged; open positions are exempt
+    #[serde(rename = "tx_map_purge_secs", default = "default_tx_map_purge_secs")]
+    pub tx_map_purge_secs: u64,
+    // Shared RPC budget: minimum ms between rate-limited RPC calls (e.g. migration pool lookups)
+    #[serde(rename = "rpc_min_interval_ms", default = "default_rpc_min_interval_ms")]
+    pub rpc_min_interval_ms: u64,
+    // Monitoring ARPC endpoints, one subscription each (empty = arpcEndpoint) and programs to monitor (empty = built-in list)
+    #[serde(rename = "monitoring_arpc_endpoints", default)]
+    pub monitoring_arpc_endpoints: Vec<String>,
+    #[serde(rename = "monitoring_programs", default)]
+    pub monitoring_programs: Vec<String>,
+    // Floor for vendor CU prices (0 = none); together with maxCUPrice clamps into [min, max]
+    #[serde(rename = "min_cu_price", default)]
+    pub min_cu_price: u64,
+    // Keep the last N vendor rejections (vendor, error kind, mint, tip, CU, slot) for the control API (0 disables)
+    #[serde(rename = "rejection_log_size", default)]
+    pub rejection_log_size: usize,
+    // Accepted swap discriminators per program type (ray_launch/pump_swap/pumpfun/ray_cpmm); replaces the built-in set
+    #[serde(rename = "program_discriminators", default)]
+    pub program_discriminators: std::collections::HashMap<String, Vec<Vec<u8>>>,
+    // Minimum swap instruction data length per program type; shorter instructions are skipped before any RPC read
+    #[serde(rename = "program_min_data_len", default)]
+    pub program_min_data_len: std::collections::HashMap<String, usize>,
+    // When a transaction has swaps for several program types, build from the first listed here
+    // (ray_launch/pump_swap/pumpfun/ray_cpmm); unlisted types follow in instruction order
+    #[serde(rename = "program_priority", default)]
+    pub program_priority: Vec<String>,
+    // Heartbeat log interval (uptime, feed message ages, open positions, balance); 0 disables
+    #[serde(rename = "heartbeat_interval_secs", default)]
+    pub heartbeat_interval_secs: u64,
+    // At startup, rebuild positions from wallet token balances (>= min_token_out) and sell them
+    #[serde(rename = "reconcile_on_startup", default)]
+    pub reconcile_on_startup: bool,
+    // Tracked wallets younger than this / with fewer txs are reported as new (0 disables each);
+    // with filter_new_leaders their transactions stop triggering buys
+    #[serde(rename = "min_leader_age_secs", default)]
+    pub min_leader_age_secs: u64,
+    #[serde(rename = "min_leader_history_txs", default)]
+    pub min_leader_history_txs: usize,
+    #[serde(rename = "filter_new_leaders", default)]
+    pub filter_new_leaders: bool,
+    #[serde(rename = "leader_age_check_interval_secs", default = "default_leader_age_check_interval_secs")]
+    pub leader_age_check_interval_secs: u64,
+    // Add an SPL Memo carrying memo_tag to each built transaction (dropped if it would exceed the size limit)
+    #[serde(rename = "include_memo", default)]
+    pub include_memo: bool,
+    #[serde(rename = "memo_tag", default = "default_memo_tag")]
+    pub memo_tag: String,
+    // Attempts for builder RPC account reads, bounded by a total latency budget
+    #[serde(rename = "rpc_read_attempts", default = "default_rpc_read_attempts")]
+    pub rpc_read_attempts: u32,
+    #[serde(rename = "rpc_read_budget_ms", default = "default_rpc_read_budget_ms")]
+    pub rpc_read_budget_ms: u64,
+    // Resolve vendor tip transfer inputs once at startup instead of per trade
+    #[serde(rename = "precompute_tip_instructions", default = "default_true")]
+    pub precompute_tip_instructions: bool,
+    // "sequential": try send RPCs in order until one succeeds; "parallel": send to all, first success wins
+    #[serde(rename = "send_rpc_mode", default = "default_send_rpc_mode")]
+    pub send_rpc_mode: String,
+    // Stop routing to a vendor once this % of its last 20 sends failed (0 disables); probe it every vendor_probe_interval_secs
+    #[serde(rename = "vendor_auto_disable_failure_pct", default)]
+    pub vendor_auto_disable_failure_pct: f64,
+    #[serde(rename = "vendor_probe_interval_secs", default = "default_vendor_probe_interval_secs")]
+    pub vendor_probe_interval_secs: u64,
+    // Skip set_compute_unit_limit so the runtime default applies (CU price is still set; max CU cost rises)
+    #[serde(rename = "omit_cu_limit", default)]
+    pub omit_cu_limit: bool,
+    // Use migration accounts from monitoring when present, skipping the sell-time RPC migration check
+    #[serde(rename = "skip_migration_rpc_check", default = "default_true")]
+    pub skip_migration_rpc_check: bool,
+    // Socket tuning for vendor/feed connections: TCP_NODELAY, TCP keepalive and gRPC HTTP/2 keepalive (0 = client default)
+    #[serde(rename = "tcp_nodelay", default = "default_true")]
+    pub tcp_nodelay: bool,
+    #[serde(rename = "tcp_keepalive_secs", default)]
+    pub tcp_keepalive_secs: u64,
+    #[serde(rename = "http2_keepalive_interval_secs", default)]
+    pub http2_keepalive_interval_secs: u64,
+    // Summary report (trades, win rate, vendor landings, tips, est. PnL, uptime, reconnects) every N hours
+    // starting at the given UTC hour; appended to daily_summary_path if set (0 hours disables)
+    #[serde(rename = "daily_summary_interval_hours", default)]
+    pub daily_summary_interval_hours: u64,
+    #[serde(rename = "daily_summary_boundary_hour_utc", default)]
+    pub daily_summary_boundary_hour_utc: u32,
+    #[serde(rename = "daily_summary_path", default)]
+    pub daily_summary_path: String,
+    // A sell that lands failed on slippage is resent with sell_slippage_step_bps more slippage,
+    // up to sell_slippage_max_bps and sell_slippage_retries resends (0 max disables)
+    #[serde(rename = "sell_slippage_max_bps", default)]
+    pub sell_slippage_max_bps: u64,
+    #[serde(rename = "sell_slippage_step_bps", default = "default_sell_slippage_step_bps")]
+    pub sell_slippage_step_bps: u64,
+    #[serde(rename = "sell_slippage_retries", default = "default_sell_slippage_retries")]
+    pub sell_slippage_retries: u32,
+    // Store one GLOBAL_TX_MAP entry per buy (under the detected signature) with the vendor signatures
+    // aliased to it, instead of a full copy per vendor signature (up to 8). Formerly map_winner_sig_only
+    #[serde(rename = "alias_vendor_sigs", alias = "map_winner_sig_only", default)]
+    pub alias_vendor_sigs: bool,
+    // Serialize vendor sends into pooled per-thread buffers instead of a fresh allocation per send
+    #[serde(rename = "reuse_send_buffers", default = "default_true")]
+    pub reuse_send_buffers: bool,
+    // OTLP/HTTP traces endpoint for per-trade spans (e.g. http://collector:4318/v1/traces); "" disables.
+    // Only takes effect in builds with the `otel` feature
+    #[serde(rename = "otel_endpoint", default)]
+    pub otel_endpoint: String,
+    #[serde(rename = "otel_service_name", default = "default_otel_service_name")]
+    pub otel_service_name: String,
+    // Alert when a worker thread spends longer than this on one message (0 disables the watchdog);
+    // respawn_stuck_workers also starts a replacement worker on the same channel
+    #[serde(rename = "worker_stall_timeout_secs", default = "default_worker_stall_timeout_secs")]
+    pub worker_stall_timeout_secs: u64,
+    #[serde(rename = "respawn_stuck_workers", default)]
+    pub respawn_stuck_workers: bool,
+    // Re-read the pool's SOL reserve right before selling; below presell_min_liquidity_sol the sell is
+    // skipped ("hold") or sent anyway ("sell") per presell_drained_policy (0 disables)
+    #[serde(rename = "presell_min_liquidity_sol", default)]
+    pub presell_min_liquidity_sol: f64,
+    #[serde(rename = "presell_drained_policy", default = "default_presell_drained_policy")]
+    pub presell_drained_policy: String,
+    // A durable-nonce account handed to a build stays leased until that tx lands or this long passes;
+    // reusing a leased account is reported as contention, and serialize_nonce_handout skips to a free one
+    #[serde(rename = "nonce_lease_ms", default = "default_nonce_lease_ms")]
+    pub nonce_lease_ms: u64,
+    #[serde(rename = "serialize_nonce_handout", default)]
+    pub serialize_nonce_handout: bool,
+    // Signatures awaiting confirmation are polled together every confirm_poll_interval_ms,
+    // up to confirm_batch_size (max 256) per getSignatureStatuses call
+    #[serde(rename = "confirm_batch_size", default = "default_confirm_batch_size")]
+    pub confirm_batch_size: usize,
+    #[serde(rename = "confirm_poll_interval_ms", default = "default_confirm_poll_interval_ms")]
+    pub confirm_poll_interval_ms: u64,
+    // Buy bid as one total (SOL): bid_tip_ratio of it is the vendor tip, the rest the priority fee
+    // (CU price over the tx's CU limit). Overrides the per-vendor cu_price / buy_tip values (0 disables)
+    #[serde(rename = "total_bid_sol", default)]
+    pub total_bid_sol: f64,
+    #[serde(rename = "bid_tip_ratio", default = "default_bid_tip_ratio")]
+    pub bid_tip_ratio: f64,
+    // Check each vendor-returned signature against the one we signed, alerting on a mismatch
+    // (bookkeeping always uses ours); flashblock may answer with its own transaction ID instead
+    #[serde(rename = "verify_vendor_signatures", default)]
+    pub verify_vendor_signatures: bool,
+    // Count instructions of known programs whose discriminator we don't build from (top ones in stats)
+    #[serde(rename = "track_unknown_discriminators", default)]
+    pub track_unknown_discriminators: bool,
+    // After the CU simulation, simulate again on the first sendRPC endpoint and report transactions
+    // that pass on the read node but fail there (adds up to send_rpc_simulation_timeout_ms per buy)
+    #[serde(rename = "simulate_on_send_rpc", default)]
+    pub simulate_on_send_rpc: bool,
+    #[serde(rename = "send_rpc_simulation_timeout_ms", default = "default_send_rpc_simulation_timeout_ms")]
+    pub send_rpc_simulation_timeout_ms: u64,
+    // Every ata_sweep_interval_mins, close the wallet's token accounts holding at most
+    // ata_sweep_dust_amount (raw units; 0 = empty only) to reclaim rent. Open positions are never closed
+    #[serde(rename = "ata_sweep_interval_mins", default)]
+    pub ata_sweep_interval_mins: u64,
+    #[serde(rename = "ata_sweep_dust_amount", default)]
+    pub ata_sweep_dust_amount: u64,
+    // Extra "quic" vendor sending straight to the upcoming leaders' TPU over QUIC; the leader
+    // schedule is tracked over quic_ws_url, sending to the leaders of the next quic_fanout_slots slots
+    #[serde(rename = "quic_send", default)]
+    pub quic_send: bool,
+    #[serde(rename = "quic_ws_url", default)]
+    pub quic_ws_url: String,
+    #[serde(rename = "quic_fanout_slots", default = "default_quic_fanout_slots")]
+    pub quic_fanout_slots: u64,
+    // Track the slot leader schedule (getSlotLeaders) every this many seconds; landed buys are then
+    // logged with their slot's leader (0 disables)
+    #[serde(rename = "slot_leader_refresh_secs", default)]
+    pub slot_leader_refresh_secs: u64,
+    // Hold a due sell until the confirmation tracker sees its buy over RPC (not just on the feed);
+    // a buy that fails on chain cancels it, one never seen in time releases it
+    #[serde(rename = "sell_requires_buy_confirmation", default)]
+    pub sell_requires_buy_confirmation: bool,
+    // Chase a buy not seen landed within a couple of slots: rebuild on a fresh nonce with tip and
+    // priority fee scaled by chase_tip_multiplier per attempt, resending up to chase_max_attempts times
+    #[serde(rename = "chase_enabled", default)]
+    pub chase_enabled: bool,
+    #[serde(rename = "chase_tip_multiplier", default = "default_chase_tip_multiplier")]
+    pub chase_tip_multiplier: f64,
+    #[serde(rename = "chase_max_attempts", default = "default_chase_max_attempts")]
+    pub chase_max_attempts: u32,
+    // Commitment ("processed", "confirmed" or "finalized") a sell must reach before its position is
+    // dropped from GLOBAL_TX_MAP and the trade's final record written; a sell rolled back first reopens it
+    #[serde(rename = "completion_commitment", default = "default_completion_commitment")]
+    pub completion_commitment: String,
+    // Cap on concurrent parallel sends (each fans out to every vendor; 0 = unlimited). A buy over the
+    // cap waits up to in_flight_send_queue_ms for a slot, then is dropped (0 = dropped at once); a
+    // sell over the cap waits for a slot
+    #[serde(rename = "max_in_flight_sends", default)]
+    pub max_in_flight_sends: usize,
+    #[serde(rename = "in_flight_send_queue_ms", default = "default_in_flight_send_queue_ms")]
+    pub in_flight_send_queue_ms: u64,
+    // Skip (and count) Triton transactions whose message header is missing or malformed instead of
+    // treating them as not signed by us, which would route a possible sell trigger as a buy
+    #[serde(rename = "skip_unrecognized_signer", default = "default_true")]
+    pub skip_unrecognized_signer: bool,
+    // Drop nonce accounts whose authority isn't the wallet from the rotation (found at startup or on
+    // first use); off = only report them
+    #[serde(rename = "exclude_mismatched_nonces", default = "default_true")]
+    pub exclude_mismatched_nonces: bool,
+    // Count buy/sell send attempts per feed slot over the last this many slots with attempts (0 = off);
+    // log_slot_attempts also logs each attempt with its slot's running counts
+    #[serde(rename = "slot_attempt_history", default)]
+    pub slot_attempt_history: usize,
+    #[serde(rename = "log_slot_attempts", default)]
+    pub log_slot_attempts: bool,
+    // Abort (and log) a sell whose instruction doesn't reference the bought mint and the wallet's ATA for it
+    #[serde(rename = "verify_sell_mint", default = "default_true")]
+    pub verify_sell_mint: bool,
+    // Seconds initialize() waits for the blockhash cache, vendor connections and nonce accounts before
+    // the subscriptions start (0 disables); startup continues, loudly, on timeout
+    #[serde(rename = "startup_ready_timeout_secs", default)]
+    pub startup_ready_timeout_secs: u64,
+    // Sell the fraction of our position that trackWallet sold of its own (from the feed's token balances)
+    // rather than all of it; applies once the tracked wallet's sell is seen before our sell goes out
+    #[serde(rename = "copy_sell_fraction", default)]
+    pub copy_sell_fraction: bool,
+    // Read each confirmed buy / (pumpfun) sell back over RPC and log its realized slippage against our estimate
+    #[serde(rename = "log_realized_slippage", default)]
+    pub log_realized_slippage: bool,
+    // While this file exists all buy and sell sends are suppressed; removing it resumes ("" disables)
+    #[serde(rename = "kill_switch_file", default)]
+    pub kill_switch_file: String,
+    #[serde(rename = "kill_switch_poll_ms", default = "default_kill_switch_poll_ms")]
+    pub kill_switch_poll_ms: u64,
+    // Raise each vendor's buy CU price to at least the copied transaction's CU price times
+    // leader_cu_price_multiplier (never lowered; still capped by maxCUPrice)
+    #[serde(rename = "match_leader_cu_price", default)]
+    pub match_leader_cu_price: bool,
+    #[serde(rename = "leader_cu_price_multiplier", default = "default_leader_cu_price_multiplier")]
+    pub leader_cu_price_multiplier: f64,
+    // BlockRazor submission: "fast" or "sandwichMitigation", safe window in slots (sandwichMitigation
+    // only, 0 = unset), revert protection; validated at startup
+    #[serde(rename = "blockrazor_mode", default = "default_blockrazor_mode")]
+    pub blockrazor_mode: String,
+    #[serde(rename = "blockrazor_safe_window", default)]
+    pub blockrazor_safe_window: i32,
+    #[serde(rename = "blockrazor_revert_protection", default)]
+    pub blockrazor_revert_protection: bool,
+    // NextBlock submission flags
+    #[serde(rename = "nextblock_front_running_protection", default)]
+    pub nextblock_front_running_protection: bool,
+    #[serde(rename = "nextblock_revert_on_fail", default)]
+    pub nextblock_revert_on_fail: bool,
+    // DEXes probed, in order, to find where a mint with no known tx_type trades (reconciliation, lost
+    // tx_type): "pumpfun" (active bonding curve), "pump_swap", "ray_cpmm"
+    #[serde(rename = "dex_probe_order", default = "default_dex_probe_order")]
+    pub dex_probe_order: Vec<String>,
+    // Sells fired within this many ms of each other share one transaction (and one tip and nonce)
+    // when they fit the size and CU limits; 0 sends each sell on its own
+    #[serde(rename = "sell_batch_window_ms", default)]
+    pub sell_batch_window_ms: u64,
+    // Monitoring messages processed at once; messages arriving over the cap are dropped. 0 = unbounded
+    #[serde(rename = "monitoring_max_concurrent", default = "default_monitoring_max_concurrent")]
+    pub monitoring_max_concurrent: usize,
+    // Most SOL held in open positions of any one mint across re-entries; a buy over it is skipped. 0 = no cap
+    #[serde(rename = "max_position_sol_per_mint", default)]
+    pub max_position_sol_per_mint: f64,
+    // Build the swap, create-ATA and memo instructions once per trade and share them between the
+    // CU simulation and every vendor transaction, instead of rebuilding them for each
+    #[serde(rename = "reuse_swap_components", default = "default_true")]
+    pub reuse_swap_components: bool,
+    // Webhook for critical alerts (circuit breaker open, all vendors failing, low balance, reconnect
+    // churn, stale streams, memory growth, map overflow, processing bottlenecks, kill switch); empty
+    // disables. Format: "discord", "slack" or "generic" JSON
+    #[serde(rename = "alert_webhook_url", default)]
+    pub alert_webhook_url: String,
+    #[serde(rename = "alert_webhook_format", default = "default_alert_webhook_format")]
+    pub alert_webhook_format: String,
+    // At most one alert of each kind per this many seconds
+    #[serde(rename = "alert_min_interval_secs", default = "default_alert_min_interval_secs")]
+    pub alert_min_interval_secs: u64,
+    // Alert when the heartbeat's wallet balance is below this (needs heartbeat_interval_secs); 0 disables
+    #[serde(rename = "alert_min_balance_sol", default)]
+    pub alert_min_balance_sol: f64,
+    // Copy only the first transaction of a leader on a mint within this many slots (1 = same slot);
+    // retries and bundle variants after it are skipped. 0 disables
+    #[serde(rename = "leader_dup_slot_window", default)]
+    pub leader_dup_slot_window: u64,
+    // Cluster to run against: "mainnet", "devnet" or "testnet". Test clusters default to their public RPC,
+    // load test_private_key.json.enc / test-nonce-account.json, send over sendRPC only and link explorer
+    // pages to that cluster; startup is refused on endpoints whose host names another cluster (hosts naming
+    // no cluster, e.g. IPs, only warn), and on a test cluster on a wallet or fee payer found in the mainnet
+    // key files or a non-empty nonceAc
+    #[serde(rename = "network", default = "default_network")]
+    pub network: String,
+    // Add this many basis points to the vendor CU price per distinct account a transaction touches,
+    // so larger transactions bid in proportion to their cost. 0 disables
+    #[serde(rename = "cu_price_scale_bps_per_account", default)]
+    pub cu_price_scale_bps_per_account: u64,
+    // Histogram of the winning vendor per send in buckets of this many minutes, shown in the stats
+    // report; keeps the last vendor_win_buckets buckets. 0 disables
+    #[serde(rename = "vendor_win_bucket_mins", default)]
+    pub vendor_win_bucket_mins: u64,
+    #[serde(rename = "vendor_win_buckets", default = "default_vendor_win_buckets")]
+    pub vendor_win_buckets: usize,
+}
+
+pub fn default_sell_slippage_step_bps() -> u64 {
+    500
+}
+
+pub fn default_sell_slippage_retries() -> u32 {
+    3
+}
+
+pub fn default_otel_service_name() -> String {
+    "copy_rust".to_string()
+}
+
+pub fn default_worker_stall_timeout_secs() -> u64 {
+    30
+}
+
+pub fn default_presell_drained_policy() -> String {
+    "hold".to_string()
+}
+
+pub fn default_nonce_lease_ms() -> u64 {
+    2000
+}
+
+pub fn default_confirm_batch_size() -> usize {
+    256
+}
+
+pub fn default_confirm_poll_interval_ms() -> u64 {
+    500
+}
+
+pub fn default_bid_tip_ratio() -> f64 {
+    0.5
+}
+
+pub fn default_send_rpc_simulation_timeout_ms() -> u64 {
+    200
+}
+
+pub fn default_quic_fanout_slots() -> u64 {
+    4
+}
+
+pub fn default_chase_tip_multiplier() -> f64 {
+    2.0
+}
+
+pub fn default_chase_max_attempts() -> u32 {
+    1
+}
+
+pub fn default_completion_commitment() -> String {
+    "processed".to_string()
+}
+
+pub fn default_in_flight_send_queue_ms() -> u64 {
+    50
+}
+
+pub fn default_alert_webhook_format() -> String {
+    "generic".to_string()
+}
+
+pub fn default_alert_min_interval_secs() -> u64 {
+    300
+}
+
+pub fn default_network() -> String {
+    "mainnet".to_string()
+}
+
+pub fn default_vendor_win_buckets() -> usize {
+    24
+}
+
+pub fn default_monitoring_max_concurrent() -> usize {
+    64
+}
+
+pub fn default_dex_probe_order() -> Vec<String> {
+    vec!["pumpfun".to_string(), "pump_swap".to_string(), "ray_cpmm".to_string()]
+}
+
+pub fn default_blockrazor_mode() -> String {
+    "fast".to_string()
+}
+
+pub fn default_leader_cu_price_multiplier() -> f64 {
+    1.0
+}
+
+pub fn default_kill_switch_poll_ms() -> u64 {
+    500
+}
+
+pub fn default_vendor_probe_interval_secs() -> u64 {
+    30
+}
+
+pub fn default_send_rpc_mode() -> String {
+    "sequential".to_string()
+}
+
+pub fn default_rpc_read_attempts() -> u32 {
+    3
+}
+
+pub fn default_rpc_read_budget_ms() -> u64 {
+    50
+}
+
+pub fn default_memo_tag() -> String {
+    "copyrust".to_string()
+}
+
+pub fn default_leader_age_check_interval_secs() -> u64 {
+    3600
+}
+
+pub fn default_rpc_min_interval_ms() -> u64 {
+    1000
+}
+
+pub fn default_tx_map_purge_secs() -> u64 {
+    10
+}
+
+pub fn default_max_instructions_to_scan() -> usize {
+    256
+}
+
+pub fn default_birdeye_price_ttl_secs() -> u64 {
+    15
+}
+
+pub fn default_birdeye_min_request_interval_ms() -> u64 {
+    1000
+}
+
+pub fn default_http_pool_size() -> usize {
+    50
+}
+
+pub fn default_vendor_throttle_max_wait_ms() -> u64 {
+    50
+}
+
+pub fn default_feed_dedup_ttl_secs() -> u64 {
+    30
+}
+
+pub fn default_feed_dedup_capacity() -> usize {
+    5000
+}
+
+pub fn default_heap_frame_bytes() -> u32 {
+    256 * 1024
+}
+
+pub fn default_true() -> bool {
+    true
+}
+
+pub fn default_send_runtime_threads() -> usize {
+    4
 }
 
 pub fn load_config() -> Config {
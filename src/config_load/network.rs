@@ -0,0 +1,213 @@
+use crate::config_load::Config;
+use once_cell::sync::OnceCell;
+use solana_sdk::pubkey::Pubkey;
+
+/// Solana cluster the bot runs against (`network`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Network {
+    Mainnet,
+    Devnet,
+    Testnet,
+}
+
+static NETWORK: OnceCell<Network> = OnceCell::new();
+
+impl Network {
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name.to_ascii_lowercase().as_str() {
+            "mainnet" | "mainnet-beta" => Ok(Network::Mainnet),
+            "devnet" => Ok(Network::Devnet),
+            "testnet" => Ok(Network::Testnet),
+            other => Err(format!("unknown network '{}' (expected mainnet, devnet or testnet)", other)),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Network::Mainnet => "mainnet",
+            Network::Devnet => "devnet",
+            Network::Testnet => "testnet",
+        }
+    }
+
+    /// Cluster an endpoint URL points at, judged by its host name; `None` when the host names no
+    /// cluster (IPs, localhost, custom provider hosts)
+    pub fn from_url(url: &str) -> Option<Self> {
+        let url = url.trim().to_ascii_lowercase();
+        let rest = url.split_once("://").map_or(url.as_str(), |(_, rest)| rest);
+        let host = rest.split(['/', '?', ':']).next().unwrap_or("");
+        if host.contains("testnet") {
+            Some(Network::Testnet)
+        } else if host.contains("devnet") {
+            Some(Network::Devnet)
+        } else if host.contains("mainnet") {
+            Some(Network::Mainnet)
+        } else {
+            None
+        }
+    }
+
+    /// Public RPC used when `rpcEndpoint` is left empty
+    pub fn default_rpc_endpoint(&self) -> &'static str {
+        match self {
+            Network::Mainnet => "https://api.mainnet-beta.solana.com",
+            Network::Devnet => "https://api.devnet.solana.com",
+            Network::Testnet => "https://api.testnet.solana.com",
+        }
+    }
+
+    /// Wallet and nonce-account files; test clusters never load the mainnet keys
+    pub fn key_files(&self) -> (&'static str, &'static str) {
+        match self {
+            Network::Mainnet => ("private_key.json.enc", "nonce-account.json"),
+            Network::Devnet | Network::Testnet => ("test_private_key.json.enc", "test-nonce-account.json"),
+        }
+    }
+
+    /// Whether transactions may go out through `vendor_name`; test clusters only send over the
+    /// configured RPC, the relays and their tip accounts are mainnet-only
+    pub fn allows_vendor(&self, vendor_name: &str) -> bool {
+        *self == Network::Mainnet || vendor_name == "rpc"
+    }
+
+    /// Solscan link for a transaction on this cluster
+    pub fn explorer_tx_url(&self, sig: &str) -> String {
+        match self {
+            Network::Mainnet => format!("https://solscan.io/tx/{}", sig),
+            _ => format!("https://solscan.io/tx/{}?cluster={}", sig, self.name()),
+        }
+    }
+}
+
+/// Endpoints whose host names a different cluster than `network`
+pub fn mismatched_endpoints(network: Network, endpoints: &[String]) -> Vec<String> {
+    endpoints.iter()
+        .filter(|url| matches!(Network::from_url(url), Some(cluster) if cluster != network))
+        .cloned()
+        .collect()
+}
+
+/// Endpoints whose host names no cluster, so `network` can't be checked against them
+pub fn unclassified_endpoints(endpoints: &[String]) -> Vec<String> {
+    endpoints.iter()
+        .filter(|url| !url.trim().is_empty() && Network::from_url(url).is_none())
+        .cloned()
+        .collect()
+}
+
+/// A test cluster refuses any `nonceAc` entry, which would be the mainnet nonce accounts
+pub fn check_test_nonces(network: Network, nonce_ac: &[String]) -> Result<(), String> {
+    if network != Network::Mainnet && !nonce_ac.is_empty() {
+        return Err(format!("network = \"{}\" but nonceAc lists {} accounts; test clusters use {} only, empty nonceAc",
+            network.name(), nonce_ac.len(), network.key_files().1));
+    }
+    Ok(())
+}
+
+/// Refuse a test cluster whose loaded keys (wallet, fee payer) include a key from the mainnet key
+/// files (`Network::Mainnet.key_files()`)
+pub fn check_test_keys(network: Network, loaded: &[(&str, Pubkey)], mainnet_keys: &[Pubkey]) -> Result<(), String> {
+    if network == Network::Mainnet {
+        return Ok(());
+    }
+    for (role, pubkey) in loaded {
+        if mainnet_keys.contains(pubkey) {
+            return Err(format!("network = \"{}\" but the {} {} is a mainnet key", network.name(), role, pubkey));
+        }
+    }
+    Ok(())
+}
+
+/// Resolve `config.network` and fill an empty `rpcEndpoint` with the cluster default. Refuses
+/// endpoints whose host names another cluster, warns on ones it can't place and, on a test
+/// cluster, refuses a non-empty `nonceAc` (`check_test_nonces`).
+pub fn apply_network(config: &mut Config) -> Result<Network, String> {
+    let network = Network::from_name(&config.network)?;
+    if config.rpc_endpoint.trim().is_empty() {
+        config.rpc_endpoint = network.default_rpc_endpoint().to_string();
+    }
+    let endpoints: Vec<String> = std::iter::once(&config.rpc_endpoint).chain(config.send_rpc.iter()).cloned().collect();
+    let mismatched = mismatched_endpoints(network, &endpoints);
+    if !mismatched.is_empty() {
+        let described: Vec<String> = mismatched.iter()
+            .filter_map(|url| Network::from_url(url).map(|cluster| format!("{} ({})", url, cluster.name())))
+            .collect();
+        return Err(format!("network = \"{}\" but these endpoints belong to another cluster: {}",
+            network.name(), described.join(", ")));
+    }
+    for url in unclassified_endpoints(&endpoints) {
+        println!("[NETWORK] WARNING: can't tell which cluster {} serves; make sure it is {}", url, network.name());
+    }
+    check_test_nonces(network, &config.nonce_ac)?;
+    let _ = NETWORK.set(network);
+    Ok(network)
+}
+
+/// The configured cluster (mainnet until `apply_network` runs)
+pub fn network() -> Network {
+    NETWORK.get().copied().unwrap_or(Network::Mainnet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_network_endpoint_selection() {
+        assert_eq!(Network::from_name("mainnet-beta"), Ok(Network::Mainnet));
+        assert_eq!(Network::from_name("Devnet"), Ok(Network::Devnet));
+        assert!(Network::from_name("localnet").is_err());
+
+        assert_eq!(Network::from_url("https://api.devnet.solana.com"), Some(Network::Devnet));
+        assert_eq!(Network::from_url("https://solana-testnet.example.com"), Some(Network::Testnet));
+        assert_eq!(Network::from_url("https://mainnet.helius-rpc.com/?api-key=x"), Some(Network::Mainnet));
+
+        // Test clusters get their own default RPC and never the mainnet key files
+        assert_eq!(Network::from_url(Network::Devnet.default_rpc_endpoint()), Some(Network::Devnet));
+        assert_ne!(Network::Devnet.key_files(), Network::Mainnet.key_files());
+        assert_eq!(Network::Testnet.key_files(), Network::Devnet.key_files());
+
+        assert_eq!(Network::Mainnet.explorer_tx_url("abc"), "https://solscan.io/tx/abc");
+        assert_eq!(Network::Devnet.explorer_tx_url("abc"), "https://solscan.io/tx/abc?cluster=devnet");
+    }
+
+    #[test]
+    fn test_only_endpoints_naming_another_cluster_are_refused() {
+        // IPs, localhost and custom hosts can't be placed: warned about, never refused
+        let mut endpoints = vec![
+            "http://86.105.224.13:8899".to_string(),
+            "http://localhost:8899".to_string(),
+            "https://rpc.example.io".to_string(),
+        ];
+        assert_eq!(Network::from_url("http://127.0.0.1:8899"), None);
+        assert!(mismatched_endpoints(Network::Devnet, &endpoints).is_empty());
+        assert_eq!(unclassified_endpoints(&endpoints), endpoints);
+
+        endpoints.push("https://api.mainnet-beta.solana.com".to_string());
+        assert_eq!(mismatched_endpoints(Network::Devnet, &endpoints), vec!["https://api.mainnet-beta.solana.com".to_string()]);
+        assert!(mismatched_endpoints(Network::Mainnet, &endpoints).is_empty());
+    }
+
+    #[test]
+    fn test_test_clusters_refuse_mainnet_keys_and_relays() {
+        let mainnet_wallet = Pubkey::new_unique();
+        let test_wallet = Pubkey::new_unique();
+        let fee_payer = Pubkey::new_unique();
+        let mainnet_keys = [mainnet_wallet];
+        assert_eq!(check_test_keys(Network::Mainnet, &[("wallet", mainnet_wallet)], &mainnet_keys), Ok(()));
+        assert_eq!(check_test_keys(Network::Devnet, &[("wallet", test_wallet), ("fee payer", fee_payer)], &mainnet_keys), Ok(()));
+        assert!(check_test_keys(Network::Devnet, &[("wallet", test_wallet), ("fee payer", mainnet_wallet)], &mainnet_keys).is_err());
+        assert!(check_test_keys(Network::Testnet, &[("wallet", mainnet_wallet)], &mainnet_keys).is_err());
+
+        let nonces = vec!["5RgvQRpF2W3V5f3Kt2FYNbKSvBQ3ifK1gjgEFMQ1rexo".to_string()];
+        assert_eq!(check_test_nonces(Network::Mainnet, &nonces), Ok(()));
+        assert_eq!(check_test_nonces(Network::Devnet, &[]), Ok(()));
+        assert!(check_test_nonces(Network::Testnet, &nonces).is_err());
+
+        // Only the RPC vendor sends on a test cluster
+        assert!(Network::Devnet.allows_vendor("rpc"));
+        assert!(!Network::Devnet.allows_vendor("zeroslot"));
+        assert!(!Network::Testnet.allows_vendor("quic"));
+        assert!(Network::Mainnet.allows_vendor("nextblock"));
+    }
+}
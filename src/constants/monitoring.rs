@@ -16,6 +16,23 @@ pub fn get_monitoring_arpc_endpoint() -> String {
         .unwrap_or_else(|| "http://86.105.224.13:20202".to_string()) // Fallback to default
 }
 
+// Monitoring endpoints from config (`monitoring_arpc_endpoints`), falling back to the single ARPC endpoint
+pub fn get_monitoring_arpc_endpoints() -> Vec<String> {
+    let configured = GLOBAL_CONFIG.get().map(|config| config.monitoring_arpc_endpoints.clone()).unwrap_or_default();
+    or_default_list(configured, || vec![get_monitoring_arpc_endpoint()])
+}
+
+// Programs to monitor from config (`monitoring_programs`), falling back to MONITORING_PROGRAMS
+pub fn get_monitoring_programs() -> Vec<String> {
+    let configured = GLOBAL_CONFIG.get().map(|config| config.monitoring_programs.clone()).unwrap_or_default();
+    or_default_list(configured, || MONITORING_PROGRAMS.iter().map(|&s| s.to_string()).collect())
+}
+
+fn or_default_list(configured: Vec<String>, default: impl FnOnce() -> Vec<String>) -> Vec<String> {
+    let configured: Vec<String> = configured.into_iter().filter(|s| !s.trim().is_empty()).collect();
+    if configured.is_empty() { default() } else { configured }
+}
+
 // Fallback to public endpoints if monitoring endpoint not available
 pub const MONITORING_FALLBACK_ENDPOINT: &str = "http://86.105.224.13:20202";
 
@@ -32,6 +49,14 @@ mod tests {
         let endpoint = get_monitoring_arpc_endpoint();
         assert_eq!(endpoint, "http://86.105.224.13:20202");
     }
+
+    #[test]
+    fn test_monitoring_lists_fall_back_to_defaults() {
+        assert_eq!(get_monitoring_arpc_endpoints(), vec!["http://86.105.224.13:20202".to_string()]);
+        assert_eq!(get_monitoring_programs().len(), MONITORING_PROGRAMS.len());
+        let configured = vec!["http://a:1".to_string(), "".to_string(), "http://b:2".to_string()];
+        assert_eq!(or_default_list(configured, Vec::new), vec!["http://a:1".to_string(), "http://b:2".to_string()]);
+    }
 }
 
  
\ No newline at end of file
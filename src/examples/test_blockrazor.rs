@@ -1,5 +1,5 @@
 use crate::send_tx::block_razor::{
-    initialize_blockrazor_client, send_tx_blockrazor, get_blockrazor_health,
+    initialize_blockrazor_client, send_tx_blockrazor, BlockRazorMode, get_blockrazor_health,
     create_instruction_blockrazor, blockrazor_tip
 };
 use crate::init::wallet_loader::get_wallet_keypair;
@@ -69,9 +69,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     match send_tx_blockrazor(
         &transaction,
         auth_key,
-        "fast",           // Mode: "fast" or "sandwichMitigation"
-        Some(3),          // Safe window (only for sandwichMitigation mode)
-        false,            // Revert protection
+        // Mode: "fast" or "sandwichMitigation"; safe window (sandwichMitigation only); revert protection
+        &BlockRazorMode::new("sandwichMitigation", 3, false)?,
     ).await {
         Ok(signature) => println!("Transaction sent successfully! Signature: {}", signature),
         Err(e) => println!("Failed to send transaction: {:?}", e),
@@ -20,6 +20,7 @@ pub fn test_unified_parallel_building() -> Result<(), Box<dyn std::error::Error
         mint,
         0, // target_token_buy not used for sell transactions
         "test_sell_sig", // sig_str for logging
+        "pump_swap",
     );
     
     match result {
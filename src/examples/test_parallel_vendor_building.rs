@@ -19,6 +19,7 @@ pub fn test_parallel_vendor_building() -> Result<(), Box<dyn std::error::Error +
         test_mint,
         1000, // test amount
         "test_sig",
+        "pump_swap",
     );
     
     match result {
@@ -70,6 +71,7 @@ pub fn benchmark_parallel_vs_sequential() -> Result<(), Box<dyn std::error::Erro
         test_mint,
         1000,
         "benchmark_sig",
+        "pump_swap",
     );
     let parallel_time = parallel_start.elapsed();
     
@@ -19,6 +19,7 @@ pub fn test_tx_builder_refactor() -> Result<(), Box<dyn std::error::Error + Send
         test_mint,
         1000, // test amount
         "test_sig",
+        "pump_swap",
     );
     
     match result {
@@ -1,9 +1,11 @@
 use crate::arpc::SubscribeResponse;
 
 use crate::config_load::Config;
+use crate::grpc::compute_budget::parse_compute_budget;
 use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
 use std::sync::atomic::{AtomicUsize, Ordering};
-use crate::utils::logger::{log_event, EventType};
+use crate::utils::logger::{log_event_cid, new_correlation_id, EventType};
 use std::sync::Arc;
 use crate::constants::raydium_launchpad::RAYDIUM_LAUNCHPAD_PROGRAM_ID_BYTES;
 use crate::constants::axiom::{AXIOM_PUMP_SWAP_PROGRAM_ID_BYTES, AXIOM_PUMP_FUN_PROGRAM_ID_BYTES};
@@ -35,6 +37,11 @@ pub fn get_dedup_stats() -> usize {
     PROCESSED_SIGNATURES.len()
 }
 
+/// Up to `limit` processed signatures with their first-seen unix time
+pub fn dedup_snapshot(limit: usize) -> Vec<(String, u64)> {
+    PROCESSED_SIGNATURES.iter().take(limit).map(|e| (e.key().clone(), *e.value())).collect()
+}
+
 // OPTIMIZATION: Faster cleanup with reduced overhead
 pub fn cleanup_old_signatures() {
     let current_time = SystemTime::now()
@@ -136,6 +143,24 @@ fn is_signature_processed_fast(sig: &str) -> bool {
     false
 }
 
+/// Returns true if `wallet` is one of the first `num_required_signatures` account keys (i.e. it signed the tx)
+pub fn is_signed_by(account_keys: &[Vec<u8>], num_required_signatures: usize, wallet: &[u8; 32]) -> bool {
+    account_keys
+        .iter()
+        .take(num_required_signatures)
+        .any(|key| key.as_slice() == wallet)
+}
+
+// Drop our own transactions from the buy-trigger path so we never copy ourselves.
+// The triton feed still sees them and routes them to the sell-follow path via is_signer.
+fn is_self_transaction(tx: &crate::arpc::SubscribeResponseTransaction, config: &Config) -> bool {
+    if !config.filter_self_transactions {
+        return false;
+    }
+    let wallet = crate::init::wallet_loader::get_wallet_keypair().pubkey().to_bytes();
+    is_signed_by(&tx.account_keys, tx.num_required_signatures as usize, &wallet)
+}
+
 // Call this in your ARPC message handler:
 // ARPC_MESSAGE_COUNT.fetch_add(1, Ordering::Relaxed);
 
@@ -153,12 +178,25 @@ pub struct ParsedTrade {
 pub use crate::grpc::arpc_worker::{ParsedArpcTrade, setup_arpc_crossbeam_worker, send_parsed_arpc_trade};
 
 // CRITICAL FIX: Sync version for worker pool to avoid nested async
-pub fn process_arpc_msg_sync(resp: &SubscribeResponse, _config: &Config) -> Option<ParsedTrade> {
+pub fn process_arpc_msg_sync(resp: &SubscribeResponse, config: &Config) -> Option<ParsedTrade> {
     // Process the message and send it to the crossbeam worker
     // This is a sync wrapper around the async processing logic
     
     let tx = resp.transaction.as_ref()?;
     let slot = tx.slot;
+    crate::grpc::arpc_worker::update_current_slot(slot);
+    
+    if is_self_transaction(tx, config) {
+        #[cfg(feature = "verbose_logging")]
+        println!("[ARPC] Skipping self-transaction at slot {}", slot);
+        return None;
+    }
+
+    if crate::grpc::leader_age::is_filtered_leader_transaction(&tx.account_keys, tx.num_required_signatures as usize) {
+        #[cfg(feature = "verbose_logging")]
+        println!("[ARPC] Skipping transaction from a too-new tracked wallet at slot {}", slot);
+        return None;
+    }
     
     // Extract signature
     let sig_bytes = tx.signatures.get(0).cloned();
@@ -178,6 +216,8 @@ pub fn process_arpc_msg_sync(resp: &SubscribeResponse, _config: &Config) -> Opti
         detection_time: std::time::Instant::now(),
         tx_instructions: Arc::new(tx.instructions.clone()),
         account_keys: Arc::new(tx.account_keys.clone()),
+        corr_id: new_correlation_id(),
+        leader_compute_budget: parse_compute_budget(&tx.instructions, &tx.account_keys),
     };
     
     // Send to crossbeam worker for processing
@@ -194,12 +234,25 @@ pub fn process_arpc_msg_sync(resp: &SubscribeResponse, _config: &Config) -> Opti
     })
 }
 
-pub async fn process_arpc_msg(resp: &SubscribeResponse, _config: &Config) -> Option<ParsedTrade> {
+pub async fn process_arpc_msg(resp: &SubscribeResponse, config: &Config) -> Option<ParsedTrade> {
     
     let total_start = std::time::Instant::now();
     
     let tx = resp.transaction.as_ref()?;
     let slot = tx.slot;
+    crate::grpc::arpc_worker::update_current_slot(slot);
+    
+    if is_self_transaction(tx, config) {
+        #[cfg(feature = "verbose_logging")]
+        println!("[ARPC] Skipping self-transaction at slot {}", slot);
+        return None;
+    }
+
+    if crate::grpc::leader_age::is_filtered_leader_transaction(&tx.account_keys, tx.num_required_signatures as usize) {
+        #[cfg(feature = "verbose_logging")]
+        println!("[ARPC] Skipping transaction from a too-new tracked wallet at slot {}", slot);
+        return None;
+    }
     
     // OPTIMIZATION: Avoid Arc wrapping - use references where possible
     // Only create Arc if we actually need to send the data
@@ -252,8 +305,9 @@ pub async fn process_arpc_msg(resp: &SubscribeResponse, _config: &Config) -> Opt
     #[cfg(feature = "verbose_logging")]
     println!("[PROFILE][{}] Dedup check (passed): {:.2?}", sig_string, dedup_time);
     
+    let corr_id = new_correlation_id();
     if let Some(ref sig_bytes) = sig_bytes {
-        log_event(EventType::ArpcDetectionProcessing, &Arc::new(sig_bytes.clone()), detection_time, None);
+        log_event_cid(EventType::ArpcDetectionProcessing, &Arc::new(sig_bytes.clone()), detection_time, None, &corr_id);
     }
     
     let log_event_time = detection_time.elapsed();
@@ -272,6 +326,8 @@ pub async fn process_arpc_msg(resp: &SubscribeResponse, _config: &Config) -> Opt
         detection_time,
         tx_instructions: Arc::new(tx_instructions.clone()), // Only clone when sending
         account_keys: Arc::new(account_keys.clone()), // Only clone when sending
+        corr_id,
+        leader_compute_budget: parse_compute_budget(tx_instructions, account_keys),
         // ... add more fields if needed ...
     };
     
@@ -349,3 +405,27 @@ pub fn print_pubkeys_from_data(data: &[u8]) {
     }
     println!("--------------------------------------------------");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_own_buy_is_filtered() {
+        let wallet = Pubkey::new_unique().to_bytes();
+        let leader = Pubkey::new_unique().to_bytes();
+        let program = Pubkey::new_unique().to_bytes();
+
+        // Our own buy: wallet is the fee payer / signer
+        let own_keys = vec![wallet.to_vec(), program.to_vec()];
+        assert!(is_signed_by(&own_keys, 1, &wallet));
+
+        // Leader buy: wallet not present
+        let leader_keys = vec![leader.to_vec(), program.to_vec()];
+        assert!(!is_signed_by(&leader_keys, 1, &wallet));
+
+        // Wallet present only as a non-signer account is not a self-transaction
+        let mentioned_keys = vec![leader.to_vec(), wallet.to_vec()];
+        assert!(!is_signed_by(&mentioned_keys, 1, &wallet));
+    }
+}
@@ -1,10 +1,15 @@
 use crossbeam::channel::{Sender};
 use crate::arpc::CompiledInstruction;
+use crate::grpc::compute_budget::{parse_compute_budget, LeaderComputeBudget};
 use once_cell::sync::OnceCell;
 use std::time::Instant;
-use core_affinity;
+use crate::utils::affinity;
+use crate::utils::otel;
+use crate::utils::watchdog;
+use crate::alerts::{alert, AlertKind};
 use crate::utils::rt_scheduler::{set_realtime_priority, RealtimePriority};
 use solana_sdk::transaction::Transaction;
+use solana_sdk::instruction::Instruction;
 use solana_sdk::pubkey::Pubkey;
 use crate::build_tx::ray_launch::RayLaunchAccounts;
 use crate::build_tx::ray_cpmm::RaydiumCpmmPoolState;
@@ -14,7 +19,7 @@ use crate::build_tx::ray_cpmm::RayCpmmSwapAccounts;
 use dashmap::DashMap;
 use once_cell::sync::Lazy;
 use std::sync::Arc;
-use crate::config_load::GLOBAL_CONFIG;
+use crate::config_load::{Config, GLOBAL_CONFIG};
 use crate::build_tx::tx_builder::{default_instruction};
 use chrono::Utc;
 use crate::constants::axiom::{AXIOM_PUMP_SWAP_PROGRAM_ID_BYTES, AXIOM_PUMP_FUN_PROGRAM_ID_BYTES};
@@ -24,7 +29,14 @@ use crate::grpc::programs::raydium_launchpad::raydium_launchpad_build_buy_tx;
 use crate::grpc::programs::axiom::axiom_pump_swap_build_buy_tx;
 use crate::grpc::programs::axiom::axiom_pump_fun_build_buy_tx;
 use crate::grpc::programs::raydium_cpmm::raydium_cpmm_build_buy_tx;
+use crate::grpc::mint_exposure::reserve_mint_exposure;
+use crate::grpc::leader_dedup::copy_leader_action;
 use std::collections::HashMap;
+use crate::init::wallet_loader::get_wallet_keypair;
+use crate::utils::ata::{CachedAta, derive_ata_cached};
+use crate::utils::control::{should_skip, Pipeline};
+use crate::utils::price_feed::{cached_price_usd, watch_price};
+use solana_sdk::signature::Signer;
 
 // Add global counters for monitoring worker performance
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -33,6 +45,8 @@ static WORKER_MESSAGES_RECEIVED: AtomicUsize = AtomicUsize::new(0);
 static WORKER_TRANSACTIONS_BUILT: AtomicUsize = AtomicUsize::new(0);
 static WORKER_TRANSACTIONS_INSERTED: AtomicUsize = AtomicUsize::new(0);
 static WORKER_ERRORS: AtomicUsize = AtomicUsize::new(0);
+static WORKER_DUST_SKIPPED: AtomicUsize = AtomicUsize::new(0);
+static SANITY_REJECTED: AtomicUsize = AtomicUsize::new(0);
 
 // Global performance counters
 static STORAGE_OPERATIONS: AtomicUsize = AtomicUsize::new(0);
@@ -58,17 +72,220 @@ static PROGRAM_ID_MAP: Lazy<HashMap<[u8; 32], ProgramType>> = Lazy::new(|| {
 });
 
 // HIGH PRIORITY OPTIMIZATION: Fast program ID lookup function
+// Program types whose DEX toggle is off are reported as no match
 #[inline]
 pub fn get_program_type(account_inst_bytes: &[u8]) -> Option<ProgramType> {
+    program_type_if_enabled(account_inst_bytes, &ENABLED_PROGRAM_TYPES)
+}
+
+#[inline]
+fn program_type_if_enabled(account_inst_bytes: &[u8], enabled: &[ProgramType]) -> Option<ProgramType> {
     if account_inst_bytes.len() == 32 {
         let mut key = [0u8; 32];
         key.copy_from_slice(account_inst_bytes);
-        PROGRAM_ID_MAP.get(&key).copied()
+        PROGRAM_ID_MAP.get(&key).copied().filter(|p| enabled.contains(p))
     } else {
         None
     }
 }
 
+/// Program types enabled by the DEX toggles (0 = off, any other value = on): `pumpMkts` gates
+/// pump.fun and PumpSwap, `rayCpmm` gates Raydium CPMM. Raydium Launchpad has no toggle, and
+/// `meteoraMkts` / `meteoraAmm` have no program type here to gate.
+fn resolve_enabled_programs(pump_mkts: u8, ray_cpmm: u8) -> Vec<ProgramType> {
+    ALL_PROGRAM_TYPES
+        .iter()
+        .copied()
+        .filter(|p| match p {
+            ProgramType::AxiomPumpSwap | ProgramType::AxiomPumpFun => pump_mkts != 0,
+            ProgramType::RaydiumCpmm => ray_cpmm != 0,
+            ProgramType::RaydiumLaunchpad => true,
+        })
+        .collect()
+}
+
+static ENABLED_PROGRAM_TYPES: Lazy<Vec<ProgramType>> = Lazy::new(|| match GLOBAL_CONFIG.get() {
+    Some(config) => {
+        let enabled = resolve_enabled_programs(config.pump_mkts, config.ray_cpmm);
+        if enabled.len() < ALL_PROGRAM_TYPES.len() {
+            println!("[ARPC] DEX toggles: copying only {:?}", enabled.iter().map(|p| p.name()).collect::<Vec<_>>());
+        }
+        enabled
+    }
+    None => ALL_PROGRAM_TYPES.to_vec(),
+});
+
+impl ProgramType {
+    /// Config key for this program type (matches the tx_type it builds)
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProgramType::RaydiumLaunchpad => "ray_launch",
+            ProgramType::AxiomPumpSwap => "pump_swap",
+            ProgramType::AxiomPumpFun => "pumpfun",
+            ProgramType::RaydiumCpmm => "ray_cpmm",
+        }
+    }
+
+    /// Built-in swap discriminators; empty accepts any instruction of the program
+    fn default_discriminators(&self) -> Vec<[u8; 8]> {
+        match self {
+            ProgramType::RaydiumLaunchpad => vec![[250, 234, 13, 123, 213, 156, 19, 236]], // buy_exact_in
+            ProgramType::RaydiumCpmm => vec![
+                [143, 190, 90, 218, 196, 30, 51, 222], // swap_base_input
+                [55, 217, 98, 86, 163, 74, 180, 173],  // swap_base_output
+            ],
+            ProgramType::AxiomPumpSwap | ProgramType::AxiomPumpFun => vec![],
+        }
+    }
+
+    /// Built-in minimum swap data length; 0 skips the check (Axiom layouts aren't fixed)
+    fn default_min_data_len(&self) -> usize {
+        match self {
+            ProgramType::RaydiumLaunchpad => 32, // discriminator + amount_in + minimum_amount_out + share_fee_rate
+            ProgramType::RaydiumCpmm => 24,      // discriminator + amount_in/out + threshold
+            ProgramType::AxiomPumpSwap | ProgramType::AxiomPumpFun => 0,
+        }
+    }
+}
+
+const ALL_PROGRAM_TYPES: [ProgramType; 4] = [
+    ProgramType::RaydiumLaunchpad,
+    ProgramType::AxiomPumpSwap,
+    ProgramType::AxiomPumpFun,
+    ProgramType::RaydiumCpmm,
+];
+
+/// Accepted swap discriminators per program type: `program_discriminators` overrides, else built-in
+fn resolve_discriminators(overrides: &HashMap<String, Vec<Vec<u8>>>) -> Vec<(ProgramType, Vec<[u8; 8]>)> {
+    for name in overrides.keys() {
+        if !ALL_PROGRAM_TYPES.iter().any(|p| p.name() == name) {
+            eprintln!("[ARPC] program_discriminators: unknown program type '{}' ignored", name);
+        }
+    }
+    ALL_PROGRAM_TYPES
+        .iter()
+        .map(|&program_type| {
+            let accepted = match overrides.get(program_type.name()) {
+                Some(list) => list
+                    .iter()
+                    .filter_map(|d| match <[u8; 8]>::try_from(d.as_slice()) {
+                        Ok(d) => Some(d),
+                        Err(_) => {
+                            eprintln!("[ARPC] program_discriminators.{}: {:?} is not 8 bytes, ignored", program_type.name(), d);
+                            None
+                        }
+                    })
+                    .collect(),
+                None => program_type.default_discriminators(),
+            };
+            (program_type, accepted)
+        })
+        .collect()
+}
+
+static ACCEPTED_DISCRIMINATORS: Lazy<Vec<(ProgramType, Vec<[u8; 8]>)>> = Lazy::new(|| {
+    let overrides = GLOBAL_CONFIG.get().map(|c| c.program_discriminators.clone()).unwrap_or_default();
+    resolve_discriminators(&overrides)
+});
+
+/// True if `data` starts with one of the accepted discriminators (an empty set accepts all)
+fn discriminator_accepted(accepted: &[[u8; 8]], data: &[u8]) -> bool {
+    accepted.is_empty() || (data.len() >= 8 && accepted.iter().any(|d| data[..8] == d[..]))
+}
+
+/// Minimum swap data length per program type: `program_min_data_len` overrides, else built-in
+fn resolve_min_data_lens(overrides: &HashMap<String, usize>) -> Vec<(ProgramType, usize)> {
+    for name in overrides.keys() {
+        if !ALL_PROGRAM_TYPES.iter().any(|p| p.name() == name) {
+            eprintln!("[ARPC] program_min_data_len: unknown program type '{}' ignored", name);
+        }
+    }
+    ALL_PROGRAM_TYPES
+        .iter()
+        .map(|&program_type| {
+            let min_len = overrides.get(program_type.name()).copied().unwrap_or_else(|| program_type.default_min_data_len());
+            (program_type, min_len)
+        })
+        .collect()
+}
+
+static MIN_DATA_LENS: Lazy<Vec<(ProgramType, usize)>> = Lazy::new(|| {
+    let overrides = GLOBAL_CONFIG.get().map(|c| c.program_min_data_len.clone()).unwrap_or_default();
+    resolve_min_data_lens(&overrides)
+});
+
+/// Only swap instructions of a matched program are dispatched to its builder; undersized data
+/// is rejected here, before the builder does any RPC read
+#[inline]
+pub fn is_swap_instruction(program_type: ProgramType, data: &[u8]) -> bool {
+    let long_enough = MIN_DATA_LENS
+        .iter()
+        .find(|(p, _)| *p == program_type)
+        .map_or(true, |(_, min_len)| data.len() >= *min_len);
+    long_enough
+        && ACCEPTED_DISCRIMINATORS
+            .iter()
+            .find(|(p, _)| *p == program_type)
+            .map_or(true, |(_, accepted)| discriminator_accepted(accepted, data))
+}
+
+// (program type, first 8 data bytes, zero-padded) -> times seen without an accepted discriminator
+static UNKNOWN_DISCRIMINATORS: Lazy<DashMap<(&'static str, [u8; 8]), usize>> = Lazy::new(DashMap::new);
+
+/// Count an instruction of a known program whose discriminator isn't one we build from,
+/// when `track_unknown_discriminators` is on. Undersized data with a known discriminator isn't counted.
+fn record_unknown_discriminator(program_type: ProgramType, data: &[u8]) {
+    let known = ACCEPTED_DISCRIMINATORS
+        .iter()
+        .find(|(p, _)| *p == program_type)
+        .map_or(true, |(_, accepted)| discriminator_accepted(accepted, data));
+    if known {
+        return;
+    }
+    let mut discriminator = [0u8; 8];
+    let len = data.len().min(8);
+    discriminator[..len].copy_from_slice(&data[..len]);
+    *UNKNOWN_DISCRIMINATORS.entry((program_type.name(), discriminator)).or_insert(0) += 1;
+}
+
+/// The `n` most frequent unrecognized discriminators: (program type, discriminator, count)
+pub fn top_unknown_discriminators(n: usize) -> Vec<(&'static str, [u8; 8], usize)> {
+    let mut counts: Vec<_> = UNKNOWN_DISCRIMINATORS
+        .iter()
+        .map(|entry| (entry.key().0, entry.key().1, *entry.value()))
+        .collect();
+    counts.sort_by(|a, b| b.2.cmp(&a.2));
+    counts.truncate(n);
+    counts
+}
+
+/// Match order for a transaction touching several known programs: `program_priority` names first
+/// (unknown names are ignored), unlisted types after them
+fn resolve_program_priority(names: &[String]) -> Vec<ProgramType> {
+    let mut priority = Vec::new();
+    for name in names {
+        match ALL_PROGRAM_TYPES.iter().find(|p| p.name() == name) {
+            Some(program_type) if !priority.contains(program_type) => priority.push(*program_type),
+            Some(_) => {}
+            None => eprintln!("[ARPC] program_priority: unknown program type '{}' ignored", name),
+        }
+    }
+    priority
+}
+
+static PROGRAM_PRIORITY: Lazy<Vec<ProgramType>> = Lazy::new(|| {
+    let names = GLOBAL_CONFIG.get().map(|c| c.program_priority.clone()).unwrap_or_default();
+    resolve_program_priority(&names)
+});
+
+/// Matched instructions ordered by program priority; equal priority keeps instruction order
+fn order_by_priority<T>(mut matches: Vec<(ProgramType, T)>, priority: &[ProgramType]) -> Vec<(ProgramType, T)> {
+    matches.sort_by_key(|(program_type, _)| {
+        priority.iter().position(|p| p == program_type).unwrap_or(priority.len())
+    });
+    matches
+}
+
 pub fn get_storage_stats() -> (usize, u64) {
     (
         STORAGE_OPERATIONS.load(Ordering::Relaxed),
@@ -85,6 +302,106 @@ pub fn get_worker_stats() -> (usize, usize, usize, usize) {
     )
 }
 
+pub fn get_dust_skipped() -> usize {
+    WORKER_DUST_SKIPPED.load(Ordering::Relaxed)
+}
+
+/// Expected token output below `min_token_out` (0 disables the check)
+fn is_dust_output(target_token_buy: u64, min_token_out: u64) -> bool {
+    min_token_out > 0 && target_token_buy < min_token_out
+}
+
+// Launchpad / pump mints use 6 decimals; used to value raw token amounts in USD
+pub const DUST_TOKEN_DECIMALS: i32 = 6;
+
+/// Expected output worth less than `min_usd` at the cached Birdeye price.
+/// Unknown price (or 0 threshold) never counts as dust.
+fn is_dust_output_usd(target_token_buy: u64, price_usd: Option<f64>, min_usd: f64) -> bool {
+    match price_usd {
+        Some(price) if min_usd > 0.0 => {
+            (target_token_buy as f64 / 10f64.powi(DUST_TOKEN_DECIMALS)) * price < min_usd
+        }
+        _ => false,
+    }
+}
+
+/// Insert a built buy under the detected signature (`original_key`) and map every vendor signature
+/// to it, since any vendor's copy may be the one that lands; returns the number of map entries.
+/// Unless `winner_only` each vendor signature gets its own copy of the entry, otherwise only an
+/// alias back to `original_key`.
+pub fn insert_buy_entries(original_key: Vec<u8>, tx_with_pubkey: &TxWithPubkey, winner_only: bool) -> usize {
+    let mut inserted = 1;
+    for (_, transaction) in &tx_with_pubkey.vendor_transactions {
+        if let Some(signature) = transaction.signatures.first() {
+            if winner_only {
+                GLOBAL_SIG_ALIASES.insert(signature.as_ref().to_vec(), original_key.clone());
+                continue;
+            }
+            let mut vendor_tx_with_pubkey = tx_with_pubkey.clone();
+            vendor_tx_with_pubkey.send_sig = signature.to_string();
+            GLOBAL_TX_MAP.insert(signature.as_ref().to_vec(), vendor_tx_with_pubkey);
+            inserted += 1;
+        }
+    }
+    GLOBAL_TX_MAP.insert(original_key, tx_with_pubkey.clone());
+    inserted
+}
+
+/// GLOBAL_TX_MAP key of the buy a landed signature belongs to: its detected signature when the
+/// signature is a `alias_vendor_sigs` alias, the signature itself otherwise
+pub fn resolve_tx_key(sig_bytes: &[u8]) -> Vec<u8> {
+    GLOBAL_SIG_ALIASES.get(sig_bytes).map_or_else(|| sig_bytes.to_vec(), |key| key.value().clone())
+}
+
+/// The buy entry a vendor signature is aliased to (`alias_vendor_sigs`)
+pub fn aliased_entry(sig_bytes: &[u8]) -> Option<TxWithPubkey> {
+    let key = GLOBAL_SIG_ALIASES.get(sig_bytes)?.value().clone();
+    GLOBAL_TX_MAP.get(&key).map(|entry| entry.value().clone())
+}
+
+pub fn get_sanity_rejected() -> usize {
+    SANITY_REJECTED.load(Ordering::Relaxed)
+}
+
+/// Token amount above `max_token_amount`, or worth more than `max_usd` at the cached
+/// Birdeye price. Such a trade almost certainly comes from a misread, so it is refused
+/// on both the buy and the sell side. A 0 bound or unknown price skips that check.
+pub fn is_implausible_amount(token_amount: u64, price_usd: Option<f64>, max_token_amount: u64, max_usd: f64) -> bool {
+    if max_token_amount > 0 && token_amount > max_token_amount {
+        return true;
+    }
+    match price_usd {
+        Some(price) if max_usd > 0.0 => {
+            (token_amount as f64 / 10f64.powi(DUST_TOKEN_DECIMALS)) * price > max_usd
+        }
+        _ => false,
+    }
+}
+
+/// Counts and loudly logs a refused trade; `side` is "buy" or "sell"
+pub fn report_implausible_amount(side: &str, token_amount: u64, price_usd: Option<f64>, mint: &str, cid: &str, config: &Config) {
+    SANITY_REJECTED.fetch_add(1, Ordering::Relaxed);
+    eprintln!("[{}] - [SANITY] [cid={}] !!! REFUSING {} of {}: token amount {} (price: {:?} USD) exceeds max_token_amount {} / max_trade_usd {} - possible parse bug !!!",
+        Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+        cid,
+        side,
+        mint,
+        token_amount,
+        price_usd,
+        config.max_token_amount,
+        config.max_trade_usd);
+}
+
+/// Instructions the matching loop may scan, capped at `max_instructions` (0 = no cap).
+/// Returns the slice to scan and whether the cap cut it short.
+fn instructions_to_scan(instructions: &[CompiledInstruction], max_instructions: usize) -> (&[CompiledInstruction], bool) {
+    if max_instructions > 0 && instructions.len() > max_instructions {
+        (&instructions[..max_instructions], true)
+    } else {
+        (instructions, false)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ParsedArpcTrade {
     pub sig_bytes: Option<Arc<Vec<u8>>>,
@@ -92,6 +409,8 @@ pub struct ParsedArpcTrade {
     pub detection_time: Instant,
     pub tx_instructions: Arc<Vec<CompiledInstruction>>,
     pub account_keys: Arc<Vec<Vec<u8>>>,
+    pub corr_id: String, // Correlation ID assigned at detection, carried into TxWithPubkey
+    pub leader_compute_budget: LeaderComputeBudget, // Leader's CU price / limit from its ComputeBudget instructions
     // Add more fields if needed for the worker
 }
 
@@ -117,12 +436,16 @@ pub struct ParsedArpcTradeLazy<'a> {
 impl<'a> ParsedArpcTradeLazy<'a> {
     // Convert to full version only when needed
     pub fn into_full(self) -> ParsedArpcTrade {
+        let tx_instructions = self.tx_instructions.cloned().unwrap_or_default();
+        let account_keys = self.account_keys.cloned().unwrap_or_default();
         ParsedArpcTrade {
             sig_bytes: self.sig_bytes.map(|s| Arc::new(s)),
             slot: self.slot,
             detection_time: self.detection_time,
-            tx_instructions: Arc::new(self.tx_instructions.unwrap_or(&Vec::new()).clone()),
-            account_keys: Arc::new(self.account_keys.unwrap_or(&Vec::new()).clone()),
+            leader_compute_budget: parse_compute_budget(&tx_instructions, &account_keys),
+            tx_instructions: Arc::new(tx_instructions),
+            account_keys: Arc::new(account_keys),
+            corr_id: crate::utils::logger::new_correlation_id(),
         }
     }
 }
@@ -142,10 +465,16 @@ pub struct TxWithPubkey {
     pub pump_fun_accounts: Option<PumpFunAccounts>,
     pub raydium_cpmm_accounts: Option<RayCpmmSwapAccounts>,
     pub ray_cpmm_pool_state: Option<Pubkey>,
+    pub user_ata: Option<CachedAta>, // Pre-warmed user ATA for the mint (set at buy time)
     pub send_sig: String,
     pub send_time: Instant,
     pub send_slot: u64,
     pub created_at: Instant, // Track when this entry was created
+    pub corr_id: String, // Correlation ID of the detected trade (logged as cid=...)
+    pub open_position: bool, // Our buy was sent and the entry is held until the sell; exempt from the age purge
+    pub lifecycle: crate::utils::lifecycle::TradeLifecycle, // detect/buy/sell send and land timestamps
+    pub entry_price_usd: Option<f64>, // Cached Birdeye price when our buy landed, for the daily PnL estimate
+    pub buy_instruction: Option<Instruction>, // Kept (chase_enabled only) to rebuild the buy at a higher bid
 }
 
 impl TxWithPubkey {
@@ -162,10 +491,16 @@ impl TxWithPubkey {
             pump_fun_accounts: None,
             raydium_cpmm_accounts: None,
             ray_cpmm_pool_state: None,
+            user_ata: None,
             send_sig: String::new(),
             send_time: Instant::now(),
             send_slot: 0,
             created_at: Instant::now(),
+            corr_id: String::new(),
+            open_position: false,
+            lifecycle: Default::default(),
+            entry_price_usd: None,
+            buy_instruction: None,
         }
     }
     
@@ -199,10 +534,54 @@ pub fn default_tx_with_pubkey() -> TxWithPubkey {
 
 // Global map: signature (String) -> Transaction
 pub static GLOBAL_TX_MAP: Lazy<DashMap<Vec<u8>, TxWithPubkey>> = Lazy::new(DashMap::new);
+// Vendor signature -> GLOBAL_TX_MAP key (detected signature) of its buy, under alias_vendor_sigs
+pub static GLOBAL_SIG_ALIASES: Lazy<DashMap<Vec<u8>, Vec<u8>>> = Lazy::new(DashMap::new);
 
 static ARPC_PARSED_SENDER: OnceCell<Sender<ParsedArpcTrade>> = OnceCell::new();
 
-/// Purge entries older than 10 seconds from GLOBAL_TX_MAP
+// Latest network slot seen by any feed (ARPC or Triton)
+pub static CURRENT_SLOT: AtomicU64 = AtomicU64::new(0);
+
+/// Record a slot observed on a feed; keeps the highest slot seen
+pub fn update_current_slot(slot: u64) {
+    CURRENT_SLOT.fetch_max(slot, Ordering::Relaxed);
+}
+
+pub fn get_current_slot() -> u64 {
+    CURRENT_SLOT.load(Ordering::Relaxed)
+}
+
+/// True if an entry sent at `send_slot` is more than `max_slots` behind `current_slot` (0 disables)
+fn is_slot_expired(send_slot: u64, current_slot: u64, max_slots: u64) -> bool {
+    max_slots > 0 && send_slot > 0 && current_slot.saturating_sub(send_slot) > max_slots
+}
+
+/// Why a GLOBAL_TX_MAP entry is purged
+#[derive(Debug, PartialEq, Eq)]
+enum PurgeReason {
+    Age,
+    SlotExpired,
+}
+
+/// Decide whether an entry should be purged. Stale buy-trigger entries age out after
+/// `purge_threshold`; open positions are only expired by slot (`max_position_slots`).
+fn purge_reason(
+    entry: &TxWithPubkey,
+    now: Instant,
+    purge_threshold: std::time::Duration,
+    current_slot: u64,
+    max_position_slots: u64,
+) -> Option<PurgeReason> {
+    if !entry.open_position && now.duration_since(entry.created_at) > purge_threshold {
+        Some(PurgeReason::Age)
+    } else if is_slot_expired(entry.send_slot, current_slot, max_position_slots) {
+        Some(PurgeReason::SlotExpired)
+    } else {
+        None
+    }
+}
+
+/// Purge stale entries (older than `tx_map_purge_secs`) from GLOBAL_TX_MAP
 fn purge_old_entries_task() {
     use std::time::Duration;
     
@@ -212,15 +591,26 @@ fn purge_old_entries_task() {
         let now = Instant::now();
         let mut to_remove = Vec::new();
         
-        // OPTIMIZATION: Reduced retention time from 10 to 6 seconds
-        let purge_threshold = Duration::from_secs(10); // Was 10 seconds
+        let purge_threshold = Duration::from_secs(GLOBAL_CONFIG.get().map(|c| c.tx_map_purge_secs).unwrap_or(10));
+        let max_position_slots = GLOBAL_CONFIG.get().map(|c| c.max_position_slots).unwrap_or(0);
+        let current_slot = get_current_slot();
+        let mut slot_expired = 0;
         
         for entry in GLOBAL_TX_MAP.iter() {
-            if now.duration_since(entry.value().created_at) > purge_threshold {
-                to_remove.push(entry.key().clone());
+            match purge_reason(entry.value(), now, purge_threshold, current_slot, max_position_slots) {
+                Some(PurgeReason::Age) => to_remove.push(entry.key().clone()),
+                Some(PurgeReason::SlotExpired) => {
+                    to_remove.push(entry.key().clone());
+                    slot_expired += 1;
+                }
+                None => {}
             }
         }
         
+        if slot_expired > 0 {
+            println!("[ARPC] Expired {} pending entries older than {} slots (current slot: {})", slot_expired, max_position_slots, current_slot);
+        }
+        
         // Remove old entries
         for key in to_remove {
             GLOBAL_TX_MAP.remove(&key);
@@ -228,9 +618,12 @@ fn purge_old_entries_task() {
         
         // OPTIMIZATION: Emergency cleanup if map gets too large
         if GLOBAL_TX_MAP.len() > 800 { // Reduced from 1000 to 800
-            println!("[ARPC] WARNING: Transaction map too large ({} entries), clearing...", GLOBAL_TX_MAP.len());
-            GLOBAL_TX_MAP.clear();
+            println!("[ARPC] WARNING: Transaction map too large ({} entries), evicting pending entries...", GLOBAL_TX_MAP.len());
+            if let Some(dropped) = shed_overflow(&GLOBAL_TX_MAP, 800) {
+                alert(AlertKind::MapOverflow, format!("transaction map still over 800 entries after evicting pending ones, cleared ({} open positions dropped)", dropped));
+            }
         }
+        GLOBAL_SIG_ALIASES.retain(|_, key| GLOBAL_TX_MAP.contains_key(key));
         
         // OPTIMIZATION: Log cleanup stats periodically
         if GLOBAL_TX_MAP.len() > 100 {
@@ -239,6 +632,18 @@ fn purge_old_entries_task() {
     }
 }
 
+/// Bring an overflowing map back under `limit`: pending entries go first, and only if the open
+/// positions alone still exceed it is the map cleared. Returns the open positions dropped that way
+fn shed_overflow(map: &DashMap<Vec<u8>, TxWithPubkey>, limit: usize) -> Option<usize> {
+    map.retain(|_, entry| entry.open_position);
+    if map.len() <= limit {
+        return None;
+    }
+    let dropped = map.len();
+    map.clear();
+    Some(dropped)
+}
+
 pub fn setup_arpc_crossbeam_worker() {
     // Use bounded channel instead of unbounded to prevent memory leaks
     let (tx, rx) = crossbeam::channel::bounded::<ParsedArpcTrade>(1000);
@@ -249,16 +654,13 @@ pub fn setup_arpc_crossbeam_worker() {
         purge_old_entries_task();
     });
     
-    // Spawn 3 worker threads for heavy processing
-    for worker_id in 0..3 {
+    // Spawn 3 worker threads for heavy processing (the watchdog respawns stuck ones through the same closure)
+    watchdog::ARPC_WORKERS.start(3, move |worker_id| {
         let rx_clone = rx.clone();
         std::thread::spawn(move || {
-            // Pin worker threads to cores 5-7 for optimal performance
-            if let Some(cores) = core_affinity::get_core_ids() {
-                if cores.len() > 5 + worker_id {
-                    core_affinity::set_for_current(cores[5 + worker_id]);
-                    println!("[arpc worker {}] Pinned to core {}", worker_id, 5 + worker_id);
-                }
+            // Pin worker threads to the arpc_workers cores (5-7 by default)
+            if let Some(&core) = affinity::cores_for(affinity::ARPC_WORKERS).get(worker_id) {
+                affinity::pin_and_verify(&format!("arpc worker {}", worker_id), core);
             }
         
             // Set critical real-time priority for processing (highest priority)
@@ -270,18 +672,26 @@ pub fn setup_arpc_crossbeam_worker() {
         let buy_sol_lamports = (config.buy_sol * 1_000_000_000.0) as u64;
 
         while let Ok(parsed) = rx_clone.recv() {
+            let _busy = watchdog::ARPC_WORKERS.busy(worker_id);
             WORKER_MESSAGES_RECEIVED.fetch_add(1, Ordering::Relaxed);
             
+            // Drop messages while the buy pipeline is paused (and those buffered during the pause)
+            if should_skip(Pipeline::Arpc, Some(parsed.detection_time)) {
+                continue;
+            }
+            
             let worker_total_start = Instant::now();
             let sig_str = parsed.sig_bytes
                 .as_ref()
                 .map(|s| bs58::encode(s.as_slice()).into_string())
                 .unwrap_or_else(|| "<no_sig>".to_string());
+            let cid = parsed.corr_id.as_str();
             
             let now = Utc::now();
-            println!("[{}] - [WORKER-{}] Processing message for sig: {} (total received: {})", 
+            println!("[{}] - [WORKER-{}] [cid={}] Processing message for sig: {} (total received: {})", 
                 now.format("%Y-%m-%d %H:%M:%S%.3f"), 
                 worker_id,
+                cid,
                 sig_str, 
                 WORKER_MESSAGES_RECEIVED.load(Ordering::Relaxed));
             
@@ -298,7 +708,10 @@ pub fn setup_arpc_crossbeam_worker() {
             let parse_start = Instant::now();
             // --- OPTIMIZED INSTRUCTION MATCHING ---
             let mut instruction_count = 0;
-            for instr in parsed.tx_instructions.iter() {
+            let (scan_instructions, scan_capped) = instructions_to_scan(&parsed.tx_instructions, config.max_instructions_to_scan);
+            // Collect every swap instruction of a known program, then build from the highest-priority one
+            let mut matched: Vec<(ProgramType, &CompiledInstruction)> = Vec::new();
+            for instr in scan_instructions.iter() {
                 instruction_count += 1;
                 let instr_start = Instant::now();
                 
@@ -312,7 +725,7 @@ pub fn setup_arpc_crossbeam_worker() {
                     let account_lookup_time = account_lookup_start.elapsed();
                     #[cfg(feature = "verbose_logging")]
                     if instruction_count <= 3 { // Only log first few instructions to avoid spam
-                        println!("[PROFILE][{}] Instruction {} - Account lookup: {:.2?}", sig_str, instruction_count, account_lookup_time);
+                        println!("[PROFILE][{}][cid={}] Instruction {} - Account lookup: {:.2?}", sig_str, cid, instruction_count, account_lookup_time);
                     }
                     
                     // HIGH PRIORITY OPTIMIZATION: Hash-based program ID matching
@@ -320,99 +733,26 @@ pub fn setup_arpc_crossbeam_worker() {
                     if let Some(program_type) = get_program_type(account_inst_bytes) {
                         let program_check_time = program_check_start.elapsed();
                         #[cfg(feature = "verbose_logging")]
-                        println!("[PROFILE][{}] Instruction {} - Program ID check: {:.2?}", sig_str, instruction_count, program_check_time);
+                        println!("[PROFILE][{}][cid={}] Instruction {} - Program ID check: {:.2?}", sig_str, cid, instruction_count, program_check_time);
                         
-                        // Process based on program type with early exit
-                        match program_type {
-                            ProgramType::RaydiumLaunchpad => {
-                                // Check discriminator for Raydium
-                                if data.len() > 8 && &data[0..8] == [250, 234, 13, 123, 213, 156, 19, 236] {
-                                    let raydium_start = Instant::now();
-                                    (buy_instruction, mint, target_token_buy, ray_launch_accounts) = raydium_launchpad_build_buy_tx(
-                                        &parsed.account_keys,
-                                        &instr.accounts,
-                                        parsed.sig_bytes.clone(),
-                                        parsed.detection_time,
-                                        data,
-                                        buy_sol_lamports,
-                                        config.buy_slippage_bps,
-                                    );
-                                    let raydium_time = raydium_start.elapsed();
-                                    println!("[PROFILE][{}] Raydium launchpad processing: {:.2?}", sig_str, raydium_time);
-                                    send_tx = true;
-                                    let mut tx = TxWithPubkey::default();
-                                    tx.tx_type = "ray_launch".to_string();
-                                    tx.ray_launch_accounts = Some(ray_launch_accounts.clone());
-                                    tx_with_pubkey = Some(tx);
-                                    break; // Early exit after match
-                                }
-                            },
-                            ProgramType::AxiomPumpSwap => {
-                                let axiom_swap_start = Instant::now();
-                                (buy_instruction, mint, target_token_buy, pump_swap_accounts) = axiom_pump_swap_build_buy_tx(
-                                    &parsed.account_keys,
-                                    &instr.accounts,
-                                    parsed.sig_bytes.clone(),
-                                    parsed.detection_time,
-                                    buy_sol_lamports,
-                                    config.buy_slippage_bps,
-                                );
-                                let axiom_swap_time = axiom_swap_start.elapsed();
-                                println!("[PROFILE][{}] Axiom pump swap processing: {:.2?}", sig_str, axiom_swap_time);
-                                send_tx = true;
-                                let mut tx = TxWithPubkey::default();
-                                tx.tx_type = "pump_swap".to_string();
-                                tx.pump_swap_accounts = Some(pump_swap_accounts.clone());
-                                tx_with_pubkey = Some(tx);
-                                break; // Early exit after match
-                            },
-                            ProgramType::AxiomPumpFun => {
-                                let axiom_fun_start = Instant::now();
-                                (buy_instruction, mint, target_token_buy, pump_fun_accounts) = axiom_pump_fun_build_buy_tx(
-                                    &parsed.account_keys,
-                                    &instr.accounts,
-                                    parsed.sig_bytes.clone(),
-                                    parsed.detection_time,
-                                    buy_sol_lamports,
-                                    config.buy_slippage_bps,
-                                );
-                                let axiom_fun_time = axiom_fun_start.elapsed();
-                                println!("[PROFILE][{}] Axiom pump fun processing: {:.2?}", sig_str, axiom_fun_time);
-                                send_tx = true;
-                                let mut tx = TxWithPubkey::default();
-                                tx.tx_type = "pumpfun".to_string();
-                                tx.pump_fun_accounts = Some(pump_fun_accounts.clone());
-                                tx_with_pubkey = Some(tx);
-                                break; // Early exit after match
-                            },
-                            ProgramType::RaydiumCpmm => {
-                                let raydium_cpmm_start = Instant::now();
-                                (buy_instruction, mint, target_token_buy, raydium_cpmm_accounts) = raydium_cpmm_build_buy_tx(
-                                    &parsed.account_keys,
-                                    &instr.accounts,
-                                    parsed.sig_bytes.clone(),
-                                    parsed.detection_time,
-                                    buy_sol_lamports,
-                                    config.buy_slippage_bps,
-                                );
-                                                                    let raydium_cpmm_time = raydium_cpmm_start.elapsed();
-                                    println!("[PROFILE][{}] Raydium CPMM processing: {:.2?}", sig_str, raydium_cpmm_time);
-                                if mint != Pubkey::default() { //buy tx
-                                    send_tx = true;
-                                    let mut tx = TxWithPubkey::default();
-                                    tx.tx_type = "ray_cpmm".to_string();
-                                    tx.raydium_cpmm_accounts = Some(raydium_cpmm_accounts.clone());
-                                    tx_with_pubkey = Some(tx);
-                                    break; // Early exit after match
-                                }
-                            },
+                        // Skip non-swap instructions of a matched program (e.g. add-liquidity)
+                        if !is_swap_instruction(program_type, data) {
+                            if config.track_unknown_discriminators {
+                                record_unknown_discriminator(program_type, data);
+                            }
+                            #[cfg(feature = "verbose_logging")]
+                            println!("[ARPC][{}][cid={}] Instruction {} - {} not a swap (discriminator or {} byte data), skipped", sig_str, cid, instruction_count, program_type.name(), data.len());
+                            continue;
                         }
-                    }
-                    #[cfg(feature = "verbose_logging")]
-                    {
-                        let program_check_time = program_check_start.elapsed();
-                        if instruction_count <= 3 {
-                            println!("[PROFILE][{}] Instruction {} - Program ID check (no match): {:.2?}", sig_str, instruction_count, program_check_time);
+
+                        matched.push((program_type, instr));
+                    } else {
+                        #[cfg(feature = "verbose_logging")]
+                        {
+                            let program_check_time = program_check_start.elapsed();
+                            if instruction_count <= 3 {
+                                println!("[PROFILE][{}][cid={}] Instruction {} - Program ID check (no match): {:.2?}", sig_str, cid, instruction_count, program_check_time);
+                            }
                         }
                     }
                 } else {
@@ -420,7 +760,7 @@ pub fn setup_arpc_crossbeam_worker() {
                     {
                         let account_lookup_time = account_lookup_start.elapsed();
                         if instruction_count <= 3 {
-                            println!("[PROFILE][{}] Instruction {} - Account lookup (failed): {:.2?}", sig_str, instruction_count, account_lookup_time);
+                            println!("[PROFILE][{}][cid={}] Instruction {} - Account lookup (failed): {:.2?}", sig_str, cid, instruction_count, account_lookup_time);
                         }
                     }
                 }
@@ -429,15 +769,154 @@ pub fn setup_arpc_crossbeam_worker() {
                 {
                     let instr_time = instr_start.elapsed();
                     if instruction_count <= 3 {
-                        println!("[PROFILE][{}] Instruction {} - Total processing: {:.2?}", sig_str, instruction_count, instr_time);
+                        println!("[PROFILE][{}][cid={}] Instruction {} - Total processing: {:.2?}", sig_str, cid, instruction_count, instr_time);
                     }
                 }
             }
+
+            for (program_type, instr) in order_by_priority(matched, &PROGRAM_PRIORITY) {
+                let data = &instr.data;
+                match program_type {
+                    ProgramType::RaydiumLaunchpad => {
+                        // Discriminator already checked above; buy data carries args after it
+                        if data.len() > 8 {
+                            let raydium_start = Instant::now();
+                            (buy_instruction, mint, target_token_buy, ray_launch_accounts) = raydium_launchpad_build_buy_tx(
+                                &parsed.account_keys,
+                                &instr.accounts,
+                                parsed.sig_bytes.clone(),
+                                parsed.detection_time,
+                                data,
+                                buy_sol_lamports,
+                                config.buy_slippage_bps,
+                            );
+                            let raydium_time = raydium_start.elapsed();
+                            println!("[PROFILE][{}][cid={}] Raydium launchpad processing: {:.2?}", sig_str, cid, raydium_time);
+                            send_tx = true;
+                            let mut tx = TxWithPubkey::default();
+                            tx.tx_type = "ray_launch".to_string();
+                            tx.ray_launch_accounts = Some(ray_launch_accounts.clone());
+                            tx_with_pubkey = Some(tx);
+                            break; // Early exit after match
+                        }
+                    },
+                    ProgramType::AxiomPumpSwap => {
+                        let axiom_swap_start = Instant::now();
+                        (buy_instruction, mint, target_token_buy, pump_swap_accounts) = axiom_pump_swap_build_buy_tx(
+                            &parsed.account_keys,
+                            &instr.accounts,
+                            parsed.sig_bytes.clone(),
+                            parsed.detection_time,
+                            buy_sol_lamports,
+                            config.buy_slippage_bps,
+                        );
+                        let axiom_swap_time = axiom_swap_start.elapsed();
+                        println!("[PROFILE][{}][cid={}] Axiom pump swap processing: {:.2?}", sig_str, cid, axiom_swap_time);
+                        send_tx = true;
+                        let mut tx = TxWithPubkey::default();
+                        tx.tx_type = "pump_swap".to_string();
+                        tx.pump_swap_accounts = Some(pump_swap_accounts.clone());
+                        tx_with_pubkey = Some(tx);
+                        break; // Early exit after match
+                    },
+                    ProgramType::AxiomPumpFun => {
+                        let axiom_fun_start = Instant::now();
+                        (buy_instruction, mint, target_token_buy, pump_fun_accounts) = axiom_pump_fun_build_buy_tx(
+                            &parsed.account_keys,
+                            &instr.accounts,
+                            parsed.sig_bytes.clone(),
+                            parsed.detection_time,
+                            buy_sol_lamports,
+                            config.buy_slippage_bps,
+                        );
+                        let axiom_fun_time = axiom_fun_start.elapsed();
+                        println!("[PROFILE][{}][cid={}] Axiom pump fun processing: {:.2?}", sig_str, cid, axiom_fun_time);
+                        send_tx = true;
+                        let mut tx = TxWithPubkey::default();
+                        tx.tx_type = "pumpfun".to_string();
+                        tx.pump_fun_accounts = Some(pump_fun_accounts.clone());
+                        tx_with_pubkey = Some(tx);
+                        break; // Early exit after match
+                    },
+                    ProgramType::RaydiumCpmm => {
+                        let raydium_cpmm_start = Instant::now();
+                        (buy_instruction, mint, target_token_buy, raydium_cpmm_accounts) = raydium_cpmm_build_buy_tx(
+                            &parsed.account_keys,
+                            &instr.accounts,
+                            parsed.sig_bytes.clone(),
+                            parsed.detection_time,
+                            buy_sol_lamports,
+                            config.buy_slippage_bps,
+                        );
+                                                            let raydium_cpmm_time = raydium_cpmm_start.elapsed();
+                            println!("[PROFILE][{}][cid={}] Raydium CPMM processing: {:.2?}", sig_str, cid, raydium_cpmm_time);
+                        if mint != Pubkey::default() { //buy tx
+                            send_tx = true;
+                            let mut tx = TxWithPubkey::default();
+                            tx.tx_type = "ray_cpmm".to_string();
+                            tx.raydium_cpmm_accounts = Some(raydium_cpmm_accounts.clone());
+                            tx_with_pubkey = Some(tx);
+                            break; // Early exit after match
+                        }
+                    },
+                }
+            }
+            
+            if scan_capped && !send_tx {
+                println!("[{}] - [WORKER-{}] [cid={}] Instruction scan cap hit ({} of {} scanned) without a match for sig: {}",
+                    Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+                    worker_id,
+                    cid,
+                    scan_instructions.len(),
+                    parsed.tx_instructions.len(),
+                    sig_str);
+            }
             
             let match_done = parse_start.elapsed();
             #[cfg(feature = "verbose_logging")]
-            println!("[PROFILE][{}] Instruction matching ({}) total: {:.2?}", sig_str, instruction_count, match_done);
+            println!("[PROFILE][{}][cid={}] Instruction matching ({}) total: {:.2?}", sig_str, cid, instruction_count, match_done);
             
+            let price_usd = if send_tx && (config.min_token_out_usd > 0.0 || config.max_trade_usd > 0.0) {
+                cached_price_usd(&mint.to_string(), std::time::Duration::from_secs(config.birdeye_price_ttl_secs))
+            } else {
+                None
+            };
+            if send_tx
+                && (is_dust_output(target_token_buy, config.min_token_out)
+                    || is_dust_output_usd(target_token_buy, price_usd, config.min_token_out_usd))
+            {
+                WORKER_DUST_SKIPPED.fetch_add(1, Ordering::Relaxed);
+                println!("[{}] - [WORKER-{}] [cid={}] Skipped: dust output {} (price: {:?} USD, min_token_out {}, min_token_out_usd {}) for sig: {}",
+                    Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+                    worker_id,
+                    cid,
+                    target_token_buy,
+                    price_usd,
+                    config.min_token_out,
+                    config.min_token_out_usd,
+                    sig_str);
+                send_tx = false;
+            }
+            if send_tx && is_implausible_amount(target_token_buy, price_usd, config.max_token_amount, config.max_trade_usd) {
+                report_implausible_amount("buy", target_token_buy, price_usd, &mint.to_string(), cid, config);
+                send_tx = false;
+            }
+            // A leader's retries / bundle variants of one action: copy only the first in the slot window
+            if send_tx && config.leader_dup_slot_window > 0 {
+                let leader = parsed.account_keys.first().and_then(|key| Pubkey::try_from(key.as_slice()).ok());
+                if let Some(leader) = leader {
+                    if !copy_leader_action(leader, mint, parsed.slot, config.leader_dup_slot_window, cid) {
+                        send_tx = false;
+                    }
+                }
+            }
+            // Re-entries (or several leaders) on one mint stop at max_position_sol_per_mint of open exposure
+            if send_tx && config.max_position_sol_per_mint > 0.0
+                && !reserve_mint_exposure(mint, cid, buy_sol_lamports, config.max_position_sol_per_mint)
+            {
+                send_tx = false;
+            }
+
             if send_tx {
                 WORKER_TRANSACTIONS_BUILT.fetch_add(1, Ordering::Relaxed);
                 
@@ -446,16 +925,24 @@ pub fn setup_arpc_crossbeam_worker() {
                 
                 // Build vendor-specific transactions in parallel
                 let vendor_build_start = Instant::now();
-                match crate::build_tx::tx_builder::build_vendor_specific_transactions_parallel(
+                if config.chase_enabled {
+                    tx_with_pubkey.buy_instruction = Some(buy_instruction.clone());
+                }
+                // Optionally bid at least the leader's priority fee
+                let leader_cu_price = parsed.leader_compute_budget.cu_price.filter(|_| config.match_leader_cu_price);
+                match crate::build_tx::tx_builder::build_vendor_specific_transactions_escalated(
                     buy_instruction,
                     mint,
                     target_token_buy,
                     &sig_str,
+                    &tx_with_pubkey.tx_type,
+                    1.0,
+                    leader_cu_price,
                 ) {
-                    Ok(vendor_transactions) => {
+                    Ok((vendor_transactions, build_failures)) => {
                         let vendor_build_time = vendor_build_start.elapsed();
                         #[cfg(feature = "verbose_logging")]
-                        println!("[PROFILE][{}] Vendor transaction building: {:.2?}", sig_str, vendor_build_time);
+                        println!("[PROFILE][{}][cid={}] Vendor transaction building: {:.2?}", sig_str, cid, vendor_build_time);
                         
                         if !vendor_transactions.is_empty() {
                             // Store all vendor transactions
@@ -463,36 +950,28 @@ pub fn setup_arpc_crossbeam_worker() {
                             tx_with_pubkey.mint = mint;
                             tx_with_pubkey.token_amount = target_token_buy;
                             tx_with_pubkey.created_at = Instant::now(); // Set creation time when inserting
+                            tx_with_pubkey.corr_id = parsed.corr_id.clone();
+                            tx_with_pubkey.send_slot = parsed.slot; // Detection slot until the buy is actually sent
+                            otel::set_trade_attributes(cid, &[("mint", &mint), ("tx_type", &tx_with_pubkey.tx_type)]);
+                            otel::record_span(cid, "build", build_start, Instant::now(), &[
+                                ("tx_type", &tx_with_pubkey.tx_type),
+                                ("vendors", &vendor_transactions.len()),
+                            ]);
+
+                            // Derive the user ATA once; it is marked existing when the buy lands, after which
+                            // the sell (and any re-buy) omits its create-ATA instruction
+                            let wallet = get_wallet_keypair().pubkey();
+                            tx_with_pubkey.user_ata = Some(derive_ata_cached(&wallet, &mint));
+                            if !config.birdeye_api.is_empty() {
+                                watch_price(&mint.to_string());
+                            }
 
                             let insert_start = Instant::now();
                             
-                            // Create multiple entries in GLOBAL_TX_MAP - one for each vendor transaction signature
-                            // This ensures that when any vendor transaction is detected by GRPC, we can find the corresponding entry
-                            let mut total_inserted = 0;
-                            
-                            // First, insert with the original detected signature (for backward compatibility)
+                            // Key the entry by the detected signature and the vendor signatures, so a landed
+                            // vendor transaction detected by GRPC finds it (see insert_buy_entries)
                             let original_key = parsed.sig_bytes.as_ref().unwrap().as_slice().to_vec();
-                            GLOBAL_TX_MAP.insert(original_key, tx_with_pubkey.clone());
-                            total_inserted += 1;
-                            
-                            // Then, insert with each vendor transaction signature
-                            for (vendor_name, transaction) in &vendor_transactions {
-                                // Extract signature from the transaction
-                                if let Some(signature) = transaction.signatures.first() {
-                                    let vendor_sig_bytes = signature.as_ref().to_vec();
-                                    
-                                    // Create a copy of tx_with_pubkey for this vendor signature
-                                    let mut vendor_tx_with_pubkey = tx_with_pubkey.clone();
-                                    vendor_tx_with_pubkey.send_sig = signature.to_string();
-                                    
-                                    GLOBAL_TX_MAP.insert(vendor_sig_bytes, vendor_tx_with_pubkey);
-                                    total_inserted += 1;
-                                    
-                                    #[cfg(feature = "verbose_logging")]
-                                    println!("[PROFILE][{}] Inserted {} vendor signature: {}", 
-                                        sig_str, vendor_name, signature);
-                                }
-                            }
+                            let total_inserted = insert_buy_entries(original_key, &tx_with_pubkey, config.alias_vendor_sigs);
                             
                             let insert_time = insert_start.elapsed();
                             
@@ -501,7 +980,7 @@ pub fn setup_arpc_crossbeam_worker() {
                             STORAGE_TIME_TOTAL.fetch_add(insert_time.as_micros() as u64, Ordering::Relaxed);
                             
                             #[cfg(feature = "verbose_logging")]
-                            println!("[PROFILE][{}] Map insert time: {:.2?} (inserted {} entries)", sig_str, insert_time, total_inserted);
+                            println!("[PROFILE][{}][cid={}] Map insert time: {:.2?} (inserted {} entries)", sig_str, cid, insert_time, total_inserted);
                             
                             WORKER_TRANSACTIONS_INSERTED.fetch_add(total_inserted, Ordering::Relaxed);
 
@@ -509,8 +988,8 @@ pub fn setup_arpc_crossbeam_worker() {
                             {
                                 let vendor_names: Vec<&String> = vendor_transactions.iter().map(|(v, _)| v).collect();
                                 let now = Utc::now();
-                                println!("[{}] - [WORKER] SUCCESS - {} vendor TXs built and {} entries inserted | detected for slot {} | time to parse: {:.2?} | total built: {}, total inserted: {}", 
-                                    now.format("%Y-%m-%d %H:%M:%S%.3f"), 
+                                println!("[{}] - [WORKER] [cid={}] SUCCESS - {} vendor TXs built and {} entries inserted | detected for slot {} | time to parse: {:.2?} | total built: {}, total inserted: {}", 
+                                    now.format("%Y-%m-%d %H:%M:%S%.3f"), cid, 
                                     vendor_transactions.len(),
                                     total_inserted,
                                     parsed.slot, 
@@ -518,8 +997,8 @@ pub fn setup_arpc_crossbeam_worker() {
                                     WORKER_TRANSACTIONS_BUILT.load(Ordering::Relaxed),
                                     WORKER_TRANSACTIONS_INSERTED.load(Ordering::Relaxed));
                                 
-                                println!("[{}] - [WORKER] INFO - Vendor versions: {:?}", 
-                                    now.format("%Y-%m-%d %H:%M:%S%.3f"),
+                                println!("[{}] - [WORKER] [cid={}] INFO - Vendor versions: {:?}", 
+                                    now.format("%Y-%m-%d %H:%M:%S%.3f"), cid,
                                     vendor_names
                                 );
                             }
@@ -528,23 +1007,22 @@ pub fn setup_arpc_crossbeam_worker() {
                             // Each vendor transaction can now be sent to its corresponding vendor
                         } else {
                             WORKER_ERRORS.fetch_add(1, Ordering::Relaxed);
-                            #[cfg(feature = "verbose_logging")]
-                            {
-                                let now = Utc::now();
-                                eprintln!("[{}] - [WORKER] ERROR - No vendor transactions built for sig: {} | total errors: {}", 
-                                    now.format("%Y-%m-%d %H:%M:%S%.3f"), 
-                                    sig_str,
-                                    WORKER_ERRORS.load(Ordering::Relaxed));
-                            }
+                            otel::end_trade(cid, "build_failed", Instant::now());
+                            eprintln!("[{}] - [WORKER] [cid={}] ERROR - No vendor transactions built for sig: {} | {} | total errors: {}", 
+                                Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), cid, 
+                                sig_str,
+                                build_failures.summary(),
+                                WORKER_ERRORS.load(Ordering::Relaxed));
                         }
                     }
                     Err(e) => {
                         WORKER_ERRORS.fetch_add(1, Ordering::Relaxed);
+                        otel::end_trade(cid, "build_failed", Instant::now());
                         #[cfg(feature = "verbose_logging")]
                         {
                             let now = Utc::now();
-                            eprintln!("[{}] - [WORKER] ERROR - Failed to build vendor transactions for sig: {} | Error: {} | total errors: {}", 
-                                now.format("%Y-%m-%d %H:%M:%S%.3f"), 
+                            eprintln!("[{}] - [WORKER] [cid={}] ERROR - Failed to build vendor transactions for sig: {} | Error: {} | total errors: {}", 
+                                now.format("%Y-%m-%d %H:%M:%S%.3f"), cid, 
                                 sig_str,
                                 e,
                                 WORKER_ERRORS.load(Ordering::Relaxed));
@@ -552,19 +1030,20 @@ pub fn setup_arpc_crossbeam_worker() {
                     }
                 }
             } else {
+                otel::end_trade(cid, "skipped", Instant::now());
                 #[cfg(feature = "verbose_logging")]
                 {
                     let now = Utc::now();
-                    println!("[{}] - [WORKER] No transaction to build for sig: {}", 
-                        now.format("%Y-%m-%d %H:%M:%S%.3f"), sig_str);
+                    println!("[{}] - [WORKER] [cid={}] No transaction to build for sig: {}", 
+                        now.format("%Y-%m-%d %H:%M:%S%.3f"), cid, sig_str);
                 }
             }
             let loop_total = worker_total_start.elapsed();
             #[cfg(feature = "verbose_logging")]
-            println!("[BENCH][sig={}] Total loop time: {:.2?}", sig_str, loop_total);
+            println!("[BENCH][sig={}][cid={}] Total loop time: {:.2?}", sig_str, cid, loop_total);
         }
     });
-    }
+    });
 }
 
 
@@ -618,6 +1097,11 @@ pub fn get_map_stats() -> (usize, Vec<(String, std::time::Duration)>) {
     (GLOBAL_TX_MAP.len(), stats)
 }
 
+/// Number of GLOBAL_TX_MAP entries holding an open position (buy sent, sell pending)
+pub fn get_open_positions() -> usize {
+    GLOBAL_TX_MAP.iter().filter(|entry| entry.value().open_position).count()
+}
+
 /// Get detailed statistics about multiple entries for the same transaction
 pub fn get_multiple_entries_stats() -> (usize, std::collections::HashMap<String, usize>) {
     let mut signature_counts = std::collections::HashMap::new();
@@ -710,4 +1194,246 @@ pub fn debug_and_cleanup() {
     
     println!("[{}] ================================================", 
         chrono::Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"));
-} 
\ No newline at end of file
+} 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_non_swap_instruction_of_matched_program_ignored() {
+        let resolved = resolve_discriminators(&HashMap::new());
+        let accepted = |p: ProgramType| resolved.iter().find(|(q, _)| *q == p).unwrap().1.clone();
+
+        let mut swap = vec![143, 190, 90, 218, 196, 30, 51, 222];
+        swap.extend_from_slice(&[0u8; 16]);
+        let mut deposit = vec![242, 35, 198, 137, 82, 225, 242, 182]; // CPMM deposit (add liquidity)
+        deposit.extend_from_slice(&[0u8; 24]);
+        assert!(discriminator_accepted(&accepted(ProgramType::RaydiumCpmm), &swap));
+        assert!(!discriminator_accepted(&accepted(ProgramType::RaydiumCpmm), &deposit));
+        assert!(!discriminator_accepted(&accepted(ProgramType::RaydiumLaunchpad), &deposit));
+        assert!(!discriminator_accepted(&accepted(ProgramType::RaydiumCpmm), &[143, 190]));
+        // No built-in set for the Axiom proxies: any instruction is accepted
+        assert!(discriminator_accepted(&accepted(ProgramType::AxiomPumpSwap), &deposit));
+
+        // Config override replaces the built-in set
+        let mut overrides = HashMap::new();
+        overrides.insert("pump_swap".to_string(), vec![vec![1, 2, 3, 4, 5, 6, 7, 8], vec![1, 2]]);
+        let resolved = resolve_discriminators(&overrides);
+        let pump_swap = &resolved.iter().find(|(p, _)| *p == ProgramType::AxiomPumpSwap).unwrap().1;
+        assert_eq!(pump_swap, &vec![[1, 2, 3, 4, 5, 6, 7, 8]]);
+        assert!(!discriminator_accepted(pump_swap, &deposit));
+    }
+
+    #[test]
+    fn test_unknown_discriminators_counted() {
+        let mut deposit = vec![242, 35, 198, 137, 82, 225, 242, 182]; // CPMM deposit
+        deposit.extend_from_slice(&[0u8; 24]);
+        let mut swap = vec![143, 190, 90, 218, 196, 30, 51, 222];
+        swap.extend_from_slice(&[0u8; 16]);
+        for _ in 0..3 {
+            record_unknown_discriminator(ProgramType::RaydiumCpmm, &deposit);
+        }
+        // Known discriminator (even truncated) is not an unknown variant
+        record_unknown_discriminator(ProgramType::RaydiumCpmm, &swap);
+        record_unknown_discriminator(ProgramType::RaydiumCpmm, &swap[..12]);
+        record_unknown_discriminator(ProgramType::RaydiumCpmm, &[9, 9]);
+
+        let top = top_unknown_discriminators(10);
+        let count = |d: [u8; 8]| top.iter().find(|(p, k, _)| *p == "ray_cpmm" && *k == d).map(|e| e.2);
+        assert_eq!(count([242, 35, 198, 137, 82, 225, 242, 182]), Some(3));
+        assert_eq!(count([143, 190, 90, 218, 196, 30, 51, 222]), None);
+        assert_eq!(count([9, 9, 0, 0, 0, 0, 0, 0]), Some(1));
+        assert!(top.windows(2).all(|w| w[0].2 >= w[1].2));
+    }
+
+    #[test]
+    fn test_undersized_instruction_data_rejected() {
+        let mut buy = vec![250, 234, 13, 123, 213, 156, 19, 236]; // launchpad buy_exact_in
+        buy.extend_from_slice(&[0u8; 24]);
+        assert!(is_swap_instruction(ProgramType::RaydiumLaunchpad, &buy));
+        // Right discriminator, truncated args: not a buy, no builder (and no RPC read) runs
+        assert!(!is_swap_instruction(ProgramType::RaydiumLaunchpad, &buy[..20]));
+
+        let mut swap = vec![143, 190, 90, 218, 196, 30, 51, 222];
+        swap.extend_from_slice(&[0u8; 16]);
+        assert!(is_swap_instruction(ProgramType::RaydiumCpmm, &swap));
+        assert!(!is_swap_instruction(ProgramType::RaydiumCpmm, &swap[..16]));
+        assert!(is_swap_instruction(ProgramType::AxiomPumpFun, &[1, 2]));
+
+        let mut overrides = HashMap::new();
+        overrides.insert("pumpfun".to_string(), 24);
+        let resolved = resolve_min_data_lens(&overrides);
+        assert!(resolved.contains(&(ProgramType::AxiomPumpFun, 24)));
+        assert!(resolved.contains(&(ProgramType::RaydiumCpmm, 24)));
+    }
+
+    #[test]
+    fn test_open_position_survives_age_purge() {
+        use std::time::Duration;
+        let threshold = Duration::from_secs(10);
+        let created_at = Instant::now();
+        let later = created_at + Duration::from_secs(30);
+
+        let mut stale = TxWithPubkey::default();
+        stale.created_at = created_at;
+        assert_eq!(purge_reason(&stale, later, threshold, 0, 0), Some(PurgeReason::Age));
+        assert_eq!(purge_reason(&stale, created_at, threshold, 0, 0), None);
+
+        let mut open = TxWithPubkey::default();
+        open.created_at = created_at;
+        open.open_position = true;
+        open.send_slot = 100;
+        assert_eq!(purge_reason(&open, later, threshold, 110, 20), None);
+        // Slot expiry still applies to open positions
+        assert_eq!(purge_reason(&open, later, threshold, 200, 20), Some(PurgeReason::SlotExpired));
+    }
+
+    #[test]
+    fn test_overflow_evicts_pending_entries_before_open_positions() {
+        let map: DashMap<Vec<u8>, TxWithPubkey> = DashMap::new();
+        for i in 0..10u8 {
+            let mut entry = TxWithPubkey::default();
+            entry.open_position = i < 3;
+            map.insert(vec![i], entry);
+        }
+        assert_eq!(shed_overflow(&map, 5), None);
+        assert_eq!(map.len(), 3);
+        assert!(map.iter().all(|entry| entry.open_position));
+
+        // Open positions alone over the limit: cleared, and reported
+        assert_eq!(shed_overflow(&map, 2), Some(3));
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn test_slot_expiry() {
+        assert!(!is_slot_expired(100, 110, 20));
+        assert!(!is_slot_expired(100, 120, 20));
+        assert!(is_slot_expired(100, 121, 20));
+        // Disabled, or slot not yet known
+        assert!(!is_slot_expired(100, 500, 0));
+        assert!(!is_slot_expired(0, 500, 20));
+        assert!(!is_slot_expired(100, 0, 20));
+    }
+
+    #[test]
+    fn test_instruction_scan_cap() {
+        let instructions: Vec<CompiledInstruction> = (0..5_000)
+            .map(|i| CompiledInstruction { program_id_index: i % 256, accounts: vec![], data: vec![] })
+            .collect();
+
+        let (scan, capped) = instructions_to_scan(&instructions, 64);
+        assert_eq!(scan.len(), 64);
+        assert!(capped);
+        assert_eq!(scan.iter().count(), 64);
+
+        // Normal-sized transactions are scanned fully; 0 disables the cap
+        let (scan, capped) = instructions_to_scan(&instructions[..10], 64);
+        assert_eq!(scan.len(), 10);
+        assert!(!capped);
+        let (scan, capped) = instructions_to_scan(&instructions, 0);
+        assert_eq!(scan.len(), 5_000);
+        assert!(!capped);
+    }
+
+    #[test]
+    fn test_dust_output_threshold() {
+        assert!(!is_dust_output(0, 0));
+        assert!(!is_dust_output(5, 0));
+        assert!(is_dust_output(999, 1_000));
+        assert!(!is_dust_output(1_000, 1_000));
+        assert!(!is_dust_output(1_000_000, 1_000));
+    }
+
+    #[test]
+    fn test_dust_output_usd() {
+        // 2 tokens (6 decimals) at $0.50 = $1.00
+        assert!(is_dust_output_usd(2_000_000, Some(0.5), 1.5));
+        assert!(!is_dust_output_usd(2_000_000, Some(0.5), 1.0));
+        // Disabled or no price known
+        assert!(!is_dust_output_usd(1, Some(0.5), 0.0));
+        assert!(!is_dust_output_usd(1, None, 1.0));
+    }
+
+    #[test]
+    fn test_multi_program_match_follows_priority() {
+        // A route touching a CPMM pool (ix 1), then a launchpad pool (ix 3), then pump swap (ix 4)
+        let matched = vec![(ProgramType::RaydiumCpmm, 1), (ProgramType::RaydiumLaunchpad, 3), (ProgramType::AxiomPumpSwap, 4)];
+
+        // No priority configured: first match wins, as before
+        assert_eq!(order_by_priority(matched.clone(), &[])[0], (ProgramType::RaydiumCpmm, 1));
+
+        let priority = resolve_program_priority(&["ray_launch".to_string(), "bogus".to_string(), "ray_launch".to_string()]);
+        assert_eq!(priority, vec![ProgramType::RaydiumLaunchpad]);
+        let ordered: Vec<usize> = order_by_priority(matched, &priority).into_iter().map(|(_, ix)| ix).collect();
+        assert_eq!(ordered, vec![3, 1, 4]);
+    }
+
+    #[test]
+    fn test_winner_only_map_entries() {
+        use solana_sdk::signature::Keypair;
+        let payer = Keypair::new();
+        let mut trade = TxWithPubkey::default();
+        trade.corr_id = "map-entries-test".to_string();
+        trade.vendor_transactions = (0..7)
+            .map(|i| {
+                let ix = solana_sdk::system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), i + 1);
+                let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[&payer], Default::default());
+                (format!("vendor{}", i), tx)
+            })
+            .collect();
+        let entries_for = |corr_id: &str| GLOBAL_TX_MAP.iter().filter(|e| e.value().corr_id == corr_id).count();
+
+        // All vendors upfront: original + 7 vendor signatures
+        assert_eq!(insert_buy_entries(vec![1u8; 64], &trade, false), 8);
+        assert_eq!(entries_for("map-entries-test"), 8);
+
+        // Winner only: one entry, every vendor signature aliased to it (any copy may land)
+        trade.corr_id = "map-entries-winner".to_string();
+        let original_key = vec![2u8; 64];
+        assert_eq!(insert_buy_entries(original_key.clone(), &trade, true), 1);
+        assert_eq!(entries_for("map-entries-winner"), 1);
+        for (_, tx) in &trade.vendor_transactions {
+            let sig = tx.signatures[0];
+            assert_eq!(aliased_entry(sig.as_ref()).map(|e| e.corr_id), Some("map-entries-winner".to_string()));
+            assert_eq!(resolve_tx_key(sig.as_ref()), original_key);
+        }
+        assert_eq!(resolve_tx_key(&[3u8; 64]), vec![3u8; 64]);
+    }
+
+    #[test]
+    fn test_dex_toggles_gate_program_types() {
+        let cpmm = RAYDIUM_CPMM_PROGRAM_ID_BYTES.to_vec();
+        let pump_swap = AXIOM_PUMP_SWAP_PROGRAM_ID_BYTES.to_vec();
+        let launchpad = RAYDIUM_LAUNCHPAD_PROGRAM_ID_BYTES.to_vec();
+
+        let all_on = resolve_enabled_programs(1, 1);
+        assert_eq!(all_on, ALL_PROGRAM_TYPES.to_vec());
+        assert_eq!(program_type_if_enabled(&cpmm, &all_on), Some(ProgramType::RaydiumCpmm));
+
+        // pumpMkts = 0: pump transactions are no match, the others still build
+        let no_pump = resolve_enabled_programs(0, 2);
+        assert_eq!(program_type_if_enabled(&pump_swap, &no_pump), None);
+        assert_eq!(program_type_if_enabled(&AXIOM_PUMP_FUN_PROGRAM_ID_BYTES.to_vec(), &no_pump), None);
+        assert_eq!(program_type_if_enabled(&cpmm, &no_pump), Some(ProgramType::RaydiumCpmm));
+        assert_eq!(program_type_if_enabled(&launchpad, &no_pump), Some(ProgramType::RaydiumLaunchpad));
+
+        let no_cpmm = resolve_enabled_programs(1, 0);
+        assert_eq!(program_type_if_enabled(&cpmm, &no_cpmm), None);
+        assert_eq!(program_type_if_enabled(&pump_swap, &no_cpmm), Some(ProgramType::AxiomPumpSwap));
+    }
+
+    #[test]
+    fn test_implausible_amount_rejected() {
+        // Garbage read as u64
+        assert!(is_implausible_amount(u64::MAX, None, 1_000_000_000_000_000, 0.0));
+        assert!(!is_implausible_amount(1_000_000_000_000_000, None, 1_000_000_000_000_000, 0.0));
+        // 1M tokens (6 decimals) at $1.00 = $1M
+        assert!(is_implausible_amount(1_000_000_000_000, Some(1.0), 0, 10_000.0));
+        assert!(!is_implausible_amount(1_000_000_000, Some(1.0), 0, 10_000.0));
+        // Disabled, or value check without a known price
+        assert!(!is_implausible_amount(u64::MAX, None, 0, 0.0));
+        assert!(!is_implausible_amount(u64::MAX, None, 0, 10_000.0));
+    }
+}
@@ -9,9 +9,12 @@ use std::sync::Arc;
 use tokio::sync::mpsc;
 use tokio_stream::wrappers::ReceiverStream;
 use tokio::time::{sleep, Duration};
+use crate::grpc::connection_stats::{record_connect, record_disconnect};
+use crate::alerts::{alert, AlertKind};
 use chrono::Utc;
 use core_affinity;
 use once_cell::sync::Lazy;
+use crate::grpc::compression::{grpc_compression_encoding, record_stream_message, StreamFeed};
 
 // Add global counters for monitoring
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -28,8 +31,14 @@ pub fn get_arpc_stats() -> (usize, usize, usize) {
 }
 
 // Add helper functions for GRPC client creation and subscription
-async fn create_grpc_client(endpoint: &str) -> Result<ArpcServiceClient<tonic::transport::Channel>, Box<dyn std::error::Error + Send + Sync>> {
-    let client = ArpcServiceClient::connect(endpoint.to_string()).await?;
+async fn create_grpc_client(endpoint: &str, compression: &str) -> Result<ArpcServiceClient<tonic::transport::Channel>, Box<dyn std::error::Error + Send + Sync>> {
+    let channel = crate::utils::socket_tuning::tuned_endpoint(endpoint)?.connect().await?;
+    let mut client = ArpcServiceClient::new(channel);
+    // Only advertise accept-compression; servers without support keep sending uncompressed
+    if let Some(encoding) = grpc_compression_encoding(compression) {
+        client = client.accept_compressed(encoding);
+        println!("ARPC client accepting {:?} compressed responses", encoding);
+    }
     Ok(client)
 }
 
@@ -136,8 +145,9 @@ pub async fn subscribe_and_print(
     accounts_to_monitor: Vec<String>,
     config: Arc<Config>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let mut client = create_grpc_client(endpoint).await?;
+    let mut client = create_grpc_client(endpoint, &config.grpc_compression).await?;
     let mut stream = subscribe_to_accounts(&mut client, accounts_to_monitor).await?;
+    record_connect("arpc");
 
     // Start stats monitoring with proper cleanup
     let _stats_config = config.clone();
@@ -177,24 +187,29 @@ pub async fn subscribe_and_print(
     }
 
     // Stream health monitoring
-    let mut last_message_time = std::time::Instant::now();
     let mut message_count = 0u64;
     let mut consecutive_errors = 0u32;
     const MAX_CONSECUTIVE_ERRORS: u32 = 10;
     const STREAM_TIMEOUT_SECONDS: u64 = 60;
 
-    while let Some(result) = stream.message().await? {
-        last_message_time = std::time::Instant::now();
+    loop {
+        // No message for STREAM_TIMEOUT_SECONDS: the stream is stale, exit to trigger reconnection
+        let result = match tokio::time::timeout(Duration::from_secs(STREAM_TIMEOUT_SECONDS), stream.message()).await {
+            Ok(next) => match next? {
+                Some(result) => result,
+                None => break,
+            },
+            Err(_) => {
+                println!("[ARPC] WARNING: Stream appears stale (no messages for {}s), reconnecting...", 
+                    STREAM_TIMEOUT_SECONDS);
+                alert(AlertKind::StaleStream, format!("ARPC stream silent for {}s, reconnecting", STREAM_TIMEOUT_SECONDS));
+                break;
+            }
+        };
         message_count += 1;
+        record_stream_message(StreamFeed::Arpc);
         consecutive_errors = 0; // Reset error counter on successful message
 
-        // Check for stale stream
-        if last_message_time.elapsed() > Duration::from_secs(STREAM_TIMEOUT_SECONDS) {
-            println!("[ARPC] WARNING: Stream appears stale (no messages for {}s), reconnecting...", 
-                STREAM_TIMEOUT_SECONDS);
-            break; // Exit to trigger reconnection
-        }
-
         // Memory pressure check - force cleanup every 1000 messages
         if message_count % 1000 == 0 {
             // Force garbage collection and cleanup
@@ -248,6 +263,7 @@ pub async fn subscribe_with_retry(
         attempt += 1;
         println!("[ARPC] Attempt {} to connect and subscribe...", attempt);
         let result = subscribe_and_print(endpoint, accounts_to_monitor.clone(), config.clone()).await;
+        record_disconnect("arpc");
         match result {
             Ok(_) => {
                 println!("[ARPC] Subscription ended gracefully.");
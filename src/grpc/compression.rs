@@ -0,0 +1,53 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use tonic::codec::CompressionEncoding;
+
+// Unix ms of the last message per feed (0 = none yet), for the heartbeat
+static ARPC_LAST_MESSAGE_MS: AtomicU64 = AtomicU64::new(0);
+static TRITON_LAST_MESSAGE_MS: AtomicU64 = AtomicU64::new(0);
+static MONITORING_LAST_MESSAGE_MS: AtomicU64 = AtomicU64::new(0);
+
+fn unix_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum StreamFeed {
+    Arpc,
+    Triton,
+    Monitoring,
+}
+
+/// Map the `grpc_compression` config value (none/gzip/zstd) to a tonic encoding
+pub fn grpc_compression_encoding(setting: &str) -> Option<CompressionEncoding> {
+    match setting.to_ascii_lowercase().as_str() {
+        "gzip" => Some(CompressionEncoding::Gzip),
+        "zstd" => Some(CompressionEncoding::Zstd),
+        "" | "none" => None,
+        other => {
+            eprintln!("[GRPC] Unknown grpc_compression '{}', using none", other);
+            None
+        }
+    }
+}
+
+pub fn record_stream_message(feed: StreamFeed) {
+    let last_message = match feed {
+        StreamFeed::Arpc => &ARPC_LAST_MESSAGE_MS,
+        StreamFeed::Triton => &TRITON_LAST_MESSAGE_MS,
+        StreamFeed::Monitoring => &MONITORING_LAST_MESSAGE_MS,
+    };
+    last_message.store(unix_ms(), Ordering::Relaxed);
+}
+
+/// (arpc, triton, monitoring) time since the last message; None if no message yet
+pub fn get_last_message_ages() -> (Option<std::time::Duration>, Option<std::time::Duration>, Option<std::time::Duration>) {
+    let now = unix_ms();
+    let age = |last: &AtomicU64| match last.load(Ordering::Relaxed) {
+        0 => None,
+        ms => Some(std::time::Duration::from_millis(now.saturating_sub(ms))),
+    };
+    (age(&ARPC_LAST_MESSAGE_MS), age(&TRITON_LAST_MESSAGE_MS), age(&MONITORING_LAST_MESSAGE_MS))
+}
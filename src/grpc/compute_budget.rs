@@ -0,0 +1,75 @@
+// compute_budget.rs
+// The leader's priority-fee settings, read from the ComputeBudget instructions of a detected transaction.
+
+use crate::arpc::CompiledInstruction;
+use solana_sdk::compute_budget;
+
+// ComputeBudgetInstruction tags (borsh enum index)
+const SET_COMPUTE_UNIT_LIMIT: u8 = 2;
+const SET_COMPUTE_UNIT_PRICE: u8 = 3;
+
+/// CU price (micro-lamports per CU) and CU limit the leader set, if any
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct LeaderComputeBudget {
+    pub cu_price: Option<u64>,
+    pub cu_limit: Option<u32>,
+}
+
+/// Read set_compute_unit_price / set_compute_unit_limit from `instructions`; the last of each wins,
+/// as on chain. Malformed data is ignored.
+pub fn parse_compute_budget(instructions: &[CompiledInstruction], account_keys: &[Vec<u8>]) -> LeaderComputeBudget {
+    let program = compute_budget::id().to_bytes();
+    let mut budget = LeaderComputeBudget::default();
+    for ix in instructions {
+        let is_compute_budget = account_keys
+            .get(ix.program_id_index as usize)
+            .map_or(false, |key| key.as_slice() == program);
+        if !is_compute_budget {
+            continue;
+        }
+        match ix.data.split_first() {
+            Some((&SET_COMPUTE_UNIT_PRICE, rest)) if rest.len() >= 8 => {
+                budget.cu_price = Some(u64::from_le_bytes(rest[..8].try_into().unwrap()));
+            }
+            Some((&SET_COMPUTE_UNIT_LIMIT, rest)) if rest.len() >= 4 => {
+                budget.cu_limit = Some(u32::from_le_bytes(rest[..4].try_into().unwrap()));
+            }
+            _ => {}
+        }
+    }
+    budget
+}
+
+/// `cu_price` raised to at least the leader's price times `multiplier` (never lowered)
+pub fn match_leader_cu_price(cu_price: u64, leader_cu_price: Option<u64>, multiplier: f64) -> u64 {
+    leader_cu_price.map_or(cu_price, |leader| cu_price.max((leader as f64 * multiplier.max(0.0)) as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::compute_budget::ComputeBudgetInstruction;
+    use solana_sdk::pubkey::Pubkey;
+
+    #[test]
+    fn test_parse_leader_compute_budget() {
+        let keys = vec![Pubkey::new_unique().to_bytes().to_vec(), compute_budget::id().to_bytes().to_vec(), Pubkey::new_unique().to_bytes().to_vec()];
+        let compiled = |program_id_index: u32, data: Vec<u8>| CompiledInstruction { program_id_index, accounts: vec![], data };
+        let instructions = vec![
+            compiled(1, ComputeBudgetInstruction::set_compute_unit_limit(180_000).data),
+            compiled(1, ComputeBudgetInstruction::set_compute_unit_price(250_000).data),
+            // Swap instruction whose data happens to start like a price instruction
+            compiled(2, vec![3, 1, 0, 0, 0, 0, 0, 0, 0]),
+        ];
+        let budget = parse_compute_budget(&instructions, &keys);
+        assert_eq!(budget, LeaderComputeBudget { cu_price: Some(250_000), cu_limit: Some(180_000) });
+
+        // No compute budget instructions, or truncated data
+        assert_eq!(parse_compute_budget(&instructions[2..], &keys), LeaderComputeBudget::default());
+        assert_eq!(parse_compute_budget(&[compiled(1, vec![3, 1, 2])], &keys), LeaderComputeBudget::default());
+
+        assert_eq!(match_leader_cu_price(100_000, budget.cu_price, 1.2), 300_000);
+        assert_eq!(match_leader_cu_price(500_000, budget.cu_price, 1.0), 500_000);
+        assert_eq!(match_leader_cu_price(100_000, None, 1.2), 100_000);
+    }
+}
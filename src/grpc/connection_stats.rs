@@ -0,0 +1,117 @@
+use crate::alerts::{alert, AlertKind};
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+// This many disconnects of one subscription within the window raise a reconnect alert
+const RECONNECT_ALERT_COUNT: usize = 5;
+const RECONNECT_ALERT_WINDOW: Duration = Duration::from_secs(600);
+
+/// Connection churn for one subscription (arpc, monitoring, or a triton feed id)
+#[derive(Debug, Clone, Default)]
+struct ConnectionState {
+    connects: usize,
+    disconnects: usize,
+    connected_since: Option<Instant>,
+    disconnected_since: Option<Instant>,
+    total_downtime: Duration,
+    recent_disconnects: VecDeque<Instant>,
+}
+
+/// Snapshot returned to the stats report
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConnectionStats {
+    pub name: String,
+    pub connects: usize,
+    pub disconnects: usize,
+    pub uptime: Option<Duration>, // None while disconnected
+    pub total_downtime: Duration, // Includes the current outage, if any
+}
+
+static CONNECTION_STATES: Lazy<DashMap<String, ConnectionState>> = Lazy::new(DashMap::new);
+
+/// Call once the subscription stream is established
+pub fn record_connect(name: &str) {
+    let now = Instant::now();
+    let mut state = CONNECTION_STATES.entry(name.to_string()).or_default();
+    if let Some(down) = state.disconnected_since.take() {
+        state.total_downtime += now.duration_since(down);
+    }
+    state.connects += 1;
+    state.connected_since = Some(now);
+}
+
+/// Call when the subscription ends (error or stream end); no-op if it never connected
+pub fn record_disconnect(name: &str) {
+    if let Some(mut state) = CONNECTION_STATES.get_mut(name) {
+        if state.connected_since.take().is_some() {
+            let now = Instant::now();
+            state.disconnects += 1;
+            state.disconnected_since = Some(now);
+            state.recent_disconnects.retain(|at| now.duration_since(*at) < RECONNECT_ALERT_WINDOW);
+            state.recent_disconnects.push_back(now);
+            if state.recent_disconnects.len() >= RECONNECT_ALERT_COUNT {
+                alert(AlertKind::RepeatedReconnects, format!("{} disconnected {} times in the last {}s",
+                    name, state.recent_disconnects.len(), RECONNECT_ALERT_WINDOW.as_secs()));
+            }
+        }
+    }
+}
+
+/// Per-subscription connects, disconnects, current uptime and total downtime, sorted by name
+pub fn get_connection_stats() -> Vec<ConnectionStats> {
+    let mut stats: Vec<ConnectionStats> = CONNECTION_STATES
+        .iter()
+        .map(|entry| {
+            let state = entry.value();
+            ConnectionStats {
+                name: entry.key().clone(),
+                connects: state.connects,
+                disconnects: state.disconnects,
+                uptime: state.connected_since.map(|since| since.elapsed()),
+                total_downtime: state.total_downtime
+                    + state.disconnected_since.map(|since| since.elapsed()).unwrap_or_default(),
+            }
+        })
+        .collect();
+    stats.sort_by(|a, b| a.name.cmp(&b.name));
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats_for(name: &str) -> ConnectionStats {
+        get_connection_stats().into_iter().find(|s| s.name == name).unwrap()
+    }
+
+    #[test]
+    fn test_connect_disconnect_cycle() {
+        let name = "test_feed_cycle";
+        // Failed attempt before ever connecting is not a disconnect
+        record_disconnect(name);
+        assert!(get_connection_stats().iter().all(|s| s.name != name));
+
+        record_connect(name);
+        let up = stats_for(name);
+        assert_eq!((up.connects, up.disconnects), (1, 0));
+        assert!(up.uptime.is_some());
+        assert_eq!(up.total_downtime, Duration::ZERO);
+
+        record_disconnect(name);
+        record_disconnect(name); // retry failures while down don't count twice
+        std::thread::sleep(Duration::from_millis(5));
+        let down = stats_for(name);
+        assert_eq!((down.connects, down.disconnects), (1, 1));
+        assert!(down.uptime.is_none());
+        assert!(down.total_downtime >= Duration::from_millis(5));
+
+        record_connect(name);
+        let back = stats_for(name);
+        assert_eq!((back.connects, back.disconnects), (2, 1));
+        assert!(back.uptime.is_some());
+        assert!(back.total_downtime >= Duration::from_millis(5));
+    }
+}
@@ -0,0 +1,148 @@
+use crate::init::initialize::GLOBAL_RPC_CLIENT;
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use solana_client::rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient};
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
+use std::time::Duration;
+
+// One page of history; a full page means the wallet has at least this many transactions
+const HISTORY_PAGE: usize = 1000;
+
+/// History depth and first-seen time of a tracked wallet (from one signatures page)
+#[derive(Debug, Clone, PartialEq)]
+pub struct LeaderHistory {
+    pub tx_count: usize,
+    pub oldest_block_time: Option<i64>,
+    pub history_capped: bool, // a full page: older history exists, so the wallet is not new
+}
+
+/// Too new if its history is shorter than `min_txs` or its first transaction is younger than `min_age_secs` (0 disables each)
+fn is_too_new(history: &LeaderHistory, now_unix: i64, min_age_secs: u64, min_txs: usize) -> bool {
+    if history.history_capped {
+        return false;
+    }
+    if min_txs > 0 && history.tx_count < min_txs {
+        return true;
+    }
+    min_age_secs > 0
+        && history
+            .oldest_block_time
+            .map_or(true, |first| now_unix.saturating_sub(first) < min_age_secs as i64)
+}
+
+// Tracked wallets currently filtered as too new (checked on the ARPC hot path)
+static FILTERED_LEADERS: Lazy<RwLock<Vec<[u8; 32]>>> = Lazy::new(|| RwLock::new(Vec::new()));
+static ANY_FILTERED: AtomicBool = AtomicBool::new(false);
+
+/// True if the transaction was signed by a tracked wallet filtered as too new
+pub fn is_filtered_leader_transaction(account_keys: &[Vec<u8>], num_required_signatures: usize) -> bool {
+    if !ANY_FILTERED.load(Ordering::Relaxed) {
+        return false;
+    }
+    FILTERED_LEADERS.read().map_or(false, |filtered| {
+        filtered
+            .iter()
+            .any(|leader| crate::grpc::arpc_parser::is_signed_by(account_keys, num_required_signatures, leader))
+    })
+}
+
+fn fetch_history(rpc: &RpcClient, wallet: &Pubkey) -> Result<LeaderHistory, String> {
+    let config = GetConfirmedSignaturesForAddress2Config { limit: Some(HISTORY_PAGE), ..Default::default() };
+    let signatures = rpc
+        .get_signatures_for_address_with_config(wallet, config)
+        .map_err(|e| e.to_string())?;
+    Ok(LeaderHistory {
+        tx_count: signatures.len(),
+        oldest_block_time: signatures.last().and_then(|s| s.block_time),
+        history_capped: signatures.len() >= HISTORY_PAGE,
+    })
+}
+
+/// Check every tracked wallet once; with `filter` set, too-new wallets stop triggering buys
+fn check_leaders(rpc: &RpcClient, wallets: &[String], min_age_secs: u64, min_txs: usize, filter: bool) {
+    let now_unix = Utc::now().timestamp();
+    let mut active = Vec::new();
+    let mut too_new = Vec::new();
+    for wallet in wallets {
+        let pubkey = match Pubkey::from_str(wallet) {
+            Ok(pubkey) => pubkey,
+            Err(_) => continue, // not a wallet (e.g. a program id)
+        };
+        match fetch_history(rpc, &pubkey) {
+            Ok(history) if is_too_new(&history, now_unix, min_age_secs, min_txs) => {
+                println!("[{}] - [LEADER_AGE] {} looks new: {} txs, first seen {:?}",
+                    Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), wallet, history.tx_count, history.oldest_block_time);
+                too_new.push(pubkey);
+            }
+            Ok(_) => active.push(pubkey),
+            // Keep following on lookup errors; a transient RPC failure shouldn't drop a leader
+            Err(e) => {
+                eprintln!("[LEADER_AGE] History lookup for {} failed: {}", wallet, e);
+                active.push(pubkey);
+            }
+        }
+    }
+
+    if filter {
+        if let Ok(mut filtered) = FILTERED_LEADERS.write() {
+            *filtered = too_new.iter().map(|p| p.to_bytes()).collect();
+            ANY_FILTERED.store(!filtered.is_empty(), Ordering::Relaxed);
+        }
+    }
+    println!("[{}] - [LEADER_AGE] active: {:?} | {}: {:?}",
+        Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+        active.iter().map(|p| p.to_string()).collect::<Vec<_>>(),
+        if filter { "filtered" } else { "too new (warn only)" },
+        too_new.iter().map(|p| p.to_string()).collect::<Vec<_>>());
+}
+
+/// Check tracked wallets at startup and then every `interval`, off the hot path
+pub async fn run_leader_age_checks(wallets: Vec<String>, min_age_secs: u64, min_txs: usize, filter: bool, interval: Duration) {
+    let mut ticker = tokio::time::interval(interval.max(Duration::from_secs(60)));
+    loop {
+        ticker.tick().await;
+        let rpc = match GLOBAL_RPC_CLIENT.get() {
+            Some(rpc) => rpc,
+            None => continue,
+        };
+        let wallets = wallets.clone();
+        let _ = tokio::task::spawn_blocking(move || check_leaders(rpc, &wallets, min_age_secs, min_txs, filter)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_too_new() {
+        let now = 1_700_000_000;
+        let day = 86_400;
+        let young = LeaderHistory { tx_count: 12, oldest_block_time: Some(now - 3600), history_capped: false };
+        let old = LeaderHistory { tx_count: 12, oldest_block_time: Some(now - 30 * day), history_capped: false };
+        let busy = LeaderHistory { tx_count: HISTORY_PAGE, oldest_block_time: Some(now - 60), history_capped: true };
+
+        assert!(is_too_new(&young, now, day as u64, 0));
+        assert!(!is_too_new(&old, now, day as u64, 0));
+        assert!(!is_too_new(&busy, now, day as u64, 5_000));
+        // History depth threshold
+        assert!(is_too_new(&old, now, 0, 50));
+        assert!(!is_too_new(&old, now, 0, 10));
+        // Both disabled
+        assert!(!is_too_new(&young, now, 0, 0));
+    }
+
+    #[test]
+    fn test_filtered_leader_transaction() {
+        let leader = Pubkey::new_unique().to_bytes();
+        let keys = vec![leader.to_vec(), Pubkey::new_unique().to_bytes().to_vec()];
+        assert!(!is_filtered_leader_transaction(&keys, 1));
+        *FILTERED_LEADERS.write().unwrap() = vec![leader];
+        ANY_FILTERED.store(true, Ordering::Relaxed);
+        assert!(is_filtered_leader_transaction(&keys, 1));
+        assert!(!is_filtered_leader_transaction(&keys[1..], 1));
+    }
+}
@@ -0,0 +1,83 @@
+use chrono::Utc;
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use solana_sdk::pubkey::Pubkey;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Entries kept before old slots are pruned
+const MAX_TRACKED_ACTIONS: usize = 4096;
+
+/// First slot a leader acted on a mint. A leader's retries or bundle variants of one action land
+/// as several transactions with different signatures, so signature dedup lets them all through.
+#[derive(Default)]
+pub struct LeaderActionDedup {
+    first_slot: DashMap<(Pubkey, Pubkey), u64>,
+}
+
+impl LeaderActionDedup {
+    /// True if this is the first action of `wallet` on `mint` within `window_slots` of the previous
+    /// first one (1 = the same slot only); later ones inside the window are duplicates
+    pub fn is_first(&self, wallet: Pubkey, mint: Pubkey, slot: u64, window_slots: u64) -> bool {
+        if self.first_slot.len() > MAX_TRACKED_ACTIONS {
+            self.first_slot.retain(|_, first| slot.saturating_sub(*first) < window_slots);
+        }
+        match self.first_slot.entry((wallet, mint)) {
+            Entry::Vacant(entry) => {
+                entry.insert(slot);
+                true
+            }
+            Entry::Occupied(mut entry) => {
+                let first = *entry.get();
+                if slot >= first && slot - first < window_slots {
+                    return false;
+                }
+                entry.insert(slot);
+                true
+            }
+        }
+    }
+}
+
+static LEADER_ACTIONS: Lazy<LeaderActionDedup> = Lazy::new(LeaderActionDedup::default);
+static SLOT_DUPLICATES_SKIPPED: AtomicUsize = AtomicUsize::new(0);
+
+/// Leader actions skipped as a repeat of one already copied in the slot window
+pub fn get_slot_duplicates_skipped() -> usize {
+    SLOT_DUPLICATES_SKIPPED.load(Ordering::Relaxed)
+}
+
+/// Whether to copy this leader action; a repeat of (wallet, mint) within `window_slots` is counted and logged
+pub fn copy_leader_action(wallet: Pubkey, mint: Pubkey, slot: u64, window_slots: u64, cid: &str) -> bool {
+    if LEADER_ACTIONS.is_first(wallet, mint, slot, window_slots) {
+        return true;
+    }
+    SLOT_DUPLICATES_SKIPPED.fetch_add(1, Ordering::Relaxed);
+    println!("[{}] - [LEADER_DEDUP] [cid={}] Skipped: {} already acted on {} within {} slot(s) of slot {}",
+        Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), cid, wallet, mint, window_slots, slot);
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_slot_duplicate_copied_once() {
+        let dedup = LeaderActionDedup::default();
+        let (leader, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        // Two transactions of one leader on one mint in slot 100: a single copy
+        let copies = [dedup.is_first(leader, mint, 100, 1), dedup.is_first(leader, mint, 100, 1)];
+        assert_eq!(copies.iter().filter(|copied| **copied).count(), 1);
+
+        // Another mint or another leader in the same slot is its own action
+        assert!(dedup.is_first(leader, Pubkey::new_unique(), 100, 1));
+        assert!(dedup.is_first(Pubkey::new_unique(), mint, 100, 1));
+
+        // A later slot outside the window is a new action; a wider window still catches it
+        assert!(dedup.is_first(leader, mint, 101, 1));
+        assert!(!dedup.is_first(leader, mint, 102, 3));
+        assert!(dedup.is_first(leader, mint, 104, 3));
+    }
+}
@@ -0,0 +1,100 @@
+use crate::grpc::arpc_worker::GLOBAL_TX_MAP;
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// A reservation is kept this long even before its trade reaches GLOBAL_TX_MAP (vendor build, send)
+const RESERVATION_GRACE: Duration = Duration::from_secs(10);
+
+/// SOL committed to one position of a mint
+#[derive(Debug, Clone)]
+struct Reservation {
+    corr_id: String,
+    lamports: u64,
+    reserved_at: Instant,
+}
+
+/// Open buy exposure per mint, summed over the positions (by corr_id) holding it
+#[derive(Default)]
+pub struct ExposureLedger {
+    by_mint: Mutex<HashMap<Pubkey, Vec<Reservation>>>,
+}
+
+impl ExposureLedger {
+    /// Reserve `lamports` of `mint` for `corr_id` unless the mint's total would exceed `max_lamports`.
+    /// Positions no longer `is_open` (past the grace period) are released first.
+    pub fn try_reserve(&self, mint: Pubkey, corr_id: &str, lamports: u64, max_lamports: u64, is_open: impl Fn(&str) -> bool) -> bool {
+        let mut by_mint = self.by_mint.lock().unwrap_or_else(|e| e.into_inner());
+        let reservations = by_mint.entry(mint).or_default();
+        reservations.retain(|r| r.reserved_at.elapsed() < RESERVATION_GRACE || is_open(&r.corr_id));
+        let held: u64 = reservations.iter().map(|r| r.lamports).sum();
+        if held.saturating_add(lamports) > max_lamports {
+            return false;
+        }
+        reservations.push(Reservation { corr_id: corr_id.to_string(), lamports, reserved_at: Instant::now() });
+        true
+    }
+
+    /// Lamports currently reserved for `mint`
+    pub fn exposure(&self, mint: &Pubkey) -> u64 {
+        let by_mint = self.by_mint.lock().unwrap_or_else(|e| e.into_inner());
+        by_mint.get(mint).map_or(0, |reservations| reservations.iter().map(|r| r.lamports).sum())
+    }
+}
+
+static MINT_EXPOSURE: Lazy<ExposureLedger> = Lazy::new(ExposureLedger::default);
+static EXPOSURE_CAPPED: AtomicUsize = AtomicUsize::new(0);
+
+/// Buys skipped by `max_position_sol_per_mint`
+pub fn get_exposure_capped() -> usize {
+    EXPOSURE_CAPPED.load(Ordering::Relaxed)
+}
+
+/// Reserve a `lamports` buy of `mint` under `max_position_sol_per_mint`; false (counted, logged) if
+/// it would take the mint's open exposure over the cap
+pub fn reserve_mint_exposure(mint: Pubkey, corr_id: &str, lamports: u64, max_sol: f64) -> bool {
+    let max_lamports = (max_sol * 1_000_000_000.0) as u64;
+    let is_open = |cid: &str| GLOBAL_TX_MAP.iter().any(|entry| entry.corr_id == cid);
+    if MINT_EXPOSURE.try_reserve(mint, corr_id, lamports, max_lamports, is_open) {
+        return true;
+    }
+    EXPOSURE_CAPPED.fetch_add(1, Ordering::Relaxed);
+    println!("[{}] - [EXPOSURE] [cid={}] Skipped buy of {}: {} SOL already open, {} SOL more exceeds max_position_sol_per_mint {}",
+        Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), corr_id, mint,
+        MINT_EXPOSURE.exposure(&mint) as f64 / 1_000_000_000.0, lamports as f64 / 1_000_000_000.0, max_sol);
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repeated_buys_capped_per_mint() {
+        let ledger = ExposureLedger::default();
+        let (mint, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let open = |_: &str| true;
+
+        // 0.25 SOL cap, 0.1 SOL buys: two fit, the third would reach 0.3
+        assert!(ledger.try_reserve(mint, "a", 100_000_000, 250_000_000, open));
+        assert!(ledger.try_reserve(mint, "b", 100_000_000, 250_000_000, open));
+        assert!(!ledger.try_reserve(mint, "c", 100_000_000, 250_000_000, open));
+        assert_eq!(ledger.exposure(&mint), 200_000_000);
+
+        // Other mints have their own budget
+        assert!(ledger.try_reserve(other, "d", 200_000_000, 250_000_000, open));
+
+        // Closed positions past the grace period free their share
+        let mut by_mint = ledger.by_mint.lock().unwrap();
+        for reservation in by_mint.get_mut(&mint).unwrap() {
+            reservation.reserved_at -= RESERVATION_GRACE;
+        }
+        drop(by_mint);
+        assert!(ledger.try_reserve(mint, "c", 100_000_000, 250_000_000, |cid| cid == "b"));
+        assert_eq!(ledger.exposure(&mint), 200_000_000);
+    }
+}
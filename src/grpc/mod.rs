@@ -3,4 +3,11 @@ pub mod client;
 pub mod arpc_worker;
 pub mod utils;
 pub mod programs;
-pub mod monitoring_client;
\ No newline at end of file
+pub mod monitoring_client;
+pub mod compression;
+pub mod compute_budget;
+pub mod connection_stats;
+pub mod leader_age;
+pub mod mint_exposure;
+pub mod leader_dedup;
+pub mod slot_leaders;
@@ -15,13 +15,19 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::sync::mpsc;
 use tokio_stream::wrappers::ReceiverStream;
 use tokio::time::{sleep, Duration};
+use crate::grpc::connection_stats::{record_connect, record_disconnect};
 use chrono::Utc;
 use core_affinity;
 use dashmap::DashMap;
-use once_cell::sync::Lazy;
+use once_cell::sync::{Lazy, OnceCell};
 use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 use crate::build_tx::pump_swap::{get_instruction_accounts_migrate_pump, PumpAmmAccounts};
+use crate::build_tx::utils::get_account;
+use crate::build_tx::dex_probe::invalidate_dex;
+use crate::alerts::{alert, AlertKind};
 use std::time::Instant;
+use crate::grpc::compression::{grpc_compression_encoding, record_stream_message, StreamFeed};
 
 /// Global struct to store monitoring data
 #[derive(Debug, Clone)]
@@ -52,6 +58,65 @@ pub fn get_monitoring_stats() -> (usize, usize, usize) {
     )
 }
 
+/// Cap on monitoring messages processed at once, shared by all monitoring endpoints. Monitoring is
+/// lower priority than trading, so a message over the cap is dropped (and counted) instead of queued.
+pub struct MonitoringLimiter {
+    slots: Arc<Semaphore>,
+    dropped: AtomicUsize,
+}
+
+impl MonitoringLimiter {
+    /// `max_concurrent` of 0 is unlimited
+    pub fn new(max_concurrent: usize) -> Self {
+        let permits = if max_concurrent == 0 { Semaphore::MAX_PERMITS } else { max_concurrent };
+        MonitoringLimiter { slots: Arc::new(Semaphore::new(permits)), dropped: AtomicUsize::new(0) }
+    }
+
+    /// A processing slot, held by the message's task; None if the message is to be dropped
+    pub fn try_acquire(&self) -> Option<OwnedSemaphorePermit> {
+        let permit = self.slots.clone().try_acquire_owned().ok();
+        if permit.is_none() {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+        permit
+    }
+
+    pub fn dropped(&self) -> usize {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+static MONITORING_LIMITER: OnceCell<MonitoringLimiter> = OnceCell::new();
+
+// Migrate instruction discriminators
+const RAYDIUM_LAUNCHPAD_MIGRATE: [u8; 8] = [136, 92, 200, 103, 28, 218, 144, 140];
+const PUMP_FUN_MIGRATE: [u8; 8] = [155, 234, 231, 146, 236, 158, 162, 30];
+
+/// Whether `transaction` carries a migrate instruction we parse; checked before the limiter, so a
+/// migration is processed even while a flood is being dropped
+fn carries_migration(transaction: &crate::arpc::SubscribeResponseTransaction) -> bool {
+    transaction.instructions.iter().any(|instruction| {
+        let program_id = match transaction.account_keys.get(instruction.program_id_index as usize) {
+            Some(key) => bs58::encode(key).into_string(),
+            None => return false,
+        };
+        match program_id.as_str() {
+            RAYDIUM_LAUNCHPAD_PROGRAM_ID => instruction.data == RAYDIUM_LAUNCHPAD_MIGRATE,
+            PUMP_FUN_PROGRAM_ID => instruction.data == PUMP_FUN_MIGRATE,
+            _ => false,
+        }
+    })
+}
+
+fn monitoring_limiter(config: &Config) -> &'static MonitoringLimiter {
+    MONITORING_LIMITER.get_or_init(|| MonitoringLimiter::new(config.monitoring_max_concurrent))
+}
+
+/// Monitoring messages dropped at `monitoring_max_concurrent`
+pub fn get_monitoring_dropped() -> usize {
+    MONITORING_LIMITER.get().map_or(0, |limiter| limiter.dropped())
+}
+
 // REMOVED: DexActivityLog struct and GLOBAL_DEX_LOGS storage
 // This was causing significant performance overhead due to:
 // - Expensive map insertions on every transaction
@@ -92,6 +157,7 @@ fn purge_old_monitoring_data() {
         // OPTIMIZATION: Emergency cleanup if map gets too large
         if GLOBAL_MONITORING_DATA.len() > 300 { // Reduced from 500 to 300
             println!("[Monitoring] WARNING: Monitoring data map too large ({} entries), clearing...", GLOBAL_MONITORING_DATA.len());
+            alert(AlertKind::MapOverflow, format!("monitoring data map at {} entries, cleared", GLOBAL_MONITORING_DATA.len()));
             GLOBAL_MONITORING_DATA.clear();
         }
         
@@ -147,13 +213,19 @@ pub fn emergency_cleanup_monitoring_data() {
 /// Start ARPC monitoring subscription (separate from trading ARPC)
 pub async fn start_arpc_monitoring_subscription(
     endpoint: &str,
+    feed_name: &str,
     programs_to_monitor: Vec<String>,
     config: Arc<Config>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let now = Utc::now();
     println!("[{}] - [Monitoring ARPC] Attempting to connect to: {}", 
         now.format("%Y-%m-%d %H:%M:%S%.3f"), endpoint);
-    let mut client = ArpcServiceClient::connect(endpoint.to_string()).await?;
+    let channel = crate::utils::socket_tuning::tuned_endpoint(endpoint)?.connect().await?;
+    let mut client = ArpcServiceClient::new(channel);
+    // Only advertise accept-compression; servers without support keep sending uncompressed
+    if let Some(encoding) = grpc_compression_encoding(&config.grpc_compression) {
+        client = client.accept_compressed(encoding);
+    }
     let now = Utc::now();
     println!("[{}] - [Monitoring ARPC] Connection successful!", 
         now.format("%Y-%m-%d %H:%M:%S%.3f"));
@@ -184,6 +256,7 @@ pub async fn start_arpc_monitoring_subscription(
     tx.send(initial_request).await?;
 
     let mut stream = client.subscribe(request_stream).await?.into_inner();
+    record_connect(feed_name);
 
     let now = Utc::now();
     println!("[{}] - [Monitoring ARPC] DEX activity subscription established. Monitoring {} programs...", 
@@ -192,35 +265,6 @@ pub async fn start_arpc_monitoring_subscription(
     println!("[{}] - [Monitoring ARPC] Programs to monitor: {:?}", 
         now.format("%Y-%m-%d %H:%M:%S%.3f"), programs_to_monitor);
 
-    // Start the purging task in a separate thread
-    std::thread::spawn(move || {
-        purge_old_monitoring_data();
-    });
-
-    // Start a periodic stats reporting task
-    let _stats_config = config.clone();
-    tokio::spawn(async move {
-        let mut interval = tokio::time::interval(Duration::from_secs(60)); // Report every minute
-        loop {
-            interval.tick().await;
-            let (received, logged, errors) = get_monitoring_stats();
-            let now = Utc::now();
-            println!("[{}] - [MONITORING ARPC STATS] Received: {}, Logged: {}, Errors: {}, Processing Rate: {:.2}%", 
-                now.format("%Y-%m-%d %H:%M:%S%.3f"),
-                received, logged, errors,
-                if received > 0 { (logged as f64 / received as f64) * 100.0 } else { 0.0 }
-            );
-            
-            // REMOVED: DEX logs stats (performance optimization)
-            
-            // Log monitoring data stats
-            println!("[{}] - [MONITORING ARPC STATS] GLOBAL_MONITORING_DATA size: {}", 
-                now.format("%Y-%m-%d %H:%M:%S%.3f"),
-                GLOBAL_MONITORING_DATA.len()
-            );
-        }
-    });
-
     // Pin the monitoring thread to core 15 (last core, separate from trading cores 0-3)
     // This ensures monitoring doesn't interfere with critical trading operations
     if let Some(cores) = core_affinity::get_core_ids() {
@@ -245,8 +289,10 @@ pub async fn start_arpc_monitoring_subscription(
             now.format("%Y-%m-%d %H:%M:%S%.3f"), e);
     }
 
+    let limiter = monitoring_limiter(&config);
     while let Some(result) = stream.message().await? {
         let result = result.clone();
+        record_stream_message(StreamFeed::Monitoring);
         
         // Increment received counter
         MONITORING_MESSAGES_RECEIVED.fetch_add(1, Ordering::Relaxed);
@@ -258,8 +304,17 @@ pub async fn start_arpc_monitoring_subscription(
         
         // println!("[Monitoring ARPC] Received message: {}", sig_str);
         
-        // Process in a separate task to avoid blocking
+        // Process in a separate task to avoid blocking, as long as a slot is free; migrations always are
+        let permit = if result.transaction.as_ref().map_or(false, carries_migration) {
+            None
+        } else {
+            match limiter.try_acquire() {
+                Some(permit) => Some(permit),
+                None => continue,
+            }
+        };
         tokio::spawn(async move {
+            let _permit = permit;
             let processing_start = std::time::Instant::now();
             
                     match process_monitoring_message(&result, "arpc").await {
@@ -332,15 +387,15 @@ async fn process_monitoring_message(
 
             match program_id.as_str() {
                 RAYDIUM_LAUNCHPAD_PROGRAM_ID => {
-                    if instruction.data == [136, 92, 200, 103, 28, 218, 144, 140] { //migrate instruction
+                    if instruction.data == RAYDIUM_LAUNCHPAD_MIGRATE {
                         // Parse Raydium Launchpad instructions
                         parse_raydium_launchpad_instruction(instruction, &transaction.account_keys, &signature, slot);
                     }
 
                 }
                 PUMP_FUN_PROGRAM_ID => {
-                    if instruction.data == [155, 234, 231, 146, 236, 158, 162, 30] { //migrate instruction
-                        parse_pump_fun_instruction(instruction, &transaction.account_keys, &signature, slot);
+                    if instruction.data == PUMP_FUN_MIGRATE {
+                        parse_pump_fun_instruction(instruction, &transaction.account_keys, &signature, slot).await;
                     }
                 }
                 _ => {
@@ -393,6 +448,7 @@ fn parse_raydium_launchpad_instruction(
     
     // Store in global map
     GLOBAL_MONITORING_DATA.insert(mint_pubkey, monitoring_data.clone());
+    invalidate_dex(&mint_pubkey);
     
     let now = Utc::now();
     println!("[{}] - [PARSER] Stored monitoring data for mint: {}", 
@@ -405,7 +461,7 @@ fn parse_raydium_launchpad_instruction(
 
 
 
-fn parse_pump_fun_instruction(
+async fn parse_pump_fun_instruction(
     instruction: &crate::arpc::CompiledInstruction,
     account_keys: &[Vec<u8>],
     signature: &str,
@@ -416,8 +472,21 @@ fn parse_pump_fun_instruction(
     println!("[{}] - [PARSER] Instruction data: sig={}, slot={}", 
         now.format("%Y-%m-%d %H:%M:%S%.3f"), signature, slot);
     
+    // Another monitoring endpoint may already have recorded this migration; skip the RPC lookup
+    let mint = get_account(account_keys, &instruction.accounts, 2);
+    if GLOBAL_MONITORING_DATA.contains_key(&mint) {
+        return;
+    }
+
     // Example: Check for specific instruction discriminators
-    let migrated_accounts = get_instruction_accounts_migrate_pump(&account_keys, &instruction.accounts);
+    let migrated_accounts = match get_instruction_accounts_migrate_pump(&account_keys, &instruction.accounts).await {
+        Ok(accounts) => accounts,
+        Err(e) => {
+            eprintln!("[{}] - [PARSER] Skipping pump migration sig={}: {}", 
+                Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), signature, e);
+            return;
+        }
+    };
 
     // Get the mint pubkey (token_1_mint from RayCpmmSwapAccounts)
     let mint_pubkey = migrated_accounts.base_mint;
@@ -435,6 +504,7 @@ fn parse_pump_fun_instruction(
     
     // Store in global map
     GLOBAL_MONITORING_DATA.insert(mint_pubkey, monitoring_data.clone());
+    invalidate_dex(&mint_pubkey);
     
     let now = Utc::now();
     println!("[{}] - [PARSER] Stored monitoring data for mint: {}", 
@@ -448,42 +518,100 @@ fn parse_pump_fun_instruction(
 
 /// Start monitoring with retry (ARPC)
 pub async fn start_arpc_monitoring_with_retry(
-    endpoint: &str,
+    endpoints: &[String],
     programs_to_monitor: Vec<String>,
     config: Arc<Config>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    // Shared by all endpoints: one purge thread and one stats reporter for GLOBAL_MONITORING_DATA
+    std::thread::spawn(move || {
+        purge_old_monitoring_data();
+    });
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(60)); // Report every minute
+        loop {
+            interval.tick().await;
+            let (received, logged, errors) = get_monitoring_stats();
+            let now = Utc::now();
+            println!("[{}] - [MONITORING ARPC STATS] Received: {}, Logged: {}, Errors: {}, Dropped: {}, Processing Rate: {:.2}%", 
+                now.format("%Y-%m-%d %H:%M:%S%.3f"),
+                received, logged, errors, get_monitoring_dropped(),
+                if received > 0 { (logged as f64 / received as f64) * 100.0 } else { 0.0 }
+            );
+            
+            // Log monitoring data stats
+            println!("[{}] - [MONITORING ARPC STATS] GLOBAL_MONITORING_DATA size: {}", 
+                now.format("%Y-%m-%d %H:%M:%S%.3f"),
+                GLOBAL_MONITORING_DATA.len()
+            );
+        }
+    });
+
+    // One subscription per endpoint, all writing to GLOBAL_MONITORING_DATA (deduped by mint)
+    let mut handles = Vec::new();
+    for (idx, endpoint) in endpoints.iter().enumerate() {
+        let endpoint = endpoint.clone();
+        let feed_name = monitoring_feed_name(idx, endpoints.len());
+        let programs = programs_to_monitor.clone();
+        let config = config.clone();
+        handles.push(tokio::spawn(async move {
+            run_monitoring_endpoint_with_retry(&endpoint, &feed_name, programs, config).await;
+        }));
+    }
+    for handle in handles {
+        let _ = handle.await;
+    }
+    Ok(())
+}
+
+/// Connection-stats name for a monitoring subscription ("monitoring", or "monitoring-N" with several)
+fn monitoring_feed_name(idx: usize, total: usize) -> String {
+    if total <= 1 {
+        "monitoring".to_string()
+    } else {
+        format!("monitoring-{}", idx + 1)
+    }
+}
+
+/// Keep one monitoring endpoint subscribed, reconnecting after errors
+async fn run_monitoring_endpoint_with_retry(
+    endpoint: &str,
+    feed_name: &str,
+    programs_to_monitor: Vec<String>,
+    config: Arc<Config>,
+) {
     let mut attempt = 0;
     loop {
         attempt += 1;
         let now = Utc::now();
-        println!("[{}] - [Monitoring ARPC] Attempt {} to connect and start monitoring...", 
-            now.format("%Y-%m-%d %H:%M:%S%.3f"), attempt);
+        println!("[{}] - [Monitoring ARPC] [{}] Attempt {} to connect and start monitoring...", 
+            now.format("%Y-%m-%d %H:%M:%S%.3f"), feed_name, attempt);
         let now = Utc::now();
-        println!("[{}] - [Monitoring ARPC] Connecting to endpoint: {}", 
-            now.format("%Y-%m-%d %H:%M:%S%.3f"), endpoint);
-        let result = start_arpc_monitoring_subscription(endpoint, programs_to_monitor.clone(), config.clone()).await;
+        println!("[{}] - [Monitoring ARPC] [{}] Connecting to endpoint: {}", 
+            now.format("%Y-%m-%d %H:%M:%S%.3f"), feed_name, endpoint);
+        let result = start_arpc_monitoring_subscription(endpoint, feed_name, programs_to_monitor.clone(), config.clone()).await;
+        record_disconnect(feed_name);
         match result {
             Ok(_) => {
                 let now = Utc::now();
-                println!("[{}] - [Monitoring ARPC] Subscription ended gracefully.", 
-                    now.format("%Y-%m-%d %H:%M:%S%.3f"));
+                println!("[{}] - [Monitoring ARPC] [{}] Subscription ended gracefully.", 
+                    now.format("%Y-%m-%d %H:%M:%S%.3f"), feed_name);
                 break;
             }
             Err(e) => {
                 let now = Utc::now();
-                eprintln!("[{}] - [Monitoring ARPC] Subscription error: {}", 
-                    now.format("%Y-%m-%d %H:%M:%S%.3f"), e);
+                eprintln!("[{}] - [Monitoring ARPC] [{}] Subscription error: {}", 
+                    now.format("%Y-%m-%d %H:%M:%S%.3f"), feed_name, e);
                 let now = Utc::now();
-                eprintln!("[{}] - [Monitoring ARPC] Error details: {:?}", 
-                    now.format("%Y-%m-%d %H:%M:%S%.3f"), e);
+                eprintln!("[{}] - [Monitoring ARPC] [{}] Error details: {:?}", 
+                    now.format("%Y-%m-%d %H:%M:%S%.3f"), feed_name, e);
                 let now = Utc::now();
-                eprintln!("[{}] - [Monitoring ARPC] Retrying in 10 seconds...", 
-                    now.format("%Y-%m-%d %H:%M:%S%.3f"));
+                eprintln!("[{}] - [Monitoring ARPC] [{}] Retrying in 10 seconds...", 
+                    now.format("%Y-%m-%d %H:%M:%S%.3f"), feed_name);
                 sleep(Duration::from_secs(10)).await;
             }
         }
     }
-    Ok(())
 }
 
 
@@ -516,4 +644,65 @@ pub fn export_monitoring_data() -> Vec<(Pubkey, MonitoringData)> {
     GLOBAL_MONITORING_DATA.iter()
         .map(|entry| (*entry.key(), entry.value().clone()))
         .collect()
-} 
\ No newline at end of file
+} 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_flood_processing_capped() {
+        let limiter = MonitoringLimiter::new(4);
+        let (active, peak) = (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+        let mut tasks = Vec::new();
+        for _ in 0..100 {
+            let permit = match limiter.try_acquire() {
+                Some(permit) => permit,
+                None => continue,
+            };
+            let (active, peak) = (active.clone(), peak.clone());
+            tasks.push(tokio::spawn(async move {
+                let _permit = permit;
+                peak.fetch_max(active.fetch_add(1, Ordering::SeqCst) + 1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                active.fetch_sub(1, Ordering::SeqCst);
+            }));
+        }
+        for task in tasks {
+            task.await.unwrap();
+        }
+        // The burst arrives faster than 20ms processing: only 4 run, the rest are dropped
+        assert_eq!(peak.load(Ordering::SeqCst), 4);
+        assert_eq!(limiter.dropped(), 96);
+
+        // Slots come back once processing finishes
+        assert!(limiter.try_acquire().is_some());
+        assert_eq!(MonitoringLimiter::new(0).try_acquire().map(|_| ()), Some(()));
+    }
+
+    #[test]
+    fn test_migrations_bypass_the_limiter() {
+        let program = bs58::decode(PUMP_FUN_PROGRAM_ID).into_vec().unwrap();
+        let mut transaction = crate::arpc::SubscribeResponseTransaction {
+            account_keys: vec![vec![1; 32], program],
+            ..Default::default()
+        };
+        transaction.instructions.push(crate::arpc::CompiledInstruction {
+            program_id_index: 1,
+            data: vec![0; 8],
+            ..Default::default()
+        });
+        assert!(!carries_migration(&transaction));
+
+        transaction.instructions.push(crate::arpc::CompiledInstruction {
+            program_id_index: 1,
+            data: PUMP_FUN_MIGRATE.to_vec(),
+            ..Default::default()
+        });
+        assert!(carries_migration(&transaction));
+
+        // Right discriminator, other program
+        transaction.instructions[1].program_id_index = 0;
+        assert!(!carries_migration(&transaction));
+    }
+}
@@ -0,0 +1,106 @@
+use crate::grpc::arpc_worker::get_current_slot;
+use crate::init::initialize::GLOBAL_RPC_CLIENT;
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+use std::time::Duration;
+
+// Slots fetched ahead of the current one per refresh (getSlotLeaders allows up to 5000)
+const LOOKAHEAD_SLOTS: u64 = 1_000;
+// Past slots kept so landed transactions can still be matched to their leader
+const KEEP_BEHIND_SLOTS: u64 = 2_000;
+
+// Slot -> leader, from KEEP_BEHIND_SLOTS behind the current slot to the end of the last fetch
+static SCHEDULE: Lazy<RwLock<BTreeMap<u64, Pubkey>>> = Lazy::new(|| RwLock::new(BTreeMap::new()));
+
+/// Add `leaders` of consecutive slots from `start_slot`, dropping slots before `keep_from`
+fn merge_leaders(schedule: &mut BTreeMap<u64, Pubkey>, start_slot: u64, leaders: &[Pubkey], keep_from: u64) {
+    for (offset, leader) in leaders.iter().enumerate() {
+        schedule.insert(start_slot + offset as u64, *leader);
+    }
+    *schedule = schedule.split_off(&keep_from);
+}
+
+/// Leader of `slot`, if it is within the tracked window
+pub fn leader_for_slot(slot: u64) -> Option<Pubkey> {
+    SCHEDULE.read().ok()?.get(&slot).copied()
+}
+
+/// (current slot, its leader) by the latest slot seen on the feeds
+pub fn current_leader() -> Option<(u64, Pubkey)> {
+    let slot = get_current_slot();
+    leader_for_slot(slot).map(|leader| (slot, leader))
+}
+
+/// The next `n` leader rotations from `from_slot` (inclusive): (first slot of the rotation, leader)
+pub fn upcoming_leaders(from_slot: u64, n: usize) -> Vec<(u64, Pubkey)> {
+    let schedule = match SCHEDULE.read() {
+        Ok(schedule) => schedule,
+        Err(_) => return Vec::new(),
+    };
+    let mut rotations: Vec<(u64, Pubkey)> = Vec::new();
+    for (slot, leader) in schedule.range(from_slot..) {
+        if rotations.last().map_or(true, |(_, last)| last != leader) {
+            if rotations.len() == n {
+                break;
+            }
+            rotations.push((*slot, *leader));
+        }
+    }
+    rotations
+}
+
+fn refresh(rpc: &RpcClient) -> Result<usize, String> {
+    let slot = match get_current_slot() {
+        0 => rpc.get_slot().map_err(|e| e.to_string())?,
+        slot => slot,
+    };
+    let leaders = rpc.get_slot_leaders(slot, LOOKAHEAD_SLOTS).map_err(|e| e.to_string())?;
+    let mut schedule = SCHEDULE.write().map_err(|e| e.to_string())?;
+    merge_leaders(&mut schedule, slot, &leaders, slot.saturating_sub(KEEP_BEHIND_SLOTS));
+    Ok(schedule.len())
+}
+
+/// Keep the slot leader schedule fresh, refetching every `interval` (well inside LOOKAHEAD_SLOTS)
+pub async fn run_slot_leader_tracking(interval: Duration) {
+    let mut ticker = tokio::time::interval(interval.max(Duration::from_secs(5)));
+    loop {
+        ticker.tick().await;
+        let rpc = match GLOBAL_RPC_CLIENT.get() {
+            Some(rpc) => rpc,
+            None => continue,
+        };
+        match tokio::task::spawn_blocking(move || refresh(rpc)).await {
+            Ok(Ok(_)) => {}
+            Ok(Err(e)) => eprintln!("[{}] - [SLOT_LEADERS] Leader schedule refresh failed: {}",
+                Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), e),
+            Err(_) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schedule_lookup_and_rotations() {
+        let (a, b, c) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let mut schedule = BTreeMap::new();
+        merge_leaders(&mut schedule, 100, &[a, a, a, a, b, b, b, b], 0);
+        // A later fetch overlapping the first, with old slots pruned
+        merge_leaders(&mut schedule, 104, &[b, b, b, b, c, c, c, c], 102);
+        assert_eq!(schedule.get(&101), None);
+        assert_eq!(schedule.get(&102), Some(&a));
+        assert_eq!(schedule.get(&109), Some(&c));
+
+        *SCHEDULE.write().unwrap() = schedule;
+        assert_eq!(leader_for_slot(106), Some(b));
+        assert_eq!(leader_for_slot(500), None);
+        assert_eq!(upcoming_leaders(103, 3), vec![(103, a), (104, b), (108, c)]);
+        assert_eq!(upcoming_leaders(105, 1), vec![(105, b)]);
+    }
+}
@@ -1,7 +1,8 @@
 use crate::config_load::{load_config, Config, GLOBAL_CONFIG};
 use crate::init::bird_eye::load_birdeye_token_addresses;
+use crate::utils::price_feed::run_price_refresh;
 use crate::init::dexscreener::{query_dexscreener, DexPairData};
-use crate::init::wallet_loader::{get_wallet_keypair, load_wallet_keypair_global};
+use crate::init::wallet_loader::{get_fee_payer_keypair, get_wallet_keypair, key_file_pubkeys, load_fee_payer_global, load_wallet_keypair_global, verify_nonce_accounts};
 use crate::send_tx::nextblock::initialize_nextblock_client;
 use futures::stream::{self, StreamExt};
 use once_cell::sync::OnceCell;
@@ -10,6 +11,7 @@ use solana_sdk::commitment_config::CommitmentConfig;
 use solana_sdk::signature::Signer;
 use crate::send_tx::rpc::{initialize_send_rpc_clients};
 use crate::utils::logger::setup_event_logger;
+use crate::utils::ata::init_ata_skip_list;
 use crate::triton_grpc::crossbeam_worker::setup_crossbeam_worker;
 use crate::grpc::arpc_parser::setup_arpc_crossbeam_worker;
 use crate::send_tx::rpc::keep_blockhash_fresh;
@@ -24,19 +26,61 @@ pub static GLOBAL_RPC_CLIENT: OnceCell<RpcClient> = OnceCell::new();
 
 pub async fn initialize() -> (Config, Vec<DexPairData>) {
     println!("Initializing...");
-    let config = load_config();
+    let mut config = load_config();
+    let network = match crate::config_load::network::apply_network(&mut config) {
+        Ok(network) => network,
+        Err(e) => panic!("Invalid network configuration: {}", e),
+    };
+    println!("Network: {} (RPC {})", network.name(), config.rpc_endpoint);
     GLOBAL_CONFIG
         .set(config.clone())
         .expect("Config already set");
 
+    if let Err(e) = crate::send_tx::tip_accounts::init_tip_accounts(&config) {
+        panic!("Invalid tip account configuration: {}", e);
+    }
+    if let Err(e) = crate::send_tx::block_razor::init_blockrazor_mode(&config) {
+        panic!("Invalid BlockRazor mode configuration: {}", e);
+    }
+
+    crate::utils::profiling::set_profiling_enabled(config.send_profiling);
+    println!("Send-path step profiling: {}", if config.send_profiling { "enabled" } else { "disabled" });
+    if config.max_cuprice > 0 {
+        println!("CU price ceiling: every vendor's CU price is capped at maxCUPrice = {}", config.max_cuprice);
+    }
+    crate::send_tx::vendor_wins::init_vendor_win_histogram(config.vendor_win_bucket_mins, config.vendor_win_buckets);
+
     let mut mint_cache: Vec<DexPairData> = Vec::new();
 
-    let _ = load_wallet_keypair_global("private_key.json.enc", "Metal@@2");
+    let (wallet_file, nonce_file) = network.key_files();
+    let _ = load_wallet_keypair_global(wallet_file, "Metal@@2");
 
     let keypair = get_wallet_keypair();
     println!("Wallet loaded: {}", keypair.pubkey());
 
-    match load_nonce_account_global("nonce-account.json") {
+    if config.precompute_tip_instructions {
+        crate::send_tx::tip_accounts::init_tip_contexts();
+    }
+
+    if !config.fee_payer_key.is_empty() {
+        if let Err(e) = load_fee_payer_global(&config.fee_payer_key) {
+            panic!("Failed to load fee payer from {}: {}", config.fee_payer_key, e);
+        }
+    }
+
+    if network != crate::config_load::network::Network::Mainnet {
+        let (mainnet_wallet_file, mainnet_nonce_file) = crate::config_load::network::Network::Mainnet.key_files();
+        let mainnet_keys = key_file_pubkeys(mainnet_wallet_file, "Metal@@2", mainnet_nonce_file);
+        let mut loaded = vec![("wallet", keypair.pubkey())];
+        if !config.fee_payer_key.is_empty() {
+            loaded.push(("fee payer", get_fee_payer_keypair().pubkey()));
+        }
+        if let Err(e) = crate::config_load::network::check_test_keys(network, &loaded, &mainnet_keys) {
+            panic!("Invalid network configuration: {}", e);
+        }
+    }
+
+    match load_nonce_account_global(nonce_file) {
         Ok(_) => {
             let nonce_account = get_nonce_account();
             println!("Nonce account loaded: {}", nonce_account.to_string());
@@ -53,8 +97,30 @@ pub async fn initialize() -> (Config, Vec<DexPairData>) {
     initialize_rpc(&config);
     println!("RPC client initialized");
 
+    let healthy_nonces = verify_nonce_accounts(
+        GLOBAL_RPC_CLIENT.get().unwrap(),
+        &config.nonce_ac,
+        config.create_missing_nonce_accounts,
+    );
+    println!("Nonce accounts verified ({} healthy)", healthy_nonces);
+
+    init_ata_skip_list(
+        GLOBAL_RPC_CLIENT.get().unwrap(),
+        &get_wallet_keypair().pubkey(),
+        config.skip_ata_creation,
+        &config.skip_ata_mints,
+    );
+
+    crate::send_tx::confirm_tracker::start_confirm_tracker(
+        GLOBAL_RPC_CLIENT.get().unwrap(),
+        config.confirm_batch_size,
+        std::time::Duration::from_millis(config.confirm_poll_interval_ms),
+    );
+    println!("Confirmation tracker started ({} sigs per call every {}ms)", config.confirm_batch_size, config.confirm_poll_interval_ms);
+
     initialize_send_rpc_clients(&config);
     println!("Send RPC clients initialized");
+    crate::send_tx::rpc::initialize_quic_sender(&config);
     // Spawn the keep-alive task in the background
     let _ = GLOBAL_LATEST_BLOCKHASH.set(RwLock::new(Hash::default()));
 
@@ -65,6 +131,7 @@ pub async fn initialize() -> (Config, Vec<DexPairData>) {
 
     setup_event_logger();
     println!("Event logger initialized");
+    crate::utils::otel::init_tracing(&config.otel_endpoint, &config.otel_service_name);
 
     setup_crossbeam_worker();
     println!("GRPC Crossbeam worker initialized");
@@ -72,6 +139,11 @@ pub async fn initialize() -> (Config, Vec<DexPairData>) {
     setup_arpc_crossbeam_worker();
     println!("ARPC crossbeam worker initialized");
 
+    if config.worker_stall_timeout_secs > 0 {
+        crate::utils::watchdog::start_watchdog(std::time::Duration::from_secs(config.worker_stall_timeout_secs), config.respawn_stuck_workers);
+        println!("Worker watchdog started ({}s stall timeout)", config.worker_stall_timeout_secs);
+    }
+
     init_jito_grpc_sender(&config.jito_url).await;
     println!("Jito gRPC sender initialized");
 
@@ -91,7 +163,24 @@ pub async fn initialize() -> (Config, Vec<DexPairData>) {
         Err(e) => eprintln!("Failed to initialize tip stream: {}", e),
     }
 
+    if config.reconcile_on_startup {
+        let recovered = crate::init::reconcile::reconcile_positions(
+            GLOBAL_RPC_CLIENT.get().unwrap(),
+            &get_wallet_keypair().pubkey(),
+            config.min_token_out,
+        );
+        println!("Position reconciliation done ({} positions recovered)", recovered);
+    }
+
     if !config.birdeye_api.is_empty() {
+        tokio::spawn(run_price_refresh(
+            config.birdeye_api.clone(),
+            config.mints_monitor.clone(),
+            std::time::Duration::from_secs(config.birdeye_price_ttl_secs.max(1)),
+            std::time::Duration::from_millis(config.birdeye_min_request_interval_ms),
+        ));
+        println!("Birdeye price refresh started");
+
         match load_birdeye_token_addresses(&config.birdeye_api, config.bird_eye_num_token as usize)
             .await
         {
@@ -134,6 +223,15 @@ pub async fn initialize() -> (Config, Vec<DexPairData>) {
         println!("birdEyeApi is not configured in config.toml. Skipping Birdeye token list load.");
     }
 
+    if config.startup_ready_timeout_secs > 0 {
+        let nonces_verified = healthy_nonces > 0 || config.nonce_ac.is_empty();
+        crate::init::readiness::wait_for_startup_readiness(
+            std::time::Duration::from_secs(config.startup_ready_timeout_secs),
+            nonces_verified,
+        )
+        .await;
+    }
+
     println!(
         "Initialization complete. Mint cache size: {}",
         mint_cache.len()
@@ -1,5 +1,8 @@
 pub mod bird_eye;
 pub mod dexscreener;
 pub mod initialize;
+pub mod nonce_leases;
+pub mod readiness;
+pub mod reconcile;
 pub mod tip_stream;
 pub mod wallet_loader;
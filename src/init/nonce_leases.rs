@@ -0,0 +1,137 @@
+use crate::config_load::GLOBAL_CONFIG;
+use crate::send_tx::rpc::uses_durable_nonce;
+use chrono::Utc;
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::Transaction;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// Landed-signature watch entries older than this are dropped (the lease itself expires sooner)
+const PENDING_LANDING_TIMEOUT: Duration = Duration::from_secs(120);
+
+// Nonce account -> when it was last handed to a build that hasn't landed yet
+static LEASES: Lazy<Mutex<HashMap<Pubkey, Instant>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+// Sent signature -> the nonce account its transaction advances
+static PENDING_LANDINGS: Lazy<DashMap<String, (Pubkey, Instant)>> = Lazy::new(DashMap::new);
+static NONCE_CONTENTION: AtomicUsize = AtomicUsize::new(0);
+
+/// Builds handed a nonce account whose previous build was still in flight
+pub fn get_nonce_contention() -> usize {
+    NONCE_CONTENTION.load(Ordering::Relaxed)
+}
+
+/// Index into `pubkeys` for the next build, starting from rotation position `start`, and whether
+/// it is contended (its previous lease is younger than `lease`). With `serialize`, in-flight
+/// accounts are skipped; only when every account is in flight is the oldest lease reused.
+pub fn choose_nonce(
+    pubkeys: &[Pubkey],
+    start: usize,
+    leases: &HashMap<Pubkey, Instant>,
+    now: Instant,
+    lease: Duration,
+    serialize: bool,
+) -> (usize, bool) {
+    let in_flight = |i: usize| leases.get(&pubkeys[i]).map_or(false, |at| now.duration_since(*at) < lease);
+    let next = start % pubkeys.len();
+    if !serialize {
+        return (next, in_flight(next));
+    }
+    if let Some(free) = (0..pubkeys.len()).map(|k| (next + k) % pubkeys.len()).find(|&i| !in_flight(i)) {
+        return (free, false);
+    }
+    let oldest = (0..pubkeys.len())
+        .min_by_key(|&i| leases.get(&pubkeys[i]).copied().unwrap_or(now))
+        .unwrap_or(next);
+    (oldest, true)
+}
+
+/// Lease a nonce account for a new build (see `choose_nonce`), counting and logging contention
+pub fn acquire(pubkeys: &[Pubkey], start: usize) -> usize {
+    let (lease, serialize) = GLOBAL_CONFIG
+        .get()
+        .map_or((Duration::from_millis(2_000), false), |c| (Duration::from_millis(c.nonce_lease_ms), c.serialize_nonce_handout));
+    let now = Instant::now();
+    let mut leases = LEASES.lock().unwrap_or_else(|e| e.into_inner());
+    let (index, contended) = choose_nonce(pubkeys, start, &leases, now, lease, serialize);
+    if contended {
+        NONCE_CONTENTION.fetch_add(1, Ordering::Relaxed);
+        let since = leases.get(&pubkeys[index]).map(|at| now.duration_since(*at));
+        eprintln!("[{}] - [NONCE] Contention: nonce account {} handed out again {:.2?} after a build that hasn't landed (total: {})",
+            Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), pubkeys[index], since.unwrap_or_default(), get_nonce_contention());
+    }
+    leases.insert(pubkeys[index], now);
+    index
+}
+
+/// The nonce account a durable-nonce transaction advances
+pub fn nonce_account_of(tx: &Transaction) -> Option<Pubkey> {
+    if !uses_durable_nonce(tx) {
+        return None;
+    }
+    let message = &tx.message;
+    let index = *message.instructions.first()?.accounts.first()?;
+    message.account_keys.get(index as usize).copied()
+}
+
+/// Free the nonce account of `tx` for reuse (its send failed, so nothing will advance it)
+pub fn release_nonce_of(tx: &Transaction) {
+    if let Some(nonce) = nonce_account_of(tx) {
+        LEASES.lock().unwrap_or_else(|e| e.into_inner()).remove(&nonce);
+    }
+}
+
+/// Hold `tx`'s nonce account until signature `sig` is seen landing
+pub fn release_on_landing(sig: &str, tx: &Transaction) {
+    if let Some(nonce) = nonce_account_of(tx) {
+        PENDING_LANDINGS.retain(|_, (_, sent_at)| sent_at.elapsed() < PENDING_LANDING_TIMEOUT);
+        PENDING_LANDINGS.insert(sig.trim_matches('"').to_string(), (nonce, Instant::now()));
+    }
+}
+
+/// One of our transactions landed: free the nonce account it advanced
+pub fn record_landed(sig: &str) {
+    if let Some((_, (nonce, _))) = PENDING_LANDINGS.remove(sig) {
+        LEASES.lock().unwrap_or_else(|e| e.into_inner()).remove(&nonce);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_concurrent_requests_get_distinct_nonces() {
+        let pubkeys: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let lease = Duration::from_secs(2);
+        let now = Instant::now();
+
+        // Rotation position collides with an in-flight build (e.g. the counter wrapped on a small pool)
+        let mut leases = HashMap::new();
+        leases.insert(pubkeys[0], now);
+        assert_eq!(choose_nonce(&pubkeys, 3, &leases, now, lease, false), (0, true));
+        assert_eq!(choose_nonce(&pubkeys, 3, &leases, now, lease, true), (1, false));
+
+        // Three concurrent builds on three accounts never share one when serialized
+        let mut leases = HashMap::new();
+        let mut handed_out = Vec::new();
+        for start in [0, 0, 0] {
+            let (index, contended) = choose_nonce(&pubkeys, start, &leases, now, lease, true);
+            assert!(!contended);
+            leases.insert(pubkeys[index], now);
+            handed_out.push(index);
+        }
+        handed_out.sort();
+        assert_eq!(handed_out, vec![0, 1, 2]);
+
+        // A fourth has to reuse the oldest lease, and is reported
+        leases.insert(pubkeys[2], now - Duration::from_millis(500));
+        assert_eq!(choose_nonce(&pubkeys, 0, &leases, now, lease, true), (2, true));
+        // An expired lease is free again
+        leases.insert(pubkeys[1], now - Duration::from_secs(3));
+        assert_eq!(choose_nonce(&pubkeys, 0, &leases, now, lease, true), (1, false));
+    }
+}
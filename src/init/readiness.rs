@@ -0,0 +1,156 @@
+// readiness.rs
+// Startup gate: hold off the subscriptions (and so the first trades) until the blockhash cache,
+// the vendor connections and the nonce accounts are usable.
+
+use crate::build_tx::tx_builder::vendor_enabled;
+use crate::config_load::GLOBAL_CONFIG;
+use crate::send_tx::block_razor::BLOCKRAZOR_CLIENT;
+use crate::send_tx::nextblock::NEXTBLOCK_CLIENT;
+use crate::send_tx::rpc::{GLOBAL_LATEST_BLOCKHASH, GLOBAL_SEND_RPC_CLIENTS};
+use chrono::Utc;
+use solana_sdk::hash::Hash;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+const READINESS_POLL_INTERVAL: Duration = Duration::from_millis(100);
+// Pause between rounds of getHealth probes while no send RPC has answered
+const SEND_RPC_PROBE_INTERVAL: Duration = Duration::from_millis(500);
+
+// Set once any send RPC answers a getHealth probe
+static SEND_RPCS_REACHABLE: AtomicBool = AtomicBool::new(false);
+
+/// Which startup prerequisites for sending trades are in place
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Readiness {
+    pub blockhash: bool, // blockhash cache holds a fetched hash, not the default
+    pub vendors: bool,   // every gRPC vendor client is up and the send RPCs answer (if any), none auto-disabled
+    pub nonces: bool,    // nonce accounts verified (or none configured)
+}
+
+impl Readiness {
+    pub fn is_ready(&self) -> bool {
+        self.blockhash && self.vendors && self.nonces
+    }
+
+    /// Names of the prerequisites still missing
+    pub fn missing(&self) -> Vec<&'static str> {
+        [(self.blockhash, "blockhash"), (self.vendors, "vendors"), (self.nonces, "nonces")]
+            .into_iter()
+            .filter(|(ready, _)| !ready)
+            .map(|(_, name)| name)
+            .collect()
+    }
+}
+
+/// Send RPCs are ready when the rpc vendor is off (empty `sendRPC`), or when its clients are up
+/// and at least one answered a probe
+fn send_rpcs_ready(configured: bool, clients_up: bool, reachable: bool) -> bool {
+    !configured || (clients_up && reachable)
+}
+
+/// Probe the send RPCs with getHealth until one answers or `deadline` passes. Blocking.
+fn probe_send_rpcs(deadline: Instant) {
+    let clients = match GLOBAL_SEND_RPC_CLIENTS.get() {
+        Some(clients) => clients.blocking_read().clone(),
+        None => return,
+    };
+    while !clients.is_empty() && Instant::now() < deadline {
+        if clients.iter().any(|client| client.get_health().is_ok()) {
+            SEND_RPCS_REACHABLE.store(true, Ordering::Relaxed);
+            return;
+        }
+        std::thread::sleep(SEND_RPC_PROBE_INTERVAL);
+    }
+}
+
+/// Current readiness; `nonces_verified` comes from the startup nonce check
+pub fn current_readiness(nonces_verified: bool) -> Readiness {
+    let blockhash = GLOBAL_LATEST_BLOCKHASH
+        .get()
+        .and_then(|lock| lock.try_read().ok().map(|hash| *hash != Hash::default()))
+        .unwrap_or(false);
+    let configured = GLOBAL_CONFIG.get().map_or(false, |config| vendor_enabled("rpc", &config.send_rpc));
+    let clients_up = GLOBAL_SEND_RPC_CLIENTS
+        .get()
+        .and_then(|clients| clients.try_read().ok().map(|clients| !clients.is_empty()))
+        .unwrap_or(false);
+    let send_rpcs = send_rpcs_ready(configured, clients_up, SEND_RPCS_REACHABLE.load(Ordering::Relaxed));
+    let vendors = send_rpcs
+        && NEXTBLOCK_CLIENT.get().is_some()
+        && BLOCKRAZOR_CLIENT.get().is_some()
+        && crate::send_tx::jito::is_jito_sender_initialized()
+        && crate::send_tx::vendor_health::disabled_vendors().is_empty();
+    Readiness { blockhash, vendors, nonces: nonces_verified }
+}
+
+/// Poll `check` until it reports ready or `timeout` passes. Ok with the time waited, or Err with
+/// the last readiness seen.
+pub async fn wait_until_ready(timeout: Duration, poll: Duration, mut check: impl FnMut() -> Readiness) -> Result<Duration, Readiness> {
+    let start = Instant::now();
+    loop {
+        let readiness = check();
+        if readiness.is_ready() {
+            return Ok(start.elapsed());
+        }
+        if start.elapsed() >= timeout {
+            return Err(readiness);
+        }
+        tokio::time::sleep(poll.min(timeout.saturating_sub(start.elapsed()))).await;
+    }
+}
+
+/// Startup gate run at the end of `initialize()`: waits up to `timeout` and logs the outcome.
+/// Startup carries on after a timeout (the first trades may then fail, as before the gate).
+pub async fn wait_for_startup_readiness(timeout: Duration, nonces_verified: bool) -> bool {
+    println!("[{}] - [READY] Waiting up to {:?} for blockhash cache, vendor connections and nonce accounts",
+        Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), timeout);
+    let deadline = Instant::now() + timeout;
+    tokio::task::spawn_blocking(move || probe_send_rpcs(deadline));
+    match wait_until_ready(timeout, READINESS_POLL_INTERVAL, || current_readiness(nonces_verified)).await {
+        Ok(waited) => {
+            println!("[{}] - [READY] Ready to trade after {:?}", Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), waited);
+            true
+        }
+        Err(readiness) => {
+            eprintln!("[{}] - [READY] !!! Not ready after {:?}, still missing: {} - starting anyway",
+                Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), timeout, readiness.missing().join(", "));
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_readiness_gate() {
+        // Blockhash shows up on the third poll: ready
+        let mut polls = 0;
+        let waited = wait_until_ready(Duration::from_secs(1), Duration::from_millis(5), || {
+            polls += 1;
+            Readiness { blockhash: polls >= 3, vendors: true, nonces: true }
+        })
+        .await;
+        assert!(waited.is_ok());
+        assert_eq!(polls, 3);
+
+        // A vendor never connects: times out, reporting what's missing
+        let stuck = Readiness { blockhash: true, vendors: false, nonces: true };
+        let start = Instant::now();
+        let result = wait_until_ready(Duration::from_millis(30), Duration::from_millis(5), || stuck).await;
+        assert_eq!(result, Err(stuck));
+        assert!(start.elapsed() >= Duration::from_millis(30));
+        assert_eq!(stuck.missing(), vec!["vendors"]);
+    }
+
+    #[test]
+    fn test_send_rpcs_ready() {
+        // Empty sendRPC: the rpc vendor is off, nothing to wait for
+        assert!(send_rpcs_ready(false, false, false));
+        // Configured: clients must be up and answer a probe
+        assert!(!send_rpcs_ready(true, true, false));
+        assert!(!send_rpcs_ready(true, false, false));
+        assert!(send_rpcs_ready(true, true, true));
+    }
+}
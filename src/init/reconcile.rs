@@ -0,0 +1,122 @@
+// reconcile.rs
+// Startup reconciliation: recover positions left in the wallet by a crash/restart and sell them.
+
+use crate::build_tx::dex_probe::detect_dex_for_mint;
+use crate::build_tx::pump_swap::pump_swap_constants::WSOL;
+use crate::grpc::arpc_worker::{TxWithPubkey, GLOBAL_TX_MAP};
+use crate::triton_grpc::crossbeam_worker::schedule_reconciled_sell;
+use crate::utils::logger::new_correlation_id;
+use chrono::Utc;
+use solana_account_decoder::UiAccountData;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_request::TokenAccountsFilter;
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EN5ZWQ8gAn7StP5YBX7";
+
+/// A token balance held by the wallet
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenHolding {
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+/// Mint and raw amount from a jsonParsed token account (`parsed.info.mint`, `parsed.info.tokenAmount.amount`)
+fn parse_holding(parsed: &serde_json::Value) -> Option<TokenHolding> {
+    let info = parsed.get("info")?;
+    let mint = Pubkey::from_str(info.get("mint")?.as_str()?).ok()?;
+    let amount = info.get("tokenAmount")?.get("amount")?.as_str()?.parse().ok()?;
+    Some(TokenHolding { mint, amount })
+}
+
+/// Worth recovering: not WSOL, and at least `min_amount` (and non-zero)
+fn is_reconcilable(holding: &TokenHolding, min_amount: u64) -> bool {
+    holding.mint != WSOL && holding.amount > 0 && holding.amount >= min_amount
+}
+
+/// All token balances of `owner` across the SPL Token and Token-2022 programs
+fn fetch_token_holdings(rpc: &RpcClient, owner: &Pubkey) -> Vec<TokenHolding> {
+    let programs = [spl_token::id(), Pubkey::from_str(TOKEN_2022_PROGRAM_ID).unwrap()];
+    let mut holdings = Vec::new();
+    for program in programs {
+        match rpc.get_token_accounts_by_owner(owner, TokenAccountsFilter::ProgramId(program)) {
+            Ok(accounts) => holdings.extend(accounts.iter().filter_map(|keyed| match &keyed.account.data {
+                UiAccountData::Json(parsed) => parse_holding(&parsed.parsed),
+                _ => None,
+            })),
+            Err(e) => eprintln!("[RECONCILE] getTokenAccountsByOwner ({}) failed: {}", program, e),
+        }
+    }
+    holdings
+}
+
+/// Find a DEX we can sell `mint` on (see `detect_dex_for_mint`).
+/// Raydium Launchpad positions can't be rebuilt without the original trade.
+fn probe_sell_route(mint: Pubkey) -> Option<TxWithPubkey> {
+    let mut tx = TxWithPubkey::default();
+    tx.mint = mint;
+    let (_, accounts) = detect_dex_for_mint(mint)?;
+    accounts.apply_to(&mut tx);
+    Some(tx)
+}
+
+/// Rebuild sellable positions from the wallet's token balances, insert them into GLOBAL_TX_MAP
+/// as open positions and schedule their sells. Returns how many positions were recovered.
+pub fn reconcile_positions(rpc: &RpcClient, owner: &Pubkey, min_amount: u64) -> usize {
+    let holdings: Vec<TokenHolding> = fetch_token_holdings(rpc, owner)
+        .into_iter()
+        .filter(|h| is_reconcilable(h, min_amount))
+        .collect();
+    println!("[{}] - [RECONCILE] {} non-dust token balances in wallet {}",
+        Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), holdings.len(), owner);
+
+    let mut recovered = 0;
+    for holding in holdings {
+        let mut tx = match probe_sell_route(holding.mint) {
+            Some(tx) => tx,
+            None => {
+                eprintln!("[{}] - [RECONCILE] No sellable pool found for {} ({} tokens), leaving it",
+                    Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), holding.mint, holding.amount);
+                continue;
+            }
+        };
+        tx.token_amount = holding.amount;
+        tx.open_position = true;
+        tx.corr_id = new_correlation_id();
+        let key = format!("reconcile:{}", holding.mint).into_bytes();
+        println!("[{}] - [RECONCILE] [cid={}] Recovered {} {} tokens of {}, scheduling sell",
+            Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), tx.corr_id, holding.amount, tx.tx_type, holding.mint);
+        GLOBAL_TX_MAP.insert(key.clone(), tx.clone());
+        schedule_reconciled_sell(tx, key);
+        recovered += 1;
+    }
+    recovered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_filter_holdings() {
+        let mint = Pubkey::new_unique();
+        let parsed = serde_json::json!({
+            "type": "account",
+            "info": {
+                "mint": mint.to_string(),
+                "owner": Pubkey::new_unique().to_string(),
+                "tokenAmount": { "amount": "1500000", "decimals": 6, "uiAmount": 1.5 }
+            }
+        });
+        let holding = parse_holding(&parsed).unwrap();
+        assert_eq!(holding, TokenHolding { mint, amount: 1_500_000 });
+        assert!(parse_holding(&serde_json::json!({ "info": {} })).is_none());
+
+        assert!(is_reconcilable(&holding, 0));
+        assert!(is_reconcilable(&holding, 1_500_000));
+        assert!(!is_reconcilable(&holding, 2_000_000)); // dust
+        assert!(!is_reconcilable(&TokenHolding { mint, amount: 0 }, 0));
+        assert!(!is_reconcilable(&TokenHolding { mint: WSOL, amount: 5_000_000 }, 0));
+    }
+}
@@ -9,6 +9,17 @@ use solana_sdk::pubkey::Pubkey;
 use std::error::Error;
 use std::fs;
 use solana_sdk::signer::Signer;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::account::Account;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::nonce::state::{State, Versions};
+use solana_sdk::system_instruction;
+use solana_sdk::system_program;
+use solana_sdk::transaction::Transaction;
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use std::collections::HashSet;
+use std::sync::RwLock;
 
 
 static GLOBAL_KEYPAIR: OnceCell<Keypair> = OnceCell::new();
@@ -16,10 +27,18 @@ static GLOBAL_WALLET_PUBKEY: OnceCell<Pubkey> = OnceCell::new();
 static GLOBAL_NONCE_ACCOUNTS: OnceCell<Vec<Keypair>> = OnceCell::new();
 static GLOBAL_NONCE_PUBKEYS: OnceCell<Vec<Pubkey>> = OnceCell::new();
 static GLOBAL_NONCE_INDEX: OnceCell<std::sync::atomic::AtomicUsize> = OnceCell::new();
+static GLOBAL_FEE_PAYER: OnceCell<Keypair> = OnceCell::new();
+// Nonce accounts whose authority isn't the wallet (their advance instruction would always fail)
+static MISMATCHED_NONCES: Lazy<RwLock<HashSet<Pubkey>>> = Lazy::new(|| RwLock::new(HashSet::new()));
 
 /// Load and decrypt the keypair, storing it in a global static.
 pub fn load_wallet_keypair_global(path: &str, passphrase: &str) -> Result<(), Box<dyn Error>> {
     let keypair = decrypt_and_load_keypair(path, passphrase)?;
+    set_wallet_keypair_global(keypair)
+}
+
+/// Store an already-loaded keypair as the global wallet (e.g. a test wallet)
+pub fn set_wallet_keypair_global(keypair: Keypair) -> Result<(), Box<dyn Error>> {
     let pubkey = keypair.pubkey();
     
     GLOBAL_KEYPAIR
@@ -38,9 +57,30 @@ pub fn get_wallet_keypair() -> &'static Keypair {
     GLOBAL_KEYPAIR.get().expect("Keypair not initialized")
 }
 
+/// Load a separate fee-payer keypair (JSON byte array file) that pays fees and rent
+/// while the wallet stays the swap authority
+pub fn load_fee_payer_global(path: &str) -> Result<(), Box<dyn Error>> {
+    let secret_bytes: Vec<u8> = serde_json::from_str(&fs::read_to_string(path)?)?;
+    let keypair = Keypair::from_bytes(&secret_bytes)?;
+    println!("[WALLET_LOADER] Loaded fee payer: {}", keypair.pubkey());
+    GLOBAL_FEE_PAYER
+        .set(keypair)
+        .map_err(|_| Box::new(std::io::Error::new(std::io::ErrorKind::Other, "Fee payer already initialized")) as Box<dyn Error>)
+}
+
+/// Fee payer for built transactions: the separate fee payer if configured, else the wallet
+pub fn get_fee_payer_keypair() -> &'static Keypair {
+    GLOBAL_FEE_PAYER.get().unwrap_or_else(get_wallet_keypair)
+}
+
 /// Load multiple nonce account keypairs, storing them in a global static.
 pub fn load_nonce_account_global(path: &str) -> Result<(), Box<dyn Error>> {
     let nonce_keypairs = load_nonce_account_keypairs(path)?;
+    set_nonce_accounts_global(nonce_keypairs)
+}
+
+/// Store already-loaded nonce account keypairs as the global rotation (e.g. test nonce accounts)
+pub fn set_nonce_accounts_global(nonce_keypairs: Vec<Keypair>) -> Result<(), Box<dyn Error>> {
     let nonce_pubkeys: Vec<Pubkey> = nonce_keypairs.iter().map(|kp| kp.pubkey()).collect();
     
     GLOBAL_NONCE_ACCOUNTS
@@ -118,10 +158,25 @@ pub fn get_next_nonce_account_atomic() -> (&'static Keypair, &'static Pubkey) {
             match GLOBAL_NONCE_INDEX.get() {
                 Some(index) => {
                     let current_index = index.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-                    let actual_index = current_index % accounts.len();
+                    let pubkeys = GLOBAL_NONCE_PUBKEYS.get().unwrap();
+                    // Rotation position, moved past accounts still in flight when serialize_nonce_handout is on
+                    // and past accounts excluded for an authority mismatch
+                    let excluded = if exclude_mismatched() {
+                        MISMATCHED_NONCES.read().unwrap_or_else(|e| e.into_inner()).clone()
+                    } else {
+                        HashSet::new()
+                    };
+                    let actual_index = match rotation_index(pubkeys, current_index, &excluded, crate::init::nonce_leases::acquire) {
+                        Some(index) => index,
+                        None => {
+                            eprintln!("[WALLET_LOADER] Every nonce account has a mismatched authority, using main wallet (atomic)");
+                            let pubkey = GLOBAL_WALLET_PUBKEY.get().expect("Wallet pubkey not initialized");
+                            return (get_wallet_keypair(), pubkey);
+                        }
+                    };
                     
                     println!("[WALLET_LOADER] Using nonce account {} of {} (atomic)", actual_index + 1, accounts.len());
-                    (&accounts[actual_index], &GLOBAL_NONCE_PUBKEYS.get().unwrap()[actual_index])
+                    (&accounts[actual_index], &pubkeys[actual_index])
                 }
                 None => {
                     println!("[WALLET_LOADER] Nonce index not initialized, using main wallet (atomic)");
@@ -140,11 +195,69 @@ pub fn get_next_nonce_account_atomic() -> (&'static Keypair, &'static Pubkey) {
     }
 }
 
+/// Index into `pubkeys` for the next build: `acquire` over the accounts not in `excluded`,
+/// mapped back to `pubkeys`. None when every account is excluded.
+fn rotation_index(
+    pubkeys: &[Pubkey],
+    start: usize,
+    excluded: &HashSet<Pubkey>,
+    acquire: impl FnOnce(&[Pubkey], usize) -> usize,
+) -> Option<usize> {
+    if excluded.is_empty() {
+        return Some(acquire(pubkeys, start));
+    }
+    let usable: Vec<usize> = (0..pubkeys.len()).filter(|&i| !excluded.contains(&pubkeys[i])).collect();
+    if usable.is_empty() {
+        return None;
+    }
+    let usable_pubkeys: Vec<Pubkey> = usable.iter().map(|&i| pubkeys[i]).collect();
+    Some(usable[acquire(&usable_pubkeys, start)])
+}
+
+fn exclude_mismatched() -> bool {
+    crate::config_load::GLOBAL_CONFIG.get().map_or(true, |c| c.exclude_mismatched_nonces)
+}
+
+/// Record that nonce account `pubkey` has `authority` instead of the wallet; unless
+/// `exclude_mismatched_nonces` is off it is dropped from the rotation
+pub fn record_mismatched_nonce(pubkey: &Pubkey, authority: &Pubkey) {
+    if !MISMATCHED_NONCES.write().unwrap_or_else(|e| e.into_inner()).insert(*pubkey) {
+        return;
+    }
+    eprintln!("[{}] - [WALLET_LOADER] !!! Nonce account {} has authority {}, not the wallet {}: every transaction using it would fail{}",
+        Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), pubkey, authority,
+        GLOBAL_WALLET_PUBKEY.get().map_or("?".to_string(), |p| p.to_string()),
+        if exclude_mismatched() { ", excluded from rotation" } else { "" });
+}
+
+/// Whether nonce account `pubkey` is excluded from the rotation for an authority mismatch
+pub fn is_nonce_excluded(pubkey: &Pubkey) -> bool {
+    exclude_mismatched() && MISMATCHED_NONCES.read().unwrap_or_else(|e| e.into_inner()).contains(pubkey)
+}
+
+/// Nonce accounts found with an authority other than the wallet
+pub fn get_mismatched_nonce_count() -> usize {
+    MISMATCHED_NONCES.read().unwrap_or_else(|e| e.into_inner()).len()
+}
+
 /// Get a reference to the global nonce account keypair (legacy function for backward compatibility)
 pub fn get_nonce_account_keypair() -> &'static Keypair {
     get_next_nonce_account_keypair()
 }
 
+/// Pubkeys held by a wallet file and a nonce-account file, without storing them globally;
+/// files that are missing or unreadable contribute nothing
+pub fn key_file_pubkeys(wallet_path: &str, passphrase: &str, nonce_path: &str) -> Vec<Pubkey> {
+    let mut pubkeys = Vec::new();
+    if let Ok(keypair) = decrypt_and_load_keypair(wallet_path, passphrase) {
+        pubkeys.push(keypair.pubkey());
+    }
+    if let Ok(keypairs) = load_nonce_account_keypairs(nonce_path) {
+        pubkeys.extend(keypairs.iter().map(|keypair| keypair.pubkey()));
+    }
+    pubkeys
+}
+
 /// Loads multiple nonce account keypairs from a JSON file containing an array of keypairs
 fn load_nonce_account_keypairs(path: &str) -> Result<Vec<Keypair>, Box<dyn Error>> {
     // Read the JSON array of keypairs from file
@@ -210,3 +323,158 @@ fn decrypt_and_load_keypair(path: &str, passphrase: &str) -> Result<Keypair, Box
 
     Ok(keypair)
 }
+
+/// Result of checking one nonce account at startup
+#[derive(Debug, Clone, PartialEq)]
+pub enum NonceAccountHealth {
+    Healthy,
+    Missing,
+    WrongOwner(Pubkey),
+    NotNonce,
+    Uninitialized,
+    WrongAuthority(Pubkey),
+}
+
+/// Classify a fetched nonce account (`None` = account does not exist)
+pub fn classify_nonce_account(account: Option<&Account>, authority: &Pubkey) -> NonceAccountHealth {
+    let account = match account {
+        Some(account) => account,
+        None => return NonceAccountHealth::Missing,
+    };
+    if account.owner != system_program::id() {
+        return NonceAccountHealth::WrongOwner(account.owner);
+    }
+    match bincode::deserialize::<Versions>(&account.data) {
+        Ok(versions) => match versions.state() {
+            State::Initialized(data) if data.authority == *authority => NonceAccountHealth::Healthy,
+            State::Initialized(data) => NonceAccountHealth::WrongAuthority(data.authority),
+            State::Uninitialized => NonceAccountHealth::Uninitialized,
+        },
+        Err(_) => NonceAccountHealth::NotNonce,
+    }
+}
+
+/// Create and initialize a nonce account (wallet as payer and authority)
+fn create_nonce_account(rpc: &RpcClient, wallet: &Keypair, nonce: &Keypair) -> Result<(), Box<dyn Error>> {
+    let rent = rpc.get_minimum_balance_for_rent_exemption(State::size())?;
+    let instructions = system_instruction::create_nonce_account(&wallet.pubkey(), &nonce.pubkey(), &wallet.pubkey(), rent);
+    let blockhash = rpc.get_latest_blockhash()?;
+    let tx = Transaction::new_signed_with_payer(&instructions, Some(&wallet.pubkey()), &[wallet, nonce], blockhash);
+    let signature = rpc.send_and_confirm_transaction(&tx)?;
+    println!("[WALLET_LOADER] Created nonce account {} ({})", nonce.pubkey(), signature);
+    Ok(())
+}
+
+/// Startup check of the loaded nonce keypairs plus any extra `nonceAc` pubkeys.
+/// Missing accounts we hold a keypair for are created when `create_missing` is set.
+/// Returns the number of healthy nonce accounts.
+pub fn verify_nonce_accounts(rpc: &RpcClient, configured: &[String], create_missing: bool) -> usize {
+    let wallet = get_wallet_keypair();
+    let authority = wallet.pubkey();
+
+    // (pubkey, keypair if we hold it)
+    let mut accounts: Vec<(Pubkey, Option<&Keypair>)> = GLOBAL_NONCE_ACCOUNTS
+        .get()
+        .map(|kps| kps.iter().map(|kp| (kp.pubkey(), Some(kp))).collect())
+        .unwrap_or_default();
+    for entry in configured {
+        match entry.parse::<Pubkey>() {
+            Ok(pubkey) if !accounts.iter().any(|(p, _)| *p == pubkey) => accounts.push((pubkey, None)),
+            Ok(_) => {}
+            Err(e) => println!("[WALLET_LOADER] Invalid nonceAc entry '{}': {}", entry, e),
+        }
+    }
+
+    let mut healthy = 0;
+    for (pubkey, keypair) in &accounts {
+        let fetch = |rpc: &RpcClient| rpc.get_account_with_commitment(pubkey, CommitmentConfig::confirmed()).map(|r| r.value);
+        let mut health = match fetch(rpc) {
+            Ok(account) => classify_nonce_account(account.as_ref(), &authority),
+            Err(e) => {
+                println!("[WALLET_LOADER] Failed to fetch nonce account {}: {}", pubkey, e);
+                continue;
+            }
+        };
+
+        if health == NonceAccountHealth::Missing && create_missing {
+            match keypair {
+                Some(kp) => match create_nonce_account(rpc, wallet, kp) {
+                    Ok(()) => {
+                        health = fetch(rpc)
+                            .map(|account| classify_nonce_account(account.as_ref(), &authority))
+                            .unwrap_or(NonceAccountHealth::Missing);
+                    }
+                    Err(e) => println!("[WALLET_LOADER] Failed to create nonce account {}: {}", pubkey, e),
+                },
+                None => println!("[WALLET_LOADER] Cannot create nonce account {}: no keypair in nonce-account.json", pubkey),
+            }
+        }
+
+        if let NonceAccountHealth::WrongAuthority(other) = health {
+            record_mismatched_nonce(pubkey, &other);
+        }
+        if health == NonceAccountHealth::Healthy {
+            healthy += 1;
+        } else {
+            println!("[WALLET_LOADER] Nonce account {} unhealthy: {:?}", pubkey, health);
+        }
+    }
+
+    println!("[WALLET_LOADER] {}/{} nonce accounts healthy", healthy, accounts.len());
+    healthy
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::hash::Hash;
+    use solana_sdk::nonce::state::{Data, DurableNonce};
+
+    fn nonce_account(state: State) -> Account {
+        let data = bincode::serialize(&Versions::new(state)).unwrap();
+        Account { lamports: 1_500_000, data, owner: system_program::id(), executable: false, rent_epoch: 0 }
+    }
+
+    #[test]
+    fn test_classify_nonce_account() {
+        let authority = Pubkey::new_unique();
+        let durable = DurableNonce::from_blockhash(&Hash::new_unique());
+
+        assert_eq!(classify_nonce_account(None, &authority), NonceAccountHealth::Missing);
+
+        let ours = nonce_account(State::Initialized(Data::new(authority, durable, 5000)));
+        assert_eq!(classify_nonce_account(Some(&ours), &authority), NonceAccountHealth::Healthy);
+
+        let other = Pubkey::new_unique();
+        let theirs = nonce_account(State::Initialized(Data::new(other, durable, 5000)));
+        assert_eq!(classify_nonce_account(Some(&theirs), &authority), NonceAccountHealth::WrongAuthority(other));
+
+        let uninit = nonce_account(State::Uninitialized);
+        assert_eq!(classify_nonce_account(Some(&uninit), &authority), NonceAccountHealth::Uninitialized);
+
+        let mut token = ours.clone();
+        token.owner = spl_token::id();
+        assert_eq!(classify_nonce_account(Some(&token), &authority), NonceAccountHealth::WrongOwner(spl_token::id()));
+
+        let mut garbage = ours;
+        garbage.data = vec![0xff; 3];
+        assert_eq!(classify_nonce_account(Some(&garbage), &authority), NonceAccountHealth::NotNonce);
+    }
+
+    #[test]
+    fn test_mismatched_authority_nonce_excluded_from_rotation() {
+        let pubkeys: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let round_robin = |keys: &[Pubkey], start: usize| start % keys.len();
+        let picks = |excluded: &HashSet<Pubkey>| -> Vec<Option<usize>> {
+            (0..4).map(|start| rotation_index(&pubkeys, start, excluded, round_robin)).collect()
+        };
+
+        assert_eq!(picks(&HashSet::new()), vec![Some(0), Some(1), Some(2), Some(0)]);
+        // The middle account's authority is someone else's: never handed out
+        let excluded: HashSet<Pubkey> = [pubkeys[1]].into_iter().collect();
+        assert_eq!(picks(&excluded), vec![Some(0), Some(2), Some(0), Some(2)]);
+        // All mismatched: nothing to hand out
+        let all: HashSet<Pubkey> = pubkeys.iter().copied().collect();
+        assert_eq!(rotation_index(&pubkeys, 0, &all, round_robin), None);
+    }
+}
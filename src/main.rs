@@ -8,10 +8,10 @@ pub mod send_tx;
 pub mod solana;
 pub mod triton_grpc;
 pub mod utils;
+pub mod alerts;
 pub mod constants;
 pub mod monitoring_example;
-use crate::constants::monitoring::MONITORING_PROGRAMS;
-use crate::constants::monitoring::get_monitoring_arpc_endpoint;
+use crate::constants::monitoring::{get_monitoring_arpc_endpoints, get_monitoring_programs};
 use crate::grpc::monitoring_client::start_arpc_monitoring_with_retry;
 
 pub mod arpc {
@@ -68,14 +68,100 @@ async fn start_stats_monitoring() {
             arpc_received, arpc_processed, arpc_errors,
             if arpc_received > 0 { (arpc_processed as f64 / arpc_received as f64) * 100.0 } else { 0.0 }
         );
-        println!("[{}] WORKER: Received={}, Built={}, Inserted={}, Errors={}", 
+        println!("[{}] WORKER: Received={}, Built={}, Inserted={}, Errors={}, SkippedDust={}, RejectedSanity={}, CappedExposure={}, SlotDuplicates={}", 
             now.format("%Y-%m-%d %H:%M:%S%.3f"),
-            worker_received, worker_built, worker_inserted, worker_errors
+            worker_received, worker_built, worker_inserted, worker_errors,
+            crate::grpc::arpc_worker::get_dust_skipped(),
+            crate::grpc::arpc_worker::get_sanity_rejected(),
+            crate::grpc::mint_exposure::get_exposure_capped(),
+            crate::grpc::leader_dedup::get_slot_duplicates_skipped()
         );
-        println!("[{}] TRITON: Received={}, Sent={}, Found={}, Errors={}", 
+        let unknown_ix = crate::grpc::arpc_worker::top_unknown_discriminators(5);
+        if !unknown_ix.is_empty() {
+            println!("[{}] UNKNOWN IX: {}", 
+                now.format("%Y-%m-%d %H:%M:%S%.3f"),
+                unknown_ix.iter().map(|(program, d, count)| format!("{}:{:?}={}", program, d, count)).collect::<Vec<_>>().join(", ")
+            );
+        }
+        println!("[{}] TRITON: Received={}, Sent={}, Found={}, Errors={}, PresellDrained={}, SellMintMismatch={}, SigMismatch={}, MalformedSkipped={}", 
             now.format("%Y-%m-%d %H:%M:%S%.3f"),
-            triton_received, triton_sent, triton_found, triton_errors
+            triton_received, triton_sent, triton_found, triton_errors,
+            crate::send_tx::presell::get_presell_drained(),
+            crate::send_tx::presell::get_sell_mint_mismatches(),
+            crate::send_tx::sig_verify::get_signature_mismatches(),
+            crate::triton_grpc::parser::get_malformed_skipped()
         );
+        let slot_attempts = crate::utils::slot_attempts::get_recent_slot_attempts();
+        if !slot_attempts.is_empty() {
+            println!("[{}] SLOT ATTEMPTS: Peak={} Recent={}", 
+                now.format("%Y-%m-%d %H:%M:%S%.3f"),
+                slot_attempts.iter().map(|b| b.total()).max().unwrap_or(0),
+                slot_attempts.iter().rev().take(10).map(|b| format!("{}:{}b/{}s", b.slot, b.buys, b.sells)).collect::<Vec<_>>().join(", ")
+            );
+        }
+        let (buy_fills, buy_slippage, sell_fills, sell_slippage) = crate::utils::realized_slippage::get_realized_slippage_stats();
+        if buy_fills + sell_fills > 0 {
+            let avg = |bps: Option<f64>| bps.map_or("n/a".to_string(), |bps| format!("{:.1}bps", bps));
+            println!("[{}] REALIZED SLIPPAGE: Buys={} (avg {}), Sells={} (avg {})", 
+                now.format("%Y-%m-%d %H:%M:%S%.3f"),
+                buy_fills, avg(buy_slippage), sell_fills, avg(sell_slippage)
+            );
+        }
+        if let Some((slot, leader)) = crate::grpc::slot_leaders::current_leader() {
+            println!("[{}] LEADERS: Slot={} Current={} Next={}", 
+                now.format("%Y-%m-%d %H:%M:%S%.3f"),
+                slot, leader,
+                crate::grpc::slot_leaders::upcoming_leaders(slot, 4).iter().skip(1).map(|(s, l)| format!("{}@{}", l, s)).collect::<Vec<_>>().join(", ")
+            );
+        }
+        {
+            let (in_flight, cap_hits, dropped) = crate::send_tx::send_limit::get_send_limit_stats();
+            println!("[{}] SEND LIMIT: InFlight={}, CapHits={}, Dropped={}", 
+                now.format("%Y-%m-%d %H:%M:%S%.3f"),
+                in_flight, cap_hits, dropped
+            );
+        }
+        let (alerts_sent, alerts_failed, alerts_limited) = crate::alerts::get_alert_stats();
+        if alerts_sent + alerts_failed + alerts_limited > 0 {
+            println!("[{}] ALERTS: Sent={}, Failed={}, RateLimited={}", 
+                now.format("%Y-%m-%d %H:%M:%S%.3f"),
+                alerts_sent, alerts_failed, alerts_limited
+            );
+        }
+        let (halted, suppressed) = crate::utils::kill_switch::get_kill_switch_stats();
+        if halted || suppressed > 0 {
+            println!("[{}] KILL SWITCH: Halted={}, Suppressed={}", 
+                now.format("%Y-%m-%d %H:%M:%S%.3f"),
+                halted, suppressed
+            );
+        }
+        if let Some(buckets) = crate::send_tx::vendor_wins::get_vendor_win_distribution() {
+            println!("[{}] VENDOR WINS: {}", 
+                now.format("%Y-%m-%d %H:%M:%S%.3f"),
+                buckets.join(" | ")
+            );
+        }
+        let (sells_batched, batch_txs) = crate::send_tx::sell_batch::get_sell_batch_stats();
+        if batch_txs > 0 {
+            println!("[{}] SELL BATCH: Sells={}, Transactions={}", 
+                now.format("%Y-%m-%d %H:%M:%S%.3f"),
+                sells_batched, batch_txs
+            );
+        }
+        println!("[{}] NONCE: Contention={}, AuthorityMismatched={}", 
+            now.format("%Y-%m-%d %H:%M:%S%.3f"),
+            crate::init::nonce_leases::get_nonce_contention(),
+            crate::init::wallet_loader::get_mismatched_nonce_count()
+        );
+        {
+            let (calls, resolved, pending) = crate::send_tx::confirm_tracker::get_confirm_stats();
+            println!("[{}] CONFIRM: StatusCalls={}, Resolved={}, Pending={}, SellsHeld={}, BuysChased={}", 
+                now.format("%Y-%m-%d %H:%M:%S%.3f"),
+                calls, resolved, pending,
+                crate::send_tx::sell_gate::get_sells_held(),
+                crate::send_tx::chase::get_chase_resends()
+            );
+        }
         
         // Add monitoring stats
         println!("[{}] MONITORING: Received={}, Logged={}, Errors={}, Active Logs={}, Rate={:.2}%", 
@@ -95,6 +181,121 @@ async fn start_stats_monitoring() {
             dedup_size
         );
         
+        {
+            static LAST_FEED_DEDUP_EVICTED: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+            let (feed_dedup_size, feed_dedup_expired, feed_dedup_evicted) = crate::triton_grpc::crossbeam_worker::get_feed_dedup_stats();
+            let last_evicted = LAST_FEED_DEDUP_EVICTED.swap(feed_dedup_evicted, std::sync::atomic::Ordering::Relaxed);
+            println!("[{}] FEED DEDUP: Size={}, Expired={}, Evicted={}, Evicted/min={}", 
+                now.format("%Y-%m-%d %H:%M:%S%.3f"),
+                feed_dedup_size, feed_dedup_expired, feed_dedup_evicted,
+                feed_dedup_evicted.saturating_sub(last_evicted)
+            );
+        }
+
+        
+        {
+            let status = crate::send_tx::rpc::get_blockhash_cache_status().await;
+            println!("[{}] BLOCKHASH: Hash={}, LastValidHeight={:?}, EstHeight={:?}, Expired={}, ExpiredSendsSkipped={}", 
+                now.format("%Y-%m-%d %H:%M:%S%.3f"),
+                status.blockhash,
+                status.last_valid_block_height,
+                status.estimated_block_height,
+                status.expired,
+                status.expired_sends_skipped
+            );
+        }
+        
+        {
+            let connections = crate::grpc::connection_stats::get_connection_stats();
+            println!("[{}] CONNECTIONS: {}", 
+                now.format("%Y-%m-%d %H:%M:%S%.3f"),
+                connections.iter().map(|c| format!("{} connects={} disconnects={} uptime={} downtime={:.1?}",
+                    c.name,
+                    c.connects,
+                    c.disconnects,
+                    c.uptime.map(|u| format!("{:.1?}", u)).unwrap_or_else(|| "down".to_string()),
+                    c.total_downtime
+                )).collect::<Vec<_>>().join(" | ")
+            );
+        }
+        
+        {
+            use crate::utils::control::{is_paused, get_paused_skipped, Pipeline};
+            println!("[{}] PAUSE: ARPC paused={} skipped={}, TRITON paused={} skipped={}", 
+                now.format("%Y-%m-%d %H:%M:%S%.3f"),
+                is_paused(Pipeline::Arpc), get_paused_skipped(Pipeline::Arpc),
+                is_paused(Pipeline::Triton), get_paused_skipped(Pipeline::Triton)
+            );
+        }
+        
+        {
+            let throttle_stats = crate::send_tx::rate_guard::get_vendor_throttle_stats();
+            if !throttle_stats.is_empty() {
+                println!("[{}] THROTTLE: {}", 
+                    now.format("%Y-%m-%d %H:%M:%S%.3f"),
+                    throttle_stats.iter()
+                        .map(|(vendor, queued, skipped)| format!("{} queued={} skipped={}", vendor, queued, skipped))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            }
+        }
+        
+        {
+            let latency = crate::utils::lifecycle::latency_report();
+            if !latency.is_empty() {
+                println!("[{}] LATENCY: {}",
+                    now.format("%Y-%m-%d %H:%M:%S%.3f"),
+                    latency.iter()
+                        .map(|(stage, n, p50, p95)| format!("{} n={} p50={:.2?} p95={:.2?}", stage.name(), n, p50, p95))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            }
+        }
+        
+        {
+            use crate::utils::watchdog::{ARPC_WORKERS, TRITON_WORKERS};
+            let timeout = std::time::Duration::from_secs(crate::config_load::GLOBAL_CONFIG.get().map_or(0, |c| c.worker_stall_timeout_secs));
+            println!("[{}] WORKER PROGRESS: triton [{}], arpc [{}]",
+                now.format("%Y-%m-%d %H:%M:%S%.3f"), TRITON_WORKERS.progress_line(timeout), ARPC_WORKERS.progress_line(timeout));
+        }
+        
+        {
+            let (reused, allocated) = crate::send_tx::tx_buffer::get_buffer_stats();
+            println!("[{}] SEND BUFFERS: Reused={}, Allocated={}",
+                now.format("%Y-%m-%d %H:%M:%S%.3f"), reused, allocated);
+        }
+        
+        {
+            let disabled = crate::send_tx::vendor_health::disabled_vendors();
+            if !disabled.is_empty() {
+                println!("[{}] VENDOR HEALTH: auto-disabled {:?}", now.format("%Y-%m-%d %H:%M:%S%.3f"), disabled);
+            }
+        }
+        
+        {
+            let (sim_calls, sim_waited, sim_avg_wait_us, sim_queue_timeouts, sim_rpc_timeouts) = crate::build_tx::simulation::get_simulation_stats();
+            let (send_node_sims, send_node_discrepancies) = crate::build_tx::simulation::get_send_node_stats();
+            println!("[{}] SIMULATION: Calls={}, Waited={}, AvgWait={}us, QueueTimeouts={}, RpcTimeouts={}, SendNodeSims={}, SendNodeDiverged={}", 
+                now.format("%Y-%m-%d %H:%M:%S%.3f"),
+                sim_calls, sim_waited, sim_avg_wait_us, sim_queue_timeouts, sim_rpc_timeouts,
+                send_node_sims, send_node_discrepancies
+            );
+        }        
+        {
+            let pool_stats = crate::send_tx::http_pool::get_http_pool_stats();
+            if !pool_stats.is_empty() {
+                println!("[{}] HTTP POOL: Size={}, {}", 
+                    now.format("%Y-%m-%d %H:%M:%S%.3f"),
+                    crate::send_tx::http_pool::http_pool_size(),
+                    pool_stats.iter()
+                        .map(|(vendor, sends, waited, avg_us, max_us)| format!("{} sends={} waited={} avg_wait={}us max_wait={}us", vendor, sends, waited, avg_us, max_us))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            }
+        }        
         if let Some((rss, vm_size)) = memory_info {
             println!("[{}] MEMORY: RSS={}, Virtual={}", 
                 now.format("%Y-%m-%d %H:%M:%S%.3f"),
@@ -118,6 +319,7 @@ async fn start_stats_monitoring() {
         if map_size > 100 {
             println!("[{}] WARNING: Large map size detected ({}) - potential memory leak!", 
                 now.format("%Y-%m-%d %H:%M:%S%.3f"), map_size);
+            crate::alerts::alert(crate::alerts::AlertKind::MemoryLeak, format!("transaction map at {} entries", map_size));
             
             // Trigger debug cleanup
             crate::grpc::arpc_worker::debug_and_cleanup();
@@ -145,6 +347,8 @@ async fn start_stats_monitoring() {
                             rss_diff / (1024 * 1024),
                             time_diff as usize
                         );
+                        crate::alerts::alert(crate::alerts::AlertKind::MemoryLeak,
+                            format!("RSS increased by {} MB in {} seconds", rss_diff / (1024 * 1024), time_diff));
                         
                         // Trigger emergency cleanup
                         println!("[{}] WARNING: Triggering emergency cleanup...", 
@@ -171,11 +375,15 @@ async fn start_stats_monitoring() {
         if worker_received > 0 && worker_built < worker_received / 2 {
             println!("[{}] WARNING: Worker processing bottleneck detected! Received: {}, Built: {}", 
                 now.format("%Y-%m-%d %H:%M:%S%.3f"), worker_received, worker_built);
+            crate::alerts::alert(crate::alerts::AlertKind::Bottleneck,
+                format!("worker built {} of {} received transactions", worker_built, worker_received));
         }
         
         if triton_found > 0 && triton_sent < triton_found / 2 {
             println!("[{}] WARNING: Triton sending bottleneck detected! Found: {}, Sent: {}", 
                 now.format("%Y-%m-%d %H:%M:%S%.3f"), triton_found, triton_sent);
+            crate::alerts::alert(crate::alerts::AlertKind::Bottleneck,
+                format!("Triton sent {} of {} found transactions", triton_sent, triton_found));
         }
         
         println!("[{}] ================================================", now.format("%Y-%m-%d %H:%M:%S%.3f"));
@@ -203,6 +411,51 @@ async fn main() {
     let stats_handle = tokio::spawn(start_stats_monitoring());
     handles.push(stats_handle);
 
+    // Terse periodic liveness line, independent of activity
+    if config_arc.heartbeat_interval_secs > 0 {
+        handles.push(tokio::spawn(crate::utils::heartbeat::run_heartbeat(config_arc.heartbeat_interval_secs)));
+    }
+
+    // Critical events (circuit breaker, vendors down, low balance, reconnect churn, memory, kill switch)
+    if !config_arc.alert_webhook_url.is_empty() {
+        handles.push(tokio::spawn(crate::alerts::run_alert_webhook(
+            config_arc.alert_webhook_url.clone(),
+            config_arc.alert_webhook_format.clone(),
+            Duration::from_secs(config_arc.alert_min_interval_secs),
+        )));
+    }
+
+    // Emergency halt: no sends while the kill-switch file exists
+    if !config_arc.kill_switch_file.is_empty() {
+        handles.push(tokio::spawn(crate::utils::kill_switch::run_kill_switch(
+            config_arc.kill_switch_file.clone(),
+            Duration::from_millis(config_arc.kill_switch_poll_ms.max(1)),
+        )));
+    }
+
+    // Daily rollup of trades, vendor landings, tips and reconnects
+    if config_arc.daily_summary_interval_hours > 0 {
+        handles.push(tokio::spawn(crate::utils::daily_summary::run_daily_summary(
+            config_arc.daily_summary_interval_hours,
+            config_arc.daily_summary_boundary_hour_utc,
+            config_arc.daily_summary_path.clone(),
+        )));
+    }
+
+    // Reclaim rent from empty / dust token accounts
+    if config_arc.ata_sweep_interval_mins > 0 {
+        handles.push(tokio::spawn(crate::utils::ata_sweep::run_ata_sweep(
+            config_arc.ata_sweep_interval_mins,
+            config_arc.ata_sweep_dust_amount,
+        )));
+    }
+
+    // Start control interface (pause/resume pipelines)
+    if !config_arc.control_addr.is_empty() {
+        let control_handle = tokio::spawn(crate::utils::control::start_control_server(config_arc.control_addr.clone()));
+        handles.push(control_handle);
+    }
+
     let triton_config = Arc::clone(&config_arc);
     let handle = tokio::spawn(async move {
         println!("[Main] Starting Triton multi-feed gRPC clients...");
@@ -218,6 +471,24 @@ async fn main() {
     });
     handles.push(handle);
 
+    // Tracked-wallet age check (startup + slow interval), off the hot path
+    if config_arc.min_leader_age_secs > 0 || config_arc.min_leader_history_txs > 0 {
+        handles.push(tokio::spawn(crate::grpc::leader_age::run_leader_age_checks(
+            config_arc.accounts_monitor.clone(),
+            config_arc.min_leader_age_secs,
+            config_arc.min_leader_history_txs,
+            config_arc.filter_new_leaders,
+            Duration::from_secs(config_arc.leader_age_check_interval_secs),
+        )));
+    }
+
+    // Slot leader schedule, for leader-aware sends and correlating landings to leaders
+    if config_arc.slot_leader_refresh_secs > 0 {
+        handles.push(tokio::spawn(crate::grpc::slot_leaders::run_slot_leader_tracking(
+            Duration::from_secs(config_arc.slot_leader_refresh_secs),
+        )));
+    }
+
     let arpc_config = Arc::clone(&config_arc);
     let handle = tokio::spawn(async move {
         let endpoint = arpc_config.arpc_endpoint.clone();
@@ -233,14 +504,12 @@ async fn main() {
     // NEW: Start monitoring system (separate from trading pipes)
     let monitoring_config = Arc::clone(&config_arc);
     let monitoring_handle = tokio::spawn(async move {
-        let programs_to_monitor: Vec<String> = MONITORING_PROGRAMS
-            .iter()
-            .map(|&s| s.to_string())
-            .collect();
+        let programs_to_monitor = get_monitoring_programs();
+        let endpoints = get_monitoring_arpc_endpoints();
         
-        println!("[Main] Starting DEX monitoring system...");
+        println!("[Main] Starting DEX monitoring system on {} endpoint(s)...", endpoints.len());
         if let Err(e) = start_arpc_monitoring_with_retry(
-            &get_monitoring_arpc_endpoint(), 
+            &endpoints, 
             programs_to_monitor, 
             monitoring_config
         ).await {
@@ -59,7 +59,7 @@ async fn main() {
         
         println!("[Main] Starting DEX monitoring system...");
         if let Err(e) = start_arpc_monitoring_with_retry(
-            &get_monitoring_arpc_endpoint(), 
+            &[get_monitoring_arpc_endpoint()], 
             programs_to_monitor, 
             monitoring_config
         ).await {
@@ -30,7 +30,7 @@ pub async fn start_arpc_monitoring_system(config: Arc<Config>) -> JoinHandle<()>
     
     let handle = tokio::spawn(async move {
         if let Err(e) = start_arpc_monitoring_with_retry(
-            &get_monitoring_arpc_endpoint(), 
+            &[get_monitoring_arpc_endpoint()], 
             programs_to_monitor, 
             monitoring_config
         ).await {
@@ -2,7 +2,6 @@ use crate::init::wallet_loader::{get_wallet_keypair, get_nonce_account};
 use crate::init::tip_stream::get_tip_percentile;
 use base64::{engine::general_purpose, Engine as _};
 use isahc::{HttpClient, prelude::*};
-use rand::seq::SliceRandom;
 use solana_sdk::{
     pubkey::Pubkey,
     signature::{Keypair, Signer},
@@ -11,16 +10,15 @@ use solana_sdk::{
 };
 use solana_sdk::instruction::Instruction;
 use crate::config_load::GLOBAL_CONFIG;
-use rand::Rng;
 use solana_sdk::compute_budget;
-use std::str::FromStr;
 use std::time::Instant;
 
 use chrono::Utc;
 use std::sync::OnceLock;
+use crate::send_tx::tx_buffer::{recycle_buffer, take_buffer};
 
 // Astralane tip accounts as specified in the documentation
-static ASTRALANE_TIP_ACCOUNTS: &[&str] = &[
+pub(crate) static ASTRALANE_TIP_ACCOUNTS: &[&str] = &[
     "astrazznxsGUhWShqgNtAdfrzP2G83DzcWVJDxwV9bF",
     "astra4uejePWneqNaJKuFFA8oonqCE1sqF6b45kDMZm",
     "astra9xWY93QyfG6yM8zwsKsRodscjQ2uU2HKNL5prk",
@@ -32,10 +30,12 @@ static ISAHC_CLIENT: OnceLock<HttpClient> = OnceLock::new();
 
 fn get_isahc_client() -> HttpClient {
     ISAHC_CLIENT.get_or_init(|| {
-        HttpClient::builder()
-            .max_connections_per_host(50) // Allow up to 50 connections per host
+        let builder = HttpClient::builder()
+            .max_connections_per_host(crate::send_tx::http_pool::http_pool_size()) // Allow up to http_pool_size connections per host
             .timeout(std::time::Duration::from_secs(3)) // 3 second timeout
-            .connect_timeout(std::time::Duration::from_millis(500)) // 500ms connect timeout
+            .connect_timeout(std::time::Duration::from_millis(500)); // 500ms connect timeout
+        // TCP_NODELAY and configured keepalive
+        crate::utils::socket_tuning::tune_isahc(builder, crate::utils::socket_tuning::socket_tuning())
             .build()
             .expect("Failed to create isahc client")
     }).clone()
@@ -43,13 +43,8 @@ fn get_isahc_client() -> HttpClient {
 
 /// Create a system transfer instruction for Astralane tips
 pub fn astralane_tip(tip: u64, from_pubkey: &Pubkey) -> Instruction {
-    // Randomly select a tip account from the list
-    let tip_account = ASTRALANE_TIP_ACCOUNTS
-        .choose(&mut rand::thread_rng())
-        .expect("Failed to select random tip account");
-    
-    let tip_pubkey = Pubkey::from_str(tip_account).expect("Invalid pubkey");
-    system_instruction::transfer(from_pubkey, &tip_pubkey, tip)
+    // Random tip account (configured via vendor_tip_accounts or built-in list), precomputed at startup
+    crate::send_tx::tip_accounts::tip_instruction("astralane", tip, from_pubkey)
 }
 
 /// Build compute budget instructions for Astralane with optimizations
@@ -66,11 +61,9 @@ pub fn create_instruction_astralane(
     println!("[{}] - [ASTRALANE_INSTRUCTION_PROFILE] 🔧 Starting Astralane instruction building", 
         now.format("%Y-%m-%d %H:%M:%S%.3f"));
     
-    // Step 1: Random number generation for compute unit price variation
+    // Step 1: Random compute unit price variation, clamped to [min_cu_price, maxCUPrice]
     let rng_start = Instant::now();
-    let mut rng = rand::thread_rng();
-    let random_addition: u64 = rng.gen_range(1..=100);
-    let adjusted_cu_price = cu_price + random_addition;
+    let adjusted_cu_price = crate::send_tx::cu_price::adjust_cu_price(cu_price, &instructions);
     let rng_time = rng_start.elapsed();
     
     #[cfg(feature = "verbose_logging")]
@@ -139,6 +132,7 @@ pub fn create_instruction_astralane(
 /// Send a signed Solana transaction via Astralane HTTP API with isahc optimizations
 /// This implements the sendTransaction method as described in the Astralane documentation
 pub async fn send_tx_astralane(tx: &Transaction) -> Result<String, Box<dyn std::error::Error>> {
+    crate::send_tx::rate_guard::throttle_vendor_send("astralane").await?;
     let total_start = Instant::now();
     let now = Utc::now();
     
@@ -158,7 +152,7 @@ pub async fn send_tx_astralane(tx: &Transaction) -> Result<String, Box<dyn std::
     
     // Step 1: Serialize transaction (measure serialization time)
     let serialize_start = Instant::now();
-    let mut buffer = Vec::with_capacity(4096); // Pre-allocate 4KB buffer
+    let mut buffer = take_buffer(); // Pooled 4KB buffer, reused across sends
     bincode::serialize_into(&mut buffer, tx)?;
     let serialize_time = serialize_start.elapsed();
     
@@ -169,6 +163,7 @@ pub async fn send_tx_astralane(tx: &Transaction) -> Result<String, Box<dyn std::
     // Step 2: Base64 encoding (measure encoding time)
     let encode_start = Instant::now();
     let tx_b64 = general_purpose::STANDARD.encode(&buffer);
+    recycle_buffer(buffer);
     let encode_time = encode_start.elapsed();
     
     #[cfg(feature = "verbose_logging")]
@@ -208,9 +203,11 @@ pub async fn send_tx_astralane(tx: &Transaction) -> Result<String, Box<dyn std::
         println!("[ASTRALANE_DEBUG] 🔄 Attempt {}/{}", attempt, max_retries);
         
         // Use our persistent client to keep connections warm
+        let pool_slot = crate::send_tx::http_pool::acquire_pool_slot("astralane").await;
         response_result = Some(get_isahc_client()
             .post_async(&config.astralane_url, request_json.clone())
             .await);
+        drop(pool_slot);
         
         match &response_result {
             Some(Ok(_)) => {
@@ -314,7 +311,7 @@ pub async fn send_tx_astralane(tx: &Transaction) -> Result<String, Box<dyn std::
     
     // Calculate total time and breakdown
     let total_time = total_start.elapsed();
-    let processing_time = total_time - network_time; // Time spent in our code
+    let processing_time = total_time.saturating_sub(network_time); // Time spent in our code
     
     #[cfg(feature = "verbose_logging")]
     println!("[{}] - [ASTRALANE_PROFILE] ✅ Transaction sent successfully!", 
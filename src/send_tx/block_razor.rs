@@ -13,20 +13,21 @@ use tonic::metadata::MetadataValue;
 use tonic::transport::{Channel, ClientTlsConfig};
 use tonic::{Request, Status};
 use solana_sdk::signer::keypair::Keypair;
-use rand::Rng;
 use solana_sdk::compute_budget;
 use std::time::Instant;
 use chrono::Utc;
+use crate::utils::profiling::StepTimer;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::collections::VecDeque;
 use std::sync::Mutex;
+use crate::send_tx::tx_buffer::{recycle_buffer, take_buffer};
 
 // BlockRazor proto definitions
 pub mod blockrazor_proto {
     tonic::include_proto!("serverpb");
 }
 
-static BLOCKRAZOR_TIP_ACCOUNTS: &[&str] = &[
+pub(crate) static BLOCKRAZOR_TIP_ACCOUNTS: &[&str] = &[
     "FjmZZrFvhnqqb9ThCuMVnENaM3JGVuGWNyCAxRJcFpg9",
     "6No2i3aawzHsjtThw81iq1EXPJN6rh8eSJCLaYZfKDTG",
     "A9cWowVAiHe9pJfKAj3TJiN9VpbzMUq6E4kEvf5mUT22",
@@ -100,7 +101,7 @@ pub async fn connect_to_blockrazor(
     plaintext: bool,
 ) -> Result<Channel, Box<dyn std::error::Error>> {
     let channel = if plaintext {
-        Channel::from_shared(address.to_string())?
+        crate::utils::socket_tuning::tuned_endpoint(address)?
             .connect_timeout(std::time::Duration::from_secs(2)) // Reduced from 5s
             .timeout(std::time::Duration::from_secs(5)) // Reduced from 10s
             .tcp_keepalive(Some(std::time::Duration::from_secs(10))) // Reduced from 30s
@@ -109,7 +110,7 @@ pub async fn connect_to_blockrazor(
             .initial_connection_window_size(1024 * 1024) // 1MB connection window
             .connect_lazy() // Use lazy connection for persistence
     } else {
-        Channel::from_shared(address.to_string())?
+        crate::utils::socket_tuning::tuned_endpoint(address)?
             .tls_config(ClientTlsConfig::new())?
             .connect_timeout(std::time::Duration::from_secs(2)) // Reduced from 5s
             .timeout(std::time::Duration::from_secs(5)) // Reduced from 10s
@@ -122,15 +123,67 @@ pub async fn connect_to_blockrazor(
     Ok(channel)
 }
 
+// Submission modes BlockRazor accepts
+const BLOCKRAZOR_MODES: [&str; 2] = ["fast", "sandwichMitigation"];
+
+/// BlockRazor submission settings (blockrazor_mode / blockrazor_safe_window / blockrazor_revert_protection)
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockRazorMode {
+    pub mode: String,
+    pub safe_window: Option<i32>, // slots; sandwichMitigation only
+    pub revert_protection: bool,
+}
+
+impl BlockRazorMode {
+    /// Validated settings; a `safe_window` of 0 leaves it unset
+    pub fn new(mode: &str, safe_window: i32, revert_protection: bool) -> Result<Self, String> {
+        if !BLOCKRAZOR_MODES.contains(&mode) {
+            return Err(format!("unknown blockrazor_mode {:?} (expected one of {:?})", mode, BLOCKRAZOR_MODES));
+        }
+        if safe_window < 0 {
+            return Err(format!("blockrazor_safe_window must not be negative (got {})", safe_window));
+        }
+        if safe_window > 0 && mode != "sandwichMitigation" {
+            return Err(format!("blockrazor_safe_window only applies to sandwichMitigation mode (mode is {:?})", mode));
+        }
+        Ok(BlockRazorMode { mode: mode.to_string(), safe_window: (safe_window > 0).then_some(safe_window), revert_protection })
+    }
+}
+
+static BLOCKRAZOR_MODE: OnceCell<BlockRazorMode> = OnceCell::new();
+
+/// Validate and install the configured BlockRazor mode; call once at startup
+pub fn init_blockrazor_mode(config: &crate::config_load::Config) -> Result<(), String> {
+    let mode = BlockRazorMode::new(&config.blockrazor_mode, config.blockrazor_safe_window, config.blockrazor_revert_protection)?;
+    println!("[BLOCKRAZOR] Submission mode: {} (safe window: {:?}, revert protection: {})", mode.mode, mode.safe_window, mode.revert_protection);
+    let _ = BLOCKRAZOR_MODE.set(mode);
+    Ok(())
+}
+
+/// The configured BlockRazor mode ("fast", no revert protection, before init)
+pub fn blockrazor_mode() -> &'static BlockRazorMode {
+    BLOCKRAZOR_MODE.get_or_init(|| BlockRazorMode { mode: "fast".to_string(), safe_window: None, revert_protection: false })
+}
+
+/// SendRequest for the base64 transaction `transaction` under `mode`
+pub fn build_send_request(transaction: String, mode: &BlockRazorMode) -> blockrazor_proto::SendRequest {
+    blockrazor_proto::SendRequest {
+        transaction,
+        mode: mode.mode.clone(),
+        safe_window: mode.safe_window,
+        revert_protection: mode.revert_protection,
+    }
+}
+
 /// Send a signed Solana transaction via BlockRazor gRPC with detailed profiling
 pub async fn send_tx_blockrazor(
     tx: &Transaction,
     token: &str,
-    mode: &str,
-    safe_window: Option<i32>,
-    revert_protection: bool,
+    mode: &BlockRazorMode,
 ) -> Result<String, Box<dyn std::error::Error>> {
-    let total_start = Instant::now();
+    crate::send_tx::rate_guard::throttle_vendor_send("blockrazor").await?;
+    let total_start = StepTimer::start();
+    #[cfg(feature = "verbose_logging")]
     let now = Utc::now();
     
     #[cfg(feature = "verbose_logging")]
@@ -138,7 +191,7 @@ pub async fn send_tx_blockrazor(
         now.format("%Y-%m-%d %H:%M:%S%.3f"));
     
     // Step 1: Get client (measure client acquisition time)
-    let client_start = Instant::now();
+    let client_start = StepTimer::start();
     let client = get_blockrazor_client();
     let mut client = Arc::try_unwrap(client).unwrap_or_else(|arc| (*arc).clone());
     let client_time = client_start.elapsed();
@@ -148,8 +201,9 @@ pub async fn send_tx_blockrazor(
         Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), client_time);
     
     // Step 2: Serialize transaction (measure serialization time)
-    let serialize_start = Instant::now();
-    let tx_bytes = bincode::serialize(tx)?;
+    let serialize_start = StepTimer::start();
+    let mut tx_bytes = take_buffer();
+    bincode::serialize_into(&mut tx_bytes, tx)?;
     let serialize_time = serialize_start.elapsed();
     
     #[cfg(feature = "verbose_logging")]
@@ -157,8 +211,9 @@ pub async fn send_tx_blockrazor(
         Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), serialize_time, tx_bytes.len());
     
     // Step 3: Base64 encoding (measure encoding time)
-    let encode_start = Instant::now();
+    let encode_start = StepTimer::start();
     let tx_b64 = general_purpose::STANDARD.encode(&tx_bytes);
+    recycle_buffer(tx_bytes);
     let encode_time = encode_start.elapsed();
     
     #[cfg(feature = "verbose_logging")]
@@ -166,13 +221,8 @@ pub async fn send_tx_blockrazor(
         Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), encode_time, tx_b64.len());
     
     // Step 4: Build request (measure request building time)
-    let request_start = Instant::now();
-    let request = blockrazor_proto::SendRequest {
-        transaction: tx_b64,
-        mode: mode.to_string(),
-        safe_window,
-        revert_protection,
-    };
+    let request_start = StepTimer::start();
+    let request = build_send_request(tx_b64, mode);
     let request_time = request_start.elapsed();
     
     #[cfg(feature = "verbose_logging")]
@@ -180,7 +230,7 @@ pub async fn send_tx_blockrazor(
         Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), request_time);
     
     // Step 5: Create gRPC request with metadata (measure request creation time)
-    let grpc_request_start = Instant::now();
+    let grpc_request_start = StepTimer::start();
     let mut req = tonic::Request::new(request);
     req.metadata_mut()
         .insert("apikey", MetadataValue::from_str(token)?);
@@ -200,7 +250,7 @@ pub async fn send_tx_blockrazor(
         Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), network_time);
     
     // Step 7: Extract response (measure response processing time)
-    let response_start = Instant::now();
+    let response_start = StepTimer::start();
     let signature = response.into_inner().signature;
     let response_time = response_start.elapsed();
     
@@ -210,7 +260,7 @@ pub async fn send_tx_blockrazor(
     
     // Calculate total time and breakdown
     let total_time = total_start.elapsed();
-    let processing_time = total_time - network_time; // Time spent in our code
+    let processing_time = total_time.saturating_sub(network_time); // Time spent in our code (total is zero unless profiling, network time is not)
     
     #[cfg(feature = "verbose_logging")]
     {
@@ -292,7 +342,8 @@ pub fn create_instruction_blockrazor(
     cu_price: u64,
     nonce_account: &Pubkey,
 ) -> Vec<Instruction> {
-    let total_start = Instant::now();
+    let total_start = StepTimer::start();
+    #[cfg(feature = "verbose_logging")]
     let now = Utc::now();
     
     #[cfg(feature = "verbose_logging")]
@@ -300,10 +351,8 @@ pub fn create_instruction_blockrazor(
         now.format("%Y-%m-%d %H:%M:%S%.3f"));
     
     // Step 1: Random number generation (measure RNG time)
-    let rng_start = Instant::now();
-    let mut rng = rand::thread_rng();
-    let random_addition: u64 = rng.gen_range(1..=100);
-    let adjusted_cu_price = cu_price + random_addition;
+    let rng_start = StepTimer::start();
+    let adjusted_cu_price = crate::send_tx::cu_price::adjust_cu_price(cu_price, &instructions);
     let rng_time = rng_start.elapsed();
     
     #[cfg(feature = "verbose_logging")]
@@ -311,7 +360,7 @@ pub fn create_instruction_blockrazor(
         Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), rng_time, cu_price, adjusted_cu_price);
     
     // Step 2: Get wallet keypair (measure keypair access time)
-    let keypair_start = Instant::now();
+    let keypair_start = StepTimer::start();
     let keypair: &'static Keypair = get_wallet_keypair();
     let keypair_time = keypair_start.elapsed();
     
@@ -320,7 +369,7 @@ pub fn create_instruction_blockrazor(
         Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), keypair_time);
     
     // Step 3: Create compute budget instruction (measure instruction creation time)
-    let compute_start = Instant::now();
+    let compute_start = StepTimer::start();
     let price_ix = compute_budget::ComputeBudgetInstruction::set_compute_unit_price(adjusted_cu_price);
     let compute_time = compute_start.elapsed();
     
@@ -328,27 +377,17 @@ pub fn create_instruction_blockrazor(
     println!("[{}] - [BLOCKRAZOR_INSTRUCTION_PROFILE] 💰 Compute budget instruction: {:.2?}", 
         Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), compute_time);
     
-    // Step 4: Select tip account (measure tip account selection time)
-    let tip_select_start = Instant::now();
-    let random_index = rng.gen_range(0..BLOCKRAZOR_TIP_ACCOUNTS.len());
-    let tip_account = BLOCKRAZOR_TIP_ACCOUNTS[random_index];
-    let tip_select_time = tip_select_start.elapsed();
-    
-    #[cfg(feature = "verbose_logging")]
-    println!("[{}] - [BLOCKRAZOR_INSTRUCTION_PROFILE] 🎯 Tip account selection: {:.2?} (account: {})", 
-        Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), tip_select_time, tip_account);
-    
-    // Step 5: Create tip instruction (measure tip instruction creation time)
-    let tip_ix_start = Instant::now();
-    let tip_ix = blockrazor_tip(tip_account, tip, &keypair.pubkey());
+    // Step 4: Create tip instruction (tip account selection included) (measure tip instruction creation time)
+    let tip_ix_start = StepTimer::start();
+    let tip_ix = crate::send_tx::tip_accounts::tip_instruction("blockrazor", tip, &keypair.pubkey());
     let tip_ix_time = tip_ix_start.elapsed();
     
     #[cfg(feature = "verbose_logging")]
     println!("[{}] - [BLOCKRAZOR_INSTRUCTION_PROFILE] 💸 Tip instruction creation: {:.2?} (tip: {} lamports)", 
         Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), tip_ix_time, tip);
     
-    // Step 6: Create nonce instruction (measure nonce instruction creation time)
-    let nonce_start = Instant::now();
+    // Step 5: Create nonce instruction (measure nonce instruction creation time)
+    let nonce_start = StepTimer::start();
     let advance_nonce_ix = system_instruction::advance_nonce_account(
         nonce_account,
         &keypair.pubkey(),
@@ -359,8 +398,8 @@ pub fn create_instruction_blockrazor(
     println!("[{}] - [BLOCKRAZOR_INSTRUCTION_PROFILE] 🔄 Nonce instruction creation: {:.2?} (nonce: {})", 
         Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), nonce_time, nonce_account);
     
-    // Step 7: Combine all instructions (measure instruction combination time)
-    let combine_start = Instant::now();
+    // Step 6: Combine all instructions (measure instruction combination time)
+    let combine_start = StepTimer::start();
     let mut result = vec![advance_nonce_ix, tip_ix, price_ix];
     result.extend(instructions);
     let combine_time = combine_start.elapsed();
@@ -385,9 +424,6 @@ pub fn create_instruction_blockrazor(
         println!("[{}] - [BLOCKRAZOR_INSTRUCTION_PROFILE]   • Compute budget instruction: {:.2?} ({:.1}%)", 
             Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), compute_time,
             (compute_time.as_micros() as f64 / total_time.as_micros() as f64) * 100.0);
-        println!("[{}] - [BLOCKRAZOR_INSTRUCTION_PROFILE]   • Tip account selection: {:.2?} ({:.1}%)", 
-            Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), tip_select_time,
-            (tip_select_time.as_micros() as f64 / total_time.as_micros() as f64) * 100.0);
         println!("[{}] - [BLOCKRAZOR_INSTRUCTION_PROFILE]   • Tip instruction creation: {:.2?} ({:.1}%)", 
             Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), tip_ix_time,
             (tip_ix_time.as_micros() as f64 / total_time.as_micros() as f64) * 100.0);
@@ -405,7 +441,6 @@ pub fn create_instruction_blockrazor(
             ("RNG generation", rng_time),
             ("Keypair access", keypair_time),
             ("Compute budget instruction", compute_time),
-            ("Tip account selection", tip_select_time),
             ("Tip instruction creation", tip_ix_time),
             ("Nonce instruction creation", nonce_time),
             ("Instruction combination", combine_time),
@@ -424,7 +459,8 @@ pub fn create_instruction_blockrazor(
 
 /// Create a system transfer instruction for BlockRazor tips with profiling
 pub fn blockrazor_tip(tip_ac: &str, tip: u64, from_pubkey: &Pubkey) -> Instruction {
-    let total_start = Instant::now();
+    let total_start = StepTimer::start();
+    #[cfg(feature = "verbose_logging")]
     let now = Utc::now();
     
     #[cfg(feature = "verbose_logging")]
@@ -432,7 +468,7 @@ pub fn blockrazor_tip(tip_ac: &str, tip: u64, from_pubkey: &Pubkey) -> Instructi
         now.format("%Y-%m-%d %H:%M:%S%.3f"));
     
     // Step 1: Parse tip account pubkey (measure parsing time)
-    let parse_start = Instant::now();
+    let parse_start = StepTimer::start();
     let tip_pubkey = Pubkey::from_str(tip_ac).expect("Invalid pubkey");
     let parse_time = parse_start.elapsed();
     
@@ -441,7 +477,7 @@ pub fn blockrazor_tip(tip_ac: &str, tip: u64, from_pubkey: &Pubkey) -> Instructi
         Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), parse_time, tip_ac);
     
     // Step 2: Create system transfer instruction (measure instruction creation time)
-    let transfer_start = Instant::now();
+    let transfer_start = StepTimer::start();
     let instruction = system_instruction::transfer(from_pubkey, &tip_pubkey, tip);
     let transfer_time = transfer_start.elapsed();
     
@@ -540,4 +576,25 @@ pub fn init_blockrazor_performance_monitoring() {
     let _ = BLOCKRAZOR_LATENCY_HISTORY.set(Mutex::new(VecDeque::new()));
     println!("[{}] - [BLOCKRAZOR] Performance monitoring initialized", 
         Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"));
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_configured_mode_reaches_request() {
+        let mode = BlockRazorMode::new("sandwichMitigation", 5, true).unwrap();
+        let request = build_send_request("dHg=".to_string(), &mode);
+        assert_eq!(request.mode, "sandwichMitigation");
+        assert_eq!(request.safe_window, Some(5));
+        assert!(request.revert_protection);
+
+        let fast = build_send_request("dHg=".to_string(), &BlockRazorMode::new("fast", 0, false).unwrap());
+        assert_eq!((fast.mode.as_str(), fast.safe_window, fast.revert_protection), ("fast", None, false));
+
+        assert!(BlockRazorMode::new("turbo", 0, false).is_err());
+        assert!(BlockRazorMode::new("fast", 3, false).is_err());
+        assert!(BlockRazorMode::new("sandwichMitigation", -1, false).is_err());
+    }
+}
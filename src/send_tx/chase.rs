@@ -0,0 +1,90 @@
+use crate::config_load::Config;
+use crate::send_tx::confirm_tracker::{watch, Resolution};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::signature::Signature;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+// A buy not seen landed this many slots after its send gets chased
+pub const CHASE_DEADLINE_SLOTS: u64 = 2;
+// Guards against the confirmation tracker not running
+const CHASE_WAIT_TIMEOUT: Duration = Duration::from_secs(3);
+
+static CHASE_RESENDS: AtomicUsize = AtomicUsize::new(0);
+
+/// Buys resent at an escalated bid
+pub fn get_chase_resends() -> usize {
+    CHASE_RESENDS.load(Ordering::Relaxed)
+}
+
+pub fn record_chase_resend() {
+    CHASE_RESENDS.fetch_add(1, Ordering::Relaxed);
+}
+
+/// `lamports` (a tip or CU price) scaled by `multiplier`
+pub fn escalate_bid(lamports: u64, multiplier: f64) -> u64 {
+    (lamports as f64 * multiplier.max(0.0)).round() as u64
+}
+
+/// Resend policy for buys that don't land quickly
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChasePolicy {
+    pub tip_multiplier: f64,
+    pub max_attempts: u32,
+}
+
+impl ChasePolicy {
+    /// None when `chase_enabled` is off
+    pub fn from_config(config: &Config) -> Option<Self> {
+        config.chase_enabled.then(|| ChasePolicy {
+            tip_multiplier: config.chase_tip_multiplier,
+            max_attempts: config.chase_max_attempts,
+        })
+    }
+
+    /// Bid multiplier for resend number `attempt` (1-based), relative to the original send;
+    /// None once the attempts are used up or the multiplier wouldn't raise the bid
+    pub fn bid_multiplier(&self, attempt: u32) -> Option<f64> {
+        if attempt == 0 || attempt > self.max_attempts || self.tip_multiplier <= 1.0 {
+            return None;
+        }
+        Some(self.tip_multiplier.powi(attempt as i32))
+    }
+}
+
+/// Whether the buy sent as `sent` (every vendor version of one attempt) landed. Waits on the
+/// confirmation tracker for the winning signature `winner` up to CHASE_DEADLINE_SLOTS, then asks RPC
+/// about all of them directly: any vendor version can land, and a late landing must not be chased
+/// into a double fill. A landed-but-failed buy also counts, so a program error isn't resent.
+pub fn attempt_landed(rpc: &RpcClient, winner: Signature, sent: &[Signature]) -> bool {
+    if let Ok(Resolution::Landed { .. }) = watch(winner, CHASE_DEADLINE_SLOTS).recv_timeout(CHASE_WAIT_TIMEOUT) {
+        return true;
+    }
+    match rpc.get_signature_statuses(sent) {
+        Ok(response) => response.value.iter().any(Option::is_some),
+        // Unknown: assume it may have landed rather than risk buying twice
+        Err(_) => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chase_escalation() {
+        let policy = ChasePolicy { tip_multiplier: 2.0, max_attempts: 2 };
+        assert_eq!(policy.bid_multiplier(0), None);
+        assert_eq!(policy.bid_multiplier(1), Some(2.0));
+        assert_eq!(policy.bid_multiplier(2), Some(4.0));
+        assert_eq!(policy.bid_multiplier(3), None);
+
+        // A 0.001 SOL tip chased twice
+        let tip = 1_000_000;
+        let tips: Vec<u64> = (1..=3).filter_map(|n| policy.bid_multiplier(n)).map(|m| escalate_bid(tip, m)).collect();
+        assert_eq!(tips, vec![2_000_000, 4_000_000]);
+
+        // A multiplier that doesn't raise the bid never resends
+        assert_eq!(ChasePolicy { tip_multiplier: 1.0, max_attempts: 3 }.bid_multiplier(1), None);
+    }
+}
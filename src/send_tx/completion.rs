@@ -0,0 +1,251 @@
+use crate::grpc::arpc_worker::{TxWithPubkey, GLOBAL_TX_MAP};
+use crate::send_tx::confirm_tracker::{watch_at, Commitment, Resolution};
+use crate::utils::logger::{log_event_cid, EventType};
+use crate::utils::otel;
+use chrono::Utc;
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use solana_sdk::signature::Signature;
+use std::time::{Duration, Instant};
+
+// Slots a sell gets to reach the completion commitment (finalized takes ~32 after processed)
+pub const COMPLETION_DEADLINE_SLOTS: u64 = 150;
+// Guards against the confirmation tracker not running
+const COMPLETION_TIMEOUT: Duration = Duration::from_secs(120);
+
+// Keys of positions whose landed buy was rolled back; the sell that landing scheduled is skipped
+static ROLLED_BACK_BUYS: Lazy<DashMap<Vec<u8>, Instant>> = Lazy::new(DashMap::new);
+
+/// What to do with a position once its sell resolved at the completion commitment
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CompletionAction {
+    Complete, // sell reached the commitment: close the position and write its final record
+    Reopen,   // sell seen at processed, then rolled back: the tokens are still ours, sell again
+    Abandon,  // sell failed or never showed up: drop the position as before
+}
+
+impl CompletionAction {
+    pub fn from_resolution(resolution: Option<&Resolution>) -> Self {
+        match resolution {
+            Some(Resolution::Landed { err: None, .. }) => CompletionAction::Complete,
+            Some(Resolution::RolledBack) => CompletionAction::Reopen,
+            _ => CompletionAction::Abandon,
+        }
+    }
+}
+
+/// Apply `action` to the position stored under `key` whose sell was for `sold` tokens. A completed
+/// partial sell (copy_sell_fraction) leaves the position open with the rest; otherwise it is removed
+/// unless reopened, in which case it is marked open again and handed to `reopen` (which schedules a
+/// new sell). Returns the tokens still held.
+pub fn apply_completion(action: CompletionAction, key: Vec<u8>, sold: u64, reopen: impl FnOnce(TxWithPubkey, Vec<u8>)) -> u64 {
+    if action == CompletionAction::Complete {
+        let remaining = GLOBAL_TX_MAP.get_mut(&key).map_or(0, |mut entry| {
+            entry.token_amount = entry.token_amount.saturating_sub(sold);
+            entry.token_amount
+        });
+        if remaining == 0 {
+            GLOBAL_TX_MAP.remove(&key);
+        }
+        return remaining;
+    }
+    if action == CompletionAction::Abandon {
+        GLOBAL_TX_MAP.remove(&key);
+        return 0;
+    }
+    let entry = GLOBAL_TX_MAP.get_mut(&key).map(|mut entry| {
+        entry.open_position = true;
+        entry.value().clone()
+    });
+    match entry {
+        Some(entry) => {
+            let held = entry.token_amount;
+            reopen(entry, key);
+            held
+        }
+        None => 0,
+    }
+}
+
+/// Keep the position under `key` until its sell `sig` of `sold` tokens reaches `commitment`, then close
+/// it (or, after a partial sell, keep the rest open) and write the trade's final record; a sell rolled
+/// back before that reopens the position through `reopen`
+pub fn complete_on_commitment(
+    sig: &str,
+    key: Vec<u8>,
+    sold: u64,
+    corr_id: String,
+    commitment: Commitment,
+    reopen: impl Fn(TxWithPubkey, Vec<u8>) + Send + 'static,
+) {
+    complete_batch_on_commitment(sig, vec![(key, sold, corr_id)], commitment, reopen);
+}
+
+/// `complete_on_commitment` for several positions sold by one transaction `sig`: one watch, its
+/// resolution applied to every `(key, sold, corr_id)`
+pub fn complete_batch_on_commitment(
+    sig: &str,
+    positions: Vec<(Vec<u8>, u64, String)>,
+    commitment: Commitment,
+    reopen: impl Fn(TxWithPubkey, Vec<u8>) + Send + 'static,
+) {
+    let signature = match sig.trim_matches('"').parse::<Signature>() {
+        Ok(signature) => signature,
+        Err(_) => {
+            for (key, _, _) in positions {
+                GLOBAL_TX_MAP.remove(&key);
+            }
+            return;
+        }
+    };
+    let sent_at = Instant::now();
+    let resolution = watch_at(signature, COMPLETION_DEADLINE_SLOTS, commitment);
+    std::thread::spawn(move || {
+        let resolution = resolution.recv_timeout(COMPLETION_TIMEOUT).ok();
+        let action = CompletionAction::from_resolution(resolution.as_ref());
+        for (key, sold, corr_id) in positions {
+            let remaining = apply_completion(action, key, sold, &reopen);
+            match action {
+                CompletionAction::Complete if remaining > 0 => println!("[{}] - [COMPLETION] [cid={}] Partial sell {} of {} tokens reached {:?}, {} tokens stay open",
+                    Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), corr_id, signature, sold, commitment, remaining),
+                CompletionAction::Complete => {
+                    log_event_cid(EventType::Custom("TradeComplete".to_string()), signature.as_ref(), sent_at, None, &corr_id);
+                    otel::end_trade(&corr_id, "closed", Instant::now());
+                }
+                CompletionAction::Reopen => println!("[{}] - [COMPLETION] [cid={}] Sell {} rolled back before {:?}, reopening the position",
+                    Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), corr_id, signature, commitment),
+                CompletionAction::Abandon => {
+                    otel::end_trade(&corr_id, "sell_unconfirmed", Instant::now());
+                    println!("[{}] - [COMPLETION] [cid={}] Sell {} did not reach {:?} ({:?}), dropping the position",
+                        Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), corr_id, signature, commitment, resolution);
+                }
+            }
+        }
+    });
+}
+
+/// The buy stored under `key` was rolled back after it was seen landing: keep the position open and
+/// waiting for the buy to land again, and mark the sell its first landing scheduled to be skipped
+pub fn reopen_buy(key: &[u8]) -> bool {
+    let reopened = GLOBAL_TX_MAP.get_mut(key).map(|mut entry| entry.open_position = true).is_some();
+    if reopened {
+        ROLLED_BACK_BUYS.retain(|_, at| at.elapsed() < COMPLETION_TIMEOUT);
+        ROLLED_BACK_BUYS.insert(key.to_vec(), Instant::now());
+    }
+    reopened
+}
+
+/// Whether the sell about to be built for `key` was scheduled by a buy landing that rolled back
+/// (consumes the mark, so the sell scheduled by the buy landing again goes through)
+pub fn take_rolled_back_buy(key: &[u8]) -> bool {
+    ROLLED_BACK_BUYS.remove(key).is_some()
+}
+
+/// Watch buy `sig` (stored under `key`) until it reaches `commitment`; seen at processed and then
+/// rolled back, the position is reopened to wait for the buy to land again
+pub fn confirm_buy_on_commitment(sig: &str, key: Vec<u8>, corr_id: String, commitment: Commitment) {
+    let signature = match sig.trim_matches('"').parse::<Signature>() {
+        Ok(signature) => signature,
+        Err(_) => return,
+    };
+    let resolution = watch_at(signature, COMPLETION_DEADLINE_SLOTS, commitment);
+    std::thread::spawn(move || {
+        if let Ok(Resolution::RolledBack) = resolution.recv_timeout(COMPLETION_TIMEOUT) {
+            if reopen_buy(&key) {
+                println!("[{}] - [COMPLETION] [cid={}] Buy {} rolled back before {:?}, reopening the position",
+                    Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), corr_id, signature, commitment);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::pubkey::Pubkey;
+    use solana_sdk::transaction::TransactionError;
+
+    #[test]
+    fn test_rolled_back_sell_reopens_position() {
+        assert_eq!(CompletionAction::from_resolution(Some(&Resolution::Landed { slot: 9, err: None })), CompletionAction::Complete);
+        assert_eq!(CompletionAction::from_resolution(Some(&Resolution::RolledBack)), CompletionAction::Reopen);
+        assert_eq!(CompletionAction::from_resolution(Some(&Resolution::Landed { slot: 9, err: Some(TransactionError::AccountNotFound) })),
+            CompletionAction::Abandon);
+        assert_eq!(CompletionAction::from_resolution(None), CompletionAction::Abandon);
+
+        let key = Pubkey::new_unique().to_bytes().to_vec();
+        let mut tx = TxWithPubkey::default();
+        tx.mint = Pubkey::new_unique();
+        tx.token_amount = 1_000;
+        GLOBAL_TX_MAP.insert(key.clone(), tx.clone());
+
+        // Rolled back: still in the map, open, and sold again
+        let mut resold = None;
+        apply_completion(CompletionAction::Reopen, key.clone(), 1_000, |entry, k| resold = Some((entry.mint, k)));
+        assert_eq!(resold, Some((tx.mint, key.clone())));
+        assert!(GLOBAL_TX_MAP.get(&key).map_or(false, |e| e.open_position));
+
+        // The resend reaches the commitment: closed
+        assert_eq!(apply_completion(CompletionAction::Complete, key.clone(), 1_000, |_, _| panic!("completed position reopened")), 0);
+        assert!(GLOBAL_TX_MAP.get(&key).is_none());
+    }
+
+    #[test]
+    fn test_partial_sell_keeps_the_rest_open() {
+        let key = Pubkey::new_unique().to_bytes().to_vec();
+        let mut tx = TxWithPubkey::default();
+        tx.token_amount = 1_000;
+        tx.open_position = true;
+        GLOBAL_TX_MAP.insert(key.clone(), tx);
+
+        // Copied a 40% sell: 600 stay open
+        assert_eq!(apply_completion(CompletionAction::Complete, key.clone(), 400, |_, _| {}), 600);
+        assert!(GLOBAL_TX_MAP.get(&key).map_or(false, |e| e.open_position && e.token_amount == 600));
+
+        // Selling the rest closes it
+        assert_eq!(apply_completion(CompletionAction::Complete, key.clone(), 600, |_, _| {}), 0);
+        assert!(GLOBAL_TX_MAP.get(&key).is_none());
+    }
+
+    #[test]
+    fn test_rolled_back_buy_reopens_position() {
+        let key = Pubkey::new_unique().to_bytes().to_vec();
+        let tx = TxWithPubkey::default();
+        GLOBAL_TX_MAP.insert(key.clone(), tx);
+
+        // The buy seen at processed rolls back: the position stays, open, and the sell it scheduled is skipped once
+        assert!(reopen_buy(&key));
+        assert!(GLOBAL_TX_MAP.get(&key).map_or(false, |e| e.open_position));
+        assert!(take_rolled_back_buy(&key));
+        // The buy landing again schedules a sell that goes through
+        assert!(!take_rolled_back_buy(&key));
+
+        // Nothing to reopen once the position is gone
+        GLOBAL_TX_MAP.remove(&key);
+        assert!(!reopen_buy(&key));
+        assert!(!take_rolled_back_buy(&key));
+    }
+
+    #[test]
+    fn test_batched_sell_completes_every_position() {
+        let keys: Vec<Vec<u8>> = (0..3).map(|_| Pubkey::new_unique().to_bytes().to_vec()).collect();
+        for key in &keys {
+            let mut tx = TxWithPubkey::default();
+            tx.token_amount = 500;
+            GLOBAL_TX_MAP.insert(key.clone(), tx);
+        }
+        let sig = Signature::new_unique();
+        let positions = keys.iter().map(|key| (key.clone(), 500, "cafe0002".to_string())).collect();
+        complete_batch_on_commitment(&sig.to_string(), positions, Commitment::Processed, |_, _| {});
+
+        // One landing closes all three positions
+        for _ in 0..200 {
+            crate::send_tx::confirm_tracker::poll_with_statuses(|s| (*s == sig).then(|| (9, None)));
+            if keys.iter().all(|key| GLOBAL_TX_MAP.get(key).is_none()) {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        panic!("batched positions were not completed");
+    }
+}
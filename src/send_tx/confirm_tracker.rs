@@ -0,0 +1,283 @@
+use crate::grpc::arpc_worker::get_current_slot;
+use once_cell::sync::{Lazy, OnceCell};
+use solana_client::rpc_client::RpcClient;
+use solana_transaction_status::TransactionConfirmationStatus;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::TransactionError;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// getSignatureStatuses takes at most this many signatures per call
+pub const MAX_STATUS_BATCH: usize = 256;
+// Used to turn a slot deadline into a time deadline while no slot has been seen yet
+const SLOT_DURATION: Duration = Duration::from_millis(400);
+// Once seen below its commitment, a signature gets this many more slots to reach it (finality is ~32)
+const COMMITMENT_GRACE_SLOTS: u64 = 64;
+
+/// Commitment a watched signature must reach to count as landed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Commitment {
+    Processed,
+    Confirmed,
+    Finalized,
+}
+
+impl Commitment {
+    /// `completion_commitment` value; anything unrecognised is processed
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "confirmed" => Commitment::Confirmed,
+            "finalized" => Commitment::Finalized,
+            _ => Commitment::Processed,
+        }
+    }
+}
+
+/// How a watched signature resolved
+#[derive(Debug, Clone, PartialEq)]
+pub enum Resolution {
+    Landed { slot: u64, err: Option<TransactionError> },
+    Dropped,    // not seen by its deadline slot
+    RolledBack, // seen below its commitment, then gone (its fork was abandoned)
+}
+
+// Per-signature status from one batch call: (slot, err, commitment reached) once the signature is known
+pub type Status = Option<(u64, Option<TransactionError>, Commitment)>;
+
+struct Pending {
+    deadline_slot: u64,
+    deadline: Instant,
+    commitment: Commitment,
+    seen: bool, // reported below `commitment` by an earlier poll
+    notify: Sender<Resolution>,
+}
+
+/// Watched signatures, each with every watcher waiting on it (a sell can be watched for slippage
+/// failure and completion at once, at different commitments)
+#[derive(Default)]
+struct Tracker {
+    pending: Mutex<HashMap<Signature, Vec<Pending>>>,
+    status_calls: AtomicUsize,
+    resolved: AtomicUsize,
+}
+
+static TRACKER: Lazy<Tracker> = Lazy::new(Tracker::default);
+static STARTED: OnceCell<()> = OnceCell::new();
+
+/// (getSignatureStatuses calls, watches resolved, signatures pending)
+pub fn get_confirm_stats() -> (usize, usize, usize) {
+    let pending = TRACKER.pending.lock().unwrap_or_else(|e| e.into_inner()).len();
+    (TRACKER.status_calls.load(Ordering::Relaxed), TRACKER.resolved.load(Ordering::Relaxed), pending)
+}
+
+/// Watch `sig` until it lands or `deadline_slots` slots pass; the receiver gets exactly one resolution
+pub fn watch(sig: Signature, deadline_slots: u64) -> Receiver<Resolution> {
+    watch_at(sig, deadline_slots, Commitment::Processed)
+}
+
+/// `watch`, counting `sig` as landed only once it reaches `commitment`. Seen below it, it gets
+/// COMMITMENT_GRACE_SLOTS more; if it then disappears it resolves as RolledBack.
+pub fn watch_at(sig: Signature, deadline_slots: u64, commitment: Commitment) -> Receiver<Resolution> {
+    TRACKER.watch_at(sig, get_current_slot(), deadline_slots, commitment)
+}
+
+/// Resolution of a pending watch given its signature's latest `status`, if it resolves now. A status
+/// below the wanted commitment marks it seen and extends its deadline; a seen one gone again rolled back.
+fn resolve_status(entry: &mut Pending, status: &Status, current_slot: u64, now: Instant) -> Option<Resolution> {
+    match status {
+        Some((slot, err, reached)) if *reached >= entry.commitment => Some(Resolution::Landed { slot: *slot, err: err.clone() }),
+        Some(_) => {
+            if !entry.seen {
+                entry.seen = true;
+                entry.deadline_slot = current_slot.max(entry.deadline_slot) + COMMITMENT_GRACE_SLOTS;
+                entry.deadline = entry.deadline.max(now) + SLOT_DURATION * COMMITMENT_GRACE_SLOTS as u32;
+            }
+            None
+        }
+        None if entry.seen => Some(Resolution::RolledBack),
+        None => None,
+    }
+}
+
+impl Tracker {
+    fn watch_at(&self, sig: Signature, current_slot: u64, deadline_slots: u64, commitment: Commitment) -> Receiver<Resolution> {
+        let (notify, resolution) = channel();
+        let pending = Pending {
+            deadline_slot: current_slot + deadline_slots,
+            deadline: Instant::now() + SLOT_DURATION * deadline_slots as u32,
+            commitment,
+            seen: false,
+            notify,
+        };
+        self.pending.lock().unwrap_or_else(|e| e.into_inner()).entry(sig).or_default().push(pending);
+        resolution
+    }
+
+    fn is_idle(&self) -> bool {
+        self.pending.lock().unwrap_or_else(|e| e.into_inner()).is_empty()
+    }
+
+    /// One polling pass: look the pending signatures up `batch_size` at a time through `fetch`
+    /// (one getSignatureStatuses call per batch), then resolve the watches that landed and those past
+    /// their deadline. A failed fetch leaves its batch pending.
+    fn poll_once<F>(&self, batch_size: usize, current_slot: u64, now: Instant, mut fetch: F)
+    where
+        F: FnMut(&[Signature]) -> Option<Vec<Status>>,
+    {
+        let sigs: Vec<Signature> = self.pending.lock().unwrap_or_else(|e| e.into_inner()).keys().copied().collect();
+        let mut statuses_seen = Vec::new();
+        for batch in sigs.chunks(batch_size.clamp(1, MAX_STATUS_BATCH)) {
+            self.status_calls.fetch_add(1, Ordering::Relaxed);
+            let statuses = match fetch(batch) {
+                Some(statuses) => statuses,
+                None => continue,
+            };
+            statuses_seen.extend(batch.iter().copied().zip(statuses));
+        }
+
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        for (sig, status) in statuses_seen {
+            if let Some(watchers) = pending.get_mut(&sig) {
+                watchers.retain_mut(|entry| match resolve_status(entry, &status, current_slot, now) {
+                    Some(resolution) => {
+                        self.resolved.fetch_add(1, Ordering::Relaxed);
+                        let _ = entry.notify.send(resolution);
+                        false
+                    }
+                    None => true,
+                });
+            }
+        }
+        for watchers in pending.values_mut() {
+            watchers.retain(|entry| {
+                let expired = if current_slot > 0 { current_slot > entry.deadline_slot } else { now > entry.deadline };
+                if expired {
+                    self.resolved.fetch_add(1, Ordering::Relaxed);
+                    let _ = entry.notify.send(Resolution::Dropped);
+                }
+                !expired
+            });
+        }
+        pending.retain(|_, watchers| !watchers.is_empty());
+    }
+}
+
+/// One polling pass with `status` standing in for getSignatureStatuses (processed commitment)
+#[cfg(test)]
+pub(crate) fn poll_with_statuses<F>(status: F)
+where
+    F: Fn(&Signature) -> Option<(u64, Option<TransactionError>)>,
+{
+    TRACKER.poll_once(MAX_STATUS_BATCH, 0, Instant::now(), |batch| {
+        Some(batch.iter().map(|sig| status(sig).map(|(slot, err)| (slot, err, Commitment::Processed))).collect())
+    });
+}
+
+/// Poll every watched signature every `interval`, batching `batch_size` (max 256) per RPC call
+pub fn start_confirm_tracker(rpc: &'static RpcClient, batch_size: usize, interval: Duration) {
+    if STARTED.set(()).is_err() {
+        return;
+    }
+    std::thread::spawn(move || loop {
+        std::thread::sleep(interval);
+        if TRACKER.is_idle() {
+            continue;
+        }
+        TRACKER.poll_once(batch_size, get_current_slot(), Instant::now(), |batch| {
+            let response = rpc.get_signature_statuses(batch).ok()?;
+            Some(response.value.into_iter().map(|status| status.map(|s| {
+                // No confirmation status: an old node, or rooted
+                let reached = match s.confirmation_status {
+                    Some(TransactionConfirmationStatus::Processed) => Commitment::Processed,
+                    Some(TransactionConfirmationStatus::Confirmed) => Commitment::Confirmed,
+                    Some(TransactionConfirmationStatus::Finalized) | None => Commitment::Finalized,
+                };
+                (s.slot, s.err, reached)
+            })).collect())
+        });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::instruction::InstructionError;
+
+    #[test]
+    fn test_batches_resolve_landed_and_keep_pending() {
+        // 300 watched signatures: every 3rd landed, every 3rd+1 landed with an error, the rest unseen
+        let sigs: Vec<Signature> = (0..300).map(|_| Signature::new_unique()).collect();
+        let failure = TransactionError::InstructionError(2, InstructionError::Custom(6004));
+        let tracker = Tracker::default();
+        let receivers: Vec<_> = sigs.iter().map(|sig| tracker.watch_at(*sig, 0, 10, Commitment::Processed)).collect();
+        let status_of = |sig: &Signature| -> Status {
+            match sigs.iter().position(|s| s == sig).unwrap() % 3 {
+                0 => Some((7, None, Commitment::Processed)),
+                1 => Some((8, Some(failure.clone()), Commitment::Confirmed)),
+                _ => None,
+            }
+        };
+
+        let mut batch_sizes = Vec::new();
+        tracker.poll_once(MAX_STATUS_BATCH, 0, Instant::now(), |batch| {
+            batch_sizes.push(batch.len());
+            Some(batch.iter().map(status_of).collect())
+        });
+        batch_sizes.sort();
+        assert_eq!(batch_sizes, vec![300 - MAX_STATUS_BATCH, MAX_STATUS_BATCH]);
+
+        for (i, resolution) in receivers.iter().enumerate() {
+            match i % 3 {
+                0 => assert_eq!(resolution.try_recv(), Ok(Resolution::Landed { slot: 7, err: None })),
+                1 => assert_eq!(resolution.try_recv(), Ok(Resolution::Landed { slot: 8, err: Some(failure.clone()) })),
+                _ => assert!(resolution.try_recv().is_err()),
+            }
+        }
+
+        // The unseen ones stay pending until the deadline slot passes, then drop
+        let still_pending: Vec<_> = receivers.iter().enumerate().filter(|(i, _)| i % 3 == 2).map(|(_, r)| r).collect();
+        tracker.poll_once(MAX_STATUS_BATCH, 11, Instant::now(), |batch| Some(vec![None; batch.len()]));
+        for resolution in still_pending {
+            assert_eq!(resolution.try_recv(), Ok(Resolution::Dropped));
+        }
+        assert!(tracker.is_idle());
+    }
+
+    #[test]
+    fn test_every_watcher_of_a_signature_is_notified() {
+        // A sell watched for a slippage failure (processed) and for completion (confirmed)
+        let tracker = Tracker::default();
+        let sig = Signature::new_unique();
+        let processed = tracker.watch_at(sig, 100, 25, Commitment::Processed);
+        let confirmed = tracker.watch_at(sig, 100, 25, Commitment::Confirmed);
+
+        tracker.poll_once(MAX_STATUS_BATCH, 101, Instant::now(), |batch| Some(vec![Some((101, None, Commitment::Processed)); batch.len()]));
+        assert_eq!(processed.try_recv(), Ok(Resolution::Landed { slot: 101, err: None }));
+        assert!(confirmed.try_recv().is_err());
+
+        tracker.poll_once(MAX_STATUS_BATCH, 102, Instant::now(), |batch| Some(vec![Some((101, None, Commitment::Confirmed)); batch.len()]));
+        assert_eq!(confirmed.try_recv(), Ok(Resolution::Landed { slot: 101, err: None }));
+        assert!(tracker.is_idle());
+    }
+
+    #[test]
+    fn test_commitment_and_rollback() {
+        let (now, (notify, _resolution)) = (Instant::now(), channel());
+        let mut entry = Pending { deadline_slot: 110, deadline: now, commitment: Commitment::Confirmed, seen: false, notify };
+
+        // Unseen: stays pending. Processed is below confirmed: seen, with a grace period past the deadline
+        assert_eq!(resolve_status(&mut entry, &None, 100, now), None);
+        assert_eq!(resolve_status(&mut entry, &Some((105, None, Commitment::Processed)), 100, now), None);
+        assert!(entry.seen);
+        assert_eq!(entry.deadline_slot, 110 + COMMITMENT_GRACE_SLOTS);
+
+        // Confirmed lands it
+        assert_eq!(resolve_status(&mut entry, &Some((105, None, Commitment::Confirmed)), 101, now),
+            Some(Resolution::Landed { slot: 105, err: None }));
+        // Gone after being seen: its fork was dropped
+        assert_eq!(resolve_status(&mut entry, &None, 102, now), Some(Resolution::RolledBack));
+    }
+}
@@ -0,0 +1,105 @@
+use crate::config_load::GLOBAL_CONFIG;
+use rand::Rng;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashSet;
+
+/// Clamp a CU price into `[min, max]`; a `max` of 0 means no ceiling. If misconfigured with
+/// `min > max`, the floor wins so we never submit at a price that can't land.
+fn clamp_cu_price(cu_price: u64, min: u64, max: u64) -> u64 {
+    let capped = if max > 0 { cu_price.min(max) } else { cu_price };
+    capped.max(min)
+}
+
+/// `cu_price` clamped into the configured `[min_cu_price, maxCUPrice]`
+pub fn clamp_configured_cu_price(cu_price: u64) -> u64 {
+    let (min, max) = GLOBAL_CONFIG.get().map(|c| (c.min_cu_price, c.max_cuprice)).unwrap_or((0, 0));
+    clamp_cu_price(cu_price, min, max)
+}
+
+/// Raise `cu_price` by `bps_per_account` basis points for every distinct account (programs included)
+/// `instructions` touch, so account-heavy transactions bid in proportion to their cost; 0 leaves it as is
+fn size_scaled_cu_price(cu_price: u64, instructions: &[Instruction], bps_per_account: u64) -> u64 {
+    if bps_per_account == 0 {
+        return cu_price;
+    }
+    let accounts: HashSet<&Pubkey> = instructions
+        .iter()
+        .flat_map(|ix| std::iter::once(&ix.program_id).chain(ix.accounts.iter().map(|meta| &meta.pubkey)))
+        .collect();
+    let scale_bps = 10_000u64.saturating_add(bps_per_account.saturating_mul(accounts.len() as u64));
+    (cu_price as u128 * scale_bps as u128 / 10_000).min(u64::MAX as u128) as u64
+}
+
+/// Vendor CU price for a transaction carrying `instructions`: `cu_price` scaled by its size
+/// (`cu_price_scale_bps_per_account`) plus a random 1..=100 (so parallel sends differ), clamped
+/// into `[min_cu_price, maxCUPrice]`
+pub fn adjust_cu_price(cu_price: u64, instructions: &[Instruction]) -> u64 {
+    let random_addition: u64 = rand::thread_rng().gen_range(1..=100);
+    let bps_per_account = GLOBAL_CONFIG.get().map_or(0, |c| c.cu_price_scale_bps_per_account);
+    let scaled = size_scaled_cu_price(cu_price, instructions, bps_per_account);
+    clamp_configured_cu_price(scaled.saturating_add(random_addition))
+}
+
+/// Split a total bid of `total_lamports` into (CU price in micro-lamports, tip in lamports):
+/// `tip_ratio` of it (clamped to 0..=1) goes to the tip, the rest to the priority fee over `cu_limit`
+/// units. The CU price is capped at `max_cu_price` (0 = no ceiling) and the fee it can't carry goes to
+/// the tip, so the whole bid is still spent.
+pub fn split_bid(total_lamports: u64, tip_ratio: f64, cu_limit: u32, max_cu_price: u64) -> (u64, u64) {
+    let tip = (total_lamports as f64 * tip_ratio.clamp(0.0, 1.0)).round() as u64;
+    let priority_fee = total_lamports.saturating_sub(tip);
+    let cu_price = if cu_limit == 0 { 0 } else { priority_fee.saturating_mul(1_000_000) / cu_limit as u64 };
+    let cu_price = if max_cu_price > 0 { cu_price.min(max_cu_price) } else { cu_price };
+    let fee_spent = cu_price.saturating_mul(cu_limit as u64) / 1_000_000;
+    (cu_price, total_lamports.saturating_sub(fee_spent))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clamp_cu_price() {
+        // Below the floor is raised, above the ceiling is capped
+        assert_eq!(clamp_cu_price(10, 1_000, 800_000), 1_000);
+        assert_eq!(clamp_cu_price(5_000_000, 1_000, 800_000), 800_000);
+        assert_eq!(clamp_cu_price(50_000, 1_000, 800_000), 50_000);
+        // No ceiling configured
+        assert_eq!(clamp_cu_price(5_000_000, 0, 0), 5_000_000);
+        // Floor wins over a lower ceiling
+        assert_eq!(clamp_cu_price(10, 2_000, 1_000), 2_000);
+    }
+
+    #[test]
+    fn test_size_scaled_cu_price() {
+        let ix = |accounts: usize| {
+            let metas = (0..accounts).map(|_| solana_sdk::instruction::AccountMeta::new(Pubkey::new_unique(), false)).collect();
+            Instruction::new_with_bytes(Pubkey::new_unique(), &[], metas)
+        };
+        let small = vec![ix(3)];
+        let large = vec![ix(3), ix(12)];
+
+        // 100 bps per account: 4 accounts -> +4%, 17 accounts -> +17%
+        assert_eq!(size_scaled_cu_price(100_000, &small, 100), 104_000);
+        assert_eq!(size_scaled_cu_price(100_000, &large, 100), 117_000);
+        assert!(size_scaled_cu_price(100_000, &large, 100) > size_scaled_cu_price(100_000, &small, 100));
+
+        // Disabled: every size bids the same
+        assert_eq!(size_scaled_cu_price(100_000, &small, 0), 100_000);
+        assert_eq!(size_scaled_cu_price(100_000, &large, 0), 100_000);
+    }
+
+    #[test]
+    fn test_bid_ratio_split() {
+        // 0.001 SOL, 70% tip: 700_000 lamports tip, 300_000 lamports over 150k CU = 2_000_000 µlamports/CU
+        assert_eq!(split_bid(1_000_000, 0.7, 150_000, 0), (2_000_000, 700_000));
+        // All tip, all priority fee
+        assert_eq!(split_bid(1_000_000, 1.0, 150_000, 0), (0, 1_000_000));
+        assert_eq!(split_bid(1_000_000, 0.0, 200_000, 0), (5_000_000, 0));
+        // Out-of-range ratio is clamped, zero CU limit leaves the whole bid to the tip
+        assert_eq!(split_bid(1_000_000, 1.5, 150_000, 0), (0, 1_000_000));
+        assert_eq!(split_bid(1_000_000, 0.5, 0, 0), (0, 1_000_000));
+        // maxCUPrice of 1_000_000: the fee over it (150_000 lamports) moves to the tip
+        assert_eq!(split_bid(1_000_000, 0.7, 150_000, 1_000_000), (1_000_000, 850_000));
+    }
+}
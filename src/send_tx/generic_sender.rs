@@ -4,14 +4,18 @@ use solana_sdk::transaction::Transaction;
 use crate::init::wallet_loader::get_wallet_keypair;
 use crate::build_tx::tx_builder::{build_and_sign_transaction, create_instruction};
 use crate::init::initialize::GLOBAL_RPC_CLIENT;
-use crate::send_tx::rpc::send_tx_via_send_rpcs;
+use crate::send_tx::rejections::{record_rejection, TradeTag};
+use crate::send_tx::sig_verify::verify_vendor_signature;
+use crate::send_tx::send_limit::acquire_send_slot;
+use crate::send_tx::rpc::{check_blockhash_not_expired, send_tx_quic, send_tx_via_send_rpcs};
 use crate::send_tx::zero_slot::send_tx_zeroslot;
 use crate::send_tx::jito::send_jito_bundle;
 use crate::send_tx::nextblock::send_tx_nextblock;
-use crate::send_tx::block_razor::send_tx_blockrazor;
+use crate::send_tx::block_razor::{blockrazor_mode, send_tx_blockrazor};
 use crate::send_tx::flashblock::send_tx_flashblock;
 use crate::send_tx::astralane::send_tx_astralane;
 use crate::send_tx::temporal::send_tx_temporal;
+use crate::alerts::{alert, AlertKind};
 use chrono::Utc;
 use std::time::Instant;
 use rayon::prelude::*;
@@ -44,6 +48,12 @@ pub async fn send_rpc(cu_limit: u32, _cu_price: u64, mint: Pubkey, instructions:
 /// Send a transaction to a specific vendor
 pub async fn send_to_vendor(vendor_name: &str, transaction: &Transaction) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
     let send_start = Instant::now();
+
+    // Non-nonce transactions with an expired blockhash can never land
+    if crate::config_load::GLOBAL_CONFIG.get().map_or(true, |c| c.skip_expired_blockhash_sends) {
+        check_blockhash_not_expired(transaction)
+            .map_err(|e| Box::new(std::io::Error::new(std::io::ErrorKind::Other, e)) as Box<dyn std::error::Error + Send + Sync>)?;
+    }
     
     let result = match vendor_name {
         "rpc" => {
@@ -53,6 +63,13 @@ pub async fn send_to_vendor(vendor_name: &str, transaction: &Transaction) -> Res
                     format!("RPC send failed: {}", e)
                 )) as Box<dyn std::error::Error + Send + Sync>)
         }
+        "quic" => {
+            send_tx_quic(transaction).await
+                .map_err(|e| Box::new(std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    format!("QUIC send failed: {}", e)
+                )) as Box<dyn std::error::Error + Send + Sync>)
+        }
         "zeroslot" => {
             send_tx_zeroslot(transaction).await
                 .map_err(|e| Box::new(std::io::Error::new(
@@ -69,7 +86,7 @@ pub async fn send_to_vendor(vendor_name: &str, transaction: &Transaction) -> Res
         }
         "nextblock" => {
             let config = crate::config_load::GLOBAL_CONFIG.get().expect("Config not initialized");
-            send_tx_nextblock(transaction, &config.nextblock_api).await
+            send_tx_nextblock(transaction, &config.nextblock_api, config.nextblock_front_running_protection, config.nextblock_revert_on_fail).await
                 .map_err(|e| Box::new(std::io::Error::new(
                     std::io::ErrorKind::Other,
                     format!("NextBlock send failed: {}", e)
@@ -77,7 +94,7 @@ pub async fn send_to_vendor(vendor_name: &str, transaction: &Transaction) -> Res
         }
         "blockrazor" => {
             let config = crate::config_load::GLOBAL_CONFIG.get().expect("Config not initialized");
-            send_tx_blockrazor(transaction, &config.blockrazor_api, "fast", None, false).await
+            send_tx_blockrazor(transaction, &config.blockrazor_api, blockrazor_mode()).await
                 .map_err(|e| Box::new(std::io::Error::new(
                     std::io::ErrorKind::Other,
                     format!("BlockRazor send failed: {}", e)
@@ -117,38 +134,113 @@ pub async fn send_to_vendor(vendor_name: &str, transaction: &Transaction) -> Res
     result
 }
 
+/// Launch rank per vendor transaction: vendors listed in `launch_order` go first in that order,
+/// the rest keep their original order after them.
+pub fn vendor_launch_ranks(vendor_transactions: &[(String, Transaction)], launch_order: &[String]) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..vendor_transactions.len()).collect();
+    indices.sort_by_key(|&i| {
+        launch_order
+            .iter()
+            .position(|v| v == &vendor_transactions[i].0)
+            .unwrap_or(launch_order.len())
+    });
+    let mut ranks = vec![0; vendor_transactions.len()];
+    for (rank, i) in indices.into_iter().enumerate() {
+        ranks[i] = rank;
+    }
+    ranks
+}
+
 /// Send all vendor transactions in parallel and return the first successful result
 pub async fn send_all_vendors_parallel(
     vendor_transactions: &[(String, Transaction)],
     detection_time: Instant,
+    corr_id: &str,
+    trade: &TradeTag,
 ) -> Result<(String, String), Box<dyn std::error::Error + Send + Sync>> {
     let send_start = Instant::now();
+    if !crate::utils::kill_switch::allows_send(trade.side, corr_id) {
+        return Err("suppressed: kill switch file present".into());
+    }
+    crate::utils::slot_attempts::record_attempt(trade.side, corr_id);
+    let _send_slot = match acquire_send_slot(corr_id, trade.side).await {
+        Some(slot) => slot,
+        None => return Err("dropped: max_in_flight_sends reached".into()),
+    };
     
     println!(
-        "[{}] - [GENERIC_SENDER] Starting parallel send to {} vendors",
+        "[{}] - [GENERIC_SENDER] [cid={}] Starting parallel send to {} vendors",
         Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+        corr_id,
         vendor_transactions.len()
     );
     
+    // Launch order and stagger (default: all at once, in the given order)
+    let config = crate::config_load::GLOBAL_CONFIG.get();
+    let launch_ranks = vendor_launch_ranks(
+        vendor_transactions,
+        config.map(|c| c.vendor_launch_order.as_slice()).unwrap_or(&[]),
+    );
+    let stagger = std::time::Duration::from_millis(config.map(|c| c.vendor_launch_stagger_ms).unwrap_or(0));
+    let skip_after_accept = config.map(|c| c.vendor_launch_skip_after_accept).unwrap_or(false);
+    // Set once any vendor accepts; staggered vendors launching later can then be skipped,
+    // since all share the same nonce and only one can land
+    let accepted = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+    
     // Create futures for all vendor sends with individual timing
     let mut futures = Vec::new();
-    for (vendor_name, transaction) in vendor_transactions {
+    for ((vendor_name, transaction), rank) in vendor_transactions.iter().zip(launch_ranks) {
         let vendor_name = vendor_name.clone();
         let transaction = transaction.clone();
-        let vendor_start = Instant::now();
+        let accepted = accepted.clone();
+        let corr_id = corr_id.to_string();
+        let side = trade.side;
         let future = async move {
+            if !crate::send_tx::vendor_health::should_route(&vendor_name) {
+                #[cfg(feature = "verbose_logging")]
+                println!("[GENERIC_SENDER] [cid={}] Skipping {}, auto-disabled (waiting for recovery probe)", corr_id, vendor_name);
+                let skipped: Result<String, Box<dyn std::error::Error + Send + Sync>> =
+                    Err("skipped: vendor auto-disabled".into());
+                return (vendor_name, skipped, std::time::Duration::ZERO);
+            }
+            if !stagger.is_zero() && rank > 0 {
+                tokio::time::sleep(stagger * rank as u32).await;
+                if skip_after_accept && accepted.load(std::sync::atomic::Ordering::Relaxed) {
+                    println!(
+                        "[{}] - [GENERIC_SENDER] [cid={}] Skipping {} launch (rank {}), an earlier vendor already accepted",
+                        Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+                        corr_id,
+                        vendor_name,
+                        rank
+                    );
+                    let skipped: Result<String, Box<dyn std::error::Error + Send + Sync>> =
+                        Err("skipped: earlier vendor accepted".into());
+                    return (vendor_name, skipped, std::time::Duration::ZERO);
+                }
+            }
+            let vendor_start = Instant::now();
             #[cfg(feature = "verbose_logging")]
             println!(
-                "[{}] - [GENERIC_SENDER] 🚀 Starting {} send...",
+                "[{}] - [GENERIC_SENDER] [cid={}] 🚀 Starting {} send...",
                 Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+                corr_id,
                 vendor_name
             );
-            let result = send_to_vendor(&vendor_name, &transaction).await;
+            let result = crate::send_tx::http_pool::with_send_side(side, send_to_vendor(&vendor_name, &transaction)).await;
             let vendor_time = vendor_start.elapsed();
+            if result.is_ok() {
+                accepted.store(true, std::sync::atomic::Ordering::Relaxed);
+            }
+            crate::utils::otel::record_span(&corr_id, "send", vendor_start, Instant::now(), &[
+                ("vendor", &vendor_name),
+                ("side", &side),
+                ("accepted", &result.is_ok()),
+            ]);
             #[cfg(feature = "verbose_logging")]
             println!(
-                "[{}] - [GENERIC_SENDER] ✅ {} completed in {:.2?}",
+                "[{}] - [GENERIC_SENDER] [cid={}] ✅ {} completed in {:.2?}",
                 Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+                corr_id,
                 vendor_name,
                 vendor_time
             );
@@ -160,8 +252,9 @@ pub async fn send_all_vendors_parallel(
     // Execute all futures in parallel using join_all
     #[cfg(feature = "verbose_logging")]
     println!(
-        "[{}] - [GENERIC_SENDER] 🔄 Executing {} futures in parallel...",
+        "[{}] - [GENERIC_SENDER] [cid={}] 🔄 Executing {} futures in parallel...",
         Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+        corr_id,
         futures.len()
     );
     
@@ -171,8 +264,9 @@ pub async fn send_all_vendors_parallel(
     
     #[cfg(feature = "verbose_logging")]
     println!(
-        "[{}] - [GENERIC_SENDER] ✅ Parallel execution completed in {:.2?}",
+        "[{}] - [GENERIC_SENDER] [cid={}] ✅ Parallel execution completed in {:.2?}",
         Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+        corr_id,
         parallel_time
     );
     
@@ -183,11 +277,25 @@ pub async fn send_all_vendors_parallel(
     for (vendor_name, result, vendor_time) in results {
         match result {
             Ok(signature) => {
+                crate::send_tx::vendor_health::record_send_outcome(&vendor_name, true);
+                let signature = match vendor_transactions.iter().find(|(name, _)| *name == vendor_name) {
+                    Some((_, tx)) if config.map_or(false, |c| c.verify_vendor_signatures) => {
+                        verify_vendor_signature(&vendor_name, &signature, tx, corr_id)
+                    }
+                    _ => signature,
+                };
                 successful_vendors.push((vendor_name.clone(), signature.clone(), vendor_time));
             }
             Err(e) => {
                 failed_vendors.push((vendor_name.clone(), vendor_time));
-                eprintln!("[GENERIC_SENDER] {} failed: {}", vendor_name, e);
+                eprintln!("[GENERIC_SENDER] [cid={}] {} failed: {}", corr_id, vendor_name, e);
+                let error = e.to_string();
+                if !error.starts_with("skipped:") {
+                    crate::send_tx::vendor_health::record_send_outcome(&vendor_name, false);
+                    if let Some((_, tx)) = vendor_transactions.iter().find(|(name, _)| *name == vendor_name) {
+                        record_rejection(&vendor_name, tx, trade, corr_id, &error);
+                    }
+                }
             }
         }
     }
@@ -195,8 +303,9 @@ pub async fn send_all_vendors_parallel(
     // Display all vendor performance
     #[cfg(feature = "verbose_logging")]
     println!(
-        "[{}] - [GENERIC_SENDER] ===== VENDOR PERFORMANCE REPORT =====",
-        Utc::now().format("%Y-%m-%d %H:%M:%S%.3f")
+        "[{}] - [GENERIC_SENDER] [cid={}] ===== VENDOR PERFORMANCE REPORT =====",
+        Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+        corr_id
     );
     
     // Show successful vendors first (sorted by speed)
@@ -205,8 +314,9 @@ pub async fn send_all_vendors_parallel(
         
         #[cfg(feature = "verbose_logging")]
         println!(
-            "[{}] - [GENERIC_SENDER] ✅ SUCCESSFUL VENDORS ({}):",
+            "[{}] - [GENERIC_SENDER] [cid={}] ✅ SUCCESSFUL VENDORS ({}):",
             Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+            corr_id,
             successful_vendors.len()
         );
         
@@ -216,8 +326,9 @@ pub async fn send_all_vendors_parallel(
             
             #[cfg(feature = "verbose_logging")]
             println!(
-                "[{}] - [GENERIC_SENDER] {} {}: {:.2?} | sig: {} | total elapsed: {:.2?}",
+                "[{}] - [GENERIC_SENDER] [cid={}] {} {}: {:.2?} | sig: {} | total elapsed: {:.2?}",
                 Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+                corr_id,
                 rank,
                 vendor_name,
                 send_time,
@@ -231,16 +342,18 @@ pub async fn send_all_vendors_parallel(
     if !failed_vendors.is_empty() {
         #[cfg(feature = "verbose_logging")]
         println!(
-            "[{}] - [GENERIC_SENDER] ❌ FAILED VENDORS ({}):",
+            "[{}] - [GENERIC_SENDER] [cid={}] ❌ FAILED VENDORS ({}):",
             Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+            corr_id,
             failed_vendors.len()
         );
         
         for (vendor_name, send_time) in failed_vendors {
             #[cfg(feature = "verbose_logging")]
             println!(
-                "[{}] - [GENERIC_SENDER]   {}: {:.2?} (FAILED)",
+                "[{}] - [GENERIC_SENDER] [cid={}]   {}: {:.2?} (FAILED)",
                 Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+                corr_id,
                 vendor_name,
                 send_time
             );
@@ -258,8 +371,9 @@ pub async fn send_all_vendors_parallel(
     
     #[cfg(feature = "verbose_logging")]
     println!(
-        "[{}] - [GENERIC_SENDER] 📊 SUMMARY: Total time: {:.2?} | Parallel execution: {:.2?} | Avg vendor time: {:.2?} | Success rate: {}/{}",
+        "[{}] - [GENERIC_SENDER] [cid={}] 📊 SUMMARY: Total time: {:.2?} | Parallel execution: {:.2?} | Avg vendor time: {:.2?} | Success rate: {}/{}",
         Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+        corr_id,
         total_time,
         parallel_time,
         avg_time,
@@ -269,15 +383,21 @@ pub async fn send_all_vendors_parallel(
     
     #[cfg(feature = "verbose_logging")]
     println!(
-        "[{}] - [GENERIC_SENDER] ================================================",
-        Utc::now().format("%Y-%m-%d %H:%M:%S%.3f")
+        "[{}] - [GENERIC_SENDER] [cid={}] ================================================",
+        Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+        corr_id
     );
     
     // Return the fastest successful vendor
     if let Some((fastest_vendor, fastest_signature, _)) = successful_vendors.first() {
+        crate::send_tx::vendor_wins::record_vendor_win(fastest_vendor);
         Ok((fastest_vendor.clone(), fastest_signature.clone()))
     } else {
         // If we get here, all vendors failed
+        if !vendor_transactions.is_empty() {
+            alert(AlertKind::AllVendorsFailing, format!("all {} vendors rejected the {} of {} (cid={})",
+                vendor_transactions.len(), trade.side, trade.mint, corr_id));
+        }
         Err(Box::new(std::io::Error::new(
             std::io::ErrorKind::Other,
             "All vendors failed to send transaction"
@@ -307,8 +427,28 @@ pub async fn test_parallel_execution() {
     let test_start = Instant::now();
     
     // This will fail but we can see the timing
-    let _result = send_all_vendors_parallel(&dummy_transactions, test_start).await;
+    let trade = TradeTag { mint: Pubkey::default(), tx_type: "test".to_string(), side: "buy" };
+    let _result = send_all_vendors_parallel(&dummy_transactions, test_start, "test", &trade).await;
     
     println!("[GENERIC_SENDER] 🧪 Parallel execution test completed");
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vendor_launch_ranks() {
+        let txs: Vec<(String, Transaction)> = ["rpc", "zeroslot", "nextblock", "temporal"]
+            .iter()
+            .map(|v| (v.to_string(), Transaction::default()))
+            .collect();
+
+        // No order configured: launch in the given order
+        assert_eq!(vendor_launch_ranks(&txs, &[]), vec![0, 1, 2, 3]);
+
+        // Listed vendors first, unknown names ignored, unlisted keep relative order
+        let order = vec!["temporal".to_string(), "missing".to_string(), "zeroslot".to_string()];
+        assert_eq!(vendor_launch_ranks(&txs, &order), vec![2, 1, 3, 0]);
+    }
+}
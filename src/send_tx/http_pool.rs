@@ -0,0 +1,103 @@
+use crate::config_load::GLOBAL_CONFIG;
+use chrono::Utc;
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+// One connection budget per HTTP vendor so vendors never contend for each other's pool
+static VENDOR_POOLS: Lazy<DashMap<&'static str, Arc<Semaphore>>> = Lazy::new(DashMap::new);
+// Pool wait stats per vendor: (sends, waited, total wait us, max wait us)
+static VENDOR_POOL_WAITS: Lazy<DashMap<&'static str, (usize, usize, u64, u64)>> = Lazy::new(DashMap::new);
+
+tokio::task_local! {
+    // "buy" or "sell" for the vendor send running in this task
+    static SEND_SIDE: &'static str;
+}
+
+/// Run a vendor send as `side`, so `acquire_pool_slot` knows whether it may wait
+pub async fn with_send_side<F: Future>(side: &'static str, send: F) -> F::Output {
+    SEND_SIDE.scope(side, send).await
+}
+
+/// Connections per host for the vendor HTTP clients (`http_pool_size`, default 50)
+pub fn http_pool_size() -> usize {
+    GLOBAL_CONFIG
+        .get()
+        .map(|c| c.http_pool_size)
+        .unwrap_or_else(crate::config_load::default_http_pool_size)
+        .max(1)
+}
+
+/// Take a connection slot for `vendor` for one send attempt; drop it before retrying. Sells beyond
+/// the pool size wait here, so the wait is measured and logged. Buys never wait: over the cap they
+/// go out without a slot (None).
+pub async fn acquire_pool_slot(vendor: &'static str) -> Option<OwnedSemaphorePermit> {
+    let max = http_pool_size();
+    let semaphore = VENDOR_POOLS
+        .entry(vendor)
+        .or_insert_with(|| Arc::new(Semaphore::new(max)))
+        .clone();
+
+    let (permit, waited_us) = match semaphore.clone().try_acquire_owned() {
+        Ok(permit) => (Some(permit), None),
+        Err(_) if SEND_SIDE.try_with(|side| *side == "buy").unwrap_or(false) => {
+            println!("[{}] - [HTTP_POOL] {} buy sent over the pool cap ({}/{} in use)",
+                Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), vendor, max - semaphore.available_permits(), max);
+            (None, None)
+        }
+        Err(_) => {
+            let wait_start = Instant::now();
+            let permit = semaphore.clone().acquire_owned().await.expect("HTTP pool semaphore closed");
+            (Some(permit), Some(wait_start.elapsed().as_micros() as u64))
+        }
+    };
+
+    let mut stats = VENDOR_POOL_WAITS.entry(vendor).or_insert((0, 0, 0, 0));
+    stats.0 += 1;
+    if let Some(waited_us) = waited_us {
+        stats.1 += 1;
+        stats.2 += waited_us;
+        stats.3 = stats.3.max(waited_us);
+        println!("[{}] - [HTTP_POOL] {} send waited {}us for a pooled connection ({}/{} in use)",
+            Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), vendor, waited_us, max - semaphore.available_permits(), max);
+    }
+    permit
+}
+
+/// Per-vendor pool stats: (vendor, sends, waited, avg wait us, max wait us)
+pub fn get_http_pool_stats() -> Vec<(String, usize, usize, u64, u64)> {
+    let mut stats: Vec<(String, usize, usize, u64, u64)> = VENDOR_POOL_WAITS
+        .iter()
+        .map(|e| {
+            let (sends, waited, total_us, max_us) = *e.value();
+            let avg_us = if waited > 0 { total_us / waited as u64 } else { 0 };
+            (e.key().to_string(), sends, waited, avg_us, max_us)
+        })
+        .collect();
+    stats.sort();
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_full_pool_holds_sells_but_not_buys() {
+        let held: Vec<_> = futures::future::join_all((0..http_pool_size()).map(|_| acquire_pool_slot("pool_test"))).await;
+        assert!(held.iter().all(|slot| slot.is_some()));
+
+        // Saturated: a buy goes out without a slot right away
+        assert!(with_send_side("buy", acquire_pool_slot("pool_test")).await.is_none());
+
+        // A sell waits until a slot is released
+        let sell = tokio::spawn(with_send_side("sell", acquire_pool_slot("pool_test")));
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        assert!(!sell.is_finished());
+        drop(held);
+        assert!(sell.await.unwrap().is_some());
+    }
+}
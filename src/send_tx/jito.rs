@@ -19,25 +19,20 @@ use jito::packet::Packet;
 use thiserror::Error;
 use std::str::FromStr;
 use crate::send_tx::jito_authenticator::ClientInterceptor;
-use solana_sdk::{
-    pubkey::Pubkey,
-    system_instruction,
-};
+use solana_sdk::pubkey::Pubkey;
 use solana_sdk::instruction::Instruction;
-use rand::seq::SliceRandom;
 use crate::init::wallet_loader::get_wallet_keypair;
 
 use tonic::{
     codegen::{Body, Bytes, InterceptedService, StdError},
     transport,
-    transport::{Endpoint},
     Response, Status, Streaming,
 };
 
 pub type BlockEngineConnectionResult<T> = Result<T, BlockEngineConnectionError>;
 
 // List of Jito tip accounts
-static JITO_TIP_ACCOUNTS: &[&str] = &[
+pub(crate) static JITO_TIP_ACCOUNTS: &[&str] = &[
     "96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5",
     "HFqU5x63VTqvQss8hp11i4wVV8bD44PvwucfZ2bU7gRe",
     "Cw8CFyM9FkoMi7K7Crf6HNQqf4uEMzpKw6QNghXLvLkY",
@@ -96,6 +91,11 @@ pub struct JitoGrpcSender<T> {
     pub client: JitoGrpcClient<T>,
 }
 
+/// Whether init_jito_grpc_sender has connected and authenticated
+pub fn is_jito_sender_initialized() -> bool {
+    JITO_GRPC_SENDER.get().is_some()
+}
+
 /// Load the global keypair from ./jito_auth.json
 pub fn load_global_keypair() -> &'static Arc<Keypair> {
     GLOBAL_KEYPAIR.get_or_init(|| {
@@ -171,7 +171,7 @@ pub async fn init_jito_grpc_sender(block_engine_url: &str) {
 
 pub async fn create_grpc_channel(url: &str) -> BlockEngineConnectionResult<Channel> {
 
-    let mut endpoint = Endpoint::from_shared(url.to_string()).expect("invalid url");
+    let mut endpoint = crate::utils::socket_tuning::tuned_endpoint(url).expect("invalid url");
     if url.starts_with("https") {
         endpoint = endpoint.tls_config(tonic::transport::ClientTlsConfig::new().with_native_roots())?;
     }
@@ -180,6 +180,7 @@ pub async fn create_grpc_channel(url: &str) -> BlockEngineConnectionResult<Chann
 
 /// Send a bundle via Jito gRPC (like SendGrpcBundle in Go)
 pub async fn send_jito_bundle(tx: &Transaction) -> Result<String, Box<dyn std::error::Error>> {
+    crate::send_tx::rate_guard::throttle_vendor_send("jito").await?;
     let access_token = ACCESS_TOKEN.get().expect("Access token not initialized");
     let tx_bytes = bincode::serialize(tx)?;
     let packet = Packet {
@@ -219,13 +220,8 @@ pub async fn send_jito_bundle(tx: &Transaction) -> Result<String, Box<dyn std::e
 // send_jito_bundle(&tx)?; 
 
 pub fn jito_tip(tip: u64, from_pubkey: &Pubkey) -> Instruction {
-    // Randomly select a tip account from the list
-    let tip_account = JITO_TIP_ACCOUNTS
-        .choose(&mut rand::thread_rng())
-        .expect("Failed to select random tip account");
-    
-    let tip_pubkey = Pubkey::from_str(tip_account).expect("Invalid pubkey");
-    system_instruction::transfer(from_pubkey, &tip_pubkey, tip)
+    // Random tip account (configured via vendor_tip_accounts or built-in list), precomputed at startup
+    crate::send_tx::tip_accounts::tip_instruction("jito", tip, from_pubkey)
 }
 
 pub fn create_instruction_jito(
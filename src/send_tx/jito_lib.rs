@@ -211,16 +211,9 @@
 //         )));
 //     }
 //     info!("Bundle landed successfully");
-//     let url: String = rpc_client.url();
-//     let cluster = if url.contains("testnet") {
-//         "testnet"
-//     } else if url.contains("devnet") {
-//         "devnet"
-//     } else {
-//         "mainnet"
-//     };
+//     let network = crate::config_load::network::network();
 //     for sig in bundle_signatures.iter() {
-//         info!("https://solscan.io/tx/{}?cluster={}", sig, cluster);
+//         info!("{}", network.explorer_tx_url(&sig.to_string()));
 //     }
 //     Ok(())
 // }
@@ -8,4 +8,21 @@ pub mod jito_authenticator;
 pub mod block_razor;
 pub mod flashblock;
 pub mod astralane;
-pub mod temporal;
\ No newline at end of file
+pub mod temporal;
+pub mod rate_guard;
+pub mod http_pool;
+pub mod tip_accounts;
+pub mod cu_price;
+pub mod rejections;
+pub mod vendor_health;
+pub mod sell_retry;
+pub mod tx_buffer;
+pub mod presell;
+pub mod confirm_tracker;
+pub mod sig_verify;
+pub mod sell_gate;
+pub mod chase;
+pub mod completion;
+pub mod send_limit;
+pub mod sell_batch;
+pub mod vendor_wins;
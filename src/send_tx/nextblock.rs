@@ -13,8 +13,8 @@ use tonic::metadata::MetadataValue;
 use tonic::transport::{Channel, ClientTlsConfig};
 use tonic::{Request, Status};
 use solana_sdk::signer::keypair::Keypair;
-use rand::Rng;
 use solana_sdk::compute_budget;
+use crate::send_tx::tx_buffer::{recycle_buffer, take_buffer};
 
 // You must have the generated gRPC client from NextBlock proto
 // Example: use nextblock_proto::api_client::ApiClient;
@@ -71,9 +71,9 @@ pub async fn connect_to_nextblock(
     plaintext: bool,
 ) -> Result<Channel, Box<dyn std::error::Error>> {
     let channel = if plaintext {
-        Channel::from_shared(address.to_string())?.connect().await?
+        crate::utils::socket_tuning::tuned_endpoint(address)?.connect().await?
     } else {
-        Channel::from_shared(address.to_string())?
+        crate::utils::socket_tuning::tuned_endpoint(address)?
             .tls_config(ClientTlsConfig::new())?
             .connect()
             .await?
@@ -85,11 +85,16 @@ pub async fn connect_to_nextblock(
 pub async fn send_tx_nextblock(
     tx: &Transaction,
     token: &str,
+    front_running_protection: bool,
+    revert_on_fail: bool,
 ) -> Result<String, Box<dyn std::error::Error>> {
+    crate::send_tx::rate_guard::throttle_vendor_send("nextblock").await?;
     let client = get_nextblock_client();
     let mut client = Arc::try_unwrap(client).unwrap_or_else(|arc| (*arc).clone());
-    let tx_bytes = bincode::serialize(tx)?;
+    let mut tx_bytes = take_buffer();
+    bincode::serialize_into(&mut tx_bytes, tx)?;
     let tx_b64 = general_purpose::STANDARD.encode(&tx_bytes);
+    recycle_buffer(tx_bytes);
 
     let request = nextblock_proto::PostSubmitRequest {
         transaction: Some(nextblock_proto::TransactionMessage {
@@ -97,11 +102,11 @@ pub async fn send_tx_nextblock(
             is_cleanup: false,
         }),
         skip_pre_flight: true,
-        front_running_protection: Some(false),
+        front_running_protection: Some(front_running_protection),
         snipe_transaction: Some(true),
         disable_retries: Some(true),
         experimental_front_running_protection: Some(false),
-        revert_on_fail: Some(false),
+        revert_on_fail: Some(revert_on_fail),
         // ... other fields as needed
     };
 
@@ -121,18 +126,12 @@ pub fn create_instruction_nextblock(
     nonce_account: &Pubkey,
 ) -> Vec<Instruction> {
 
-    let mut rng = rand::thread_rng();
-    let random_addition: u64 = rng.gen_range(1..=100);
-    let adjusted_cu_price = cu_price + random_addition;
+    let adjusted_cu_price = crate::send_tx::cu_price::adjust_cu_price(cu_price, &instructions);
     let keypair: &'static Keypair = get_wallet_keypair();
 
     let price_ix = compute_budget::ComputeBudgetInstruction::set_compute_unit_price(adjusted_cu_price);
 
-    let tip_ix = nextblock_tip(
-        "NEXTbLoCkB51HpLBLojQfpyVAMorm3zzKg7w9NFdqid",
-        tip,
-        &keypair.pubkey(),
-    );
+    let tip_ix = crate::send_tx::tip_accounts::tip_instruction("nextblock", tip, &keypair.pubkey());
     // Create advance nonce instruction using the provided nonce account
     let advance_nonce_ix = system_instruction::advance_nonce_account(
         nonce_account,
@@ -144,11 +143,9 @@ pub fn create_instruction_nextblock(
     result
 }
 
-/// Create a system transfer instruction for NextBlock tips
-pub fn nextblock_tip(tip_ac: &str, tip: u64, from_pubkey: &Pubkey) -> Instruction {
-    let tip_pubkey = Pubkey::from_str(tip_ac).expect("Invalid pubkey");
-    system_instruction::transfer(from_pubkey, &tip_pubkey, tip)
-}
+pub(crate) static NEXTBLOCK_TIP_ACCOUNTS: &[&str] = &[
+    "NEXTbLoCkB51HpLBLojQfpyVAMorm3zzKg7w9NFdqid",
+];
 
 // pub fn create_instruction_nextblock(
 //     cu_limit: u32,
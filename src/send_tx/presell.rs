@@ -0,0 +1,145 @@
+use crate::build_tx::pump_swap::get_vault_reserves;
+use crate::config_load::Config;
+use crate::grpc::arpc_worker::TxWithPubkey;
+use chrono::Utc;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static PRESELL_POOL_DRAINED: AtomicUsize = AtomicUsize::new(0);
+static SELL_MINT_MISMATCHES: AtomicUsize = AtomicUsize::new(0);
+
+/// Sells that found the pool's SOL side below `presell_min_liquidity_sol`
+pub fn get_presell_drained() -> usize {
+    PRESELL_POOL_DRAINED.load(Ordering::Relaxed)
+}
+
+/// Sells aborted because their instruction didn't target the position's mint
+pub fn get_sell_mint_mismatches() -> usize {
+    SELL_MINT_MISMATCHES.load(Ordering::Relaxed)
+}
+
+/// What to do with a sell whose pool has been drained
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DrainedPolicy {
+    Hold, // skip the sell and keep the tokens
+    Sell, // sell into the thin pool anyway
+}
+
+impl DrainedPolicy {
+    /// Unknown names fall back to Hold
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "sell" => DrainedPolicy::Sell,
+            _ => DrainedPolicy::Hold,
+        }
+    }
+}
+
+/// SOL-side reserve read from the pump_swap / CPMM vaults (the other pool types carry theirs in
+/// state the sell path already reads)
+pub fn vault_quote_reserve(tx_type: &str, tx_with_pubkey: &TxWithPubkey) -> Option<u64> {
+    let (base_vault, quote_vault) = match tx_type {
+        "pump_swap" => {
+            let accounts = tx_with_pubkey.pump_swap_accounts.as_ref()?;
+            (accounts.pool_base_token_account, accounts.pool_quote_token_account)
+        }
+        // token_0 is always WSOL (see get_ray_cpmm_accounts_rpc)
+        "ray_cpmm" | "ray_launch_cpmm" => {
+            let accounts = tx_with_pubkey.raydium_cpmm_accounts.as_ref()?;
+            (accounts.token_1_vault, accounts.token_0_vault)
+        }
+        _ => return None,
+    };
+    get_vault_reserves(base_vault, quote_vault).ok().map(|(_, quote)| quote)
+}
+
+/// None when the pool's SOL reserve is at least `min_liquidity_sol` (or unknown), else `policy`,
+/// the action to take for the drained pool
+pub fn drained_action(quote_reserve_lamports: Option<u64>, min_liquidity_sol: f64, policy: DrainedPolicy) -> Option<DrainedPolicy> {
+    let reserve = quote_reserve_lamports.filter(|_| min_liquidity_sol > 0.0)?;
+    if reserve as f64 / 1_000_000_000.0 >= min_liquidity_sol {
+        return None;
+    }
+    PRESELL_POOL_DRAINED.fetch_add(1, Ordering::Relaxed);
+    Some(policy)
+}
+
+/// Loud log line for a drained pool, naming the action taken
+pub fn report_drained(quote_reserve_lamports: Option<u64>, action: DrainedPolicy, mint: &str, tx_type: &str, cid: &str, config: &Config) {
+    eprintln!(
+        "[{}] - [PRESELL] [cid={}] Pool drained for {} ({}): {:.4} SOL left, below presell_min_liquidity_sol {} - {}",
+        Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+        cid,
+        mint,
+        tx_type,
+        quote_reserve_lamports.unwrap_or(0) as f64 / 1_000_000_000.0,
+        config.presell_min_liquidity_sol,
+        match action {
+            DrainedPolicy::Hold => "holding, sell skipped",
+            DrainedPolicy::Sell => "selling anyway",
+        }
+    );
+}
+
+/// Whether `sell_instruction` trades `mint` out of `owner`'s token account for it: both the mint and
+/// the owner's ATA must be among its accounts. Every sell builder passes both, so a miss means the
+/// accounts were resolved for some other token (a mixed-up cache entry or migration lookup).
+pub fn sell_targets_mint(sell_instruction: &Instruction, mint: &Pubkey, owner: &Pubkey) -> bool {
+    let ata = spl_associated_token_account::get_associated_token_address(owner, mint);
+    let has = |key: &Pubkey| sell_instruction.accounts.iter().any(|meta| meta.pubkey == *key);
+    has(mint) && has(&ata)
+}
+
+/// Loud log line for a sell aborted on a mint mismatch
+pub fn report_sell_mint_mismatch(sell_instruction: &Instruction, mint: &Pubkey, tx_type: &str, cid: &str) {
+    SELL_MINT_MISMATCHES.fetch_add(1, Ordering::Relaxed);
+    eprintln!(
+        "[{}] - [PRESELL] [cid={}] !!! SELL ABORTED: {} sell instruction does not target bought mint {} (accounts: {:?})",
+        Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+        cid,
+        tx_type,
+        mint,
+        sell_instruction.accounts.iter().map(|meta| meta.pubkey.to_string()).collect::<Vec<_>>()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collapsed_reserves_follow_policy() {
+        let drained = Some(20_000_000); // 0.02 SOL left
+        let before = get_presell_drained();
+        assert_eq!(drained_action(drained, 1.0, DrainedPolicy::from_name("hold")), Some(DrainedPolicy::Hold));
+        assert_eq!(drained_action(drained, 1.0, DrainedPolicy::from_name("sell")), Some(DrainedPolicy::Sell));
+        assert!(get_presell_drained() >= before + 2);
+
+        assert_eq!(drained_action(Some(5_000_000_000), 1.0, DrainedPolicy::Hold), None);
+        assert_eq!(drained_action(None, 1.0, DrainedPolicy::Hold), None);
+        assert_eq!(drained_action(drained, 0.0, DrainedPolicy::Hold), None);
+        assert_eq!(DrainedPolicy::from_name("typo"), DrainedPolicy::Hold);
+    }
+
+    #[test]
+    fn test_sell_for_other_mint_aborted() {
+        use solana_sdk::instruction::AccountMeta;
+
+        let (owner, mint, other_mint) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let sell_for = |mint: &Pubkey| Instruction {
+            program_id: Pubkey::new_unique(),
+            accounts: vec![
+                AccountMeta::new_readonly(*mint, false),
+                AccountMeta::new(spl_associated_token_account::get_associated_token_address(&owner, mint), false),
+                AccountMeta::new(owner, true),
+            ],
+            data: vec![],
+        };
+        assert!(sell_targets_mint(&sell_for(&mint), &mint, &owner));
+        // Accounts resolved for another token: aborted
+        assert!(!sell_targets_mint(&sell_for(&other_mint), &mint, &owner));
+        // Right mint, but the token account isn't the wallet's
+        assert!(!sell_targets_mint(&sell_for(&mint), &mint, &Pubkey::new_unique()));
+    }
+}
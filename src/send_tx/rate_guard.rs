@@ -0,0 +1,129 @@
+use crate::config_load::GLOBAL_CONFIG;
+use chrono::Utc;
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use std::time::{Duration, Instant};
+
+// Next allowed send time per vendor
+static VENDOR_NEXT_SEND: Lazy<DashMap<&'static str, Instant>> = Lazy::new(DashMap::new);
+// Throttle events per vendor: (queued, skipped)
+static VENDOR_THROTTLE_EVENTS: Lazy<DashMap<&'static str, (usize, usize)>> = Lazy::new(DashMap::new);
+
+/// What to do when a vendor send arrives inside its minimum interval
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ThrottleDecision {
+    Send,
+    Queue(Duration),
+    Skip,
+}
+
+/// Reserve a send slot for `vendor` at `now`. With `queue` the caller waits until the slot,
+/// otherwise the send is skipped. An interval of 0 disables the guard.
+fn reserve_send_slot(vendor: &'static str, interval: Duration, queue: bool, max_wait: Duration, now: Instant) -> ThrottleDecision {
+    if interval.is_zero() {
+        return ThrottleDecision::Send;
+    }
+    let mut next = VENDOR_NEXT_SEND.entry(vendor).or_insert(now);
+    if *next <= now {
+        *next = now + interval;
+        return ThrottleDecision::Send;
+    }
+    let wait = *next - now;
+    if queue && wait <= max_wait {
+        *next += interval;
+        ThrottleDecision::Queue(wait)
+    } else {
+        ThrottleDecision::Skip
+    }
+}
+
+/// Enforce the per-vendor minimum time between sends; call at the top of each `send_tx_*`
+pub async fn throttle_vendor_send(vendor: &'static str) -> Result<(), String> {
+    let config = match GLOBAL_CONFIG.get() {
+        Some(config) => config,
+        None => return Ok(()),
+    };
+    let interval_ms = config.vendor_min_send_interval_ms.get(vendor).copied().unwrap_or(0);
+    let queue = config.vendor_throttle_mode == "queue";
+    let max_wait = Duration::from_millis(config.vendor_throttle_max_wait_ms);
+
+    match reserve_send_slot(vendor, Duration::from_millis(interval_ms), queue, max_wait, Instant::now()) {
+        ThrottleDecision::Send => Ok(()),
+        ThrottleDecision::Queue(wait) => {
+            VENDOR_THROTTLE_EVENTS.entry(vendor).or_insert((0, 0)).0 += 1;
+            #[cfg(feature = "verbose_logging")]
+            println!("[{}] - [RATE_GUARD] {} send queued for {:.2?}", Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), vendor, wait);
+            tokio::time::sleep(wait).await;
+            Ok(())
+        }
+        ThrottleDecision::Skip => {
+            VENDOR_THROTTLE_EVENTS.entry(vendor).or_insert((0, 0)).1 += 1;
+            println!("[{}] - [RATE_GUARD] {} send skipped (min interval {}ms)", Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), vendor, interval_ms);
+            Err(format!("{} send throttled (min interval {}ms)", vendor, interval_ms))
+        }
+    }
+}
+
+/// How long an RPC call must wait to stay within the shared RPC budget (never skips)
+fn rpc_call_wait(key: &'static str, interval: Duration, now: Instant) -> Duration {
+    match reserve_send_slot(key, interval, true, Duration::MAX, now) {
+        ThrottleDecision::Queue(wait) => wait,
+        _ => Duration::ZERO,
+    }
+}
+
+/// Shared RPC rate limiter: waits (async) only as long as `rpc_min_interval_ms` requires
+pub async fn throttle_rpc_call() {
+    let interval_ms = GLOBAL_CONFIG.get().map(|c| c.rpc_min_interval_ms).unwrap_or(0);
+    let wait = rpc_call_wait("rpc_call", Duration::from_millis(interval_ms), Instant::now());
+    if !wait.is_zero() {
+        #[cfg(feature = "verbose_logging")]
+        println!("[{}] - [RATE_GUARD] RPC call queued for {:.2?}", Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), wait);
+        tokio::time::sleep(wait).await;
+    }
+}
+
+/// Per-vendor throttle events: (vendor, queued, skipped)
+pub fn get_vendor_throttle_stats() -> Vec<(String, usize, usize)> {
+    let mut stats: Vec<(String, usize, usize)> = VENDOR_THROTTLE_EVENTS
+        .iter()
+        .map(|e| (e.key().to_string(), e.value().0, e.value().1))
+        .collect();
+    stats.sort();
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reserve_send_slot() {
+        let now = Instant::now();
+        let interval = Duration::from_millis(100);
+        let max_wait = Duration::from_millis(150);
+
+        assert_eq!(reserve_send_slot("test_skip", interval, false, max_wait, now), ThrottleDecision::Send);
+        assert_eq!(reserve_send_slot("test_skip", interval, false, max_wait, now + Duration::from_millis(50)), ThrottleDecision::Skip);
+        assert_eq!(reserve_send_slot("test_skip", interval, false, max_wait, now + Duration::from_millis(100)), ThrottleDecision::Send);
+
+        assert_eq!(reserve_send_slot("test_queue", interval, true, max_wait, now), ThrottleDecision::Send);
+        assert_eq!(reserve_send_slot("test_queue", interval, true, max_wait, now), ThrottleDecision::Queue(interval));
+        // Second queued send lands one more interval later, beyond max_wait -> skipped
+        assert_eq!(reserve_send_slot("test_queue", interval, true, max_wait, now), ThrottleDecision::Skip);
+
+        assert_eq!(reserve_send_slot("test_off", Duration::ZERO, false, max_wait, now), ThrottleDecision::Send);
+        assert_eq!(reserve_send_slot("test_off", Duration::ZERO, false, max_wait, now), ThrottleDecision::Send);
+    }
+
+    #[test]
+    fn test_rpc_call_waits_only_when_over_budget() {
+        let now = Instant::now();
+        let interval = Duration::from_millis(1000);
+        // First call goes straight through, no unconditional sleep
+        assert_eq!(rpc_call_wait("test_rpc", interval, now), Duration::ZERO);
+        assert_eq!(rpc_call_wait("test_rpc", interval, now + Duration::from_millis(400)), Duration::from_millis(600));
+        assert_eq!(rpc_call_wait("test_rpc", interval, now + Duration::from_millis(5000)), Duration::ZERO);
+        assert_eq!(rpc_call_wait("test_rpc_off", Duration::ZERO, now), Duration::ZERO);
+    }
+}
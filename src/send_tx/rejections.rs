@@ -0,0 +1,227 @@
+use crate::config_load::GLOBAL_CONFIG;
+use crate::grpc::arpc_worker::get_current_slot;
+use crate::send_tx::tip_accounts::is_tip_account;
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::Transaction;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// Which trade a vendor send belongs to, for rejection records
+#[derive(Debug, Clone)]
+pub struct TradeTag {
+    pub mint: Pubkey,
+    pub tx_type: String,
+    pub side: &'static str, // "buy" or "sell"
+}
+
+/// Coarse classification of a vendor error message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RejectionKind {
+    Http,
+    GrpcStatus,
+    BundleRejected,
+    Timeout,
+    Throttled,
+    ExpiredBlockhash,
+    Other,
+}
+
+impl RejectionKind {
+    pub fn classify(error: &str) -> Self {
+        let e = error.to_ascii_lowercase();
+        if e.contains("blockhash") && e.contains("expired") {
+            RejectionKind::ExpiredBlockhash
+        } else if e.contains("throttled") {
+            RejectionKind::Throttled
+        } else if e.contains("timed out") || e.contains("timeout") {
+            RejectionKind::Timeout
+        } else if e.contains("bundle") {
+            RejectionKind::BundleRejected
+        } else if e.contains("status:") || e.contains("grpc") {
+            RejectionKind::GrpcStatus
+        } else if e.contains("http") || e.contains("status code") {
+            RejectionKind::Http
+        } else {
+            RejectionKind::Other
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            RejectionKind::Http => "http",
+            RejectionKind::GrpcStatus => "grpc_status",
+            RejectionKind::BundleRejected => "bundle_rejected",
+            RejectionKind::Timeout => "timeout",
+            RejectionKind::Throttled => "throttled",
+            RejectionKind::ExpiredBlockhash => "expired_blockhash",
+            RejectionKind::Other => "other",
+        }
+    }
+}
+
+/// One vendor rejection, kept for later analysis
+#[derive(Debug, Clone)]
+pub struct RejectedTx {
+    pub at: DateTime<Utc>,
+    pub corr_id: String,
+    pub vendor: String,
+    pub kind: RejectionKind,
+    pub mint: Pubkey,
+    pub tx_type: String,
+    pub side: &'static str,
+    pub tip_lamports: u64,
+    pub cu_limit: Option<u32>,
+    pub cu_price: Option<u64>,
+    pub slot: u64,
+    pub error: String,
+}
+
+impl RejectedTx {
+    pub fn summary(&self) -> String {
+        format!(
+            "{} cid={} {} {} {} mint={} tx_type={} tip={} cu_limit={} cu_price={} slot={} error={}",
+            self.at.format("%Y-%m-%d %H:%M:%S%.3f"),
+            self.corr_id,
+            self.side,
+            self.vendor,
+            self.kind.name(),
+            self.mint,
+            self.tx_type,
+            self.tip_lamports,
+            self.cu_limit.map_or("-".to_string(), |v| v.to_string()),
+            self.cu_price.map_or("-".to_string(), |v| v.to_string()),
+            self.slot,
+            self.error
+        )
+    }
+}
+
+// Most recent rejections, oldest first; bounded by `rejection_log_size`
+static REJECTIONS: Lazy<Mutex<VecDeque<RejectedTx>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
+
+/// (cu_limit, cu_price, tip lamports) as set by the transaction's compute-budget and tip-transfer instructions
+fn fee_details(tx: &Transaction, is_tip: impl Fn(&Pubkey) -> bool) -> (Option<u32>, Option<u64>, u64) {
+    let keys = &tx.message.account_keys;
+    let mut cu_limit = None;
+    let mut cu_price = None;
+    let mut tip = 0;
+    for ix in &tx.message.instructions {
+        let program = match keys.get(ix.program_id_index as usize) {
+            Some(program) => program,
+            None => continue,
+        };
+        let data = &ix.data;
+        if *program == solana_sdk::compute_budget::id() {
+            match data.first() {
+                Some(2) if data.len() >= 5 => cu_limit = Some(u32::from_le_bytes(data[1..5].try_into().unwrap())),
+                Some(3) if data.len() >= 9 => cu_price = Some(u64::from_le_bytes(data[1..9].try_into().unwrap())),
+                _ => {}
+            }
+        } else if *program == solana_sdk::system_program::id() && data.len() >= 12 && data[..4] == [2, 0, 0, 0] {
+            // SystemInstruction::Transfer { lamports }, destination is the second account
+            let to = ix.accounts.get(1).and_then(|&i| keys.get(i as usize));
+            if to.map_or(false, |to| is_tip(to)) {
+                tip += u64::from_le_bytes(data[4..12].try_into().unwrap());
+            }
+        }
+    }
+    (cu_limit, cu_price, tip)
+}
+
+/// Lamports a transaction transfers to vendor tip accounts
+pub fn tip_lamports(tx: &Transaction) -> u64 {
+    fee_details(tx, is_tip_account).2
+}
+
+fn push_bounded(buffer: &mut VecDeque<RejectedTx>, rejection: RejectedTx, capacity: usize) {
+    buffer.push_back(rejection);
+    while buffer.len() > capacity {
+        buffer.pop_front();
+    }
+}
+
+/// Record a vendor rejection (no-op unless `rejection_log_size` > 0)
+pub fn record_rejection(vendor: &str, tx: &Transaction, trade: &TradeTag, corr_id: &str, error: &str) {
+    let capacity = GLOBAL_CONFIG.get().map(|c| c.rejection_log_size).unwrap_or(0);
+    if capacity == 0 {
+        return;
+    }
+    let (cu_limit, cu_price, tip_lamports) = fee_details(tx, is_tip_account);
+    let rejection = RejectedTx {
+        at: Utc::now(),
+        corr_id: corr_id.to_string(),
+        vendor: vendor.to_string(),
+        kind: RejectionKind::classify(error),
+        mint: trade.mint,
+        tx_type: trade.tx_type.clone(),
+        side: trade.side,
+        tip_lamports,
+        cu_limit,
+        cu_price,
+        slot: get_current_slot(),
+        error: error.to_string(),
+    };
+    if let Ok(mut buffer) = REJECTIONS.lock() {
+        push_bounded(&mut buffer, rejection, capacity);
+    }
+}
+
+/// The last `n` rejections, newest first
+pub fn recent_rejections(n: usize) -> Vec<RejectedTx> {
+    REJECTIONS
+        .lock()
+        .map(|buffer| buffer.iter().rev().take(n).cloned().collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signature::{Keypair, Signer};
+
+    #[test]
+    fn test_fee_details_and_classification() {
+        let payer = Keypair::new();
+        let tip_account = Pubkey::new_unique();
+        let instructions = vec![
+            solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_limit(120_000),
+            solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_price(5_000),
+            solana_sdk::system_instruction::transfer(&payer.pubkey(), &tip_account, 1_000_000),
+            solana_sdk::system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 42),
+        ];
+        let tx = Transaction::new_with_payer(&instructions, Some(&payer.pubkey()));
+        assert_eq!(fee_details(&tx, |k| *k == tip_account), (Some(120_000), Some(5_000), 1_000_000));
+
+        assert_eq!(RejectionKind::classify("Jito send failed: bundle dropped"), RejectionKind::BundleRejected);
+        assert_eq!(RejectionKind::classify("zeroslot send throttled (min interval 5ms)"), RejectionKind::Throttled);
+        assert_eq!(RejectionKind::classify("status: Unavailable, message: ..."), RejectionKind::GrpcStatus);
+        assert_eq!(RejectionKind::classify("blockhash abc expired (block height ~Some(10))"), RejectionKind::ExpiredBlockhash);
+        assert_eq!(RejectionKind::classify("something odd"), RejectionKind::Other);
+    }
+
+    #[test]
+    fn test_push_bounded_keeps_newest() {
+        let mut buffer = VecDeque::new();
+        for i in 0..5u64 {
+            let rejection = RejectedTx {
+                at: Utc::now(),
+                corr_id: String::new(),
+                vendor: "jito".to_string(),
+                kind: RejectionKind::Other,
+                mint: Pubkey::default(),
+                tx_type: String::new(),
+                side: "buy",
+                tip_lamports: i,
+                cu_limit: None,
+                cu_price: None,
+                slot: i,
+                error: String::new(),
+            };
+            push_bounded(&mut buffer, rejection, 3);
+        }
+        let slots: Vec<u64> = buffer.iter().map(|r| r.slot).collect();
+        assert_eq!(slots, vec![2, 3, 4]);
+    }
+}
@@ -15,6 +15,12 @@ use solana_sdk::signature::Signer;
 use solana_client::rpc_config::RpcSendTransactionConfig;
 use solana_sdk::hash::Hash;
 use solana_program::system_instruction;
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+use solana_client::tpu_client::{TpuClient, TpuClientConfig};
+use solana_quic_client::{QuicConfig, QuicConnectionManager, QuicPool};
 
 
 // Global slice of RPC clients
@@ -22,9 +28,16 @@ pub static GLOBAL_SEND_RPC_CLIENTS: OnceCell<Arc<RwLock<Vec<Arc<RpcClient>>>>> =
 
 /// Initialize the global RPC clients from config.send_rpc
 pub fn initialize_send_rpc_clients(config: &Config) {
+    if !crate::build_tx::tx_builder::vendor_enabled("rpc", &config.send_rpc) {
+        eprintln!("[SendRPC] WARNING: sendRPC is empty - rpc vendor disabled, transactions go to the other vendors only");
+    }
+    if SendRpcMode::from_name(&config.send_rpc_mode).is_none() {
+        eprintln!("[SendRPC] WARNING: unknown send_rpc_mode '{}', using sequential", config.send_rpc_mode);
+    }
     let clients: Vec<Arc<RpcClient>> = config
         .send_rpc
         .iter()
+        .filter(|url| !url.trim().is_empty())
         .map(|url| Arc::new(RpcClient::new(url.clone())))
         .collect();
     let _ = GLOBAL_SEND_RPC_CLIENTS.set(Arc::new(RwLock::new(clients)));
@@ -38,7 +51,81 @@ fn _init_blockhash_once() {
     let _ = GLOBAL_LATEST_BLOCKHASH.set(RwLock::new(Hash::default()));
 }
 
-/// Periodically fetch and cache the latest blockhash from the first send RPC client
+// lastValidBlockHeight per cached blockhash (older hashes kept until they expire)
+static BLOCKHASH_LAST_VALID_HEIGHT: Lazy<DashMap<Hash, u64>> = Lazy::new(DashMap::new);
+// Block height at the last refresh; the current height is extrapolated from it
+static BLOCK_HEIGHT_SAMPLE: Lazy<std::sync::RwLock<Option<(u64, Instant)>>> = Lazy::new(|| std::sync::RwLock::new(None));
+static EXPIRED_SENDS_SKIPPED: AtomicUsize = AtomicUsize::new(0);
+// Slot time used to extrapolate block height between refreshes
+const MS_PER_BLOCK: u64 = 400;
+
+/// Status of the regular (non-nonce) blockhash cache
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockhashCacheStatus {
+    pub blockhash: Hash,
+    pub last_valid_block_height: Option<u64>,
+    pub estimated_block_height: Option<u64>,
+    pub expired: bool,
+    pub expired_sends_skipped: usize,
+}
+
+/// Block height `elapsed` after it was sampled at `sampled_height`
+fn extrapolate_block_height(sampled_height: u64, elapsed: Duration) -> u64 {
+    sampled_height + elapsed.as_millis() as u64 / MS_PER_BLOCK
+}
+
+/// Current block height estimated from the last refresh
+pub fn estimated_block_height() -> Option<u64> {
+    BLOCK_HEIGHT_SAMPLE
+        .read()
+        .ok()
+        .and_then(|sample| *sample)
+        .map(|(height, at)| extrapolate_block_height(height, at.elapsed()))
+}
+
+/// Whether a cached blockhash is past its lastValidBlockHeight (unknown hashes are not expired)
+pub fn is_blockhash_expired(blockhash: &Hash) -> bool {
+    match (BLOCKHASH_LAST_VALID_HEIGHT.get(blockhash), estimated_block_height()) {
+        (Some(last_valid), Some(current)) => current > *last_valid,
+        _ => false,
+    }
+}
+
+/// Transactions starting with AdvanceNonceAccount use a durable nonce and never expire by height
+pub fn uses_durable_nonce(tx: &Transaction) -> bool {
+    let message = &tx.message;
+    message.instructions.first().map_or(false, |ix| {
+        message.account_keys.get(ix.program_id_index as usize) == Some(&solana_program::system_program::id())
+            && ix.data.get(..4) == Some(&4u32.to_le_bytes()[..])
+    })
+}
+
+/// Refuse to send a non-nonce transaction whose blockhash has already expired
+pub fn check_blockhash_not_expired(tx: &Transaction) -> Result<(), String> {
+    if uses_durable_nonce(tx) || !is_blockhash_expired(&tx.message.recent_blockhash) {
+        return Ok(());
+    }
+    EXPIRED_SENDS_SKIPPED.fetch_add(1, Ordering::Relaxed);
+    Err(format!(
+        "blockhash {} expired (block height ~{:?})",
+        tx.message.recent_blockhash,
+        estimated_block_height()
+    ))
+}
+
+pub async fn get_blockhash_cache_status() -> BlockhashCacheStatus {
+    let blockhash = get_cached_blockhash().await;
+    BlockhashCacheStatus {
+        blockhash,
+        last_valid_block_height: BLOCKHASH_LAST_VALID_HEIGHT.get(&blockhash).map(|h| *h),
+        estimated_block_height: estimated_block_height(),
+        expired: is_blockhash_expired(&blockhash),
+        expired_sends_skipped: EXPIRED_SENDS_SKIPPED.load(Ordering::Relaxed),
+    }
+}
+
+/// Periodically fetch and cache the latest blockhash (with its lastValidBlockHeight)
+/// and the current block height from the first send RPC client
 pub async fn keep_blockhash_fresh() {
     let clients = GLOBAL_SEND_RPC_CLIENTS
         .get()
@@ -49,8 +136,9 @@ pub async fn keep_blockhash_fresh() {
         interval.tick().await;
         let clients_guard = clients.read().await;
         if let Some(client) = clients_guard.get(0) {
-            match client.get_latest_blockhash() {
-                Ok(blockhash) => {
+            match client.get_latest_blockhash_with_commitment(client.commitment()) {
+                Ok((blockhash, last_valid_block_height)) => {
+                    BLOCKHASH_LAST_VALID_HEIGHT.insert(blockhash, last_valid_block_height);
                     if let Some(lock) = GLOBAL_LATEST_BLOCKHASH.get() {
                         let mut hash_guard = lock.write().await;
                         *hash_guard = blockhash;
@@ -60,6 +148,17 @@ pub async fn keep_blockhash_fresh() {
                     eprintln!("[Blockhash] Failed to fetch latest blockhash: {}", e);
                 }
             }
+            match client.get_block_height() {
+                Ok(height) => {
+                    if let Ok(mut sample) = BLOCK_HEIGHT_SAMPLE.write() {
+                        *sample = Some((height, Instant::now()));
+                    }
+                    BLOCKHASH_LAST_VALID_HEIGHT.retain(|_, last_valid| *last_valid >= height);
+                }
+                Err(e) => {
+                    eprintln!("[Blockhash] Failed to fetch block height: {}", e);
+                }
+            }
         }
     }
 }
@@ -74,32 +173,103 @@ pub async fn get_cached_blockhash() -> Hash {
         .clone()
 }
 
-/// Send a transaction by looping through the list of send RPCs and sending via RPC call
+/// How a transaction is sent to the configured send RPCs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SendRpcMode {
+    Sequential, // one at a time, stop at the first success (no duplicate sends)
+    Parallel,   // all at once, first success wins (lowest latency)
+}
+
+impl SendRpcMode {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "sequential" => Some(SendRpcMode::Sequential),
+            "parallel" => Some(SendRpcMode::Parallel),
+            _ => None,
+        }
+    }
+}
+
+fn send_rpc_mode() -> SendRpcMode {
+    crate::config_load::GLOBAL_CONFIG
+        .get()
+        .and_then(|c| SendRpcMode::from_name(&c.send_rpc_mode))
+        .unwrap_or(SendRpcMode::Sequential)
+}
+
+type SendAttempt = Box<dyn FnOnce() -> Result<String, String> + Send>;
+
+/// Try each send in order; returns the index and signature of the first success
+fn send_sequential(sends: Vec<SendAttempt>) -> Result<(usize, String), String> {
+    for (i, send) in sends.into_iter().enumerate() {
+        match send() {
+            Ok(sig) => return Ok((i, sig)),
+            Err(e) => eprintln!("[SendRPC {}] Failed to send transaction: {}", i, e),
+        }
+    }
+    Err("All send RPCs failed to send transaction".to_string())
+}
+
+/// Run all sends concurrently; the first success wins and the losers are aborted
+async fn send_parallel(sends: Vec<SendAttempt>) -> Result<(usize, String), String> {
+    let mut set = tokio::task::JoinSet::new();
+    for (i, send) in sends.into_iter().enumerate() {
+        set.spawn_blocking(move || (i, send()));
+    }
+    while let Some(joined) = set.join_next().await {
+        match joined {
+            Ok((i, Ok(sig))) => {
+                // In-flight blocking sends finish in the background; their results are discarded
+                set.abort_all();
+                return Ok((i, sig));
+            }
+            Ok((i, Err(e))) => eprintln!("[SendRPC {}] Failed to send transaction: {}", i, e),
+            Err(e) => eprintln!("[SendRPC] Send task failed: {}", e),
+        }
+    }
+    Err("All send RPCs failed to send transaction".to_string())
+}
+
+/// Send a transaction to the send RPCs, sequentially or fanned out per `send_rpc_mode`
 pub async fn send_tx_via_send_rpcs(tx: &Transaction) -> Result<String, String> {
+    crate::send_tx::rate_guard::throttle_vendor_send("rpc").await?;
     let clients = GLOBAL_SEND_RPC_CLIENTS
         .get()
         .expect("Send RPC clients not initialized")
         .clone();
     let clients_guard = clients.read().await;
-    for (i, client) in clients_guard.iter().enumerate() {
-        match client.send_transaction_with_config(
-            tx,
-            RpcSendTransactionConfig {
-                skip_preflight: true,
-                ..RpcSendTransactionConfig::default()
-            },
-        ) {
-            Ok(sig) => {
-                println!("[SendRPC {}] Sent transaction: {}", i, sig);
-                return Ok(sig.to_string());
-            }
-            Err(e) => {
-                eprintln!("[SendRPC {}] Failed to send transaction: {}", i, e);
-            }
-        }
+    if clients_guard.is_empty() {
+        return Err("No send RPCs configured (sendRPC is empty)".to_string());
     }
-    Err("All send RPCs failed to send transaction".to_string())
-} 
+    let sends: Vec<SendAttempt> = clients_guard
+        .iter()
+        .map(|client| {
+            let client = Arc::clone(client);
+            let tx = tx.clone();
+            Box::new(move || {
+                client
+                    .send_transaction_with_config(
+                        &tx,
+                        RpcSendTransactionConfig {
+                            skip_preflight: true,
+                            ..RpcSendTransactionConfig::default()
+                        },
+                    )
+                    .map(|sig| sig.to_string())
+                    .map_err(|e| e.to_string())
+            }) as SendAttempt
+        })
+        .collect();
+    drop(clients_guard);
+
+    let mode = send_rpc_mode();
+    let (winner, sig) = match mode {
+        SendRpcMode::Sequential => send_sequential(sends)?,
+        SendRpcMode::Parallel => send_parallel(sends).await?,
+    };
+    println!("[SendRPC {}] Sent transaction ({:?}): {}", winner, mode, sig);
+    Ok(sig)
+}
 
 pub fn create_instruction_rpc(
     cu_limit: u32,
@@ -110,7 +280,7 @@ pub fn create_instruction_rpc(
     nonce_account: &Pubkey,
 ) -> Vec<Instruction> {
     // let limit_ix = compute_budget::ComputeBudgetInstruction::set_compute_unit_limit(cu_limit);
-    let cu_price_tip = ((tip_amount / cu_limit as u64) as f64 * 1_000_000.0) as u64;
+    let cu_price_tip = crate::send_tx::cu_price::clamp_configured_cu_price(((tip_amount / cu_limit as u64) as f64 * 1_000_000.0) as u64);
     let price_ix = compute_budget::ComputeBudgetInstruction::set_compute_unit_price(cu_price_tip);
 
     let keypair = get_wallet_keypair();
@@ -125,3 +295,109 @@ pub fn create_instruction_rpc(
     result.extend(instructions);
     result
 }
+
+// Direct-to-leader QUIC sender (vendor "quic"); tracks the leader schedule itself over the websocket
+static QUIC_TPU_CLIENT: OnceCell<TpuClient<QuicPool, QuicConnectionManager, QuicConfig>> = OnceCell::new();
+
+/// Start the QUIC TPU sender in the background (it connects to the websocket and fetches the leader
+/// schedule before it can send; until then "quic" sends fail fast)
+pub fn initialize_quic_sender(config: &Config) {
+    if !config.quic_send {
+        return;
+    }
+    let rpc = Arc::new(RpcClient::new(config.rpc_endpoint.clone()));
+    let ws_url = config.quic_ws_url.clone();
+    let fanout_slots = config.quic_fanout_slots;
+    // TpuClient::new blocks on its own runtime, so it can't be built on a tokio worker
+    std::thread::spawn(move || match TpuClient::new(rpc, &ws_url, TpuClientConfig { fanout_slots }) {
+        Ok(client) => {
+            let _ = QUIC_TPU_CLIENT.set(client);
+            println!("[QUIC] TPU sender ready (leader schedule via {}, fanout {} slots)", ws_url, fanout_slots);
+        }
+        Err(e) => eprintln!("[QUIC] TPU sender unavailable, quic sends will fail: {}", e),
+    });
+}
+
+/// Send straight to the upcoming leaders' TPU over QUIC; returns our signature once handed off
+pub async fn send_tx_quic(transaction: &Transaction) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    let client = QUIC_TPU_CLIENT.get().ok_or("QUIC TPU sender not ready")?;
+    let signature = transaction.signatures.first().ok_or("unsigned transaction")?.to_string();
+    let wire = bincode::serialize(transaction)?;
+    tokio::task::spawn_blocking(move || client.try_send_wire_transaction(wire))
+        .await?
+        .map_err(|e| format!("QUIC TPU send failed: {}", e))?;
+    Ok(signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signature::Keypair;
+
+    #[test]
+    fn test_block_height_extrapolation() {
+        assert_eq!(extrapolate_block_height(1_000, Duration::ZERO), 1_000);
+        assert_eq!(extrapolate_block_height(1_000, Duration::from_millis(399)), 1_000);
+        assert_eq!(extrapolate_block_height(1_000, Duration::from_secs(60)), 1_150);
+    }
+
+    #[test]
+    fn test_expired_blockhash_skips_only_non_nonce_sends() {
+        let payer = Keypair::new();
+        let blockhash = Hash::new_unique();
+        let transfer = system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1);
+        let advance = system_instruction::advance_nonce_account(&Pubkey::new_unique(), &payer.pubkey());
+
+        let regular = Transaction::new_signed_with_payer(&[transfer.clone()], Some(&payer.pubkey()), &[&payer], blockhash);
+        let nonce = Transaction::new_signed_with_payer(&[advance, transfer], Some(&payer.pubkey()), &[&payer], blockhash);
+        assert!(!uses_durable_nonce(&regular));
+        assert!(uses_durable_nonce(&nonce));
+
+        // Unknown validity is never treated as expired
+        assert!(check_blockhash_not_expired(&regular).is_ok());
+
+        *BLOCK_HEIGHT_SAMPLE.write().unwrap() = Some((2_000, Instant::now()));
+        BLOCKHASH_LAST_VALID_HEIGHT.insert(blockhash, 1_999);
+        assert!(is_blockhash_expired(&blockhash));
+        assert!(check_blockhash_not_expired(&regular).is_err());
+        assert!(check_blockhash_not_expired(&nonce).is_ok());
+
+        BLOCKHASH_LAST_VALID_HEIGHT.insert(blockhash, 2_150);
+        assert!(check_blockhash_not_expired(&regular).is_ok());
+    }
+
+    fn mock_send(result: Result<&'static str, &'static str>, delay_ms: u64, calls: &Arc<AtomicUsize>) -> SendAttempt {
+        let calls = Arc::clone(calls);
+        Box::new(move || {
+            calls.fetch_add(1, Ordering::SeqCst);
+            std::thread::sleep(Duration::from_millis(delay_ms));
+            result.map(str::to_string).map_err(str::to_string)
+        })
+    }
+
+    #[test]
+    fn test_send_sequential_stops_at_first_success() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let sends = vec![mock_send(Err("timeout"), 0, &calls), mock_send(Ok("sig1"), 0, &calls), mock_send(Ok("sig2"), 0, &calls)];
+        assert_eq!(send_sequential(sends), Ok((1, "sig1".to_string())));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let sends = vec![mock_send(Err("timeout"), 0, &calls)];
+        assert!(send_sequential(sends).is_err());
+        assert_eq!(SendRpcMode::from_name("Parallel"), Some(SendRpcMode::Parallel));
+        assert_eq!(SendRpcMode::from_name("other"), None);
+    }
+
+    #[tokio::test]
+    async fn test_send_parallel_first_success_wins() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let sends = vec![mock_send(Ok("slow"), 300, &calls), mock_send(Err("rejected"), 0, &calls), mock_send(Ok("fast"), 20, &calls)];
+        let start = Instant::now();
+        assert_eq!(send_parallel(sends).await, Ok((2, "fast".to_string())));
+        assert!(start.elapsed() < Duration::from_millis(300));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        let sends = vec![mock_send(Err("a"), 0, &calls), mock_send(Err("b"), 0, &calls)];
+        assert!(send_parallel(sends).await.is_err());
+    }
+}
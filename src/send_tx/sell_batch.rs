@@ -0,0 +1,159 @@
+use crate::build_tx::tx_builder::fits_packet;
+use crate::utils::lifecycle::TradeLifecycle;
+use once_cell::sync::Lazy;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+// Runtime ceiling on a transaction's compute unit limit
+const MAX_TX_COMPUTE_UNITS: u64 = 1_400_000;
+
+/// A sell instruction waiting for its batch window to close, with what the send path tracks per position
+pub struct BatchedSell {
+    pub mint: Pubkey,
+    pub instruction: Instruction,
+    pub tx_type: String,
+    pub token_amount: u64,
+    pub entry_price_usd: Option<f64>,
+    pub key: Vec<u8>,
+    pub sig_detect: String,
+    pub corr_id: String,
+    pub lifecycle: TradeLifecycle,
+    pub expected_sol_out: Option<u64>,
+    // Sends this sell alone at (slippage bps, retry), for when the batch can't carry it
+    pub resend: Box<dyn FnOnce(u64, u32) + Send>,
+}
+
+/// Sells collected during one batch window
+#[derive(Default)]
+pub struct SellBatcher {
+    pending: Mutex<Vec<BatchedSell>>,
+}
+
+impl SellBatcher {
+    /// Queue `sell`; returns true if it opened a new window, which the caller must flush
+    pub fn push(&self, sell: BatchedSell) -> bool {
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        pending.push(sell);
+        pending.len() == 1
+    }
+
+    /// Close the window, taking every queued sell
+    pub fn drain(&self) -> Vec<BatchedSell> {
+        std::mem::take(&mut *self.pending.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+/// Whether `instructions` fit one transaction: under the packet size once the CU limit, an ATA
+/// creation and the vendor's own instructions are added, and under the runtime's CU ceiling at
+/// `cu_per_sell` each
+pub fn fits_sell_batch(instructions: &[Instruction], payer: &Pubkey, owner: &Pubkey, cu_per_sell: u32) -> bool {
+    if instructions.len() as u64 * cu_per_sell as u64 > MAX_TX_COMPUTE_UNITS {
+        return false;
+    }
+    // Stand-in for the first sell's mint; counted as an extra key, so the estimate errs large by 32 bytes
+    let mint = Pubkey::new_from_array([1; 32]);
+    let mut full = vec![
+        ComputeBudgetInstruction::set_compute_unit_limit(MAX_TX_COMPUTE_UNITS as u32),
+        create_associated_token_account_idempotent(payer, owner, &mint, &spl_token::id()),
+    ];
+    full.extend_from_slice(instructions);
+    fits_packet(&full, payer)
+}
+
+/// Greedily group `sells` in arrival order into transactions that pass `fits`; a sell that
+/// doesn't fit even alone still gets its own group (the single-sell path would send it the same way)
+pub fn pack_sells<T>(
+    sells: Vec<T>,
+    instruction_of: impl Fn(&T) -> &Instruction,
+    fits: impl Fn(&[Instruction]) -> bool,
+) -> Vec<Vec<T>> {
+    let mut groups: Vec<Vec<T>> = Vec::new();
+    let mut current: Vec<T> = Vec::new();
+    let mut current_ixs: Vec<Instruction> = Vec::new();
+    for sell in sells {
+        current_ixs.push(instruction_of(&sell).clone());
+        if !current.is_empty() && !fits(&current_ixs) {
+            groups.push(std::mem::take(&mut current));
+            current_ixs = vec![instruction_of(&sell).clone()];
+        }
+        current.push(sell);
+    }
+    if !current.is_empty() {
+        groups.push(current);
+    }
+    groups
+}
+
+static SELL_BATCHER: Lazy<SellBatcher> = Lazy::new(SellBatcher::default);
+static SELLS_BATCHED: AtomicUsize = AtomicUsize::new(0);
+static SELL_BATCH_TXS: AtomicUsize = AtomicUsize::new(0);
+
+/// (sells sent through a batch, batch transactions built for them)
+pub fn get_sell_batch_stats() -> (usize, usize) {
+    (SELLS_BATCHED.load(Ordering::Relaxed), SELL_BATCH_TXS.load(Ordering::Relaxed))
+}
+
+/// Queue `sell` for the current window; the sell that opens a window flushes it after `window`,
+/// handing `send` each packed group
+pub fn batch_sell(
+    sell: BatchedSell,
+    window: Duration,
+    fits: impl Fn(&[Instruction]) -> bool + Send + 'static,
+    send: impl Fn(Vec<BatchedSell>) + Send + 'static,
+) {
+    if !SELL_BATCHER.push(sell) {
+        return;
+    }
+    std::thread::spawn(move || {
+        std::thread::sleep(window);
+        for group in pack_sells(SELL_BATCHER.drain(), |sell| &sell.instruction, &fits) {
+            SELLS_BATCHED.fetch_add(group.len(), Ordering::Relaxed);
+            SELL_BATCH_TXS.fetch_add(1, Ordering::Relaxed);
+            send(group);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::instruction::AccountMeta;
+
+    fn sell_ix(mint: Pubkey, extra_accounts: usize) -> Instruction {
+        let mut accounts = vec![AccountMeta::new_readonly(mint, false)];
+        accounts.extend((0..extra_accounts).map(|_| AccountMeta::new(Pubkey::new_unique(), false)));
+        Instruction::new_with_bytes(Pubkey::new_unique(), &[0u8; 24], accounts)
+    }
+
+    #[test]
+    fn test_small_sells_share_a_transaction_and_large_batch_splits() {
+        let payer = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let fits = |ixs: &[Instruction]| fits_sell_batch(ixs, &payer, &owner, 100_000);
+
+        // Three small sells fit one transaction
+        let small: Vec<Instruction> = (0..3).map(|_| sell_ix(Pubkey::new_unique(), 1)).collect();
+        let groups = pack_sells(small, |ix| ix, fits);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].len(), 3);
+
+        // Account-heavy sells overflow the packet: split, every sell kept, order preserved
+        let large: Vec<Instruction> = (0..6).map(|_| sell_ix(Pubkey::new_unique(), 10)).collect();
+        let order: Vec<Pubkey> = large.iter().map(|ix| ix.program_id).collect();
+        let groups = pack_sells(large, |ix| ix, fits);
+        assert!(groups.len() > 1);
+        assert!(groups.iter().all(|group| fits(&group[..]) || group.len() == 1));
+        let packed: Vec<Pubkey> = groups.iter().flatten().map(|ix| ix.program_id).collect();
+        assert_eq!(packed, order);
+
+        // The CU ceiling splits too, even when the bytes would fit
+        let cu_bound = |ixs: &[Instruction]| fits_sell_batch(ixs, &payer, &owner, 800_000);
+        let groups = pack_sells((0..2).map(|_| sell_ix(Pubkey::new_unique(), 1)).collect(), |ix| ix, cu_bound);
+        assert_eq!(groups.len(), 2);
+    }
+}
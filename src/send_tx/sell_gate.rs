@@ -0,0 +1,208 @@
+use crate::send_tx::confirm_tracker::{watch, Resolution};
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use solana_sdk::signature::Signature;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender, TryRecvError};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// How long the confirmation tracker gets to see a buy before its held sells are released anyway
+pub const BUY_CONFIRM_DEADLINE_SLOTS: u64 = 60;
+const BUY_CONFIRM_TIMEOUT: Duration = Duration::from_secs(30);
+// How often the shared waiter checks the buys it holds sells for
+const GATE_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+pub type QueuedSell = Box<dyn FnOnce() + Send>;
+
+/// Sells held per buy signature until that buy is confirmed; buys without a gate sell immediately.
+/// Keyed by the buy rather than the mint, so a second buy of the same mint never releases (or
+/// cancels) the first one's sells.
+#[derive(Default)]
+pub struct SellGate {
+    pending: Mutex<HashMap<Signature, Vec<QueuedSell>>>,
+}
+
+/// What happened to a buy's held sells when it resolved
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GateRelease {
+    Fired(usize),     // buy confirmed (or never confirmed in time): held sells sent
+    Cancelled(usize), // buy failed on chain: nothing to sell
+}
+
+impl SellGate {
+    /// Hold sells of buy `sig` until `resolve`
+    pub fn hold(&self, sig: Signature) {
+        self.pending.lock().unwrap_or_else(|e| e.into_inner()).entry(sig).or_default();
+    }
+
+    /// Fire `sell` now if buy `sig` isn't held, else queue it; returns whether it fired
+    pub fn submit(&self, sig: Signature, sell: QueuedSell) -> bool {
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(queue) = pending.get_mut(&sig) {
+            queue.push(sell);
+            return false;
+        }
+        drop(pending);
+        sell();
+        true
+    }
+
+    /// Lift the gate on buy `sig`: fire its queued sells, or drop them if the buy failed
+    pub fn resolve(&self, sig: Signature, buy_failed: bool) -> GateRelease {
+        let queued = self.pending.lock().unwrap_or_else(|e| e.into_inner()).remove(&sig).unwrap_or_default();
+        let count = queued.len();
+        if buy_failed {
+            return GateRelease::Cancelled(count);
+        }
+        for sell in queued {
+            sell();
+        }
+        GateRelease::Fired(count)
+    }
+}
+
+static SELL_GATE: Lazy<SellGate> = Lazy::new(SellGate::default);
+static SELLS_HELD: AtomicUsize = AtomicUsize::new(0);
+
+/// Sells that had to wait for their buy's confirmation
+pub fn get_sells_held() -> usize {
+    SELLS_HELD.load(Ordering::Relaxed)
+}
+
+/// A held buy the shared waiter is watching
+struct GatedBuy {
+    sig: Signature,
+    corr_id: String,
+    resolution: Receiver<Resolution>,
+    deadline: Instant,
+}
+
+// One waiter thread resolves every held buy, instead of a blocked thread per buy
+static GATE_WAITER: Lazy<Mutex<Sender<GatedBuy>>> = Lazy::new(|| {
+    let (gated, incoming) = channel();
+    std::thread::spawn(move || run_gate_waiter(incoming));
+    Mutex::new(gated)
+});
+
+/// How a held buy resolves, if it has: (buy failed, note for the log)
+fn gate_outcome(buy: &GatedBuy, now: Instant) -> Option<(bool, String)> {
+    match buy.resolution.try_recv() {
+        Ok(Resolution::Landed { err: None, slot }) => Some((false, format!("confirmed in slot {}", slot))),
+        Ok(Resolution::Landed { err: Some(err), .. }) => Some((true, format!("failed on chain ({:?})", err))),
+        // Never seen over RPC; release rather than strand the tokens
+        Ok(_) | Err(TryRecvError::Disconnected) => Some((false, "not confirmed in time, releasing anyway".to_string())),
+        Err(TryRecvError::Empty) if now >= buy.deadline => Some((false, "not confirmed in time, releasing anyway".to_string())),
+        Err(TryRecvError::Empty) => None,
+    }
+}
+
+fn run_gate_waiter(incoming: Receiver<GatedBuy>) {
+    let mut held: Vec<GatedBuy> = Vec::new();
+    loop {
+        // Idle: block until a buy is held; otherwise just pick up new ones
+        if held.is_empty() {
+            match incoming.recv() {
+                Ok(buy) => held.push(buy),
+                Err(_) => return,
+            }
+        }
+        held.extend(incoming.try_iter());
+        let now = Instant::now();
+        held.retain(|buy| match gate_outcome(buy, now) {
+            Some((buy_failed, note)) => {
+                let release = SELL_GATE.resolve(buy.sig, buy_failed);
+                println!("[{}] - [SELL_GATE] [cid={}] Buy {} {}: {:?}",
+                    Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), buy.corr_id, buy.sig, note, release);
+                false
+            }
+            None => true,
+        });
+        std::thread::sleep(GATE_POLL_INTERVAL);
+    }
+}
+
+/// Hold buy `sig`'s sells until the confirmation tracker sees it land
+pub fn hold_until_confirmed(sig: Signature, corr_id: String) {
+    SELL_GATE.hold(sig);
+    let buy = GatedBuy {
+        sig,
+        corr_id,
+        resolution: watch(sig, BUY_CONFIRM_DEADLINE_SLOTS),
+        deadline: Instant::now() + BUY_CONFIRM_TIMEOUT,
+    };
+    if GATE_WAITER.lock().unwrap_or_else(|e| e.into_inner()).send(buy).is_err() {
+        SELL_GATE.resolve(sig, false);
+    }
+}
+
+/// Send the sell of buy `sig` through the gate; returns false if it was queued behind the buy's confirmation
+pub fn submit_sell(sig: Signature, sell: QueuedSell) -> bool {
+    let fired = SELL_GATE.submit(sig, sell);
+    if !fired {
+        SELLS_HELD.fetch_add(1, Ordering::Relaxed);
+    }
+    fired
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_sell_before_confirmation_waits() {
+        let gate = SellGate::default();
+        let buy = Signature::new_unique();
+        let sold = Arc::new(AtomicUsize::new(0));
+        let sell = || {
+            let sold = Arc::clone(&sold);
+            Box::new(move || {
+                sold.fetch_add(1, Ordering::SeqCst);
+            }) as QueuedSell
+        };
+
+        // Sell signal arrives before the buy confirms: queued, not sent
+        gate.hold(buy);
+        assert!(!gate.submit(buy, sell()));
+        assert_eq!(sold.load(Ordering::SeqCst), 0);
+
+        // Confirmation fires it
+        assert_eq!(gate.resolve(buy, false), GateRelease::Fired(1));
+        assert_eq!(sold.load(Ordering::SeqCst), 1);
+
+        // No gate: immediate
+        assert!(gate.submit(buy, sell()));
+        assert_eq!(sold.load(Ordering::SeqCst), 2);
+
+        // Failed buy: held sells dropped
+        gate.hold(buy);
+        assert!(!gate.submit(buy, sell()));
+        assert_eq!(gate.resolve(buy, true), GateRelease::Cancelled(1));
+        assert_eq!(sold.load(Ordering::SeqCst), 2);
+
+        // A second buy of the same mint is gated on its own: the first one confirming doesn't fire it
+        let (first, second) = (Signature::new_unique(), Signature::new_unique());
+        gate.hold(first);
+        gate.hold(second);
+        assert!(!gate.submit(second, sell()));
+        assert_eq!(gate.resolve(first, false), GateRelease::Fired(0));
+        assert_eq!(sold.load(Ordering::SeqCst), 2);
+        assert_eq!(gate.resolve(second, false), GateRelease::Fired(1));
+        assert_eq!(sold.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_gate_outcome() {
+        let (notify, resolution) = channel();
+        let now = Instant::now();
+        let buy = GatedBuy { sig: Signature::new_unique(), corr_id: String::new(), resolution, deadline: now + Duration::from_secs(1) };
+        assert_eq!(gate_outcome(&buy, now), None);
+        // Past its deadline without a resolution: released anyway
+        assert_eq!(gate_outcome(&buy, now + Duration::from_secs(2)).map(|(failed, _)| failed), Some(false));
+
+        notify.send(Resolution::Landed { slot: 5, err: Some(solana_sdk::transaction::TransactionError::AccountInUse) }).unwrap();
+        assert_eq!(gate_outcome(&buy, now).map(|(failed, _)| failed), Some(true));
+    }
+}
@@ -0,0 +1,199 @@
+use crate::config_load::Config;
+use crate::send_tx::confirm_tracker::{watch, Resolution};
+use chrono::Utc;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::TransactionError;
+use std::sync::mpsc::TryRecvError;
+use std::time::{Duration, Instant};
+
+// Sell status watch: the Triton feed filters failed transactions, so a failed sell is only visible over RPC
+pub const SELL_STATUS_DEADLINE_SLOTS: u64 = 25;
+pub const SELL_STATUS_TIMEOUT: Duration = Duration::from_secs(15);
+// How often the per-vendor watches are checked while waiting for one of them to land
+const SELL_STATUS_POLL: Duration = Duration::from_millis(20);
+
+/// Program error codes meaning "output below the minimum" for sells that carry a slippage bound
+fn slippage_error_codes(tx_type: &str) -> &'static [u32] {
+    match tx_type {
+        "pumpfun" => &[6003],    // TooLittleSolReceived
+        "pump_swap" => &[6004],  // ExceededSlippage
+        "ray_launch" => &[6004], // ExceededSlippage
+        _ => &[],                // ray_cpmm sells are built without a minimum out
+    }
+}
+
+/// Whether this sell path takes a slippage bound that widening can relax
+pub fn supports_widening(tx_type: &str) -> bool {
+    !slippage_error_codes(tx_type).is_empty()
+}
+
+/// A `tx_type` sell failed because its output fell below the slippage minimum
+pub fn is_slippage_error(tx_type: &str, err: &TransactionError) -> bool {
+    match err {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => {
+            slippage_error_codes(tx_type).contains(code)
+        }
+        _ => false,
+    }
+}
+
+/// Retry policy for sells that fail on slippage
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SlippageWidening {
+    pub step_bps: u64,
+    pub max_bps: u64,
+    pub max_retries: u32,
+}
+
+impl SlippageWidening {
+    /// None when `sell_slippage_max_bps` is 0 (widening off)
+    pub fn from_config(config: &Config) -> Option<Self> {
+        (config.sell_slippage_max_bps > 0).then(|| SlippageWidening {
+            step_bps: config.sell_slippage_step_bps,
+            max_bps: config.sell_slippage_max_bps,
+            max_retries: config.sell_slippage_retries,
+        })
+    }
+
+    /// Slippage for retry number `retry` (1-based) after failing at `current_bps`;
+    /// None once the retries or the ceiling are used up
+    pub fn next_bps(&self, current_bps: u64, retry: u32) -> Option<u64> {
+        if retry > self.max_retries || current_bps >= self.max_bps || self.step_bps == 0 {
+            return None;
+        }
+        Some((current_bps + self.step_bps).min(self.max_bps))
+    }
+}
+
+/// Wait on the confirmation tracker for the vendor copies `sigs` of one sell (any of them may be the
+/// one that lands): Some(err) if one landed and failed, None if it succeeded or none showed up within
+/// `deadline_slots` (`timeout` guards against the tracker not running)
+pub fn wait_for_failure(sigs: &[Signature], deadline_slots: u64, timeout: Duration) -> Option<TransactionError> {
+    let mut watching: Vec<_> = sigs.iter().map(|sig| watch(*sig, deadline_slots)).collect();
+    let deadline = Instant::now() + timeout;
+    while !watching.is_empty() && Instant::now() < deadline {
+        let mut unresolved = Vec::with_capacity(watching.len());
+        for resolution in watching {
+            match resolution.try_recv() {
+                Ok(Resolution::Landed { err, .. }) => return err,
+                Err(TryRecvError::Empty) => unresolved.push(resolution),
+                _ => {}
+            }
+        }
+        watching = unresolved;
+        std::thread::sleep(SELL_STATUS_POLL);
+    }
+    None
+}
+
+/// Wait for the sell sent as `sigs` to resolve; if it failed on slippage (for any of `tx_types`, the
+/// sells it carried), call `resend` (on the blocking pool) with the next wider slippage and retry
+/// number. Returns the slippage resent at.
+#[allow(clippy::too_many_arguments)]
+pub async fn retry_sell_on_slippage<F>(
+    sigs: Vec<Signature>,
+    tx_types: Vec<String>,
+    slippage_bps: u64,
+    retry: u32,
+    widening: SlippageWidening,
+    corr_id: String,
+    resend: F,
+) -> Option<u64>
+where
+    F: FnOnce(u64, u32) + Send + 'static,
+{
+    let watched = sigs.clone();
+    let failure = tokio::task::spawn_blocking(move || {
+        wait_for_failure(&watched, SELL_STATUS_DEADLINE_SLOTS, SELL_STATUS_TIMEOUT)
+    })
+    .await
+    .ok()
+    .flatten();
+    let err = match failure {
+        Some(err) if tx_types.iter().any(|tx_type| is_slippage_error(tx_type, &err)) => err,
+        _ => return None,
+    };
+    let sig = sigs.first().map(|sig| sig.to_string()).unwrap_or_default();
+    match widening.next_bps(slippage_bps, retry + 1) {
+        Some(wider) => {
+            println!("[{}] - [TRITON] [cid={}] Sell {} failed on slippage ({:?}) at {} bps, retry {}/{} at {} bps",
+                Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), corr_id, sig, err, slippage_bps, retry + 1, widening.max_retries, wider);
+            let _ = tokio::task::spawn_blocking(move || resend(wider, retry + 1)).await;
+            Some(wider)
+        }
+        None => {
+            eprintln!("[{}] - [TRITON] [cid={}] Sell {} failed on slippage ({:?}) at {} bps, no retries left (max {} bps, {} retries)",
+                Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), corr_id, sig, err, slippage_bps, widening.max_bps, widening.max_retries);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::send_tx::confirm_tracker::poll_with_statuses;
+
+    #[test]
+    fn test_slippage_error_class() {
+        let slippage = TransactionError::InstructionError(3, InstructionError::Custom(6004));
+        assert!(is_slippage_error("pump_swap", &slippage));
+        assert!(is_slippage_error("ray_launch", &slippage));
+        assert!(!is_slippage_error("pumpfun", &slippage));
+        assert!(is_slippage_error("pumpfun", &TransactionError::InstructionError(3, InstructionError::Custom(6003))));
+        assert!(!is_slippage_error("pump_swap", &TransactionError::InsufficientFundsForFee));
+        assert!(!supports_widening("ray_cpmm"));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_slippage_failure_retries_wider_and_succeeds() {
+        let widening = SlippageWidening { step_bps: 500, max_bps: 2_000, max_retries: 3 };
+        let slippage = TransactionError::InstructionError(3, InstructionError::Custom(6004));
+        // Three vendor copies of the sell; the second vendor's lands, failing on slippage
+        let sigs: Vec<Signature> = (0..3).map(|_| Signature::new_unique()).collect();
+        let landed = sigs[1];
+
+        let (resent_tx, resent) = std::sync::mpsc::channel();
+        let retrying = tokio::spawn(retry_sell_on_slippage(
+            sigs, vec!["pump_swap".to_string()], 500, 0, widening, "cafe0001".to_string(),
+            move |bps, retry| { let _ = resent_tx.send((bps, retry)); },
+        ));
+        // Feed the tracker until the watches are registered and the landing is seen
+        let fed = tokio::task::spawn_blocking(move || {
+            for _ in 0..500 {
+                if resent.try_recv() == Ok((1_000, 1)) {
+                    break;
+                }
+                poll_with_statuses(|sig| (*sig == landed).then(|| (9, Some(slippage.clone()))));
+                std::thread::sleep(Duration::from_millis(10));
+            }
+        });
+        let wider = tokio::time::timeout(Duration::from_secs(5), retrying).await.unwrap().unwrap();
+        assert_eq!(wider, Some(1_000));
+        tokio::time::timeout(Duration::from_secs(5), fed).await.unwrap().unwrap();
+    }
+
+    #[test]
+    fn test_success_on_any_vendor_is_not_a_failure() {
+        let sigs: Vec<Signature> = (0..2).map(|_| Signature::new_unique()).collect();
+        let landed = sigs[0];
+        let feeder = std::thread::spawn(move || {
+            for _ in 0..50 {
+                poll_with_statuses(|sig| (*sig == landed).then(|| (9, None)));
+                std::thread::sleep(Duration::from_millis(10));
+            }
+        });
+        assert_eq!(wait_for_failure(&sigs, 10, Duration::from_secs(5)), None);
+        feeder.join().unwrap();
+    }
+
+    #[test]
+    fn test_widening_caps() {
+        let widening = SlippageWidening { step_bps: 500, max_bps: 1_200, max_retries: 2 };
+        assert_eq!(widening.next_bps(500, 1), Some(1_000));
+        assert_eq!(widening.next_bps(1_000, 2), Some(1_200));
+        assert_eq!(widening.next_bps(1_200, 2), None);
+        assert_eq!(widening.next_bps(500, 3), None);
+    }
+}
@@ -0,0 +1,149 @@
+use crate::config_load::GLOBAL_CONFIG;
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Global cap on concurrent `send_all_vendors_parallel` calls. Each call fans out to every vendor,
+/// so this bounds peak connections during a burst.
+pub struct SendLimiter {
+    slots: Option<Arc<Semaphore>>, // None: unlimited, only counted
+    queue_timeout: Duration,       // how long a buy over the cap waits for a slot before it is dropped
+    in_flight: Arc<AtomicUsize>,
+    cap_hits: AtomicUsize,
+    dropped: AtomicUsize,
+}
+
+/// Held for the duration of one parallel send
+pub struct SendSlot {
+    _permit: Option<OwnedSemaphorePermit>,
+    in_flight: Arc<AtomicUsize>,
+}
+
+impl Drop for SendSlot {
+    fn drop(&mut self) {
+        self.in_flight.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl SendLimiter {
+    /// `max_in_flight` of 0 is unlimited; a `queue_timeout` of zero drops buys over the cap at once
+    pub fn new(max_in_flight: usize, queue_timeout: Duration) -> Self {
+        SendLimiter {
+            slots: (max_in_flight > 0).then(|| Arc::new(Semaphore::new(max_in_flight))),
+            queue_timeout,
+            in_flight: Arc::new(AtomicUsize::new(0)),
+            cap_hits: AtomicUsize::new(0),
+            dropped: AtomicUsize::new(0),
+        }
+    }
+
+    /// A send slot, waiting up to `queue_timeout` when at the cap; None if the send is to be dropped.
+    /// Only buys are dropped: a sell over the cap waits for a slot however long it takes, since
+    /// dropping it would strand the position.
+    pub async fn acquire(&self, sell: bool) -> Option<SendSlot> {
+        let permit = match &self.slots {
+            None => None,
+            Some(slots) => match slots.clone().try_acquire_owned() {
+                Ok(permit) => Some(permit),
+                Err(_) => {
+                    self.cap_hits.fetch_add(1, Ordering::Relaxed);
+                    let queued = if sell {
+                        Ok(slots.clone().acquire_owned().await)
+                    } else {
+                        tokio::time::timeout(self.queue_timeout, slots.clone().acquire_owned()).await
+                    };
+                    match queued {
+                        Ok(Ok(permit)) => Some(permit),
+                        _ => {
+                            self.dropped.fetch_add(1, Ordering::Relaxed);
+                            return None;
+                        }
+                    }
+                }
+            },
+        };
+        self.in_flight.fetch_add(1, Ordering::Relaxed);
+        Some(SendSlot { _permit: permit, in_flight: self.in_flight.clone() })
+    }
+
+    /// (sends in flight, sends that hit the cap, sends dropped at the cap)
+    pub fn stats(&self) -> (usize, usize, usize) {
+        (
+            self.in_flight.load(Ordering::Relaxed),
+            self.cap_hits.load(Ordering::Relaxed),
+            self.dropped.load(Ordering::Relaxed),
+        )
+    }
+}
+
+static SEND_LIMITER: Lazy<SendLimiter> = Lazy::new(|| {
+    let (max, queue_ms) = GLOBAL_CONFIG.get().map_or((0, 0), |c| (c.max_in_flight_sends, c.in_flight_send_queue_ms));
+    SendLimiter::new(max, Duration::from_millis(queue_ms))
+});
+
+/// Slot for one parallel `side` send under `max_in_flight_sends`; None (logged) when a buy is dropped
+/// at the cap (sells queue instead)
+pub async fn acquire_send_slot(corr_id: &str, side: &str) -> Option<SendSlot> {
+    let slot = SEND_LIMITER.acquire(side == "sell").await;
+    if slot.is_none() {
+        let (in_flight, _, dropped) = SEND_LIMITER.stats();
+        eprintln!("[{}] - [SEND_LIMIT] [cid={}] Dropped send: {} sends in flight (total dropped: {})",
+            Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), corr_id, in_flight, dropped);
+    }
+    slot
+}
+
+/// (sends in flight, sends that hit the cap, sends dropped at the cap)
+pub fn get_send_limit_stats() -> (usize, usize, usize) {
+    SEND_LIMITER.stats()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_flood_capped() {
+        let limiter = Arc::new(SendLimiter::new(4, Duration::from_secs(5)));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let tasks: Vec<_> = (0..64)
+            .map(|_| {
+                let (limiter, peak) = (limiter.clone(), peak.clone());
+                tokio::spawn(async move {
+                    let _slot = limiter.acquire(false).await.expect("queued send dropped");
+                    peak.fetch_max(limiter.stats().0, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(5)).await;
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+        let (in_flight, cap_hits, dropped) = limiter.stats();
+        assert!(peak.load(Ordering::SeqCst) <= 4);
+        assert_eq!(in_flight, 0);
+        assert!(cap_hits > 0);
+        assert_eq!(dropped, 0);
+
+        // No queueing: buys over the cap are dropped
+        let strict = Arc::new(SendLimiter::new(1, Duration::ZERO));
+        let held = strict.acquire(false).await;
+        assert!(held.is_some());
+        assert!(strict.acquire(false).await.is_none());
+        assert_eq!(strict.stats(), (1, 1, 1));
+
+        // A sell over the cap waits for the slot instead
+        let waiting = {
+            let strict = strict.clone();
+            tokio::spawn(async move { strict.acquire(true).await.is_some() })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!waiting.is_finished());
+        drop(held);
+        assert!(tokio::time::timeout(Duration::from_secs(1), waiting).await.unwrap().unwrap());
+        assert_eq!(strict.stats().2, 1);
+    }
+}
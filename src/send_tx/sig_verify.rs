@@ -0,0 +1,71 @@
+use chrono::Utc;
+use solana_sdk::transaction::Transaction;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static SIGNATURE_MISMATCHES: AtomicUsize = AtomicUsize::new(0);
+
+/// Vendor replies that didn't carry the signature of the transaction we sent
+pub fn get_signature_mismatches() -> usize {
+    SIGNATURE_MISMATCHES.load(Ordering::Relaxed)
+}
+
+/// Vendors whose accept reply may be their own transaction ID rather than the tx signature
+/// (flashblock's `transactionIds`), so a differing value there is expected, not a bug
+fn returns_own_id(vendor: &str) -> bool {
+    matches!(vendor, "flashblock")
+}
+
+/// Check a vendor's returned signature against `tx.signatures[0]`. Returns the signature to key
+/// bookkeeping on: always ours, since a mangled one would never match the landed transaction.
+/// Mismatches from vendors that should echo the signature are counted and alerted.
+pub fn verify_vendor_signature(vendor: &str, returned: &str, tx: &Transaction, corr_id: &str) -> String {
+    let expected = match tx.signatures.first() {
+        Some(sig) => sig.to_string(),
+        None => return returned.to_string(),
+    };
+    if returned.trim_matches('"') == expected || returns_own_id(vendor) {
+        return expected;
+    }
+    SIGNATURE_MISMATCHES.fetch_add(1, Ordering::Relaxed);
+    eprintln!(
+        "[{}] - [SIG_VERIFY] !!! [cid={}] {} returned signature {} but we sent {} (total mismatches: {})",
+        Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+        corr_id,
+        vendor,
+        returned,
+        expected,
+        get_signature_mismatches()
+    );
+    expected
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::pubkey::Pubkey;
+    use solana_sdk::signature::{Keypair, Signature, Signer};
+
+    #[test]
+    fn test_mangled_signature_detected() {
+        let payer = Keypair::new();
+        let ix = solana_sdk::system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1);
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[&payer], Default::default());
+        let ours = tx.signatures[0].to_string();
+
+        // Echoed (quoted or not): no mismatch
+        let before = get_signature_mismatches();
+        assert_eq!(verify_vendor_signature("zeroslot", &ours, &tx, "t"), ours);
+        assert_eq!(verify_vendor_signature("nextblock", &format!("\"{}\"", ours), &tx, "t"), ours);
+        assert_eq!(get_signature_mismatches(), before);
+
+        // A different signature is counted, and bookkeeping still gets ours
+        let mangled = Signature::new_unique().to_string();
+        assert_eq!(verify_vendor_signature("blockrazor", &mangled, &tx, "t"), ours);
+        assert!(get_signature_mismatches() > before);
+
+        // Flashblock's own transaction ID is not a mismatch
+        let counted = get_signature_mismatches();
+        assert_eq!(verify_vendor_signature("flashblock", "fb-7f3a", &tx, "t"), ours);
+        assert_eq!(get_signature_mismatches(), counted);
+    }
+}
@@ -2,7 +2,6 @@ use crate::init::wallet_loader::{get_wallet_keypair, get_nonce_account};
 use base64::{engine::general_purpose, Engine as _};
 use once_cell::sync::Lazy;
 use reqwest::Client;
-use rand::seq::SliceRandom;
 use solana_sdk::{
     pubkey::Pubkey,
     signature::{Keypair, Signer},
@@ -11,15 +10,14 @@ use solana_sdk::{
 };
 use solana_sdk::instruction::Instruction;
 use crate::config_load::GLOBAL_CONFIG;
-use rand::Rng;
 use solana_sdk::compute_budget;
-use std::str::FromStr;
 use std::time::Instant;
 use std::error::Error;
 use chrono::Utc;
+use crate::send_tx::tx_buffer::{recycle_buffer, take_buffer};
 
 // Temporal tip accounts as specified in the documentation
-static TEMPORAL_TIP_ACCOUNTS: &[&str] = &[
+pub(crate) static TEMPORAL_TIP_ACCOUNTS: &[&str] = &[
     "TEMPaMeCRFAS9EKF53Jd6KpHxgL47uWLcpFArU1Fanq",
     "noz3jAjPiHuBPqiSPkkugaJDkJscPuRhYnSpbi8UvC4",
     "noz3str9KXfpKknefHji8L1mPgimezaiUyCHYMDv1GE",
@@ -41,25 +39,22 @@ static TEMPORAL_TIP_ACCOUNTS: &[&str] = &[
 
 // Global HTTP client with connection pooling for optimal performance
 static HTTP_CLIENT: Lazy<Client> = Lazy::new(|| {
-    Client::builder()
-        .pool_max_idle_per_host(50) // Keep up to 50 idle connections per host
+    let builder = Client::builder()
+        .pool_max_idle_per_host(crate::send_tx::http_pool::http_pool_size()) // Keep up to http_pool_size idle connections per host
         .pool_idle_timeout(std::time::Duration::from_secs(120)) // Keep connections alive for 2 minutes
-        .tcp_keepalive(Some(std::time::Duration::from_secs(30))) // Enable TCP keep-alive
+        .tcp_keepalive(Some(std::time::Duration::from_secs(30))) // Enable TCP keep-alive (tcp_keepalive_secs overrides)
         .timeout(std::time::Duration::from_secs(3)) // 3 second timeout for larger transactions
-        .connect_timeout(std::time::Duration::from_millis(500)) // 500ms connect timeout
+        .connect_timeout(std::time::Duration::from_millis(500)); // 500ms connect timeout
+    // TCP_NODELAY and configured keepalive
+    crate::utils::socket_tuning::tune_reqwest(builder, crate::utils::socket_tuning::socket_tuning())
         .build()
         .expect("Failed to create HTTP client")
 });
 
 /// Create a system transfer instruction for Temporal tips
 pub fn temporal_tip(tip: u64, from_pubkey: &Pubkey) -> Instruction {
-    // Randomly select a tip account from the list
-    let tip_account = TEMPORAL_TIP_ACCOUNTS
-        .choose(&mut rand::thread_rng())
-        .expect("Failed to select random tip account");
-    
-    let tip_pubkey = Pubkey::from_str(tip_account).expect("Invalid pubkey");
-    system_instruction::transfer(from_pubkey, &tip_pubkey, tip)
+    // Random tip account (configured via vendor_tip_accounts or built-in list), precomputed at startup
+    crate::send_tx::tip_accounts::tip_instruction("temporal", tip, from_pubkey)
 }
 
 /// Build compute budget instructions for Temporal with optimizations
@@ -76,11 +71,9 @@ pub fn create_instruction_temporal(
     println!("[{}] - [TEMPORAL_INSTRUCTION_PROFILE] 🔧 Starting Temporal instruction building", 
         now.format("%Y-%m-%d %H:%M:%S%.3f"));
     
-    // Step 1: Random number generation for compute unit price variation
+    // Step 1: Random compute unit price variation, clamped to [min_cu_price, maxCUPrice]
     let rng_start = Instant::now();
-    let mut rng = rand::thread_rng();
-    let random_addition: u64 = rng.gen_range(1..=100);
-    let adjusted_cu_price = cu_price + random_addition;
+    let adjusted_cu_price = crate::send_tx::cu_price::adjust_cu_price(cu_price, &instructions);
     let rng_time = rng_start.elapsed();
     
     #[cfg(feature = "verbose_logging")]
@@ -149,6 +142,7 @@ pub fn create_instruction_temporal(
 /// Send a signed Solana transaction via Temporal HTTP API with optimizations
 /// This implements the sendTransaction method as described in the Temporal documentation
 pub async fn send_tx_temporal(tx: &Transaction) -> Result<String, Box<dyn std::error::Error>> {
+    crate::send_tx::rate_guard::throttle_vendor_send("temporal").await?;
     let total_start = Instant::now();
     let now = Utc::now();
     
@@ -168,7 +162,7 @@ pub async fn send_tx_temporal(tx: &Transaction) -> Result<String, Box<dyn std::e
     
     // Step 1: Serialize transaction (measure serialization time)
     let serialize_start = Instant::now();
-    let mut buffer = Vec::with_capacity(4096); // Pre-allocate 4KB buffer
+    let mut buffer = take_buffer(); // Pooled 4KB buffer, reused across sends
     bincode::serialize_into(&mut buffer, tx)?;
     let serialize_time = serialize_start.elapsed();
     
@@ -179,6 +173,7 @@ pub async fn send_tx_temporal(tx: &Transaction) -> Result<String, Box<dyn std::e
     // Step 2: Base64 encoding (measure encoding time)
     let encode_start = Instant::now();
     let tx_b64 = general_purpose::STANDARD.encode(&buffer);
+    recycle_buffer(buffer);
     let encode_time = encode_start.elapsed();
     
     #[cfg(feature = "verbose_logging")]
@@ -227,12 +222,14 @@ pub async fn send_tx_temporal(tx: &Transaction) -> Result<String, Box<dyn std::e
         #[cfg(feature = "verbose_logging")]
         println!("[TEMPORAL_DEBUG] 🔄 Attempt {}/{}", attempt, max_retries);
         
+        let pool_slot = crate::send_tx::http_pool::acquire_pool_slot("temporal").await;
         response_result = Some(HTTP_CLIENT
             .post(&config.temporal_url)
             .header("Content-Type", "application/json")
             .json(&request_body)
             .send()
             .await);
+        drop(pool_slot);
         
         match &response_result {
             Some(Ok(_)) => {
@@ -369,7 +366,7 @@ pub async fn send_tx_temporal(tx: &Transaction) -> Result<String, Box<dyn std::e
     
     // Calculate total time and breakdown
     let total_time = total_start.elapsed();
-    let processing_time = total_time - network_time; // Time spent in our code
+    let processing_time = total_time.saturating_sub(network_time); // Time spent in our code
     
     #[cfg(feature = "verbose_logging")]
     println!("[{}] - [TEMPORAL_PROFILE] ✅ Transaction sent successfully!", 
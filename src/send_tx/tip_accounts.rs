@@ -0,0 +1,220 @@
+use crate::config_load::{Config, GLOBAL_CONFIG};
+use once_cell::sync::OnceCell;
+use rand::seq::SliceRandom;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::system_instruction;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+// Resolved tip accounts per vendor (config override or built-in list)
+static TIP_ACCOUNTS: OnceCell<HashMap<&'static str, Vec<Pubkey>>> = OnceCell::new();
+
+/// Built-in tip account lists, used when `vendor_tip_accounts` has no entry for a vendor
+pub fn default_tip_accounts(vendor: &str) -> &'static [&'static str] {
+    match vendor {
+        "zeroslot" => crate::send_tx::zero_slot::ZEROSLOT_TIP_ACCOUNTS,
+        "nextblock" => crate::send_tx::nextblock::NEXTBLOCK_TIP_ACCOUNTS,
+        "blockrazor" => crate::send_tx::block_razor::BLOCKRAZOR_TIP_ACCOUNTS,
+        "flashblock" => crate::send_tx::flashblock::FLASHBLOCK_TIP_ACCOUNTS,
+        "astralane" => crate::send_tx::astralane::ASTRALANE_TIP_ACCOUNTS,
+        "temporal" => crate::send_tx::temporal::TEMPORAL_TIP_ACCOUNTS,
+        "jito" => crate::send_tx::jito::JITO_TIP_ACCOUNTS,
+        _ => &[],
+    }
+}
+
+const TIP_VENDORS: &[&str] = &["zeroslot", "nextblock", "blockrazor", "flashblock", "astralane", "temporal", "jito"];
+
+/// Resolve and validate the tip accounts for every vendor. Errors name the vendor and bad entry.
+pub fn resolve_tip_accounts(overrides: &HashMap<String, Vec<String>>) -> Result<HashMap<&'static str, Vec<Pubkey>>, String> {
+    for vendor in overrides.keys() {
+        if !TIP_VENDORS.contains(&vendor.as_str()) {
+            return Err(format!("vendor_tip_accounts: unknown vendor '{}'", vendor));
+        }
+    }
+    let mut resolved = HashMap::new();
+    for &vendor in TIP_VENDORS {
+        let accounts: Vec<&str> = match overrides.get(vendor) {
+            Some(list) if !list.is_empty() => list.iter().map(|s| s.as_str()).collect(),
+            _ => default_tip_accounts(vendor).to_vec(),
+        };
+        let pubkeys = accounts
+            .iter()
+            .map(|a| Pubkey::from_str(a).map_err(|e| format!("vendor_tip_accounts.{}: invalid pubkey '{}': {}", vendor, a, e)))
+            .collect::<Result<Vec<_>, _>>()?;
+        resolved.insert(vendor, pubkeys);
+    }
+    Ok(resolved)
+}
+
+/// Validate and install the configured tip accounts; call once at startup
+pub fn init_tip_accounts(config: &Config) -> Result<(), String> {
+    let resolved = resolve_tip_accounts(&config.vendor_tip_accounts)?;
+    for vendor in config.vendor_tip_accounts.keys() {
+        println!("[TIP_ACCOUNTS] {} using {} configured tip accounts", vendor, resolved[vendor.as_str()].len());
+    }
+    let _ = TIP_ACCOUNTS.set(resolved);
+    let _ = MIN_TIPS.set(resolve_min_tips(&config.vendor_min_tips)?);
+    Ok(())
+}
+
+fn tip_accounts() -> &'static HashMap<&'static str, Vec<Pubkey>> {
+    TIP_ACCOUNTS.get_or_init(|| {
+        let overrides = GLOBAL_CONFIG.get().map(|c| c.vendor_tip_accounts.clone()).unwrap_or_default();
+        resolve_tip_accounts(&overrides).expect("Invalid vendor_tip_accounts")
+    })
+}
+
+/// Pick a random tip account for `vendor`
+pub fn random_tip_account(vendor: &str) -> Pubkey {
+    *tip_accounts()
+        .get(vendor)
+        .and_then(|list| list.choose(&mut rand::thread_rng()))
+        .expect("Failed to select random tip account")
+}
+
+/// True if `pubkey` is a tip account of any vendor
+pub fn is_tip_account(pubkey: &Pubkey) -> bool {
+    tip_accounts().values().any(|list| list.contains(pubkey))
+}
+
+/// Per-vendor static tip inputs, resolved once so a trade only picks an index and a tip amount
+pub struct VendorTipContext {
+    pub tip_accounts: Vec<Pubkey>,
+}
+
+impl VendorTipContext {
+    pub fn tip_ix(&self, from_pubkey: &Pubkey, index: usize, tip: u64) -> Instruction {
+        system_instruction::transfer(from_pubkey, &self.tip_accounts[index % self.tip_accounts.len()], tip)
+    }
+
+    pub fn random_tip_ix(&self, from_pubkey: &Pubkey, tip: u64) -> Instruction {
+        self.tip_ix(from_pubkey, rand::random::<usize>(), tip)
+    }
+}
+
+// Set at startup when precompute_tip_instructions is enabled
+static TIP_CONTEXTS: OnceCell<HashMap<&'static str, VendorTipContext>> = OnceCell::new();
+
+fn build_tip_contexts() -> HashMap<&'static str, VendorTipContext> {
+    tip_accounts()
+        .iter()
+        .filter(|(_, list)| !list.is_empty())
+        .map(|(&vendor, list)| (vendor, VendorTipContext { tip_accounts: list.clone() }))
+        .collect()
+}
+
+/// Precompute tip contexts; call once at startup
+pub fn init_tip_contexts() {
+    let contexts = build_tip_contexts();
+    println!("[TIP_ACCOUNTS] Precomputed tip contexts for {} vendors", contexts.len());
+    let _ = TIP_CONTEXTS.set(contexts);
+}
+
+// Resolved minimum tip per vendor, lamports (config override or built-in floor)
+static MIN_TIPS: OnceCell<HashMap<&'static str, u64>> = OnceCell::new();
+
+/// Published minimum tips (lamports) below which a vendor rejects or ignores the transaction
+pub fn default_min_tip(vendor: &str) -> u64 {
+    match vendor {
+        "jito" => 1_000,
+        "astralane" => 10_000,
+        "zeroslot" | "nextblock" | "blockrazor" | "flashblock" | "temporal" => 1_000_000,
+        _ => 0,
+    }
+}
+
+/// Minimum tip for every vendor, `vendor_min_tips` entries replacing the built-in floors
+pub fn resolve_min_tips(overrides: &HashMap<String, u64>) -> Result<HashMap<&'static str, u64>, String> {
+    if let Some(vendor) = overrides.keys().find(|vendor| !TIP_VENDORS.contains(&vendor.as_str())) {
+        return Err(format!("vendor_min_tips: unknown vendor '{}'", vendor));
+    }
+    Ok(TIP_VENDORS
+        .iter()
+        .map(|&vendor| (vendor, overrides.get(vendor).copied().unwrap_or_else(|| default_min_tip(vendor))))
+        .collect())
+}
+
+/// Minimum tip for `vendor`, lamports
+pub fn min_tip(vendor: &str) -> u64 {
+    MIN_TIPS
+        .get_or_init(|| {
+            let overrides = GLOBAL_CONFIG.get().map(|c| c.vendor_min_tips.clone()).unwrap_or_default();
+            resolve_min_tips(&overrides).expect("Invalid vendor_min_tips")
+        })
+        .get(vendor)
+        .copied()
+        .unwrap_or(0)
+}
+
+/// Tip transfer to a random tip account of `vendor`, from the precomputed context when available.
+/// A tip below the vendor's minimum is raised to it (dynamic tips can dip under).
+pub fn tip_instruction(vendor: &str, tip: u64, from_pubkey: &Pubkey) -> Instruction {
+    let tip = tip.max(min_tip(vendor));
+    match TIP_CONTEXTS.get().and_then(|contexts| contexts.get(vendor)) {
+        Some(context) => context.random_tip_ix(from_pubkey, tip),
+        None => system_instruction::transfer(from_pubkey, &random_tip_account(vendor), tip),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_tip_accounts() {
+        let resolved = resolve_tip_accounts(&HashMap::new()).unwrap();
+        assert_eq!(resolved["flashblock"].len(), default_tip_accounts("flashblock").len());
+        assert_eq!(resolved["nextblock"].len(), 1);
+
+        let mut overrides = HashMap::new();
+        overrides.insert("temporal".to_string(), vec!["11111111111111111111111111111111".to_string()]);
+        let resolved = resolve_tip_accounts(&overrides).unwrap();
+        assert_eq!(resolved["temporal"], vec![Pubkey::default()]);
+
+        overrides.insert("temporal".to_string(), vec!["not-a-pubkey".to_string()]);
+        assert!(resolve_tip_accounts(&overrides).unwrap_err().contains("temporal"));
+
+        let mut unknown = HashMap::new();
+        unknown.insert("nobody".to_string(), vec![]);
+        assert!(resolve_tip_accounts(&unknown).is_err());
+    }
+
+    #[test]
+    fn test_tip_below_vendor_minimum_raised() {
+        let lamports = |ix: Instruction| match bincode::deserialize(&ix.data).unwrap() {
+            system_instruction::SystemInstruction::Transfer { lamports } => lamports,
+            other => panic!("not a transfer: {:?}", other),
+        };
+        let from = Pubkey::new_unique();
+        // Built-in floors: jito's 1000 lamports
+        assert_eq!(lamports(tip_instruction("jito", 500, &from)), 1_000);
+        assert_eq!(lamports(tip_instruction("jito", 5_000, &from)), 5_000);
+        assert_eq!(lamports(tip_instruction("zeroslot", 1, &from)), default_min_tip("zeroslot"));
+
+        let mut overrides = HashMap::new();
+        overrides.insert("astralane".to_string(), 0);
+        let resolved = resolve_min_tips(&overrides).unwrap();
+        assert_eq!(resolved["astralane"], 0);
+        assert_eq!(resolved["jito"], 1_000);
+        overrides.insert("nobody".to_string(), 1);
+        assert!(resolve_min_tips(&overrides).is_err());
+    }
+
+    #[test]
+    fn test_precomputed_tip_ix_matches_parsed_path() {
+        let from = Pubkey::new_unique();
+        let contexts = build_tip_contexts();
+        for &vendor in TIP_VENDORS {
+            let context = &contexts[vendor];
+            for (index, account) in default_tip_accounts(vendor).iter().enumerate() {
+                let parsed = system_instruction::transfer(&from, &Pubkey::from_str(account).unwrap(), 1_000);
+                assert_eq!(context.tip_ix(&from, index, 1_000), parsed, "{} #{}", vendor, index);
+            }
+        }
+        // The payer is the caller's, not whoever built the contexts
+        let other = Pubkey::new_unique();
+        assert_eq!(contexts["jito"].tip_ix(&other, 0, 1_000).accounts[0].pubkey, other);
+    }
+}
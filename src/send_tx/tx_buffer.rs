@@ -0,0 +1,79 @@
+use crate::config_load::GLOBAL_CONFIG;
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Room for any transaction (PACKET_DATA_SIZE is 1232) without regrowing
+const BUFFER_CAPACITY: usize = 4096;
+// Buffers kept per thread; parallel vendor sends on one runtime thread each hold one until they recycle
+const POOL_SIZE: usize = 8;
+
+thread_local! {
+    static POOL: RefCell<Vec<Vec<u8>>> = RefCell::new(Vec::new());
+}
+
+static BUFFERS_REUSED: AtomicUsize = AtomicUsize::new(0);
+static BUFFERS_ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+fn reuse_enabled() -> bool {
+    GLOBAL_CONFIG.get().map_or(true, |c| c.reuse_send_buffers)
+}
+
+/// An empty serialization buffer, from this thread's pool when `reuse_send_buffers` is on
+pub fn take_buffer() -> Vec<u8> {
+    if reuse_enabled() {
+        if let Some(buffer) = POOL.with(|pool| pool.borrow_mut().pop()) {
+            BUFFERS_REUSED.fetch_add(1, Ordering::Relaxed);
+            return buffer;
+        }
+    }
+    BUFFERS_ALLOCATED.fetch_add(1, Ordering::Relaxed);
+    Vec::with_capacity(BUFFER_CAPACITY)
+}
+
+/// Hand a buffer back once its bytes have been encoded; dropped if the pool is full or reuse is off
+pub fn recycle_buffer(mut buffer: Vec<u8>) {
+    if !reuse_enabled() {
+        return;
+    }
+    buffer.clear();
+    POOL.with(|pool| {
+        let mut pool = pool.borrow_mut();
+        if pool.len() < POOL_SIZE {
+            pool.push(buffer);
+        }
+    });
+}
+
+/// (buffers reused, buffers allocated) since startup
+pub fn get_buffer_stats() -> (usize, usize) {
+    (BUFFERS_REUSED.load(Ordering::Relaxed), BUFFERS_ALLOCATED.load(Ordering::Relaxed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::pubkey::Pubkey;
+    use solana_sdk::signature::{Keypair, Signer};
+    use solana_sdk::transaction::Transaction;
+
+    #[test]
+    fn test_pooled_serialization_matches_fresh() {
+        let payer = Keypair::new();
+        let ix = solana_sdk::system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 42);
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[&payer], Default::default());
+
+        // Leave a dirty, used buffer in the pool
+        let mut buffer = take_buffer();
+        buffer.extend_from_slice(&[0xff; 2000]);
+        let capacity = buffer.capacity();
+        recycle_buffer(buffer);
+
+        let (reused_before, _) = get_buffer_stats();
+        let mut buffer = take_buffer();
+        assert_eq!(buffer.capacity(), capacity);
+        assert!(get_buffer_stats().0 > reused_before);
+        bincode::serialize_into(&mut buffer, &tx).unwrap();
+        assert_eq!(buffer, bincode::serialize(&tx).unwrap());
+        recycle_buffer(buffer);
+    }
+}
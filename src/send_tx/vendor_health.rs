@@ -0,0 +1,155 @@
+use crate::alerts::{alert, AlertKind};
+use chrono::Utc;
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+// Recent send outcomes kept per vendor; the failure rate is only judged on a full window
+const HEALTH_WINDOW: usize = 20;
+
+/// Per-vendor circuit breaker: disabled when the recent failure rate reaches the threshold,
+/// then one send per probe interval is let through; a successful probe re-enables it
+#[derive(Debug, Default)]
+struct VendorHealth {
+    outcomes: VecDeque<bool>, // true = accepted
+    disabled_since: Option<Instant>,
+    last_probe: Option<Instant>,
+}
+
+impl VendorHealth {
+    fn failure_pct(&self) -> f64 {
+        let failures = self.outcomes.iter().filter(|ok| !**ok).count();
+        failures as f64 * 100.0 / self.outcomes.len().max(1) as f64
+    }
+
+    /// Record a send outcome; returns Some(true) when this disabled the vendor, Some(false) when it re-enabled it
+    fn record(&mut self, ok: bool, failure_pct_threshold: f64, now: Instant) -> Option<bool> {
+        if self.disabled_since.is_some() {
+            if !ok {
+                return None;
+            }
+            self.disabled_since = None;
+            self.last_probe = None;
+            self.outcomes.clear();
+            return Some(false);
+        }
+        self.outcomes.push_back(ok);
+        if self.outcomes.len() > HEALTH_WINDOW {
+            self.outcomes.pop_front();
+        }
+        if failure_pct_threshold > 0.0 && self.outcomes.len() == HEALTH_WINDOW && self.failure_pct() >= failure_pct_threshold {
+            self.disabled_since = Some(now);
+            return Some(true);
+        }
+        None
+    }
+
+    /// Enabled vendors always get the send; a disabled one gets it as a probe once per interval
+    fn should_route(&mut self, probe_interval: Duration, now: Instant) -> bool {
+        let since = match self.disabled_since {
+            Some(since) => since,
+            None => return true,
+        };
+        let last = self.last_probe.unwrap_or(since);
+        if now.duration_since(last) >= probe_interval {
+            self.last_probe = Some(now);
+            return true;
+        }
+        false
+    }
+}
+
+static VENDOR_HEALTH: Lazy<DashMap<String, VendorHealth>> = Lazy::new(DashMap::new);
+
+fn thresholds() -> (f64, Duration) {
+    crate::config_load::GLOBAL_CONFIG.get().map_or((0.0, Duration::ZERO), |c| {
+        (c.vendor_auto_disable_failure_pct, Duration::from_secs(c.vendor_probe_interval_secs))
+    })
+}
+
+/// False if `vendor` is auto-disabled and not due for a recovery probe
+pub fn should_route(vendor: &str) -> bool {
+    let (failure_pct, probe_interval) = thresholds();
+    if failure_pct <= 0.0 {
+        return true;
+    }
+    VENDOR_HEALTH
+        .get_mut(vendor)
+        .map_or(true, |mut health| health.should_route(probe_interval, Instant::now()))
+}
+
+/// Record whether `vendor` accepted a send, logging disable/re-enable transitions
+pub fn record_send_outcome(vendor: &str, ok: bool) {
+    let (failure_pct, _) = thresholds();
+    if failure_pct <= 0.0 {
+        return;
+    }
+    let mut health = VENDOR_HEALTH.entry(vendor.to_string()).or_default();
+    match health.record(ok, failure_pct, Instant::now()) {
+        Some(true) => {
+            println!("[{}] - [VENDOR_HEALTH] {} disabled: {:.0}% of the last {} sends failed (threshold {:.0}%)",
+                Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), vendor, health.failure_pct(), HEALTH_WINDOW, failure_pct);
+            alert(AlertKind::CircuitBreakerOpen, format!("{} disabled: {:.0}% of the last {} sends failed",
+                vendor, health.failure_pct(), HEALTH_WINDOW));
+        }
+        Some(false) => println!("[{}] - [VENDOR_HEALTH] {} re-enabled: recovery probe accepted",
+            Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), vendor),
+        None => {}
+    }
+}
+
+/// Vendors currently auto-disabled
+pub fn disabled_vendors() -> Vec<String> {
+    VENDOR_HEALTH
+        .iter()
+        .filter(|entry| entry.disabled_since.is_some())
+        .map(|entry| entry.key().clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failing_vendor_disabled_then_probed() {
+        let mut health = VendorHealth::default();
+        let start = Instant::now();
+        let probe = Duration::from_secs(30);
+
+        // 50% failures stay below an 80% threshold
+        for i in 0..HEALTH_WINDOW {
+            assert_eq!(health.record(i % 2 == 0, 80.0, start), None);
+        }
+        assert!(health.should_route(probe, start));
+
+        // Failures push the window past the threshold
+        let mut disabled = false;
+        for _ in 0..HEALTH_WINDOW {
+            disabled |= health.record(false, 80.0, start) == Some(true);
+        }
+        assert!(disabled);
+        assert!(!health.should_route(probe, start + Duration::from_secs(1)));
+
+        // One probe per interval; a failed probe keeps it disabled
+        assert!(health.should_route(probe, start + probe));
+        assert!(!health.should_route(probe, start + probe + Duration::from_secs(1)));
+        assert_eq!(health.record(false, 80.0, start + probe), None);
+
+        // A successful probe re-enables it with a fresh window
+        assert!(health.should_route(probe, start + probe * 2));
+        assert_eq!(health.record(true, 80.0, start + probe * 2), Some(false));
+        assert!(health.should_route(probe, start + probe * 2));
+        assert!(health.outcomes.is_empty());
+    }
+
+    #[test]
+    fn test_zero_threshold_never_disables() {
+        let mut health = VendorHealth::default();
+        for _ in 0..HEALTH_WINDOW * 2 {
+            assert_eq!(health.record(false, 0.0, Instant::now()), None);
+        }
+        assert!(health.should_route(Duration::from_secs(30), Instant::now()));
+    }
+}
@@ -0,0 +1,119 @@
+use chrono::{TimeZone, Utc};
+use once_cell::sync::OnceCell;
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Mutex;
+
+/// Winning vendor counts for one time bucket
+#[derive(Debug, Clone, PartialEq)]
+pub struct WinBucket {
+    pub start_secs: u64,
+    pub wins: BTreeMap<String, usize>,
+}
+
+/// Rolling histogram of which vendor won each send, in `bucket_secs` buckets; only the last
+/// `max_buckets` are kept
+pub struct VendorWinHistogram {
+    buckets: Mutex<VecDeque<WinBucket>>,
+    bucket_secs: u64,
+    max_buckets: usize,
+}
+
+impl VendorWinHistogram {
+    pub fn new(bucket_secs: u64, max_buckets: usize) -> Self {
+        VendorWinHistogram { buckets: Mutex::new(VecDeque::new()), bucket_secs: bucket_secs.max(1), max_buckets: max_buckets.max(1) }
+    }
+
+    /// Count a win for `vendor` at `now_secs` (unix seconds)
+    pub fn record(&self, vendor: &str, now_secs: u64) {
+        let start_secs = now_secs - now_secs % self.bucket_secs;
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        if buckets.back().map_or(true, |bucket| bucket.start_secs < start_secs) {
+            buckets.push_back(WinBucket { start_secs, wins: BTreeMap::new() });
+            while buckets.len() > self.max_buckets {
+                buckets.pop_front();
+            }
+        }
+        // A win stamped before the newest bucket (clock skew) is counted in the newest one
+        if let Some(bucket) = buckets.back_mut() {
+            *bucket.wins.entry(vendor.to_string()).or_insert(0) += 1;
+        }
+    }
+
+    /// Kept buckets, oldest first
+    pub fn distribution(&self) -> Vec<WinBucket> {
+        self.buckets.lock().unwrap_or_else(|e| e.into_inner()).iter().cloned().collect()
+    }
+}
+
+/// "vendor=share%" for each vendor in `wins`, most wins first
+pub fn format_shares(wins: &BTreeMap<String, usize>) -> String {
+    let total: usize = wins.values().sum();
+    let mut sorted: Vec<(&String, &usize)> = wins.iter().collect();
+    sorted.sort_by(|a, b| b.1.cmp(a.1));
+    sorted
+        .iter()
+        .map(|(vendor, count)| format!("{}={:.0}%({})", vendor, **count as f64 / total.max(1) as f64 * 100.0, count))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+static VENDOR_WINS: OnceCell<VendorWinHistogram> = OnceCell::new();
+
+/// Start tracking winners when `vendor_win_bucket_mins` is set
+pub fn init_vendor_win_histogram(bucket_mins: u64, max_buckets: usize) {
+    if bucket_mins > 0 {
+        let _ = VENDOR_WINS.set(VendorWinHistogram::new(bucket_mins * 60, max_buckets));
+    }
+}
+
+/// Record the vendor that won a send; a no-op unless tracking is enabled
+pub fn record_vendor_win(vendor: &str) {
+    if let Some(histogram) = VENDOR_WINS.get() {
+        histogram.record(vendor, Utc::now().timestamp().max(0) as u64);
+    }
+}
+
+/// One "HH:MM vendor=share%(wins) ..." entry per kept bucket, oldest first; None when disabled
+pub fn get_vendor_win_distribution() -> Option<Vec<String>> {
+    VENDOR_WINS.get().map(|histogram| {
+        histogram
+            .distribution()
+            .iter()
+            .map(|bucket| {
+                let start = Utc.timestamp_opt(bucket.start_secs as i64, 0).single().unwrap_or_else(Utc::now);
+                format!("{} {}", start.format("%H:%M"), format_shares(&bucket.wins))
+            })
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_winners_accumulate_per_bucket() {
+        let histogram = VendorWinHistogram::new(3600, 2);
+
+        // 09:00 bucket: zeroslot mostly; 10:00 bucket: jito mostly
+        for (vendor, at) in [("zeroslot", 9 * 3600), ("zeroslot", 9 * 3600 + 60), ("jito", 9 * 3600 + 3599)] {
+            histogram.record(vendor, at);
+        }
+        for (vendor, at) in [("jito", 10 * 3600), ("jito", 10 * 3600 + 5), ("nextblock", 10 * 3600 + 10)] {
+            histogram.record(vendor, at);
+        }
+        let buckets = histogram.distribution();
+        assert_eq!(buckets.len(), 2);
+        assert_eq!(buckets[0].start_secs, 9 * 3600);
+        assert_eq!(buckets[0].wins.get("zeroslot"), Some(&2));
+        assert_eq!(buckets[0].wins.get("jito"), Some(&1));
+        assert_eq!(buckets[1].wins.get("jito"), Some(&2));
+        assert_eq!(buckets[1].wins.get("nextblock"), Some(&1));
+        assert_eq!(format_shares(&buckets[1].wins), "jito=67%(2) nextblock=33%(1)");
+
+        // A third hour rolls the oldest bucket off
+        histogram.record("zeroslot", 11 * 3600);
+        let buckets = histogram.distribution();
+        assert_eq!(buckets.iter().map(|b| b.start_secs).collect::<Vec<_>>(), vec![10 * 3600, 11 * 3600]);
+    }
+}
@@ -3,7 +3,6 @@ use std::str::FromStr;
 use bs58;
 use base64::{Engine as _, engine::general_purpose};
 use reqwest::Client;
-use rand::seq::SliceRandom;
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
     pubkey::Pubkey,
@@ -16,11 +15,11 @@ use solana_sdk::instruction::Instruction;
 use crate::config_load::GLOBAL_CONFIG;
 use crate::init::wallet_loader::{get_wallet_keypair, get_nonce_account};
 use once_cell::sync::Lazy;
-use rand::Rng;
 use solana_sdk::compute_budget;
+use crate::send_tx::tx_buffer::{recycle_buffer, take_buffer};
 
 // List of ZeroSlot tip accounts
-static ZEROSLOT_TIP_ACCOUNTS: &[&str] = &[
+pub(crate) static ZEROSLOT_TIP_ACCOUNTS: &[&str] = &[
     "4HiwLEP2Bzqj3hM2ENxJuzhcPCdsafwiet3oGkMkuQY4",
     "7toBU3inhmrARGngC7z6SjyP85HgGMmCTEwGNRAcYnEK",
     "8mR3wB1nh4D6J9RUCugxUpc6ya8w38LPxZ3ZjcBhgzws",
@@ -35,13 +34,15 @@ static ZEROSLOT_TIP_ACCOUNTS: &[&str] = &[
 
 // Global HTTP client with connection pooling for better performance
 static HTTP_CLIENT: Lazy<Client> = Lazy::new(|| {
-    Client::builder()
-        .pool_max_idle_per_host(50) // Keep up to 50 idle connections per host for high throughput
+    // Remove HTTP/2 prior knowledge to avoid frame size issues with large transactions
+    let builder = Client::builder()
+        .pool_max_idle_per_host(crate::send_tx::http_pool::http_pool_size()) // Keep up to http_pool_size idle connections per host
         .pool_idle_timeout(std::time::Duration::from_secs(120)) // Keep connections alive for 2 minutes
-        .tcp_keepalive(Some(std::time::Duration::from_secs(30))) // Enable TCP keep-alive
+        .tcp_keepalive(Some(std::time::Duration::from_secs(30))) // Enable TCP keep-alive (tcp_keepalive_secs overrides)
         .timeout(std::time::Duration::from_secs(3)) // 3 second timeout for larger transactions
-        .connect_timeout(std::time::Duration::from_millis(500)) // 500ms connect timeout
-        // Remove HTTP/2 prior knowledge to avoid frame size issues with large transactions
+        .connect_timeout(std::time::Duration::from_millis(500)); // 500ms connect timeout
+    // TCP_NODELAY and configured keepalive
+    crate::utils::socket_tuning::tune_reqwest(builder, crate::utils::socket_tuning::socket_tuning())
         .build()
         .expect("Failed to create HTTP client")
 });
@@ -107,13 +108,8 @@ async fn send_solana_transaction(
 
 
 pub fn zeroslot_tip(tip: u64, from_pubkey: &Pubkey) -> Instruction {
-    // Randomly select a tip account from the list
-    let tip_account = ZEROSLOT_TIP_ACCOUNTS
-        .choose(&mut rand::thread_rng())
-        .expect("Failed to select random tip account");
-    
-    let tip_pubkey = Pubkey::from_str(tip_account).expect("Invalid pubkey");
-    system_instruction::transfer(from_pubkey, &tip_pubkey, tip)
+    // Random tip account (configured via vendor_tip_accounts or built-in list), precomputed at startup
+    crate::send_tx::tip_accounts::tip_instruction("zeroslot", tip, from_pubkey)
 }
 
 pub fn create_instruction_zeroslot(
@@ -122,10 +118,8 @@ pub fn create_instruction_zeroslot(
     cu_price: u64,
     nonce_account: &Pubkey,
 ) -> Vec<Instruction> {
-    // Add a random number between 1-100 to the compute unit price
-    let mut rng = rand::thread_rng();
-    let random_addition: u64 = rng.gen_range(1..=100);
-    let adjusted_cu_price = cu_price + random_addition;
+    // Add a random number between 1-100 to the compute unit price, clamped to [min_cu_price, maxCUPrice]
+    let adjusted_cu_price = crate::send_tx::cu_price::adjust_cu_price(cu_price, &instructions);
     let keypair: &'static Keypair = get_wallet_keypair();
 
     let price_ix = compute_budget::ComputeBudgetInstruction::set_compute_unit_price(adjusted_cu_price);
@@ -148,14 +142,16 @@ pub fn create_instruction_zeroslot(
 
 
 pub async fn send_tx_zeroslot(tx: &Transaction) -> Result<String, Box<dyn std::error::Error>> {
+    crate::send_tx::rate_guard::throttle_vendor_send("zeroslot").await?;
     let config = GLOBAL_CONFIG.get().expect("Config not initialized");
 
-    // Pre-allocate buffer for serialization to avoid allocations
-    let mut buffer = Vec::with_capacity(4096); // Pre-allocate 4KB buffer for larger transactions
+    // Serialize into a pooled buffer to avoid an allocation per send
+    let mut buffer = take_buffer();
     bincode::serialize_into(&mut buffer, tx)?;
     
     // Use a more efficient base64 encoding approach
     let base64_encoded_transaction = general_purpose::STANDARD.encode(&buffer);
+    recycle_buffer(buffer);
 
     // Log transaction size for debugging
     let tx_size = base64_encoded_transaction.len();
@@ -179,6 +175,7 @@ pub async fn send_tx_zeroslot(tx: &Transaction) -> Result<String, Box<dyn std::e
     });
 
     // Send the request using the global client with connection pooling
+    let _pool_slot = crate::send_tx::http_pool::acquire_pool_slot("zeroslot").await;
     let response = HTTP_CLIENT
         .post(&config.zero_slot_url) // Use reference to avoid cloning
         .header("Content-Type", "application/json")
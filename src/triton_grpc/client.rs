@@ -8,7 +8,9 @@ use crate::triton_grpc::parser::process_triton_message;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::time::{sleep, Duration};
-use tonic::transport::Endpoint;
+use crate::grpc::connection_stats::{record_connect, record_disconnect};
+use crate::alerts::{alert, AlertKind};
+use crate::grpc::compression::{grpc_compression_encoding, record_stream_message, StreamFeed};
 use crate::init::wallet_loader::get_wallet_keypair;
 use solana_sdk::signature::Signer;
 use core_affinity;
@@ -74,13 +76,18 @@ pub async fn subscribe_and_print_triton(
     config: Arc<Config>,
     feed_id: &str, // OPTIMIZATION: Add feed_id parameter
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let client = Endpoint::from_shared(endpoint.to_string())?
+    let client = crate::utils::socket_tuning::tuned_endpoint(endpoint)?
         .connect()
         .await?;
 
     let mut client = GeyserClient::new(client)
         .max_decoding_message_size(8 * 1024 * 1024) // 8 MB
         .max_encoding_message_size(8 * 1024 * 1024);
+    // Only advertise accept-compression; servers without support keep sending uncompressed
+    if let Some(encoding) = grpc_compression_encoding(&config.grpc_compression) {
+        client = client.accept_compressed(encoding);
+        println!("[Triton] Accepting {:?} compressed responses (feed: {})", encoding, feed_id);
+    }
 
     let mut accounts_to_monitor = config.accounts_monitor.clone();
     accounts_to_monitor.push(get_wallet_keypair().pubkey().to_string());
@@ -137,6 +144,7 @@ pub async fn subscribe_and_print_triton(
         .await?
         .into_inner();
     println!("[Triton] Subscription stream established.");
+    record_connect(feed_id);
 
     // Pin the main Triton processing thread to core 0 (once, outside the loop)
     if let Some(cores) = core_affinity::get_core_ids() {
@@ -152,24 +160,29 @@ pub async fn subscribe_and_print_triton(
     }
 
     // Stream health monitoring
-    let mut last_message_time = std::time::Instant::now();
     let mut message_count = 0u64;
     let mut consecutive_errors = 0u32;
     const MAX_CONSECUTIVE_ERRORS: u32 = 10;
     const STREAM_TIMEOUT_SECONDS: u64 = 60;
 
-    while let Some(message) = stream.message().await? {
-        last_message_time = std::time::Instant::now();
+    loop {
+        // No message for STREAM_TIMEOUT_SECONDS: the stream is stale, exit to trigger reconnection
+        let message = match tokio::time::timeout(Duration::from_secs(STREAM_TIMEOUT_SECONDS), stream.message()).await {
+            Ok(next) => match next? {
+                Some(message) => message,
+                None => break,
+            },
+            Err(_) => {
+                println!("[Triton] WARNING: Stream appears stale (no messages for {}s), reconnecting...", 
+                    STREAM_TIMEOUT_SECONDS);
+                alert(AlertKind::StaleStream, format!("Triton stream silent for {}s, reconnecting", STREAM_TIMEOUT_SECONDS));
+                break;
+            }
+        };
         message_count += 1;
+        record_stream_message(StreamFeed::Triton);
         consecutive_errors = 0; // Reset error counter on successful message
 
-        // Check for stale stream
-        if last_message_time.elapsed() > Duration::from_secs(STREAM_TIMEOUT_SECONDS) {
-            println!("[Triton] WARNING: Stream appears stale (no messages for {}s), reconnecting...", 
-                STREAM_TIMEOUT_SECONDS);
-            break; // Exit to trigger reconnection
-        }
-
         // Memory pressure check - force cleanup every 1000 messages
         if message_count % 1000 == 0 {
             // Force garbage collection and cleanup
@@ -241,6 +254,7 @@ pub async fn subscribe_with_retry_triton(
         attempt += 1;
         println!("[Triton] Attempt {} to connect and subscribe for feed {}...", attempt, feed_id);
         let result = subscribe_and_print_triton(endpoint, config.clone(), feed_id).await; // OPTIMIZATION: Pass feed_id
+        record_disconnect(feed_id);
         match result {
             Ok(_) => {
                 println!("[Triton] Subscription ended gracefully for feed {}.", feed_id);
@@ -308,7 +322,7 @@ pub async fn test_endpoint_latency() -> Result<(), Box<dyn std::error::Error>> {
         let start = std::time::Instant::now();
         
         // Try to establish a connection to test latency
-        match Endpoint::from_shared(endpoint.to_string()) {
+        match crate::utils::socket_tuning::tuned_endpoint(endpoint) {
             Ok(channel_endpoint) => {
                 let channel = channel_endpoint.connect().await;
                 let latency = start.elapsed();
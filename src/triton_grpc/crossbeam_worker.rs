@@ -1,38 +1,63 @@
 use crossbeam::channel::{bounded, Sender};
 use once_cell::sync::OnceCell;
 use bs58;
-use core_affinity;
+use crate::utils::affinity;
+use crate::utils::watchdog;
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::instruction::Instruction;
 use chrono::Utc;
 use solana_transaction_status;
-use crate::utils::logger::{log_event, setup_event_logger, EventType};
+use crate::utils::logger::{log_event_cid, setup_event_logger, EventType};
 use crate::utils::rt_scheduler::{set_realtime_priority, RealtimePriority};
 
 // use tokio::time::{sleep, Duration};
-use crate::grpc::arpc_worker::GLOBAL_TX_MAP;
-use crate::build_tx::pump_fun::{build_sell_instruction, get_bonding_curve_state, BondingCurve};
-use crate::init::wallet_loader::get_wallet_keypair;
-use crate::build_tx::pump_swap::build_pump_sell_instruction;
+use crate::grpc::arpc_worker::{aliased_entry, insert_buy_entries, is_implausible_amount, resolve_tx_key, report_implausible_amount, GLOBAL_TX_MAP, TxWithPubkey};
+use crate::utils::price_feed::cached_price_usd;
+use crate::build_tx::pump_fun::{build_sell_instruction, calculate_pump_fun_swap_amount, get_bonding_curve_state, BondingCurve};
+use crate::init::wallet_loader::{get_fee_payer_keypair, get_wallet_keypair};
+use crate::build_tx::pump_swap::{build_pump_sell_instruction, SwapDirection};
 
-use crate::build_tx::ray_launch::build_ray_launch_sell_instruction;
+use crate::build_tx::ray_launch::{build_ray_launch_sell_instruction, RaydiumPoolState};
 use crate::build_tx::ray_cpmm::{build_ray_cpmm_sell_instruction};
 use crate::send_tx::rpc::send_tx_via_send_rpcs;
 use crate::send_tx::zero_slot::{create_instruction_zeroslot, send_tx_zeroslot};
 use crate::build_tx::tx_builder::{create_instruction};
 use crate::build_tx::tx_builder::build_and_sign_transaction_fast;
 use solana_sdk::signature::Signer;
-use crate::config_load::GLOBAL_CONFIG;
+use crate::config_load::{Config, GLOBAL_CONFIG};
 use crate::init::initialize::GLOBAL_RPC_CLIENT;
 use borsh::BorshDeserialize;
 use std::time::Instant;
-use std::thread;
 use std::time::Duration;
 use crate::grpc::monitoring_client::GLOBAL_MONITORING_DATA;
 use crate::send_tx::jito::send_jito_bundle;
 use crate::send_tx::jito::create_instruction_jito;
 use crate::send_tx::generic_sender::send_all_vendors_parallel;
+use crate::send_tx::rejections::{tip_lamports, TradeTag};
+use crate::grpc::slot_leaders::leader_for_slot;
+use crate::init::nonce_leases::{nonce_account_of, record_landed as record_nonce_landed, release_nonce_of, release_on_landing};
+use crate::send_tx::completion::{complete_batch_on_commitment, complete_on_commitment, confirm_buy_on_commitment, take_rolled_back_buy};
+use crate::send_tx::confirm_tracker::Commitment;
+use crate::send_tx::chase::{attempt_landed, record_chase_resend, ChasePolicy, CHASE_DEADLINE_SLOTS};
+use crate::build_tx::tx_builder::{build_vendor_specific_transactions_escalated, build_vendor_specific_transactions_multi, heap_frame_for_tx_type, with_nonce};
+use crate::send_tx::sell_gate::{hold_until_confirmed, submit_sell, QueuedSell};
+use crate::send_tx::sell_batch::{batch_sell, fits_sell_batch, BatchedSell};
+use crate::build_tx::dex_probe::detect_dex_for_mint;
+use crate::triton_grpc::sell_fraction::{fraction_of, record_leader_sells, take_leader_sell_fraction};
+use crate::send_tx::presell::{drained_action, report_drained, report_sell_mint_mismatch, sell_targets_mint, vault_quote_reserve, DrainedPolicy};
+use crate::send_tx::sell_retry::{retry_sell_on_slippage, supports_widening, SlippageWidening};
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::Transaction;
 use crate::grpc::utils;
+use crate::utils::ata::mark_ata_exists;
+use crate::build_tx::utils::{validate_account_data, with_rpc_retry, BuildError};
+use crate::utils::realized_slippage::record_fill;
+use crate::utils::daily_summary::{record_buy_landed, record_sell_landed, SellSale};
+use crate::utils::lifecycle::{record_sell_landing, track_sell_landing, Stage};
+use crate::build_tx::pump_swap::{get_pump_swap_accounts_rpc, PumpAmmAccounts};
+use crate::build_tx::ray_cpmm::{get_ray_cpmm_accounts_rpc, RayCpmmSwapAccounts};
+use crate::grpc::monitoring_client::MonitoringData;
+use crate::utils::control::{should_skip, Pipeline};
 
 
 // Add global counters for monitoring triton worker performance
@@ -93,10 +118,41 @@ pub fn print_triton_performance_summary() {
     println!("================================");
 }
 
-// Create a global Tokio runtime for async operations
+// Dedicated Tokio runtime for the send path (buy/sell vendor sends).
+// Sizing: each send fans out to every vendor concurrently and is mostly network-bound,
+// so a handful of worker threads is enough; `send_runtime_threads` (default 4) controls it.
+// Threads are named `send-rt-N` and, if the `send_runtime` core set is non-empty, pinned round-robin
+// to those cores, which should be kept apart from the real-time worker cores.
 use once_cell::sync::Lazy;
 static ASYNC_RUNTIME: Lazy<tokio::runtime::Runtime> = Lazy::new(|| {
-    tokio::runtime::Runtime::new().expect("Failed to create async runtime")
+    let threads = match GLOBAL_CONFIG.get() {
+        Some(config) => config.send_runtime_threads.max(1),
+        None => crate::config_load::default_send_runtime_threads(),
+    };
+    let cores = affinity::cores_for(affinity::SEND_RUNTIME);
+    let shared = affinity::overlapping_cores(affinity::SEND_RUNTIME, &[affinity::ARPC_WORKERS, affinity::TRITON_WORKERS]);
+    if !shared.is_empty() {
+        eprintln!("[TRITON] WARNING: send runtime cores {:?} are shared with real-time workers", shared);
+    }
+    // Separate counters: names are handed out on the spawning thread, pins on the new one
+    let name_index = AtomicUsize::new(0);
+    let pin_index = AtomicUsize::new(0);
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(threads)
+        .thread_name_fn(move || format!("send-rt-{}", name_index.fetch_add(1, Ordering::Relaxed)))
+        .on_thread_start(move || {
+            if cores.is_empty() {
+                return;
+            }
+            let idx = pin_index.fetch_add(1, Ordering::Relaxed);
+            let name = std::thread::current().name().map(str::to_string).unwrap_or_else(|| format!("send-rt-{}", idx));
+            affinity::pin_and_verify(&name, cores[idx % cores.len()]);
+        })
+        .enable_all()
+        .build()
+        .expect("Failed to create send runtime");
+    println!("[TRITON] Send runtime initialized with {} worker threads", threads);
+    runtime
 });
 
 #[derive(Debug, Clone)]
@@ -145,32 +201,118 @@ pub fn is_signature_processed_by_feed(sig: &str, feed_id: &str) -> bool {
     }
 }
 
+static FEED_DEDUP_EXPIRED: AtomicUsize = AtomicUsize::new(0);
+static FEED_DEDUP_EVICTED: AtomicUsize = AtomicUsize::new(0);
+
+/// (current size, total expired by TTL, total evicted over capacity)
+pub fn get_feed_dedup_stats() -> (usize, usize, usize) {
+    (
+        FEED_DEDUP_MAP.len(),
+        FEED_DEDUP_EXPIRED.load(Ordering::Relaxed),
+        FEED_DEDUP_EVICTED.load(Ordering::Relaxed),
+    )
+}
+
+/// Up to `limit` feed-dedup entries: (signature, first feed, age)
+pub fn feed_dedup_snapshot(limit: usize) -> Vec<(String, String, Duration)> {
+    FEED_DEDUP_MAP.iter().take(limit).map(|e| (e.key().clone(), e.value().0.clone(), e.value().1.elapsed())).collect()
+}
+
+/// Remove the oldest entries until the map is within `capacity`; returns how many were evicted
+fn evict_oldest_dedup_entries(map: &DashMap<String, (String, Instant)>, capacity: usize) -> usize {
+    let len = map.len();
+    if len <= capacity {
+        return 0;
+    }
+    let mut entries: Vec<(String, Instant)> = map.iter().map(|e| (e.key().clone(), e.value().1)).collect();
+    entries.sort_by_key(|(_, seen_at)| *seen_at);
+    let excess = len - capacity;
+    for (key, _) in entries.into_iter().take(excess) {
+        map.remove(&key);
+    }
+    excess
+}
+
 // OPTIMIZATION: Cleanup old deduplication entries to prevent memory leaks
 pub fn cleanup_feed_dedup_map() {
+    let (ttl_secs, capacity) = GLOBAL_CONFIG
+        .get()
+        .map(|c| (c.feed_dedup_ttl_secs, c.feed_dedup_capacity))
+        .unwrap_or((crate::config_load::default_feed_dedup_ttl_secs(), crate::config_load::default_feed_dedup_capacity()));
     let current_time = Instant::now();
     let mut to_remove = Vec::new();
     
-    // Remove entries older than 30 seconds
+    // Remove entries older than the dedup window
     for entry in FEED_DEDUP_MAP.iter() {
-        if current_time.duration_since(entry.value().1) > Duration::from_secs(30) {
+        if current_time.duration_since(entry.value().1) > Duration::from_secs(ttl_secs) {
             to_remove.push(entry.key().clone());
         }
     }
     
     // Remove old entries
+    FEED_DEDUP_EXPIRED.fetch_add(to_remove.len(), Ordering::Relaxed);
     for key in to_remove {
         FEED_DEDUP_MAP.remove(&key);
     }
     
-    // Emergency cleanup if map gets too large
-    if FEED_DEDUP_MAP.len() > 5000 {
-        println!("[Triton] WARNING: Feed dedup map too large ({} entries), clearing...", FEED_DEDUP_MAP.len());
-        FEED_DEDUP_MAP.clear();
+    // Over capacity: evict oldest first instead of clearing, so recent sigs stay deduped
+    let evicted = evict_oldest_dedup_entries(&FEED_DEDUP_MAP, capacity);
+    if evicted > 0 {
+        FEED_DEDUP_EVICTED.fetch_add(evicted, Ordering::Relaxed);
+        println!("[Triton] WARNING: Feed dedup map over capacity ({}), evicted {} oldest entries", capacity, evicted);
     }
 }
 
 static PARSED_TX_SENDER: OnceCell<Sender<ParsedTx>> = OnceCell::new();
 
+/// Sell wait time randomized uniformly within ±`jitter_pct`% of `wait_time_secs` (0 = exact)
+fn jittered_wait_time(wait_time_secs: f64, jitter_pct: f64, rng: &mut impl rand::Rng) -> Duration {
+    let jitter_pct = jitter_pct.clamp(0.0, 100.0);
+    let factor = if jitter_pct > 0.0 {
+        1.0 + rng.gen_range(-jitter_pct..=jitter_pct) / 100.0
+    } else {
+        1.0
+    };
+    Duration::from_secs_f64((wait_time_secs * factor).max(0.0))
+}
+
+/// Accounts for a migrated position: taken from the monitoring data captured at migration,
+/// or derived over RPC when the monitoring feed missed the migration.
+fn resolve_migrated_accounts<T>(
+    mint: &Pubkey,
+    from_monitoring: impl FnOnce(&MonitoringData) -> T,
+    from_rpc: impl FnOnce() -> Result<T, BuildError>,
+) -> Result<T, BuildError> {
+    if let Some(data) = GLOBAL_MONITORING_DATA.get(mint) {
+        return Ok(from_monitoring(data.value()));
+    }
+    println!("[{}] - [TRITON] No monitoring data for migrated mint {}, deriving accounts via RPC",
+        Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), mint);
+    from_rpc()
+}
+
+/// Post-migration sell accounts the monitoring feed already captured for a mint
+#[derive(Debug, Clone)]
+enum MonitoredMigration {
+    PumpSwap(PumpAmmAccounts),
+    RayCpmm(RayCpmmSwapAccounts),
+}
+
+/// Migration known from GLOBAL_MONITORING_DATA for a pumpfun / ray_launch position; a hit
+/// lets the sell skip the RPC status read (bonding curve / launchpad pool)
+fn monitored_migration(mint: &Pubkey, tx_type: &str) -> Option<MonitoredMigration> {
+    let data = GLOBAL_MONITORING_DATA.get(mint)?;
+    match tx_type {
+        "pumpfun" if data.pump_fun_accounts.pool != Pubkey::default() => {
+            Some(MonitoredMigration::PumpSwap(data.pump_fun_accounts))
+        }
+        "ray_launch" if data.ray_cpmm_accounts.pool_state != Pubkey::default() => {
+            Some(MonitoredMigration::RayCpmm(data.ray_cpmm_accounts.clone()))
+        }
+        _ => None,
+    }
+}
+
 /// Call this once at startup (e.g., in main.rs) to spawn the worker thread.
 pub fn setup_crossbeam_worker() {
     // OPTIMIZATION: Use bounded channel instead of unbounded to prevent memory leaks
@@ -185,16 +327,13 @@ pub fn setup_crossbeam_worker() {
         }
     });
     
-    // Spawn 3 worker threads for heavy processing
-    for worker_id in 0..3 {
+    // Spawn 3 worker threads for heavy processing (the watchdog respawns stuck ones through the same closure)
+    watchdog::TRITON_WORKERS.start(3, move |worker_id| {
         let rx_clone = rx.clone();
         std::thread::spawn(move || {
-            // Pin worker threads to cores 2-4 for optimal performance
-            if let Some(cores) = core_affinity::get_core_ids() {
-                if cores.len() > 2 + worker_id {
-                    core_affinity::set_for_current(cores[2 + worker_id]);
-                    println!("[triton crossbeam worker {}] Pinned to core {}", worker_id, 2 + worker_id);
-                }
+            // Pin worker threads to the triton_workers cores (2-4 by default)
+            if let Some(&core) = affinity::cores_for(affinity::TRITON_WORKERS).get(worker_id) {
+                affinity::pin_and_verify(&format!("triton crossbeam worker {}", worker_id), core);
             }
             
             // Set critical real-time priority for processing (highest priority)
@@ -206,10 +345,16 @@ pub fn setup_crossbeam_worker() {
             const MAX_CONSECUTIVE_ERRORS: usize = 10;
             
             while let Ok(parsed) = rx_clone.recv() {
+                let _busy = watchdog::TRITON_WORKERS.busy(worker_id);
                 let receive_start = Instant::now();
                 let processing_start = Instant::now();
                 TRITON_MESSAGES_RECEIVED.fetch_add(1, Ordering::Relaxed);
                 
+                // Drop messages while the triton pipeline is paused (and those buffered during the pause)
+                if should_skip(Pipeline::Triton, parsed.detection_time) {
+                    continue;
+                }
+                
 
                 
                 // OPTIMIZATION: Fast signature extraction
@@ -222,7 +367,7 @@ pub fn setup_crossbeam_worker() {
                 let sig_extract_time = sig_extract_start.elapsed();
                 // Initialize profiling variables
                 let mut map_search_time = std::time::Duration::ZERO;
-                let mut wait_time = std::time::Duration::ZERO;
+                let wait_time = std::time::Duration::ZERO;
                 let mut build_time = std::time::Duration::ZERO;
                 let mut send_time = std::time::Duration::ZERO;
                 let mut buy_send_time = std::time::Duration::ZERO;
@@ -267,8 +412,6 @@ pub fn setup_crossbeam_worker() {
                     }
                 };
 
-                let mut found = None;
-                
                 // OPTIMIZATION: Only log in verbose mode
                 #[cfg(feature = "verbose_logging")]
                 {
@@ -281,7 +424,15 @@ pub fn setup_crossbeam_worker() {
                 let is_signer = parsed.is_signer;
                 let is_signer_check_time = is_signer_check_start.elapsed();
                 
+                // One of our sells landed: close its lifecycle, nothing else to do
                 if is_signer {
+                    record_nonce_landed(&sig_detect);
+                }
+                let landed_sale = if is_signer { record_sell_landing(&sig_detect, parsed.detection_time.unwrap()) } else { None };
+                if let Some(sale) = landed_sale {
+                    TRITON_TRANSACTIONS_FOUND.fetch_add(1, Ordering::Relaxed);
+                    record_sell_landed(&sale, Duration::from_secs(config.birdeye_price_ttl_secs));
+                } else if is_signer {
                     let map_size_start = Instant::now();
                     let map_size = GLOBAL_TX_MAP.len();
                     let map_size_time = map_size_start.elapsed();
@@ -295,7 +446,13 @@ pub fn setup_crossbeam_worker() {
                     
                     // OPTIMIZATION: Fast map search
                     let map_search_start = Instant::now();
-                    for entry in GLOBAL_TX_MAP.iter() {
+                    // Vendor copies of a alias_vendor_sigs buy are aliased to its entry
+                    let mut found = parsed.sig_bytes.as_deref().and_then(aliased_entry);
+                    let aliased = found.is_some();
+                    if aliased {
+                        TRITON_TRANSACTIONS_FOUND.fetch_add(1, Ordering::Relaxed);
+                    }
+                    for entry in GLOBAL_TX_MAP.iter().filter(|_| !aliased) {
                         if entry.value().send_sig.trim_matches('\"') == sig_detect {
                             found = Some(entry.value().clone());
                             TRITON_TRANSACTIONS_FOUND.fetch_add(1, Ordering::Relaxed);
@@ -303,8 +460,8 @@ pub fn setup_crossbeam_worker() {
                             // OPTIMIZATION: Only log in verbose mode
                             {
                                 let now = Utc::now();
-                                println!("[{}] - [TRITON-{}] FOUND transaction in map for sig: {} (feed: {}) (tx_type: {})", 
-                                    Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), worker_id, sig_detect, parsed.feed_id, entry.value().tx_type);
+                                println!("[{}] - [TRITON-{}] [cid={}] FOUND transaction in map for sig: {} (feed: {}) (tx_type: {})", 
+                                    Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), worker_id, entry.value().corr_id, sig_detect, parsed.feed_id, entry.value().tx_type);
                             }
                             break;
                         }
@@ -323,225 +480,92 @@ pub fn setup_crossbeam_worker() {
                     let found_check_time = found_check_start.elapsed();
                     
                     if let Some(mut tx_with_pubkey) = found {
-                        let now = Utc::now();
-                        let mut send_tx: bool = false;
 
-                        let sig_bytes = parsed.sig_bytes.as_ref().unwrap();
+                        let landed_sig_bytes = parsed.sig_bytes.as_ref().unwrap();
+                        let sig_bytes = &resolve_tx_key(landed_sig_bytes);
+                        tx_with_pubkey.lifecycle.advance(Stage::BuySendToLand, parsed.detection_time.unwrap());
+                        if let Some((vendor, tx)) = tx_with_pubkey.vendor_transactions.iter()
+                            .find(|(_, tx)| tx.signatures.first().map_or(false, |s| s.to_string() == sig_detect))
+                        {
+                            record_buy_landed(vendor, tip_lamports(tx));
+                            if config.log_realized_slippage {
+                                record_fill("buy", &sig_detect, tx_with_pubkey.mint, tx_with_pubkey.token_amount, tip_lamports(tx), tx_with_pubkey.corr_id.clone());
+                            }
+                        }
+                        tx_with_pubkey.entry_price_usd = cached_price_usd(&tx_with_pubkey.mint.to_string(), Duration::from_secs(config.birdeye_price_ttl_secs));
                         
                         // OPTIMIZATION: Only log in verbose mode
 
-                        log_event(
+                        log_event_cid(
                             EventType::GrpcLanded,
-                            sig_bytes,
+                            landed_sig_bytes,
                             tx_with_pubkey.send_time,
-                            Some((parsed.slot.unwrap() - tx_with_pubkey.send_slot) as i64)
+                            Some((parsed.slot.unwrap() - tx_with_pubkey.send_slot) as i64),
+                            &tx_with_pubkey.corr_id,
                         );
-
-                        // Use configurable wait time instead of hardcoded 4 seconds
-                        let wait_time_secs = config.wait_time as u64;
-                        let wait_start = Instant::now();
-                        thread::sleep(Duration::from_secs(wait_time_secs));
-                        wait_time = wait_start.elapsed();
-                        let mut sell_instruction: Instruction = Instruction{
-                            program_id: Pubkey::new_unique(),
-                            accounts: vec![],
-                            data: vec![],
-                        };
-                        let mut tx_type = tx_with_pubkey.tx_type;
-
-                        //check if pumpfun token has migrated or not, if true, switch to pumpswap sell logic
-                        let rpc: &solana_client::rpc_client::RpcClient = GLOBAL_RPC_CLIENT.get().expect("RPC client not initialized");
-                        let mut bonding_curve_state = BondingCurve::default();
-                        
-                        if tx_type == "pumpfun" {
-                            if let Some(pump_fun_accounts) = &tx_with_pubkey.pump_fun_accounts {
-                                bonding_curve_state = get_bonding_curve_state(pump_fun_accounts);
-                                
-                                if bonding_curve_state.complete {
-                                    tx_type = "pump_swap".to_string();
-                                    #[cfg(feature = "verbose_logging")]
-                                    println!("[{}] - [grpc] Pumpfun token has migrated to pumpswap - applying pumpswap sell logic", now.format("%Y-%m-%d %H:%M:%S%.3f"));
-                                    tx_with_pubkey.pump_swap_accounts = Some(GLOBAL_MONITORING_DATA.get(&tx_with_pubkey.mint).unwrap().pump_fun_accounts.clone());
-                                    //need to figure out how to build pump swap struct!!!!!!!!!!!!!
-                                }
-                            }
+                        if config.slot_leader_refresh_secs > 0 {
+                            let landed_slot = parsed.slot.unwrap();
+                            let leader_of = |slot: u64| leader_for_slot(slot).map_or("unknown".to_string(), |l| l.to_string());
+                            println!("[{}] - [TRITON-{}] [cid={}] Landed in slot {} led by {} (sent at slot {} led by {})",
+                                Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), worker_id, tx_with_pubkey.corr_id,
+                                landed_slot, leader_of(landed_slot), tx_with_pubkey.send_slot, leader_of(tx_with_pubkey.send_slot));
                         }
 
-                        if tx_type == "ray_launch" {
-                            if let Some(ray_launch_accounts) = &tx_with_pubkey.ray_launch_accounts {
-                                let pool_state = ray_launch_accounts.pool_state;
-                                let rpc_start = Instant::now();
-                                let res = match rpc.get_account_data(&pool_state) {
-                                    Ok(data) => data,
-                                    Err(e) => {
-                                        eprintln!("[crossbeam_worker] Error: get_account_data (raylaunch) failed: {:?}", e);
-                                        continue;
-                                    }
-                                };
-                                let rpc_time = rpc_start.elapsed();
-                                let status = res[17];
-                                let migrate = res[20];
-                                
-                                if status > 0 {
-                                    // tx_type = "ray_cpmm".to_string();
-                                    if migrate == 1 {
-                                        #[cfg(feature = "verbose_logging")]
-                                        println!("[{}] - [grpc] Raylaunch pool is complete - applying Raydium CPMM sell logic", now.format("%Y-%m-%d %H:%M:%S%.3f"));
-                                        tx_type = "ray_cpmm".to_string();
-                                        tx_with_pubkey.raydium_cpmm_accounts = Some(GLOBAL_MONITORING_DATA.get(&tx_with_pubkey.mint).unwrap().ray_cpmm_accounts.clone());
-                                    }
-                                }
-                            }
-                        }
+                        // Our buy landed, so its idempotent ATA creation did too: the sell can omit it
+                        let user_ata = mark_ata_exists(&get_wallet_keypair().pubkey(), &tx_with_pubkey.mint);
+                        tx_with_pubkey.user_ata = Some(user_ata);
 
-                        if tx_type == "pumpfun" {
-                            if let Some(pump_fun_accounts) = &tx_with_pubkey.pump_fun_accounts {
-                                sell_instruction = build_sell_instruction(
-                                    tx_with_pubkey.token_amount,
-                                    config.sell_slippage_bps,
-                                    pump_fun_accounts,
-                                    bonding_curve_state,
-                                );
-                                send_tx = true;
-                            }
-                        }
-                        if tx_type == "pump_swap" {
-                            if let Some(pump_swap_accounts) = &tx_with_pubkey.pump_swap_accounts {
-                                sell_instruction = build_pump_sell_instruction(
-                                    tx_with_pubkey.token_amount,
-                                    config.sell_slippage_bps,
-                                    pump_swap_accounts,
-                                );
-                                send_tx = true;
-                            }
-                        }
-                        if tx_type == "ray_launch" {
-                            if let Some(ray_launch_accounts) = &tx_with_pubkey.ray_launch_accounts {
-                                sell_instruction = build_ray_launch_sell_instruction(
-                                    tx_with_pubkey.token_amount,
-                                    config.sell_slippage_bps,
-                                    ray_launch_accounts,
-                                );
-                                send_tx = true;
-                            }
-                        }
-                        if tx_type == "ray_cpmm" {
-                            if let Some(raydium_cpmm_accounts) = &tx_with_pubkey.raydium_cpmm_accounts {
-                                sell_instruction = build_ray_cpmm_sell_instruction(
-                                    tx_with_pubkey.token_amount,
-                                    raydium_cpmm_accounts,
-                                );
-                                send_tx = true;
-                            }
-                        }
-                        if tx_type == "ray_launch_cpmm" {
-                            if let Some(raydium_cpmm_accounts) = &tx_with_pubkey.raydium_cpmm_accounts {
-                                sell_instruction = build_ray_cpmm_sell_instruction(
-                                    tx_with_pubkey.token_amount,
-                                    raydium_cpmm_accounts,
-                                );
-                                send_tx = true;
-                            }
+                        // Seen at processed only: a rollback before completion_commitment reopens the position
+                        let commitment = Commitment::from_name(&config.completion_commitment);
+                        if commitment > Commitment::Processed {
+                            confirm_buy_on_commitment(&sig_detect, sig_bytes.clone(), tx_with_pubkey.corr_id.clone(), commitment);
                         }
 
-                        if send_tx {
-                            let build_start = Instant::now();
-                            #[cfg(feature = "verbose_logging")]
-                            {
-                                let now = Utc::now();
-                                println!("[{}] - [TRITON] Building sell transaction for sig: {} (tx_type: {})", 
-                                    Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), sig_detect, tx_type);
-                            }
-                            
-                            // Build vendor-specific sell transactions in parallel using the same function as buy
-                            let build_result = crate::build_tx::tx_builder::build_vendor_specific_transactions_parallel(
-                                sell_instruction,
-                                tx_with_pubkey.mint,
-                                0, // target_token_buy not used for sell transactions
-                                &sig_detect, // sig_str for logging
-                            );
-                            let build_time = build_start.elapsed();
-                            
-                            match build_result {
-                                Ok(vendor_transactions) => {
-                                    if !vendor_transactions.is_empty() {
-                                        #[cfg(feature = "verbose_logging")]
-                                        {
-                                            let now = Utc::now();
-                                            println!("[{}] - [TRITON] SUCCESS - Built {} vendor sell transactions for sig: {}", 
-                                                Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), vendor_transactions.len(), sig_detect);
-                                        }
-                                        
-                                        // Send all vendor transactions in parallel
-                                        let sig_detect_clone = sig_detect.clone();
-                                        let sig_bytes_clone = sig_bytes.clone();
-                                        let detection_time = parsed.detection_time.unwrap();
-                                        
-                                        let send_start = Instant::now();
-                                        ASYNC_RUNTIME.spawn(async move {
-                                            let send_result = send_all_vendors_parallel(&vendor_transactions, detection_time).await;
-                                            let send_time = send_start.elapsed();
-                                            
-                                            match send_result {
-                                                Ok((winning_vendor, sig)) => {
-                                                    TRITON_TRANSACTIONS_SENT.fetch_add(1, Ordering::Relaxed);
-                                                    #[cfg(feature = "verbose_logging")]
-                                                    {
-                                                        let now = Utc::now();
-                                                        println!(
-                                                            "[{}] - [TRITON] PARALLEL SELL SUCCESS - {} won with sig: {} | total sent: {}",
-                                                            Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"),
-                                                            winning_vendor,
-                                                            sig,
-                                                            TRITON_TRANSACTIONS_SENT.load(Ordering::Relaxed)
-                                                        );
-                                                    }
-                                                    // Remove the processed transaction from GLOBAL_TX_MAP to prevent memory leaks
-                                                    GLOBAL_TX_MAP.remove(&sig_bytes_clone);
-                                                }
-                                                Err(e) => {
-                                                    TRITON_ERRORS.fetch_add(1, Ordering::Relaxed);
-                                                    #[cfg(feature = "verbose_logging")]
-                                                    {
-                                                        let now = Utc::now();
-                                                        eprintln!("[{}] - [TRITON] ERROR - Parallel sell send failed for sig: {} - Error: {:?}", 
-                                                            Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), sig_detect_clone, e);
-                                                    }
-                                                }
-                                            }
-                                        });
-                                    } else {
-                                        TRITON_ERRORS.fetch_add(1, Ordering::Relaxed);
-                                        #[cfg(feature = "verbose_logging")]
-                                        {
-                                            let now = Utc::now();
-                                            eprintln!("[{}] - [TRITON] ERROR - No vendor sell transactions built for sig: {}", 
-                                                Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), sig_detect);
-                                        }
-                                    }
-                                }
-                                Err(e) => {
-                                    TRITON_ERRORS.fetch_add(1, Ordering::Relaxed);
-                                    #[cfg(feature = "verbose_logging")]
-                                    {
-                                        let now = Utc::now();
-                                        eprintln!("[{}] - [TRITON] ERROR - Failed to build vendor sell transactions for sig: {} - Error: {:?}", 
-                                            Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), sig_detect, e);
-                                    }
-                                }
-                            }
+                        // Wait off the worker thread: the sell is built and sent from the async runtime
+                        let wait = jittered_wait_time(config.wait_time, config.wait_time_jitter_pct, &mut rand::thread_rng());
+                        println!("[{}] - [TRITON-{}] [cid={}] Scheduling sell for sig: {} in {:.2?}",
+                            Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), worker_id, tx_with_pubkey.corr_id, sig_detect, wait);
+                        let sig_detect_clone = sig_detect.clone();
+                        let sig_bytes_clone = sig_bytes.clone();
+                        let detection_time = parsed.detection_time.unwrap();
+                        // Seen on the feed isn't confirmed: optionally hold the sell until RPC confirms the buy
+                        let gate_sig = if config.sell_requires_buy_confirmation {
+                            sig_detect.trim_matches('"').parse::<Signature>().ok()
                         } else {
-                            #[cfg(feature = "verbose_logging")]
-                            {
-                                let now = Utc::now();
-                                println!("[{}] - [TRITON] No sell transaction to build for sig: {} (tx_type: {})", 
-                                    Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), sig_detect, tx_type);
-                            }
+                            None
+                        };
+                        if let Some(buy_sig) = gate_sig {
+                            hold_until_confirmed(buy_sig, tx_with_pubkey.corr_id.clone());
                         }
+                        ASYNC_RUNTIME.spawn(async move {
+                            let (tx_with_pubkey, _) = tokio::join!(resolve_lost_dex(tx_with_pubkey), tokio::time::sleep(wait));
+                            let corr_id = tx_with_pubkey.corr_id.clone();
+                            let sell: QueuedSell = Box::new(move || {
+                                ASYNC_RUNTIME.spawn_blocking(move || {
+                                    build_and_send_sell(tx_with_pubkey, sig_detect_clone, sig_bytes_clone, detection_time, worker_id, config.sell_slippage_bps, 0, true, config)
+                                });
+                            });
+                            let fired = match gate_sig {
+                                Some(buy_sig) => submit_sell(buy_sig, sell),
+                                None => {
+                                    sell();
+                                    true
+                                }
+                            };
+                            if !fired {
+                                println!("[{}] - [TRITON] [cid={}] Sell due but buy not confirmed yet, held until it is",
+                                    Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), corr_id);
+                            }
+                        });
                     }
 
                 } else { //send tx
                     let else_branch_start = Instant::now();
+                    if config.copy_sell_fraction {
+                        if let (Some(pre), Some(post)) = (&parsed.pre_token_balances, &parsed.post_token_balances) {
+                            record_leader_sells(pre, post, &config.track_wallet, &sig_detect);
+                        }
+                    }
                     #[cfg(feature = "verbose_logging")]
                     {
                         let now = Utc::now();
@@ -564,14 +588,17 @@ pub fn setup_crossbeam_worker() {
                             let detection_time = parsed.detection_time.unwrap();
                             let slot = parsed.slot.unwrap();
                             let sig_bytes_clone = sig_bytes.clone();
+                            let corr_id = tx_with_pubkey.corr_id.clone();
+                            let trade = TradeTag { mint: tx_with_pubkey.mint, tx_type: tx_with_pubkey.tx_type.clone(), side: "buy" };
                             
                             // Update the transaction info immediately (non-blocking) - set send_slot agnostic to which vendor wins
                             tx_with_pubkey.send_time = Instant::now();
                             tx_with_pubkey.send_slot = slot; // Set send_slot immediately when we start sending
+                            tx_with_pubkey.lifecycle.detected = Some(detection_time);
                             
                             let buy_send_start = Instant::now();
                             ASYNC_RUNTIME.spawn(async move {
-                                let buy_send_result = send_all_vendors_parallel(&vendor_transactions, detection_time).await;
+                                let buy_send_result = send_all_vendors_parallel(&vendor_transactions, detection_time, &corr_id, &trade).await;
                                 let buy_send_time = buy_send_start.elapsed();
                                 
                                 match buy_send_result {
@@ -580,8 +607,9 @@ pub fn setup_crossbeam_worker() {
                                         
                                         let now = Utc::now();
                                         println!(
-                                            "[{}] - [TRITON] PARALLEL SUCCESS - {} won with sig: {} | total sent: {}",
+                                            "[{}] - [TRITON] [cid={}] PARALLEL SUCCESS - {} won with sig: {} | total sent: {}",
                                             Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+                                            corr_id,
                                             winning_vendor,
                                             sig,
                                             TRITON_TRANSACTIONS_SENT.load(Ordering::Relaxed)
@@ -590,6 +618,8 @@ pub fn setup_crossbeam_worker() {
                                         // Update only the signature in the map (send_slot already set above)
                                         if let Some(mut tx_with_pubkey) = GLOBAL_TX_MAP.get_mut(&sig_bytes_clone) {
                                             tx_with_pubkey.send_sig = sig.clone();
+                                            tx_with_pubkey.open_position = true;
+                                            tx_with_pubkey.lifecycle.advance(Stage::DetectToBuySend, Instant::now());
                                             // send_slot is already set above, so we don't need to set it again
                                             #[cfg(feature = "verbose_logging")]
                                             {
@@ -597,13 +627,23 @@ pub fn setup_crossbeam_worker() {
                                                 println!("[{}] - [TRITON] Saved sig: {} to GLOBAL_TX_MAP (send_slot already set)", Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), sig);
                                             }
                                         }
+                                        if let Some((_, tx)) = vendor_transactions.iter().find(|(vendor, _)| *vendor == winning_vendor) {
+                                            release_on_landing(&sig, tx);
+                                        }
+                                        if let Some(policy) = ChasePolicy::from_config(config) {
+                                            ASYNC_RUNTIME.spawn(chase_buy(sig_bytes_clone, sig, vendor_transactions, detection_time, policy, config));
+                                        }
                                     }
                                     Err(e) => {
                                         TRITON_ERRORS.fetch_add(1, Ordering::Relaxed);
+                                        // Nothing was accepted, so nothing will advance the shared nonce
+                                        if let Some((_, tx)) = vendor_transactions.first() {
+                                            release_nonce_of(tx);
+                                        }
                                         #[cfg(feature = "verbose_logging")]
                                         {
                                             let now = Utc::now();
-                                            eprintln!("[crossbeam_worker] Error: Parallel send failed: {:?}", e);
+                                            eprintln!("[crossbeam_worker] [cid={}] Error: Parallel send failed: {:?}", corr_id, e);
                                         }
                                     }
                                 }
@@ -647,6 +687,621 @@ pub fn setup_crossbeam_worker() {
                 }
             }
         });
+    });
+}
+
+/// Chase buy `sig` (stored under `key`): while no version of the last attempt has landed, rebuild it at
+/// the policy's next bid and resend, up to `policy.max_attempts` times. Each resend reuses the first
+/// attempt's nonce account and value, so if an earlier attempt lands late the resend fails (and vice
+/// versa) instead of buying twice.
+async fn chase_buy(
+    key: Vec<u8>,
+    sig: String,
+    vendor_transactions: Vec<(String, Transaction)>,
+    detection_time: Instant,
+    policy: ChasePolicy,
+    config: &'static Config,
+) {
+    let rpc = match GLOBAL_RPC_CLIENT.get() {
+        Some(rpc) => rpc,
+        None => return,
+    };
+    // Every attempt shares this durable nonce; a buy sent without one can't be chased safely
+    let pinned_nonce = match vendor_transactions.first().and_then(|(_, tx)| nonce_account_of(tx).map(|nonce| (nonce, tx.message.recent_blockhash))) {
+        Some(pinned_nonce) => pinned_nonce,
+        None => return,
+    };
+    let (mut sig, mut sent) = (sig, vendor_transactions);
+    for attempt in 1.. {
+        let winner = match sig.trim_matches('"').parse::<Signature>() {
+            Ok(winner) => winner,
+            Err(_) => return,
+        };
+        let sent_sigs: Vec<Signature> = sent.iter().filter_map(|(_, tx)| tx.signatures.first().copied()).collect();
+        let landed = tokio::task::spawn_blocking(move || attempt_landed(rpc, winner, &sent_sigs)).await.unwrap_or(true);
+        if landed {
+            return;
+        }
+        let mut entry = match GLOBAL_TX_MAP.get(&key) {
+            Some(entry) => entry.value().clone(),
+            None => return,
+        };
+        let (multiplier, buy_instruction) = match (policy.bid_multiplier(attempt), entry.buy_instruction.clone()) {
+            (Some(multiplier), Some(buy_instruction)) => (multiplier, buy_instruction),
+            _ => {
+                println!("[{}] - [CHASE] [cid={}] Buy {} not landed after {} resends, giving up",
+                    Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), entry.corr_id, sig, attempt - 1);
+                return;
+            }
+        };
+        println!("[{}] - [CHASE] [cid={}] Buy {} not landed within {} slots, resend {}/{} at {:.2}x bid",
+            Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), entry.corr_id, sig, CHASE_DEADLINE_SLOTS, attempt, policy.max_attempts, multiplier);
+
+        let (mint, token_amount, corr_id, tx_type) = (entry.mint, entry.token_amount, entry.corr_id.clone(), entry.tx_type.clone());
+        let build = tokio::task::spawn_blocking(move || {
+            with_nonce(pinned_nonce.0, pinned_nonce.1, || {
+                build_vendor_specific_transactions_escalated(buy_instruction, mint, token_amount, &corr_id, &tx_type, multiplier, None)
+                    .map_err(|e| e.to_string())
+            })
+        })
+        .await;
+        let vendor_transactions = match build {
+            Ok(Ok((vendor_transactions, _))) if !vendor_transactions.is_empty() => vendor_transactions,
+            Ok(Ok((_, failures))) => {
+                eprintln!("[{}] - [CHASE] [cid={}] Resend build failed: {}",
+                    Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), entry.corr_id, failures.summary());
+                return;
+            }
+            Ok(Err(e)) => {
+                eprintln!("[{}] - [CHASE] [cid={}] Resend build failed: {}",
+                    Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), entry.corr_id, e);
+                return;
+            }
+            Err(_) => return,
+        };
+
+        // Key the new versions too, so whichever attempt lands triggers the sell
+        entry.vendor_transactions = vendor_transactions.clone();
+        insert_buy_entries(key.clone(), &entry, config.alias_vendor_sigs);
+        let trade = TradeTag { mint: entry.mint, tx_type: entry.tx_type.clone(), side: "buy" };
+        match send_all_vendors_parallel(&vendor_transactions, detection_time, &entry.corr_id, &trade).await {
+            Ok((winning_vendor, resent_sig)) => {
+                record_chase_resend();
+                // The landing scan matches the entry on its send signature
+                if let Some(mut stored) = GLOBAL_TX_MAP.get_mut(&key) {
+                    stored.send_sig = resent_sig.clone();
+                }
+                if let Some((_, tx)) = vendor_transactions.iter().find(|(vendor, _)| *vendor == winning_vendor) {
+                    release_on_landing(&resent_sig, tx);
+                }
+                sig = resent_sig;
+                sent = vendor_transactions;
+            }
+            Err(e) => {
+                if let Some((_, tx)) = vendor_transactions.first() {
+                    release_nonce_of(tx);
+                }
+                eprintln!("[{}] - [CHASE] [cid={}] Resend failed: {:?}",
+                    Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), entry.corr_id, e);
+                return;
+            }
+        }
+    }
+}
+
+/// Recover a lost tx_type (e.g. an entry restored without it) from chain state. The probe is a
+/// getProgramAccounts scan, so it runs on the blocking pool alongside the sell wait instead of in
+/// build_and_send_sell.
+async fn resolve_lost_dex(mut tx_with_pubkey: TxWithPubkey) -> TxWithPubkey {
+    if !tx_with_pubkey.tx_type.is_empty() {
+        return tx_with_pubkey;
+    }
+    let mint = tx_with_pubkey.mint;
+    let detected = tokio::task::spawn_blocking(move || detect_dex_for_mint(mint)).await.ok().flatten();
+    if let Some((program, accounts)) = detected {
+        accounts.apply_to(&mut tx_with_pubkey);
+        println!("[{}] - [TRITON] [cid={}] No tx_type for {}, detected {:?} ({} sell)",
+            Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), tx_with_pubkey.corr_id, mint, program, tx_with_pubkey.tx_type);
+    }
+    tx_with_pubkey
+}
+
+/// Sell a position recovered at startup; no landed buy will trigger it, so it is scheduled here
+pub fn schedule_reconciled_sell(tx_with_pubkey: TxWithPubkey, key: Vec<u8>) {
+    let config = GLOBAL_CONFIG.get().expect("Config not initialized");
+    let wait = jittered_wait_time(config.wait_time, config.wait_time_jitter_pct, &mut rand::thread_rng());
+    let sig_detect = format!("reconcile:{}", tx_with_pubkey.mint);
+    ASYNC_RUNTIME.spawn(async move {
+        let (tx_with_pubkey, _) = tokio::join!(resolve_lost_dex(tx_with_pubkey), tokio::time::sleep(wait));
+        let _ = tokio::task::spawn_blocking(move || {
+            build_and_send_sell(tx_with_pubkey, sig_detect, key, Instant::now(), 0, config.sell_slippage_bps, 0, true, config)
+        })
+        .await;
+    });
+}
+
+/// Build and send the sell for a landed buy. Runs on the blocking pool after the (jittered) wait.
+/// `retry` counts slippage-widening resends; the first send is 0. `batchable` lets it share a
+/// transaction with other sells (sell_batch_window_ms); resends go out alone.
+#[allow(clippy::too_many_arguments)]
+fn build_and_send_sell(
+    mut tx_with_pubkey: TxWithPubkey,
+    sig_detect: String,
+    sig_bytes: Vec<u8>,
+    detection_time: Instant,
+    worker_id: usize,
+    slippage_bps: u64,
+    retry: u32,
+    batchable: bool,
+    config: &'static Config,
+) {
+    #[cfg(feature = "verbose_logging")]
+    let now = Utc::now();
+    let mut send_tx: bool = false;
+    let mut sell_instruction: Instruction = Instruction{
+        program_id: Pubkey::new_unique(),
+        accounts: vec![],
+        data: vec![],
+    };
+    let corr_id = tx_with_pubkey.corr_id.clone();
+    let mut tx_type = tx_with_pubkey.tx_type.clone();
+
+    // Scheduled by a buy landing that was since rolled back: wait for the buy to land again
+    if take_rolled_back_buy(&sig_bytes) {
+        println!("[{}] - [TRITON] [cid={}] Buy for sig: {} rolled back, skipping its sell until it lands again",
+            Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), corr_id, sig_detect);
+        return;
+    }
+
+    // Tracked wallet sold only part of its holding: sell the same part of ours
+    if config.copy_sell_fraction {
+        if let Some(fraction) = take_leader_sell_fraction(&tx_with_pubkey.mint) {
+            let amount = fraction_of(tx_with_pubkey.token_amount, fraction);
+            println!("[{}] - [COPY_SELL] [cid={}] Selling {:.1}% of the position ({} of {} tokens)",
+                Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), corr_id, fraction * 100.0, amount, tx_with_pubkey.token_amount);
+            tx_with_pubkey.token_amount = amount;
+        }
+    }
+
+    // Token count only: a position that gained value past max_trade_usd must still be sellable
+    if is_implausible_amount(tx_with_pubkey.token_amount, None, config.max_token_amount, 0.0) {
+        report_implausible_amount("sell", tx_with_pubkey.token_amount, None, &tx_with_pubkey.mint.to_string(), &corr_id, config);
+        return;
+    }
+
+    //check if pumpfun token has migrated or not, if true, switch to pumpswap sell logic
+    let rpc: &solana_client::rpc_client::RpcClient = GLOBAL_RPC_CLIENT.get().expect("RPC client not initialized");
+    let mut bonding_curve_state = BondingCurve::default();
+    let mut launch_quote_reserve = None; // ray_launch pool's real_quote, for the pre-sell liquidity check
+
+    // Migration already seen by monitoring: use its accounts, no RPC status read
+    let known_migration = if config.skip_migration_rpc_check {
+        monitored_migration(&tx_with_pubkey.mint, &tx_type)
+    } else {
+        None
+    };
+    match &known_migration {
+        Some(MonitoredMigration::PumpSwap(accounts)) => {
+            tx_type = "pump_swap".to_string();
+            tx_with_pubkey.pump_swap_accounts = Some(accounts.clone());
+        }
+        Some(MonitoredMigration::RayCpmm(accounts)) => {
+            tx_type = "ray_cpmm".to_string();
+            tx_with_pubkey.raydium_cpmm_accounts = Some(accounts.clone());
+        }
+        None => {}
+    }
+    // tx_type lost and not recovered by resolve_lost_dex during the sell wait
+    if tx_type.is_empty() {
+        eprintln!("[{}] - [TRITON-{}] [cid={}] No tx_type for {} and no known DEX trades it, skipping sell",
+            Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), worker_id, corr_id, tx_with_pubkey.mint);
+        return;
+    }
+    #[cfg(feature = "verbose_logging")]
+    {
+        if known_migration.is_some() {
+            println!("[{}] - [TRITON] [cid={}] Monitoring has migration for {}, skipping RPC status check ({} sell)",
+                now.format("%Y-%m-%d %H:%M:%S%.3f"), corr_id, tx_with_pubkey.mint, tx_type);
+        }
+    }
+
+    if tx_type == "pumpfun" {
+        if let Some(pump_fun_accounts) = &tx_with_pubkey.pump_fun_accounts {
+            bonding_curve_state = get_bonding_curve_state(pump_fun_accounts);
+
+            if bonding_curve_state.complete {
+                tx_type = "pump_swap".to_string();
+                #[cfg(feature = "verbose_logging")]
+                println!("[{}] - [grpc] Pumpfun token has migrated to pumpswap - applying pumpswap sell logic", now.format("%Y-%m-%d %H:%M:%S%.3f"));
+                let mint = tx_with_pubkey.mint;
+                match resolve_migrated_accounts(&mint, |data| data.pump_fun_accounts.clone(), || get_pump_swap_accounts_rpc(mint)) {
+                    Ok(accounts) => tx_with_pubkey.pump_swap_accounts = Some(accounts),
+                    Err(e) => eprintln!("[{}] - [TRITON-{}] [cid={}] Skipping pump_swap sell for {}: {}",
+                        Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), worker_id, corr_id, mint, e),
+                }
+            }
+        }
+    }
+
+    if tx_type == "ray_launch" {
+        if let Some(ray_launch_accounts) = &tx_with_pubkey.ray_launch_accounts {
+            let pool_state = ray_launch_accounts.pool_state;
+            let rpc_start = Instant::now();
+            let res = match with_rpc_retry(|| rpc.get_account_data(&pool_state)) {
+                Ok(data) => data,
+                Err(e) => {
+                    eprintln!("[crossbeam_worker] Error: get_account_data (raylaunch) failed: {:?}", e);
+                    return;
+                }
+            };
+            let rpc_time = rpc_start.elapsed();
+            let res = match validate_account_data(&res, 21) {
+                Ok(data) => data,
+                Err(e) => {
+                    eprintln!("[crossbeam_worker] Error: invalid raylaunch pool state {}: {}", pool_state, e);
+                    return;
+                }
+            };
+            let status = res[17];
+            let migrate = res[20];
+            launch_quote_reserve = RaydiumPoolState::deserialize(&mut &res[8..]).ok().map(|pool| pool.real_quote);
+
+            if status > 0 {
+                // tx_type = "ray_cpmm".to_string();
+                if migrate == 1 {
+                    #[cfg(feature = "verbose_logging")]
+                    println!("[{}] - [grpc] Raylaunch pool is complete - applying Raydium CPMM sell logic", now.format("%Y-%m-%d %H:%M:%S%.3f"));
+                    tx_type = "ray_cpmm".to_string();
+                    let mint = tx_with_pubkey.mint;
+                    match resolve_migrated_accounts(&mint, |data| data.ray_cpmm_accounts.clone(), || get_ray_cpmm_accounts_rpc(mint)) {
+                        Ok(accounts) => tx_with_pubkey.raydium_cpmm_accounts = Some(accounts),
+                        Err(e) => eprintln!("[{}] - [TRITON-{}] [cid={}] Skipping ray_cpmm sell for {}: {}",
+                            Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), worker_id, corr_id, mint, e),
+                    }
+                }
+            }
+        }
+    }
+
+    // Re-check the pool's SOL side: a pool drained since the buy would only fail the sell or fill at near zero
+    if config.presell_min_liquidity_sol > 0.0 {
+        let quote_reserve = match tx_type.as_str() {
+            "pumpfun" => tx_with_pubkey.pump_fun_accounts.as_ref().map(|_| bonding_curve_state.real_sol_reserves),
+            "ray_launch" => launch_quote_reserve,
+            _ => vault_quote_reserve(&tx_type, &tx_with_pubkey),
+        };
+        let policy = DrainedPolicy::from_name(&config.presell_drained_policy);
+        if let Some(action) = drained_action(quote_reserve, config.presell_min_liquidity_sol, policy) {
+            report_drained(quote_reserve, action, &tx_with_pubkey.mint.to_string(), &tx_type, &corr_id, config);
+            if action == DrainedPolicy::Hold {
+                return;
+            }
+        }
+    }
+
+    // Pre-send SOL estimate, for realized slippage (only the bonding curve's comes without another read)
+    let mut expected_sol_out = None;
+    if tx_type == "pumpfun" {
+        if let Some(pump_fun_accounts) = &tx_with_pubkey.pump_fun_accounts {
+            if config.log_realized_slippage {
+                expected_sol_out = Some(calculate_pump_fun_swap_amount(SwapDirection::Sell, bonding_curve_state, tx_with_pubkey.token_amount, 0).0);
+            }
+            sell_instruction = build_sell_instruction(
+                tx_with_pubkey.token_amount,
+                slippage_bps,
+                pump_fun_accounts,
+                bonding_curve_state,
+            );
+            send_tx = true;
+        }
+    }
+    if tx_type == "pump_swap" {
+        if let Some(pump_swap_accounts) = &tx_with_pubkey.pump_swap_accounts {
+            sell_instruction = build_pump_sell_instruction(
+                tx_with_pubkey.token_amount,
+                slippage_bps,
+                pump_swap_accounts,
+            );
+            send_tx = true;
+        }
+    }
+    if tx_type == "ray_launch" {
+        if let Some(ray_launch_accounts) = &tx_with_pubkey.ray_launch_accounts {
+            sell_instruction = build_ray_launch_sell_instruction(
+                tx_with_pubkey.token_amount,
+                slippage_bps,
+                ray_launch_accounts,
+            );
+            send_tx = true;
+        }
+    }
+    if tx_type == "ray_cpmm" {
+        if let Some(raydium_cpmm_accounts) = &tx_with_pubkey.raydium_cpmm_accounts {
+            sell_instruction = build_ray_cpmm_sell_instruction(
+                tx_with_pubkey.token_amount,
+                raydium_cpmm_accounts,
+            );
+            send_tx = true;
+        }
+    }
+    if tx_type == "ray_launch_cpmm" {
+        if let Some(raydium_cpmm_accounts) = &tx_with_pubkey.raydium_cpmm_accounts {
+            sell_instruction = build_ray_cpmm_sell_instruction(
+                tx_with_pubkey.token_amount,
+                raydium_cpmm_accounts,
+            );
+            send_tx = true;
+        }
+    }
+
+    // Never send a sell whose accounts were resolved for a different token than the one bought
+    if send_tx && config.verify_sell_mint
+        && !sell_targets_mint(&sell_instruction, &tx_with_pubkey.mint, &get_wallet_keypair().pubkey())
+    {
+        report_sell_mint_mismatch(&sell_instruction, &tx_with_pubkey.mint, &tx_type, &corr_id);
+        return;
+    }
+
+    // Share a transaction with other sells firing in the same window
+    if send_tx && batchable && config.sell_batch_window_ms > 0 {
+        let (entry, resend_detect, resend_key) = (tx_with_pubkey.clone(), sig_detect.clone(), sig_bytes.clone());
+        let sell = BatchedSell {
+            mint: tx_with_pubkey.mint,
+            instruction: sell_instruction,
+            tx_type,
+            token_amount: tx_with_pubkey.token_amount,
+            entry_price_usd: tx_with_pubkey.entry_price_usd,
+            key: sig_bytes,
+            sig_detect,
+            corr_id,
+            lifecycle: tx_with_pubkey.lifecycle,
+            expected_sol_out,
+            resend: Box::new(move |slippage_bps, retry| {
+                build_and_send_sell(entry, resend_detect, resend_key, Instant::now(), worker_id, slippage_bps, retry, false, config)
+            }),
+        };
+        let (payer, owner) = (get_fee_payer_keypair().pubkey(), get_wallet_keypair().pubkey());
+        batch_sell(
+            sell,
+            Duration::from_millis(config.sell_batch_window_ms),
+            move |instructions| fits_sell_batch(instructions, &payer, &owner, config.cu_limit),
+            move |group| send_sell_batch(group, config),
+        );
+        return;
+    }
+
+    if send_tx {
+        let build_start = Instant::now();
+        #[cfg(feature = "verbose_logging")]
+        {
+            let now = Utc::now();
+            println!("[{}] - [TRITON] [cid={}] Building sell transaction for sig: {} (tx_type: {})", 
+                Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), corr_id, sig_detect, tx_type);
+        }
+
+        // Build vendor-specific sell transactions in parallel using the same function as buy
+        let build_result = crate::build_tx::tx_builder::build_vendor_specific_transactions_parallel(
+            sell_instruction,
+            tx_with_pubkey.mint,
+            0, // target_token_buy not used for sell transactions
+            &sig_detect, // sig_str for logging
+            &tx_type,
+        );
+        let build_time = build_start.elapsed();
+
+        match build_result {
+            Ok((vendor_transactions, build_failures)) => {
+                if !vendor_transactions.is_empty() {
+                    #[cfg(feature = "verbose_logging")]
+                    {
+                        let now = Utc::now();
+                        println!("[{}] - [TRITON] [cid={}] SUCCESS - Built {} vendor sell transactions for sig: {}", 
+                            Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), corr_id, vendor_transactions.len(), sig_detect);
+                    }
+
+                    // Send all vendor transactions in parallel
+                    let sig_detect_clone = sig_detect.clone();
+                    let sig_bytes_clone = sig_bytes.clone();
+                    let trade = TradeTag { mint: tx_with_pubkey.mint, tx_type: tx_type.clone(), side: "sell" };
+                    let mut lifecycle = tx_with_pubkey.lifecycle;
+                    let (mint, token_amount, entry_price_usd) = (tx_with_pubkey.mint, tx_with_pubkey.token_amount, tx_with_pubkey.entry_price_usd);
+                    // Kept to resend with wider slippage if this sell fails on slippage
+                    let widening = SlippageWidening::from_config(config)
+                        .filter(|_| supports_widening(&tx_type))
+                        .map(|widening| (widening, tx_with_pubkey.clone(), tx_type.clone()));
+                    
+                    let send_start = Instant::now();
+                    ASYNC_RUNTIME.spawn(async move {
+                        let send_result = send_all_vendors_parallel(&vendor_transactions, detection_time, &corr_id, &trade).await;
+                        let send_time = send_start.elapsed();
+
+                        match send_result {
+                            Ok((winning_vendor, sig)) => {
+                                TRITON_TRANSACTIONS_SENT.fetch_add(1, Ordering::Relaxed);
+                                #[cfg(feature = "verbose_logging")]
+                                {
+                                    let now = Utc::now();
+                                    println!(
+                                        "[{}] - [TRITON] [cid={}] PARALLEL SELL SUCCESS - {} won with sig: {} | total sent: {}",
+                                        Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), corr_id,
+                                        winning_vendor,
+                                        sig,
+                                        TRITON_TRANSACTIONS_SENT.load(Ordering::Relaxed)
+                                    );
+                                }
+                                lifecycle.advance(Stage::SellTriggerToSend, Instant::now());
+                                let sell_tip = vendor_transactions.iter()
+                                    .find(|(vendor, _)| *vendor == winning_vendor)
+                                    .map_or(0, |(_, tx)| tip_lamports(tx));
+                                if let Some(expected) = expected_sol_out {
+                                    record_fill("sell", &sig, mint, expected, sell_tip, corr_id.clone());
+                                }
+                                if let Some((_, tx)) = vendor_transactions.iter().find(|(vendor, _)| *vendor == winning_vendor) {
+                                    release_on_landing(&sig, tx);
+                                }
+                                if let Some((widening, entry, sell_tx_type)) = widening {
+                                    // Any vendor's copy may be the one that lands, so watch them all
+                                    let sigs = vendor_transactions.iter().map(|(_, tx)| tx.signatures[0]).collect();
+                                    let (retry_detect, retry_key) = (sig_detect_clone.clone(), sig_bytes_clone.clone());
+                                    ASYNC_RUNTIME.spawn(retry_sell_on_slippage(
+                                        sigs, vec![sell_tx_type], slippage_bps, retry, widening, corr_id.clone(),
+                                        move |wider, next_retry| {
+                                            build_and_send_sell(entry, retry_detect, retry_key, Instant::now(), worker_id, wider, next_retry, false, config)
+                                        },
+                                    ));
+                                }
+                                // The position leaves GLOBAL_TX_MAP once the sell reaches completion_commitment
+                                complete_on_commitment(&sig, sig_bytes_clone.clone(), token_amount, corr_id.clone(),
+                                    Commitment::from_name(&config.completion_commitment), schedule_reconciled_sell);
+                                let sale = SellSale { vendor: winning_vendor.clone(), tip_lamports: sell_tip, mint: mint.to_string(), token_amount, entry_price_usd };
+                                track_sell_landing(sig, lifecycle, corr_id.clone(), sale);
+                            }
+                            Err(e) => {
+                                TRITON_ERRORS.fetch_add(1, Ordering::Relaxed);
+                                if let Some((_, tx)) = vendor_transactions.first() {
+                                    release_nonce_of(tx);
+                                }
+                                #[cfg(feature = "verbose_logging")]
+                                {
+                                    let now = Utc::now();
+                                    eprintln!("[{}] - [TRITON] [cid={}] ERROR - Parallel sell send failed for sig: {} - Error: {:?}", 
+                                        Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), corr_id, sig_detect_clone, e);
+                                }
+                            }
+                        }
+                    });
+                } else {
+                    TRITON_ERRORS.fetch_add(1, Ordering::Relaxed);
+                    eprintln!("[{}] - [TRITON] [cid={}] ERROR - No vendor sell transactions built for sig: {} | {}", 
+                        Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), corr_id, sig_detect, build_failures.summary());
+                }
+            }
+            Err(e) => {
+                TRITON_ERRORS.fetch_add(1, Ordering::Relaxed);
+                #[cfg(feature = "verbose_logging")]
+                {
+                    let now = Utc::now();
+                    eprintln!("[{}] - [TRITON] [cid={}] ERROR - Failed to build vendor sell transactions for sig: {} - Error: {:?}", 
+                        Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), corr_id, sig_detect, e);
+                }
+            }
+        }
+    } else {
+        #[cfg(feature = "verbose_logging")]
+        {
+            let now = Utc::now();
+            println!("[{}] - [TRITON] [cid={}] No sell transaction to build for sig: {} (tx_type: {})", 
+                Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), corr_id, sig_detect, tx_type);
+        }
+    }
+}
+
+/// Send one packed group of batched sells as a single transaction. Every position closes on the
+/// shared signature; the daily summary and lifecycle latencies count it once, under the first sell.
+/// If the batch can't be built or sent, or fails on slippage, each sell is resent alone.
+fn send_sell_batch(sells: Vec<BatchedSell>, config: &'static Config) {
+    let (first_mint, first_tx_type) = match sells.first() {
+        Some(first) => (first.mint, first.tx_type.clone()),
+        None => return,
+    };
+    // Request the heap frame if any sell in the batch needs it
+    let tx_type = sells.iter()
+        .map(|sell| sell.tx_type.clone())
+        .find(|tx_type| heap_frame_for_tx_type(config, tx_type).is_some())
+        .unwrap_or(first_tx_type);
+    let corr_id = sells.iter().map(|sell| sell.corr_id.as_str()).collect::<Vec<_>>().join(",");
+    let label = sells.iter().map(|sell| sell.sig_detect.as_str()).collect::<Vec<_>>().join(",");
+    println!("[{}] - [SELL_BATCH] [cid={}] Sending {} sells in one transaction: {}",
+        Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), corr_id, sells.len(),
+        sells.iter().map(|sell| sell.mint.to_string()).collect::<Vec<_>>().join(", "));
+
+    let instructions = sells.iter().map(|sell| sell.instruction.clone()).collect();
+    let vendor_transactions = match build_vendor_specific_transactions_multi(instructions, first_mint, 0, &label, &tx_type, 1.0, None) {
+        Ok((vendor_transactions, _)) if !vendor_transactions.is_empty() => vendor_transactions,
+        Ok((_, build_failures)) => {
+            TRITON_ERRORS.fetch_add(1, Ordering::Relaxed);
+            eprintln!("[{}] - [SELL_BATCH] [cid={}] ERROR - No vendor sell transactions built, sending each sell alone | {}",
+                Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), corr_id, build_failures.summary());
+            resend_each(sells, config.sell_slippage_bps, 0);
+            return;
+        }
+        Err(e) => {
+            TRITON_ERRORS.fetch_add(1, Ordering::Relaxed);
+            eprintln!("[{}] - [SELL_BATCH] [cid={}] ERROR - Failed to build batched sell, sending each sell alone: {:?}",
+                Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), corr_id, e);
+            resend_each(sells, config.sell_slippage_bps, 0);
+            return;
+        }
+    };
+
+    let trade = TradeTag { mint: first_mint, tx_type, side: "sell" };
+    let detection_time = Instant::now();
+    ASYNC_RUNTIME.spawn(async move {
+        match send_all_vendors_parallel(&vendor_transactions, detection_time, &corr_id, &trade).await {
+            Ok((winning_vendor, sig)) => {
+                TRITON_TRANSACTIONS_SENT.fetch_add(1, Ordering::Relaxed);
+                let winning_tx = vendor_transactions.iter().find(|(vendor, _)| *vendor == winning_vendor).map(|(_, tx)| tx);
+                let sell_tip = winning_tx.map_or(0, tip_lamports);
+                if let Some(tx) = winning_tx {
+                    release_on_landing(&sig, tx);
+                }
+                // The wallet's SOL delta covers the whole batch, so it is recorded as one fill
+                let expected: Option<u64> = sells.iter().map(|sell| sell.expected_sol_out).sum();
+                if let Some(expected) = expected {
+                    record_fill("sell", &sig, first_mint, expected, sell_tip, corr_id.clone());
+                }
+                let tx_types: Vec<String> = sells.iter().map(|sell| sell.tx_type.clone()).collect();
+                let mut positions = Vec::with_capacity(sells.len());
+                let mut resends = Vec::with_capacity(sells.len());
+                for (index, sell) in sells.into_iter().enumerate() {
+                    if index == 0 {
+                        let mut lifecycle = sell.lifecycle;
+                        lifecycle.advance(Stage::SellTriggerToSend, Instant::now());
+                        let sale = SellSale {
+                            vendor: winning_vendor.clone(),
+                            tip_lamports: sell_tip,
+                            mint: sell.mint.to_string(),
+                            token_amount: sell.token_amount,
+                            entry_price_usd: sell.entry_price_usd,
+                        };
+                        track_sell_landing(sig.clone(), lifecycle, sell.corr_id.clone(), sale);
+                    }
+                    positions.push((sell.key, sell.token_amount, sell.corr_id));
+                    resends.push(sell.resend);
+                }
+                // A slippage failure reverts the whole batch: resend every sell alone, wider
+                let widening = SlippageWidening::from_config(config)
+                    .filter(|_| tx_types.iter().any(|tx_type| supports_widening(tx_type)));
+                if let Some(widening) = widening {
+                    let sigs = vendor_transactions.iter().map(|(_, tx)| tx.signatures[0]).collect();
+                    ASYNC_RUNTIME.spawn(retry_sell_on_slippage(
+                        sigs, tx_types, config.sell_slippage_bps, 0, widening, corr_id.clone(),
+                        move |wider, next_retry| {
+                            for resend in resends {
+                                resend(wider, next_retry);
+                            }
+                        },
+                    ));
+                }
+                complete_batch_on_commitment(&sig, positions, Commitment::from_name(&config.completion_commitment), schedule_reconciled_sell);
+            }
+            Err(e) => {
+                TRITON_ERRORS.fetch_add(1, Ordering::Relaxed);
+                if let Some((_, tx)) = vendor_transactions.first() {
+                    release_nonce_of(tx);
+                }
+                eprintln!("[{}] - [SELL_BATCH] [cid={}] ERROR - Batched sell send failed, sending each sell alone: {:?}",
+                    Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), corr_id, e);
+                let _ = tokio::task::spawn_blocking(move || resend_each(sells, config.sell_slippage_bps, 0)).await;
+            }
+        }
+    });
+}
+
+/// Fall back from a batch: send each of `sells` in its own transaction
+fn resend_each(sells: Vec<BatchedSell>, slippage_bps: u64, retry: u32) {
+    for sell in sells {
+        (sell.resend)(slippage_bps, retry);
     }
 }
 
@@ -676,89 +1331,85 @@ pub fn send_parsed_tx(parsed: ParsedTx) {
     }
 } 
 
+#[cfg(test)]
+mod tests {
+    use super::*;
 
+    #[test]
+    fn test_dedup_eviction_keeps_newest() {
+        let map: DashMap<String, (String, Instant)> = DashMap::new();
+        let base = Instant::now();
+        for i in 0..10u64 {
+            map.insert(format!("sig{}", i), ("feed1".to_string(), base + Duration::from_millis(i)));
+        }
 
+        assert_eq!(evict_oldest_dedup_entries(&map, 20), 0);
+        assert_eq!(evict_oldest_dedup_entries(&map, 4), 6);
+        assert_eq!(map.len(), 4);
+        for i in 6..10 {
+            assert!(map.contains_key(&format!("sig{}", i)));
+        }
+        for i in 0..6 {
+            assert!(!map.contains_key(&format!("sig{}", i)));
+        }
+    }
+
+    #[test]
+    fn test_resolve_migrated_accounts_falls_back_without_monitoring_data() {
+        let missing = Pubkey::new_unique();
+        // No monitoring data: RPC derivation is used
+        let resolved = resolve_migrated_accounts(&missing, |_| 1u8, || Ok(2u8));
+        assert_eq!(resolved, Ok(2));
+        // RPC derivation failing too: error is returned instead of panicking
+        let resolved = resolve_migrated_accounts(&missing, |_| 1u8, || Err(BuildError::AccountNotFound("pool".to_string())));
+        assert_eq!(resolved, Err(BuildError::AccountNotFound("pool".to_string())));
+
+        let captured = Pubkey::new_unique();
+        GLOBAL_MONITORING_DATA.insert(captured, MonitoringData {
+            mint_pubkey: captured,
+            timestamp: 0,
+            ray_cpmm_accounts: Default::default(),
+            pump_fun_accounts: Default::default(),
+        });
+        let resolved = resolve_migrated_accounts(&captured, |data| data.mint_pubkey, || panic!("RPC must not be used"));
+        assert_eq!(resolved, Ok(captured));
+        GLOBAL_MONITORING_DATA.remove(&captured);
+    }
 
-// pub async fn process_triton_message(resp: &SubscribeUpdate) {
-//     let config = GLOBAL_CONFIG.get().expect("Config not initialized");
-
-//     if let Some(update) = &resp.update_oneof {
-//         match update {
-//             UpdateOneof::Transaction(tx_update) => {
-//                 if let Some(tx_info) = &tx_update.transaction {
-//                     if let Some(tx) = &tx_info.transaction {
-//                         let sig_bytes = tx.signatures.get(0).map(|s| s.clone());
-//                         let wallet_pubkey = get_wallet_keypair().pubkey();
-//                         let is_signer = if let Some(message) = &tx.message {
-//                             if let Some(header) = &message.header {
-//                                 let num_signers = header.num_required_signatures as usize;
-//                                 message.account_keys
-//                                     .iter()
-//                                     .take(num_signers)
-//                                     .any(|bytes| {
-//                                         let pubkey = unsafe {
-//                                             Pubkey::new_from_array(*(bytes.as_ptr() as *const [u8; 32]))
-//                                         };
-//                                         pubkey == wallet_pubkey
-//                                     })
-//                             } else { false }
-//                         } else { false };
-//                         // Handoff to crossbeam worker for heavy processing
-//                         let parsed = ParsedTx {
-//                             sig_bytes,
-//                             is_signer,
-//                             slot: Some(tx_update.slot),
-//                         };
-//                         send_parsed_tx(parsed);
-//                         return;
-//                     }
-//                 }
-//             }
-//             // The following arms are commented out for speed. Uncomment if needed for debugging or additional features.
-//             // UpdateOneof::Slot(slot_info) => {
-//             //     let now = Utc::now();
-//             //     println!(
-//             //         "[{}] - [Triton] Slot Update: slot={}, status={:?}",
-//             //         now.format("%Y-%m-%d %H:%M:%S%.3f"),
-//             //         slot_info.slot,
-//             //         slot_info.status()
-//             //     );
-//             // }
-//             // UpdateOneof::Account(account_info) => {
-//             //     let now = Utc::now();
-//             //     println!(
-//             //         "[{}] - [Triton] Account Update: {:?}",
-//             //         now.format("%Y-%m-%d %H:%M:%S%.3f"),
-//             //         account_info.account
-//             //     );
-//             // }
-//             // UpdateOneof::Block(block_info) => {
-//             //     // let now = Utc::now();
-//             //     // println!("[{}] - [Triton] Block Update: slot={}", now.format("%Y-%m-%d %H:%M:%S%.3f"), block_info.slot);
-//             // }
-//             // UpdateOneof::Ping(_) => {
-//             //     // let now = Utc::now();
-//             //     // println!(
-//             //     //     "[{}] - [Triton] Received ping.",
-//             //     //     now.format("%Y-%m-%d %H:%M:%S%.3f")
-//             //     // );
-//             // }
-//             // UpdateOneof::Pong(_) => {
-//             //     // let now = Utc::now();
-//             //     // println!(
-//             //     //     "[{}] - [Triton] Received pong.",
-//             //     //     now.format("%Y-%m-%d %H:%M:%S%.3f")
-//             //     // );
-//             // }
-//             // _ => {
-//             //     let now = Utc::now();
-//             //     println!(
-//             //         "[{}] - [Triton] Received other message type: {:?}",
-//             //         now.format("%Y-%m-%d %H:%M:%S%.3f"),
-//             //         update
-//             //     );
-//             // }
-//             _ => {}
-//         }
-//     }
-// }
+    #[test]
+    fn test_jittered_wait_time_bounds() {
+        let mut rng = rand::thread_rng();
+        assert_eq!(jittered_wait_time(3.0, 0.0, &mut rng), Duration::from_secs(3));
+        for _ in 0..1000 {
+            let wait = jittered_wait_time(3.0, 20.0, &mut rng).as_secs_f64();
+            assert!((2.4..=3.6).contains(&wait), "wait {} outside ±20%", wait);
+        }
+        // Jitter above 100% is clamped, so the wait never goes negative
+        for _ in 0..1000 {
+            assert!(jittered_wait_time(1.0, 500.0, &mut rng).as_secs_f64() <= 2.0);
+        }
+    }
+
+    #[test]
+    fn test_monitored_migration_cache_hit() {
+        let mint = Pubkey::new_unique();
+        assert!(monitored_migration(&mint, "pumpfun").is_none());
+
+        let mut pump_swap = PumpAmmAccounts::default();
+        pump_swap.pool = Pubkey::new_unique();
+        GLOBAL_MONITORING_DATA.insert(mint, MonitoringData {
+            mint_pubkey: mint,
+            timestamp: 0,
+            ray_cpmm_accounts: RayCpmmSwapAccounts::default(),
+            pump_fun_accounts: pump_swap,
+        });
+        match monitored_migration(&mint, "pumpfun") {
+            Some(MonitoredMigration::PumpSwap(accounts)) => assert_eq!(accounts.pool, pump_swap.pool),
+            other => panic!("expected pump_swap accounts, got {:?}", other),
+        }
+        // Only the pump migration is known: a ray_launch position still needs the RPC check
+        assert!(monitored_migration(&mint, "ray_launch").is_none());
+        assert!(monitored_migration(&mint, "pump_swap").is_none());
+        GLOBAL_MONITORING_DATA.remove(&mint);
+    }
+}
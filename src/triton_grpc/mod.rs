@@ -1,6 +1,7 @@
 pub mod client;
 pub mod parser;
 pub mod crossbeam_worker;
+pub mod sell_fraction;
 
 // OPTIMIZATION: Re-export main functions for easier access
 pub use client::{setup_multiple_triton_feeds, subscribe_with_retry_triton};
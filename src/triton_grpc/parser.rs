@@ -7,7 +7,28 @@ use crate::init::wallet_loader::get_wallet_keypair;
 use std::time::Instant;
 use crate::utils::logger::{log_event, EventType};
 use crate::triton_grpc::crossbeam_worker::{ParsedTx, send_parsed_tx, is_signature_processed_by_feed};
+use crate::solana::storage::confirmed_block::Message;
+use crate::triton_grpc::sell_fraction::to_ui_token_balances;
 use chrono::Utc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static MALFORMED_SKIPPED: AtomicUsize = AtomicUsize::new(0);
+
+/// Transactions skipped because their signer set couldn't be read
+pub fn get_malformed_skipped() -> usize {
+    MALFORMED_SKIPPED.load(Ordering::Relaxed)
+}
+
+/// Whether `wallet` is among the signers of `message`. None when the message or its header is
+/// missing, or the header's signer count doesn't fit the account keys, so the signers are unknown.
+pub fn signer_check(message: Option<&Message>, wallet: &[u8; 32]) -> Option<bool> {
+    let message = message?;
+    let num_signers = message.header.as_ref()?.num_required_signatures as usize;
+    if num_signers == 0 || num_signers > message.account_keys.len() {
+        return None;
+    }
+    Some(message.account_keys[..num_signers].iter().any(|bytes| bytes.as_slice() == wallet))
+}
 
 // OPTIMIZATION: Enhanced parser for multiple feeds
 pub fn process_triton_message(resp: &SubscribeUpdate, feed_id: &str) {
@@ -28,6 +49,7 @@ pub fn process_triton_message(resp: &SubscribeUpdate, feed_id: &str) {
     if let Some(update) = &resp.update_oneof {
         match update {
             UpdateOneof::Transaction(tx_update) => {
+                crate::grpc::arpc_worker::update_current_slot(tx_update.slot);
                 let tx_update_check_start = std::time::Instant::now();
                 let tx_update_check = tx_update.transaction.is_some();
                 let tx_update_check_time = tx_update_check_start.elapsed();
@@ -66,15 +88,17 @@ pub fn process_triton_message(resp: &SubscribeUpdate, feed_id: &str) {
                         let wallet_check_start = std::time::Instant::now();
                         let wallet_pubkey = get_wallet_keypair().pubkey();
                         let wallet_pubkey_bytes = wallet_pubkey.to_bytes();
-                        let is_signer = if let Some(message) = &tx.message {
-                            if let Some(header) = &message.header {
-                                let num_signers = header.num_required_signatures as usize;
-                                message.account_keys
-                                    .iter()
-                                    .take(num_signers)
-                                    .any(|bytes| bytes.as_slice() == wallet_pubkey_bytes)
-                            } else { false }
-                        } else { false };
+                        let is_signer = match signer_check(tx.message.as_ref(), &wallet_pubkey_bytes) {
+                            Some(is_signer) => is_signer,
+                            // Unknown signer set: defaulting to false would send a possible sell trigger down the buy path
+                            None if config.skip_unrecognized_signer => {
+                                MALFORMED_SKIPPED.fetch_add(1, Ordering::Relaxed);
+                                eprintln!("[{}] - [TRITON] SKIPPED transaction with missing or malformed message header for sig: {} (feed: {}) (total skipped: {})",
+                                    Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), sig_string, feed_id, get_malformed_skipped());
+                                return;
+                            }
+                            None => false,
+                        };
                         let wallet_check_time = wallet_check_start.elapsed();
                         
                         // OPTIMIZATION: Only log if verbose mode is enabled
@@ -83,11 +107,14 @@ pub fn process_triton_message(resp: &SubscribeUpdate, feed_id: &str) {
                             log_event(EventType::GrpcDetectionProcessing, sig_bytes, start_time, None);
                         }
                         
-                        // Extract token balances from gRPC transaction data
-                        // For now, we'll skip the conversion and use the existing RPC-based approach
-                        // TODO: Implement proper conversion from proto TokenBalance to UiTransactionTokenBalance
-                        let pre_token_balances = None;
-                        let post_token_balances = None;
+                        // Token balances are only converted when the copy-sell needs them
+                        let (pre_token_balances, post_token_balances) = match &tx_info.meta {
+                            Some(meta) if config.copy_sell_fraction => (
+                                Some(to_ui_token_balances(&meta.pre_token_balances)),
+                                Some(to_ui_token_balances(&meta.post_token_balances)),
+                            ),
+                            _ => (None, None),
+                        };
                         
                         let parsed = ParsedTx {
                             sig_bytes,
@@ -178,3 +205,30 @@ pub fn get_blockhash() -> Hash {
     };
     blockhash
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solana::storage::confirmed_block::MessageHeader;
+
+    #[test]
+    fn test_malformed_header_skipped_not_misrouted() {
+        let wallet = [7u8; 32];
+        let header = |n: u32| Some(MessageHeader { num_required_signatures: n, ..Default::default() });
+        let message = |header: Option<MessageHeader>, keys: Vec<[u8; 32]>| Message {
+            header,
+            account_keys: keys.into_iter().map(|k| k.to_vec()).collect(),
+            ..Default::default()
+        };
+
+        // Well-formed: our wallet as fee payer, or only as a non-signer account
+        assert_eq!(signer_check(Some(&message(header(1), vec![wallet, [1; 32]])), &wallet), Some(true));
+        assert_eq!(signer_check(Some(&message(header(1), vec![[1; 32], wallet])), &wallet), Some(false));
+
+        // Malformed: unknown rather than "not a signer", so it isn't routed as a buy trigger
+        assert_eq!(signer_check(None, &wallet), None);
+        assert_eq!(signer_check(Some(&message(None, vec![wallet])), &wallet), None);
+        assert_eq!(signer_check(Some(&message(header(0), vec![wallet])), &wallet), None);
+        assert_eq!(signer_check(Some(&message(header(3), vec![wallet, [1; 32]])), &wallet), None);
+    }
+}
@@ -0,0 +1,121 @@
+// sell_fraction.rs
+// Proportional copy-sells: when the tracked wallet sells part of a token we hold, record the fraction
+// of its holding it sold (from the feed's pre/post token balances) and sell that fraction of ours.
+
+use crate::grpc::arpc_worker::GLOBAL_TX_MAP;
+use crate::solana::storage::confirmed_block::TokenBalance;
+use chrono::Utc;
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use solana_account_decoder::parse_token::UiTokenAmount;
+use solana_sdk::pubkey::Pubkey;
+use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::UiTransactionTokenBalance;
+use std::collections::HashSet;
+
+// Latest partial sell by the tracked wallet per mint we hold, taken by the next sell of that mint
+static LEADER_SELL_FRACTIONS: Lazy<DashMap<Pubkey, f64>> = Lazy::new(DashMap::new);
+
+/// Feed token balances in the RPC shape the rest of the balance helpers use
+pub fn to_ui_token_balances(balances: &[TokenBalance]) -> Vec<UiTransactionTokenBalance> {
+    balances
+        .iter()
+        .map(|balance| {
+            let amount = balance.ui_token_amount.as_ref();
+            UiTransactionTokenBalance {
+                account_index: balance.account_index as u8,
+                mint: balance.mint.clone(),
+                ui_token_amount: UiTokenAmount {
+                    ui_amount: amount.map(|a| a.ui_amount),
+                    decimals: amount.map_or(0, |a| a.decimals as u8),
+                    amount: amount.map_or_else(|| "0".to_string(), |a| a.amount.clone()),
+                    ui_amount_string: amount.map_or_else(String::new, |a| a.ui_amount_string.clone()),
+                },
+                owner: OptionSerializer::Some(balance.owner.clone()),
+                program_id: OptionSerializer::Some(balance.program_id.clone()),
+            }
+        })
+        .collect()
+}
+
+/// Raw amount of `mint` held by `owner` across its token accounts in `balances`
+pub fn holding_of(balances: &[UiTransactionTokenBalance], owner: &str, mint: &str) -> u64 {
+    balances
+        .iter()
+        .filter(|b| b.mint == mint && matches!(&b.owner, OptionSerializer::Some(o) if o == owner))
+        .filter_map(|b| b.ui_token_amount.amount.parse::<u64>().ok())
+        .sum()
+}
+
+/// Fraction (0, 1] of its `mint` holding `owner` sold in a transaction; None if it held none or didn't sell
+pub fn sold_fraction(pre: &[UiTransactionTokenBalance], post: &[UiTransactionTokenBalance], owner: &str, mint: &str) -> Option<f64> {
+    let before = holding_of(pre, owner, mint);
+    let after = holding_of(post, owner, mint);
+    if before == 0 || after >= before {
+        return None;
+    }
+    Some((before - after) as f64 / before as f64)
+}
+
+/// `fraction` of `amount`, rounded down; a full (or out of range) fraction keeps the whole amount
+pub fn fraction_of(amount: u64, fraction: f64) -> u64 {
+    if !(fraction > 0.0 && fraction < 1.0) {
+        return amount;
+    }
+    (amount as f64 * fraction) as u64
+}
+
+/// Record the fraction `leader` sold of every mint we hold an open position in
+pub fn record_leader_sells(pre: &[UiTransactionTokenBalance], post: &[UiTransactionTokenBalance], leader: &str, sig: &str) {
+    let held: HashSet<Pubkey> = GLOBAL_TX_MAP.iter().filter(|e| e.open_position).map(|e| e.mint).collect();
+    for mint in held {
+        if let Some(fraction) = sold_fraction(pre, post, leader, &mint.to_string()) {
+            LEADER_SELL_FRACTIONS.insert(mint, fraction);
+            println!("[{}] - [COPY_SELL] Tracked wallet sold {:.1}% of its {} (sig: {})",
+                Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), fraction * 100.0, mint, sig);
+        }
+    }
+}
+
+/// The tracked wallet's recorded sell fraction for `mint`, if any (consumed)
+pub fn take_leader_sell_fraction(mint: &Pubkey) -> Option<f64> {
+    LEADER_SELL_FRACTIONS.remove(mint).map(|(_, fraction)| fraction)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn balance(owner: &str, mint: &str, amount: u64) -> UiTransactionTokenBalance {
+        UiTransactionTokenBalance {
+            account_index: 1,
+            mint: mint.to_string(),
+            ui_token_amount: UiTokenAmount {
+                ui_amount: None,
+                decimals: 6,
+                amount: amount.to_string(),
+                ui_amount_string: String::new(),
+            },
+            owner: OptionSerializer::Some(owner.to_string()),
+            program_id: OptionSerializer::None,
+        }
+    }
+
+    #[test]
+    fn test_partial_sell_fraction() {
+        let (leader, pool, mint) = ("leader", "pool", "mint");
+        // Leader sells 250k of 1M; the pool's side grows, which mustn't count
+        let pre = vec![balance(leader, mint, 1_000_000), balance(pool, mint, 9_000_000)];
+        let post = vec![balance(leader, mint, 750_000), balance(pool, mint, 9_250_000)];
+        let fraction = sold_fraction(&pre, &post, leader, mint).unwrap();
+        assert!((fraction - 0.25).abs() < 1e-9);
+        assert_eq!(fraction_of(400_000, fraction), 100_000);
+
+        // Full exit: the account is gone from the post balances
+        assert_eq!(sold_fraction(&pre, &[balance(pool, mint, 10_000_000)], leader, mint), Some(1.0));
+        assert_eq!(fraction_of(400_000, 1.0), 400_000);
+        // A buy, or a mint the leader never held: no sell
+        assert_eq!(sold_fraction(&post, &pre, leader, mint), None);
+        assert_eq!(sold_fraction(&pre, &post, leader, "other"), None);
+    }
+}
@@ -0,0 +1,122 @@
+use crate::config_load::GLOBAL_CONFIG;
+
+// Thread pools pinned through the `core_affinity` config map
+pub const ARPC_WORKERS: &str = "arpc_workers";
+pub const TRITON_WORKERS: &str = "triton_workers";
+pub const SEND_RUNTIME: &str = "send_runtime";
+
+/// Built-in core set per pool; an empty set leaves the pool unpinned
+fn default_cores(role: &str) -> Vec<usize> {
+    match role {
+        ARPC_WORKERS => vec![5, 6, 7],
+        TRITON_WORKERS => vec![2, 3, 4],
+        _ => Vec::new(),
+    }
+}
+
+/// Cores for `role`: the `core_affinity` entry, else (for the send runtime) `send_runtime_cores`, else the built-in set
+pub fn cores_for(role: &str) -> Vec<usize> {
+    let config = match GLOBAL_CONFIG.get() {
+        Some(config) => config,
+        None => return default_cores(role),
+    };
+    if let Some(cores) = config.core_affinity.get(role) {
+        return cores.clone();
+    }
+    if role == SEND_RUNTIME && !config.send_runtime_cores.is_empty() {
+        return config.send_runtime_cores.clone();
+    }
+    default_cores(role)
+}
+
+/// Cores of `role` also assigned to any of `others`
+pub fn overlapping_cores(role: &str, others: &[&str]) -> Vec<usize> {
+    let others: Vec<usize> = others.iter().flat_map(|other| cores_for(other)).collect();
+    cores_for(role).into_iter().filter(|core| others.contains(core)).collect()
+}
+
+/// Pin the current thread to core `id`; false if the core doesn't exist or pinning failed
+pub fn pin_current_to(id: usize) -> bool {
+    core_affinity::get_core_ids()
+        .and_then(|ids| ids.into_iter().find(|core| core.id == id))
+        .map_or(false, core_affinity::set_for_current)
+}
+
+/// Parse a kernel CPU list such as `2-4,7`
+pub fn parse_cpu_list(list: &str) -> Vec<usize> {
+    list.trim()
+        .split(',')
+        .filter(|part| !part.is_empty())
+        .flat_map(|part| match part.split_once('-') {
+            Some((start, end)) => match (start.parse::<usize>(), end.parse::<usize>()) {
+                (Ok(start), Ok(end)) => (start..=end).collect(),
+                _ => Vec::new(),
+            },
+            None => part.parse().map(|core| vec![core]).unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// Cores the current thread may run on, as the kernel reports them (Linux only)
+pub fn current_thread_cores() -> Option<Vec<usize>> {
+    let status = std::fs::read_to_string("/proc/thread-self/status").ok()?;
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("Cpus_allowed_list:"))
+        .map(parse_cpu_list)
+}
+
+/// Pin the current thread to `id` and check the kernel agrees; logs the outcome under `label`
+pub fn pin_and_verify(label: &str, id: usize) -> bool {
+    if !pin_current_to(id) {
+        eprintln!("[AFFINITY] {}: failed to pin to core {}", label, id);
+        return false;
+    }
+    match current_thread_cores() {
+        Some(cores) if cores == [id] => {
+            println!("[AFFINITY] {} pinned to core {}", label, id);
+            true
+        }
+        Some(cores) => {
+            eprintln!("[AFFINITY] {}: pinned to core {} but allowed cores are {:?}", label, id, cores);
+            false
+        }
+        // No /proc (non-Linux): trust the pin call
+        None => {
+            println!("[AFFINITY] {} pinned to core {} (unverified)", label, id);
+            true
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cpu_list() {
+        assert_eq!(parse_cpu_list("2-4,7\n"), vec![2, 3, 4, 7]);
+        assert_eq!(parse_cpu_list("0"), vec![0]);
+        assert!(parse_cpu_list("").is_empty());
+    }
+
+    #[test]
+    fn test_default_cores_are_disjoint() {
+        assert!(overlapping_cores(SEND_RUNTIME, &[ARPC_WORKERS, TRITON_WORKERS]).is_empty());
+        assert!(overlapping_cores(ARPC_WORKERS, &[TRITON_WORKERS]).is_empty());
+    }
+
+    #[test]
+    fn test_pin_lands_on_intended_core() {
+        let core = match core_affinity::get_core_ids().and_then(|ids| ids.last().copied()) {
+            Some(core) => core.id,
+            None => return,
+        };
+        let pinned = std::thread::spawn(move || pin_and_verify("test", core).then(current_thread_cores))
+            .join()
+            .unwrap();
+        if let Some(Some(cores)) = pinned {
+            assert_eq!(cores, vec![core]);
+        }
+    }
+}
@@ -2,6 +2,21 @@ use solana_sdk::signature::Signer;
 use solana_sdk::{pubkey::Pubkey, signature::Keypair};
 use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
 use spl_token::solana_program::instruction::Instruction;
+use spl_associated_token_account::get_associated_token_address;
+use solana_client::rpc_client::RpcClient;
+use dashmap::DashMap;
+use once_cell::sync::{Lazy, OnceCell};
+use std::collections::HashSet;
+
+/// Cached user ATA for a (wallet, mint) pair
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CachedAta {
+    pub address: Pubkey,
+    pub exists: bool,
+}
+
+// (wallet, mint) -> ATA address and whether it is known to exist on chain
+static ATA_CACHE: Lazy<DashMap<(Pubkey, Pubkey), CachedAta>> = Lazy::new(DashMap::new);
 
 pub fn create_ata(payer: &Keypair, wallet_address: &Pubkey, mint: &Pubkey) -> Instruction {
     let program_id_str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
@@ -14,3 +29,115 @@ pub fn create_ata(payer: &Keypair, wallet_address: &Pubkey, mint: &Pubkey) -> In
     );
     associated_token_account_ix
 }
+
+/// Get the cached ATA for (wallet, mint), deriving and caching the address if missing
+pub fn derive_ata_cached(wallet: &Pubkey, mint: &Pubkey) -> CachedAta {
+    *ATA_CACHE
+        .entry((*wallet, *mint))
+        .or_insert_with(|| CachedAta {
+            address: get_associated_token_address(wallet, mint),
+            exists: false,
+        })
+}
+
+/// Look up the cached ATA without deriving
+pub fn get_cached_ata(wallet: &Pubkey, mint: &Pubkey) -> Option<CachedAta> {
+    ATA_CACHE.get(&(*wallet, *mint)).map(|entry| *entry)
+}
+
+/// Fetch the ATA's existence via RPC and cache the result
+pub fn fetch_and_cache_ata(rpc: &RpcClient, wallet: &Pubkey, mint: &Pubkey) -> CachedAta {
+    let mut ata = derive_ata_cached(wallet, mint);
+    if !ata.exists {
+        ata.exists = rpc.get_account(&ata.address).is_ok();
+        ATA_CACHE.insert((*wallet, *mint), ata);
+    }
+    ata
+}
+
+/// Mark the ATA as existing (e.g. after our buy, which creates it, has landed)
+pub fn mark_ata_exists(wallet: &Pubkey, mint: &Pubkey) -> CachedAta {
+    let mut entry = ATA_CACHE
+        .entry((*wallet, *mint))
+        .or_insert_with(|| CachedAta {
+            address: get_associated_token_address(wallet, mint),
+            exists: true,
+        });
+    entry.exists = true;
+    *entry
+}
+
+/// Drop the cached ATA for a mint once the position is closed
+pub fn remove_cached_ata(wallet: &Pubkey, mint: &Pubkey) {
+    ATA_CACHE.remove(&(*wallet, *mint));
+}
+
+/// Mints whose create-ATA instruction is omitted (the ATA is trusted to exist)
+#[derive(Debug, Default)]
+pub struct AtaSkipList {
+    pub all: bool,
+    pub mints: HashSet<Pubkey>,
+}
+
+static ATA_SKIP_LIST: OnceCell<AtaSkipList> = OnceCell::new();
+
+/// Build the skip list from config and verify the listed ATAs exist on chain.
+/// Mints whose ATA is missing stay on the normal create-ATA path.
+pub fn init_ata_skip_list(rpc: &RpcClient, wallet: &Pubkey, skip_all: bool, skip_mints: &[String]) {
+    let mut mints = HashSet::new();
+    for entry in skip_mints {
+        let mint = match entry.parse::<Pubkey>() {
+            Ok(mint) => mint,
+            Err(e) => {
+                println!("[ATA] Invalid skip_ata_mints entry '{}': {}", entry, e);
+                continue;
+            }
+        };
+        let ata = fetch_and_cache_ata(rpc, wallet, &mint);
+        if ata.exists {
+            mints.insert(mint);
+        } else {
+            println!("[ATA] WARNING: ATA {} for skip-listed mint {} does not exist, keeping create-ATA", ata.address, mint);
+        }
+    }
+    println!(
+        "[ATA] Create-ATA skipped for {}",
+        if skip_all { "all mints".to_string() } else { format!("{}/{} skip-listed mints", mints.len(), skip_mints.len()) }
+    );
+    let _ = ATA_SKIP_LIST.set(AtaSkipList { all: skip_all, mints });
+}
+
+/// Whether the create-ATA instruction should be omitted for this mint
+pub fn skip_ata_creation(mint: &Pubkey) -> bool {
+    ATA_SKIP_LIST
+        .get()
+        .map_or(false, |list| list.all || list.mints.contains(mint))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ata_cache_population_and_reuse() {
+        let wallet = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        assert!(get_cached_ata(&wallet, &mint).is_none());
+
+        let first = derive_ata_cached(&wallet, &mint);
+        assert_eq!(first.address, get_associated_token_address(&wallet, &mint));
+        assert!(!first.exists);
+
+        // Second lookup reuses the cached entry
+        assert_eq!(get_cached_ata(&wallet, &mint), Some(first));
+        assert_eq!(derive_ata_cached(&wallet, &mint), first);
+
+        let landed = mark_ata_exists(&wallet, &mint);
+        assert_eq!(landed.address, first.address);
+        assert!(landed.exists);
+        assert!(derive_ata_cached(&wallet, &mint).exists);
+
+        remove_cached_ata(&wallet, &mint);
+        assert!(get_cached_ata(&wallet, &mint).is_none());
+    }
+}
@@ -0,0 +1,193 @@
+// ata_sweep.rs
+// Periodic close of empty / dust token accounts left by closed positions, reclaiming their rent.
+
+use crate::build_tx::pump_swap::pump_swap_constants::WSOL;
+use crate::grpc::arpc_worker::GLOBAL_TX_MAP;
+use crate::init::initialize::GLOBAL_RPC_CLIENT;
+use crate::init::wallet_loader::get_wallet_keypair;
+use crate::utils::ata::{remove_cached_ata, skip_ata_creation};
+use chrono::Utc;
+use solana_account_decoder::UiAccountData;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_request::TokenAccountsFilter;
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::Transaction;
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::time::Duration;
+
+const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EN5ZWQ8gAn7StP5YBX7";
+// Token accounts closed per transaction (a dust account takes a burn + close)
+const CLOSES_PER_TX: usize = 8;
+// SPL Token / Token-2022 instruction tags (same layout in both programs)
+const BURN_TAG: u8 = 8;
+const CLOSE_ACCOUNT_TAG: u8 = 9;
+
+/// A token account owned by the wallet
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenAccount {
+    pub address: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub token_program: Pubkey,
+}
+
+/// Accounts to close: empty or at most `dust_amount`, never WSOL, a mint with an open position,
+/// or a mint on the ATA skip list (buys there rely on the ATA existing)
+pub fn select_closable(accounts: &[TokenAccount], open_mints: &HashSet<Pubkey>, dust_amount: u64, keep: impl Fn(&Pubkey) -> bool) -> Vec<TokenAccount> {
+    accounts
+        .iter()
+        .filter(|a| a.amount <= dust_amount && a.mint != WSOL && !open_mints.contains(&a.mint) && !keep(&a.mint))
+        .cloned()
+        .collect()
+}
+
+/// Burn any dust, then close the account to `owner`
+fn close_instructions(account: &TokenAccount, owner: &Pubkey) -> Vec<Instruction> {
+    let mut instructions = Vec::new();
+    if account.amount > 0 {
+        let mut data = vec![BURN_TAG];
+        data.extend_from_slice(&account.amount.to_le_bytes());
+        instructions.push(Instruction {
+            program_id: account.token_program,
+            accounts: vec![
+                AccountMeta::new(account.address, false),
+                AccountMeta::new(account.mint, false),
+                AccountMeta::new_readonly(*owner, true),
+            ],
+            data,
+        });
+    }
+    instructions.push(Instruction {
+        program_id: account.token_program,
+        accounts: vec![
+            AccountMeta::new(account.address, false),
+            AccountMeta::new(*owner, false),
+            AccountMeta::new_readonly(*owner, true),
+        ],
+        data: vec![CLOSE_ACCOUNT_TAG],
+    });
+    instructions
+}
+
+/// All token accounts of `owner` across the SPL Token and Token-2022 programs
+fn fetch_token_accounts(rpc: &RpcClient, owner: &Pubkey) -> Vec<TokenAccount> {
+    let programs = [spl_token::id(), Pubkey::from_str(TOKEN_2022_PROGRAM_ID).unwrap()];
+    let mut accounts = Vec::new();
+    for program in programs {
+        match rpc.get_token_accounts_by_owner(owner, TokenAccountsFilter::ProgramId(program)) {
+            Ok(keyed) => accounts.extend(keyed.iter().filter_map(|keyed| {
+                let parsed = match &keyed.account.data {
+                    UiAccountData::Json(parsed) => &parsed.parsed,
+                    _ => return None,
+                };
+                let info = parsed.get("info")?;
+                Some(TokenAccount {
+                    address: Pubkey::from_str(&keyed.pubkey).ok()?,
+                    mint: Pubkey::from_str(info.get("mint")?.as_str()?).ok()?,
+                    amount: info.get("tokenAmount")?.get("amount")?.as_str()?.parse().ok()?,
+                    token_program: program,
+                })
+            })),
+            Err(e) => eprintln!("[ATA_SWEEP] getTokenAccountsByOwner ({}) failed: {}", program, e),
+        }
+    }
+    accounts
+}
+
+/// Mints currently held as open positions in GLOBAL_TX_MAP
+fn open_position_mints() -> HashSet<Pubkey> {
+    GLOBAL_TX_MAP
+        .iter()
+        .filter(|entry| entry.value().open_position)
+        .map(|entry| entry.value().mint)
+        .collect()
+}
+
+/// One sweep: close the closable accounts, `CLOSES_PER_TX` per transaction. Returns accounts closed.
+pub fn sweep_once(rpc: &RpcClient, wallet: &Keypair, dust_amount: u64) -> usize {
+    let owner = wallet.pubkey();
+    let accounts = fetch_token_accounts(rpc, &owner);
+    let closable = select_closable(&accounts, &open_position_mints(), dust_amount, skip_ata_creation);
+    let mut closed = 0;
+    for batch in closable.chunks(CLOSES_PER_TX) {
+        // Re-check right before sending: a buy may have opened one of these mints meanwhile
+        let open_mints = open_position_mints();
+        let batch: Vec<&TokenAccount> = batch.iter().filter(|a| !open_mints.contains(&a.mint)).collect();
+        if batch.is_empty() {
+            continue;
+        }
+        let instructions: Vec<Instruction> = batch.iter().flat_map(|a| close_instructions(a, &owner)).collect();
+        let result = rpc.get_latest_blockhash().map_err(|e| e.to_string()).and_then(|blockhash| {
+            let tx = Transaction::new_signed_with_payer(&instructions, Some(&owner), &[wallet], blockhash);
+            rpc.send_and_confirm_transaction(&tx).map_err(|e| e.to_string())
+        });
+        match result {
+            Ok(sig) => {
+                for account in &batch {
+                    remove_cached_ata(&owner, &account.mint);
+                }
+                closed += batch.len();
+                println!("[{}] - [ATA_SWEEP] Closed {} token accounts, sig {}",
+                    Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), batch.len(), sig);
+            }
+            Err(e) => eprintln!("[{}] - [ATA_SWEEP] Close of {} token accounts failed: {}",
+                Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), batch.len(), e),
+        }
+    }
+    closed
+}
+
+/// Sweep every `interval_mins`, closing empty accounts and those holding at most `dust_amount` (raw units)
+pub async fn run_ata_sweep(interval_mins: u64, dust_amount: u64) {
+    loop {
+        tokio::time::sleep(Duration::from_secs(interval_mins * 60)).await;
+        let rpc = match GLOBAL_RPC_CLIENT.get() {
+            Some(rpc) => rpc,
+            None => continue,
+        };
+        let closed = tokio::task::spawn_blocking(move || sweep_once(rpc, get_wallet_keypair(), dust_amount))
+            .await
+            .unwrap_or(0);
+        if closed > 0 {
+            println!("[{}] - [ATA_SWEEP] Sweep done, {} accounts closed (~{:.4} SOL rent reclaimed)",
+                Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), closed, closed as f64 * 0.00203928);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(mint: Pubkey, amount: u64) -> TokenAccount {
+        TokenAccount { address: Pubkey::new_unique(), mint, amount, token_program: spl_token::id() }
+    }
+
+    #[test]
+    fn test_select_closable_accounts() {
+        let (empty, dust, held, open, kept) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let accounts = vec![
+            account(empty, 0),
+            account(dust, 500),
+            account(held, 1_000_000),
+            account(open, 0),  // position still open (e.g. buy in flight)
+            account(kept, 0),  // on the ATA skip list
+            account(WSOL, 0),
+        ];
+        let open_mints: HashSet<Pubkey> = [open].into_iter().collect();
+        let keep = |mint: &Pubkey| *mint == kept;
+
+        let mints = |selected: Vec<TokenAccount>| selected.into_iter().map(|a| a.mint).collect::<Vec<_>>();
+        assert_eq!(mints(select_closable(&accounts, &open_mints, 0, keep)), vec![empty]);
+        assert_eq!(mints(select_closable(&accounts, &open_mints, 1_000, keep)), vec![empty, dust]);
+
+        // Dust gets a burn before the close
+        let owner = Pubkey::new_unique();
+        assert_eq!(close_instructions(&accounts[0], &owner).len(), 1);
+        let dust_ixs = close_instructions(&accounts[1], &owner);
+        assert_eq!(dust_ixs.iter().map(|ix| ix.data[0]).collect::<Vec<_>>(), vec![BURN_TAG, CLOSE_ACCOUNT_TAG]);
+    }
+}
@@ -0,0 +1,215 @@
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::RwLock;
+use std::time::Instant;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+
+/// Pipelines that can be paused independently
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pipeline {
+    Arpc,   // buy pipeline (leader detection -> build buy)
+    Triton, // send/sell pipeline (leader landed -> send buy, our buy landed -> sell)
+}
+
+impl Pipeline {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "arpc" | "buy" => Some(Pipeline::Arpc),
+            "triton" | "sell" => Some(Pipeline::Triton),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Pipeline::Arpc => "arpc",
+            Pipeline::Triton => "triton",
+        }
+    }
+}
+
+static ARPC_PAUSED: AtomicBool = AtomicBool::new(false);
+static TRITON_PAUSED: AtomicBool = AtomicBool::new(false);
+static ARPC_PAUSED_SKIPPED: AtomicUsize = AtomicUsize::new(0);
+static TRITON_PAUSED_SKIPPED: AtomicUsize = AtomicUsize::new(0);
+
+// Last resume time per pipeline; messages detected before it were buffered during the pause
+static ARPC_RESUMED_AT: Lazy<RwLock<Option<Instant>>> = Lazy::new(|| RwLock::new(None));
+static TRITON_RESUMED_AT: Lazy<RwLock<Option<Instant>>> = Lazy::new(|| RwLock::new(None));
+
+fn paused_flag(pipeline: Pipeline) -> &'static AtomicBool {
+    match pipeline {
+        Pipeline::Arpc => &ARPC_PAUSED,
+        Pipeline::Triton => &TRITON_PAUSED,
+    }
+}
+
+fn skipped_counter(pipeline: Pipeline) -> &'static AtomicUsize {
+    match pipeline {
+        Pipeline::Arpc => &ARPC_PAUSED_SKIPPED,
+        Pipeline::Triton => &TRITON_PAUSED_SKIPPED,
+    }
+}
+
+fn resumed_at(pipeline: Pipeline) -> &'static RwLock<Option<Instant>> {
+    match pipeline {
+        Pipeline::Arpc => &ARPC_RESUMED_AT,
+        Pipeline::Triton => &TRITON_RESUMED_AT,
+    }
+}
+
+pub fn is_paused(pipeline: Pipeline) -> bool {
+    paused_flag(pipeline).load(Ordering::Relaxed)
+}
+
+pub fn pause(pipeline: Pipeline) {
+    paused_flag(pipeline).store(true, Ordering::Relaxed);
+    println!("[{}] - [CONTROL] Pipeline {} paused", Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), pipeline.name());
+}
+
+pub fn resume(pipeline: Pipeline) {
+    if let Ok(mut guard) = resumed_at(pipeline).write() {
+        *guard = Some(Instant::now());
+    }
+    paused_flag(pipeline).store(false, Ordering::Relaxed);
+    println!("[{}] - [CONTROL] Pipeline {} resumed", Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), pipeline.name());
+}
+
+pub fn get_paused_skipped(pipeline: Pipeline) -> usize {
+    skipped_counter(pipeline).load(Ordering::Relaxed)
+}
+
+/// Called at the top of each worker loop. Returns true if the message must be dropped:
+/// either the pipeline is paused, or the message was detected before the last resume
+/// (buffered in the channel during the pause) and must not be replayed.
+pub fn should_skip(pipeline: Pipeline, detection_time: Option<Instant>) -> bool {
+    let skip = if is_paused(pipeline) {
+        true
+    } else {
+        match (detection_time, resumed_at(pipeline).read().ok().and_then(|g| *g)) {
+            (Some(detected), Some(resumed)) => detected < resumed,
+            _ => false,
+        }
+    };
+    if skip {
+        skipped_counter(pipeline).fetch_add(1, Ordering::Relaxed);
+    }
+    skip
+}
+
+// Entries per map in a `dump` without a count
+const DUMP_LIMIT: usize = 50;
+
+/// Handle a single control command line and return the response
+pub fn handle_command(line: &str) -> String {
+    let parts: Vec<&str> = line.split_whitespace().collect();
+    match parts.as_slice() {
+        ["pause", name] => match Pipeline::from_name(name) {
+            Some(p) => {
+                pause(p);
+                format!("ok: {} paused", p.name())
+            }
+            None => format!("error: unknown pipeline '{}'", name),
+        },
+        ["resume", name] => match Pipeline::from_name(name) {
+            Some(p) => {
+                resume(p);
+                format!("ok: {} resumed", p.name())
+            }
+            None => format!("error: unknown pipeline '{}'", name),
+        },
+        ["status"] => [Pipeline::Arpc, Pipeline::Triton]
+            .iter()
+            .map(|p| format!("{}: paused={} skipped={}", p.name(), is_paused(*p), get_paused_skipped(*p)))
+            .collect::<Vec<_>>()
+            .join(", "),
+        ["rejections"] => format_rejections(20),
+        ["rejections", n] => match n.parse::<usize>() {
+            Ok(n) => format_rejections(n),
+            Err(_) => format!("error: invalid count '{}'", n),
+        },
+        ["dump"] => crate::utils::state_dump::dump_state(DUMP_LIMIT).to_string(),
+        ["dump", n] => match n.parse::<usize>() {
+            Ok(n) => crate::utils::state_dump::dump_state(n).to_string(),
+            Err(_) => format!("error: invalid count '{}'", n),
+        },
+        _ => "error: commands are 'pause <arpc|triton>', 'resume <arpc|triton>', 'status', 'rejections [n]', 'dump [n]'".to_string(),
+    }
+}
+
+/// Last `n` vendor rejections, newest first, one per line
+fn format_rejections(n: usize) -> String {
+    let rejections = crate::send_tx::rejections::recent_rejections(n);
+    if rejections.is_empty() {
+        return "no rejections recorded (rejection_log_size = 0 disables recording)".to_string();
+    }
+    rejections.iter().map(|r| r.summary()).collect::<Vec<_>>().join("\n")
+}
+
+/// Line-based TCP control interface (e.g. `echo "pause arpc" | nc 127.0.0.1 7878`)
+pub async fn start_control_server(addr: String) {
+    let listener = match TcpListener::bind(&addr).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("[CONTROL] Failed to bind control interface on {}: {}", addr, e);
+            return;
+        }
+    };
+    println!("[CONTROL] Control interface listening on {}", addr);
+
+    loop {
+        let (stream, peer) = match listener.accept().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                eprintln!("[CONTROL] Accept failed: {}", e);
+                continue;
+            }
+        };
+        tokio::spawn(async move {
+            let (reader, mut writer) = stream.into_split();
+            let mut lines = BufReader::new(reader).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                println!("[CONTROL] {} -> {}", peer, line.trim());
+                let response = handle_command(line.trim());
+                if writer.write_all(format!("{}\n", response).as_bytes()).await.is_err() {
+                    break;
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pause_resume_skips_buffered_messages() {
+        let buffered = Instant::now();
+        assert!(!should_skip(Pipeline::Triton, Some(buffered)));
+
+        pause(Pipeline::Triton);
+        assert!(should_skip(Pipeline::Triton, Some(Instant::now())));
+
+        resume(Pipeline::Triton);
+        // Message detected before the resume must not be replayed
+        assert!(should_skip(Pipeline::Triton, Some(buffered)));
+        assert!(!should_skip(Pipeline::Triton, Some(Instant::now())));
+        assert!(get_paused_skipped(Pipeline::Triton) >= 2);
+    }
+
+    #[test]
+    fn test_handle_command() {
+        assert_eq!(handle_command("pause arpc"), "ok: arpc paused");
+        assert!(is_paused(Pipeline::Arpc));
+        assert_eq!(handle_command("resume buy"), "ok: arpc resumed");
+        assert!(!is_paused(Pipeline::Arpc));
+        assert!(handle_command("pause foo").starts_with("error"));
+        assert!(handle_command("rejections").starts_with("no rejections"));
+        assert!(handle_command("rejections x").starts_with("error"));
+        assert!(handle_command("dump 5").starts_with("{"));
+        assert!(handle_command("dump x").starts_with("error"));
+    }
+}
@@ -0,0 +1,207 @@
+use crate::grpc::arpc_worker::DUST_TOKEN_DECIMALS;
+use crate::grpc::connection_stats::get_connection_stats;
+use crate::utils::price_feed::cached_price_usd;
+use chrono::{DateTime, Duration as ChronoDuration, TimeZone, Utc};
+use once_cell::sync::Lazy;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Rollup of one reporting window (a day by default)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DailySummary {
+    pub buys_landed: usize,
+    pub sells_landed: usize,
+    pub vendor_landings: BTreeMap<String, usize>, // vendor -> our landed buys + sells
+    pub tips_lamports: u64,                       // tips on our landed transactions
+    pub priced_trades: usize,                     // round trips with an entry and exit price
+    pub wins: usize,
+    pub pnl_usd: f64, // estimated from Birdeye prices at buy and sell landing
+}
+
+impl DailySummary {
+    fn record_landing(&mut self, vendor: &str, tip_lamports: u64) {
+        *self.vendor_landings.entry(vendor.to_string()).or_default() += 1;
+        self.tips_lamports += tip_lamports;
+    }
+
+    pub fn record_buy(&mut self, vendor: &str, tip_lamports: u64) {
+        self.buys_landed += 1;
+        self.record_landing(vendor, tip_lamports);
+    }
+
+    /// A sell landed; `pnl_usd` is None when either price was unknown
+    pub fn record_sell(&mut self, vendor: &str, tip_lamports: u64, pnl_usd: Option<f64>) {
+        self.sells_landed += 1;
+        self.record_landing(vendor, tip_lamports);
+        if let Some(pnl) = pnl_usd {
+            self.priced_trades += 1;
+            self.pnl_usd += pnl;
+            if pnl > 0.0 {
+                self.wins += 1;
+            }
+        }
+    }
+
+    /// Share of priced round trips that made money
+    pub fn win_rate(&self) -> Option<f64> {
+        (self.priced_trades > 0).then(|| self.wins as f64 / self.priced_trades as f64 * 100.0)
+    }
+
+    /// Multi-line report body
+    pub fn report(&self, uptime: Duration, reconnects: usize) -> String {
+        let vendors = if self.vendor_landings.is_empty() {
+            "-".to_string()
+        } else {
+            self.vendor_landings
+                .iter()
+                .map(|(vendor, n)| format!("{}={}", vendor, n))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        format!(
+            "trades: buys_landed={} sells_landed={}\nwin rate: {} ({} of {} priced)\nest. pnl: {:+.2} USD\nlandings by vendor: {}\ntips spent: {:.6} SOL\nuptime: {}s, reconnects: {}",
+            self.buys_landed,
+            self.sells_landed,
+            self.win_rate().map_or("-".to_string(), |r| format!("{:.1}%", r)),
+            self.wins,
+            self.priced_trades,
+            self.pnl_usd,
+            vendors,
+            self.tips_lamports as f64 / 1_000_000_000.0,
+            uptime.as_secs(),
+            reconnects,
+        )
+    }
+}
+
+/// Estimated USD PnL of selling `token_amount` bought at `entry` and sold at `exit`
+pub fn estimate_pnl_usd(token_amount: u64, entry: Option<f64>, exit: Option<f64>) -> Option<f64> {
+    Some((exit? - entry?) * token_amount as f64 / 10f64.powi(DUST_TOKEN_DECIMALS))
+}
+
+static CURRENT: Lazy<Mutex<DailySummary>> = Lazy::new(|| Mutex::new(DailySummary::default()));
+
+/// What a sent sell contributes to the summary once it lands (tracked with its lifecycle)
+#[derive(Debug, Clone, Default)]
+pub struct SellSale {
+    pub vendor: String,
+    pub tip_lamports: u64,
+    pub mint: String,
+    pub token_amount: u64,
+    pub entry_price_usd: Option<f64>,
+}
+
+/// Count one of our buys seen landing
+pub fn record_buy_landed(vendor: &str, tip_lamports: u64) {
+    if let Ok(mut summary) = CURRENT.lock() {
+        summary.record_buy(vendor, tip_lamports);
+    }
+}
+
+/// Count one of our sells seen landing, with the exit price cached within `price_ttl`
+pub fn record_sell_landed(sale: &SellSale, price_ttl: Duration) {
+    let exit = cached_price_usd(&sale.mint, price_ttl);
+    let pnl = estimate_pnl_usd(sale.token_amount, sale.entry_price_usd, exit);
+    if let Ok(mut summary) = CURRENT.lock() {
+        summary.record_sell(&sale.vendor, sale.tip_lamports, pnl);
+    }
+}
+
+/// First report time after `now`: `boundary_hour` UTC, then every `interval_hours`
+pub fn next_boundary(now: DateTime<Utc>, boundary_hour: u32, interval_hours: u64) -> DateTime<Utc> {
+    let step = ChronoDuration::hours(interval_hours.max(1) as i64);
+    let today = now.date_naive().and_hms_opt(boundary_hour.min(23), 0, 0).unwrap();
+    let mut next = Utc.from_utc_datetime(&today);
+    while next > now {
+        next -= ChronoDuration::days(1);
+    }
+    while next <= now {
+        next += step;
+    }
+    next
+}
+
+fn total_disconnects() -> usize {
+    get_connection_stats().iter().map(|s| s.disconnects).sum()
+}
+
+fn append_report(path: &str, report: &str) -> std::io::Result<()> {
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}\n", report)
+}
+
+/// Log (and append to `path`, if set) a summary at each window boundary, then start a new window
+pub async fn run_daily_summary(interval_hours: u64, boundary_hour: u32, path: String) {
+    let started_at = Instant::now();
+    let mut window_start = Utc::now();
+    let mut disconnects_at_start = total_disconnects();
+    loop {
+        let next = next_boundary(Utc::now(), boundary_hour, interval_hours);
+        let wait = (next - Utc::now()).to_std().unwrap_or_default();
+        tokio::time::sleep(wait).await;
+
+        let summary = CURRENT.lock().map(|mut s| std::mem::take(&mut *s)).unwrap_or_default();
+        let disconnects = total_disconnects();
+        let report = format!(
+            "[{}] ========== DAILY SUMMARY {} -> {} ==========\n{}",
+            Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+            window_start.format("%Y-%m-%d %H:%M"),
+            next.format("%Y-%m-%d %H:%M"),
+            summary.report(started_at.elapsed(), disconnects.saturating_sub(disconnects_at_start)),
+        );
+        println!("{}", report);
+        if !path.is_empty() {
+            if let Err(e) = append_report(&path, &report) {
+                eprintln!("[DAILY] Failed to write summary to {}: {}", path, e);
+            }
+        }
+        window_start = next;
+        disconnects_at_start = disconnects;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_aggregation() {
+        let mut summary = DailySummary::default();
+        summary.record_buy("jito", 1_000_000);
+        summary.record_buy("nextblock", 500_000);
+        summary.record_buy("jito", 1_000_000);
+        // 1000 tokens: +$0.50, -$0.20, unpriced
+        summary.record_sell("jito", 1_000_000, estimate_pnl_usd(1_000_000_000, Some(0.001), Some(0.0015)));
+        summary.record_sell("zeroslot", 200_000, estimate_pnl_usd(1_000_000_000, Some(0.001), Some(0.0008)));
+        summary.record_sell("jito", 1_000_000, estimate_pnl_usd(1_000_000_000, Some(0.001), None));
+
+        assert_eq!(summary.buys_landed, 3);
+        assert_eq!(summary.sells_landed, 3);
+        assert_eq!(summary.vendor_landings.get("jito"), Some(&4));
+        assert_eq!(summary.vendor_landings.get("zeroslot"), Some(&1));
+        assert_eq!(summary.tips_lamports, 4_700_000);
+        assert_eq!(summary.priced_trades, 2);
+        assert_eq!(summary.win_rate(), Some(50.0));
+        assert!((summary.pnl_usd - 0.3).abs() < 1e-9);
+
+        let report = summary.report(Duration::from_secs(86_400), 2);
+        assert!(report.contains("win rate: 50.0% (1 of 2 priced)"));
+        assert!(report.contains("jito=4, nextblock=1, zeroslot=1"));
+        assert!(report.contains("tips spent: 0.004700 SOL"));
+        assert!(report.contains("reconnects: 2"));
+        assert_eq!(DailySummary::default().win_rate(), None);
+    }
+
+    #[test]
+    fn test_next_boundary() {
+        let at = |h, m| Utc.with_ymd_and_hms(2024, 5, 1, h, m, 0).unwrap();
+        let next_day = Utc.with_ymd_and_hms(2024, 5, 2, 0, 0, 0).unwrap();
+        assert_eq!(next_boundary(at(13, 30), 0, 24), next_day);
+        assert_eq!(next_boundary(at(0, 0), 0, 24), next_day);
+        assert_eq!(next_boundary(at(13, 30), 14, 24), at(14, 0));
+        assert_eq!(next_boundary(at(13, 30), 0, 6), at(18, 0));
+        assert_eq!(next_boundary(at(1, 0), 14, 24), at(14, 0));
+    }
+}
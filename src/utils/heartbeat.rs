@@ -0,0 +1,95 @@
+use crate::alerts::{alert, AlertKind};
+use crate::config_load::GLOBAL_CONFIG;
+use crate::grpc::arpc_worker::get_open_positions;
+use crate::grpc::compression::get_last_message_ages;
+use crate::init::initialize::GLOBAL_RPC_CLIENT;
+use crate::init::wallet_loader::get_wallet_keypair;
+use chrono::Utc;
+use solana_sdk::signer::Signer;
+use std::time::{Duration, Instant};
+
+/// Snapshot for one heartbeat line
+#[derive(Debug, Clone, PartialEq)]
+pub struct Heartbeat {
+    pub uptime: Duration,
+    pub arpc_age: Option<Duration>,
+    pub triton_age: Option<Duration>,
+    pub monitoring_age: Option<Duration>,
+    pub open_positions: usize,
+    pub balance_lamports: Option<u64>,
+}
+
+fn format_age(age: Option<Duration>) -> String {
+    age.map_or("-".to_string(), |a| format!("{}s", a.as_secs()))
+}
+
+impl Heartbeat {
+    /// Terse one-line status, e.g. `up=3600s arpc=2s triton=0s mon=15s open=1 bal=1.2345SOL`
+    pub fn line(&self) -> String {
+        format!(
+            "up={}s arpc={} triton={} mon={} open={} bal={}",
+            self.uptime.as_secs(),
+            format_age(self.arpc_age),
+            format_age(self.triton_age),
+            format_age(self.monitoring_age),
+            self.open_positions,
+            self.balance_lamports
+                .map_or("?".to_string(), |l| format!("{:.4}SOL", l as f64 / 1_000_000_000.0)),
+        )
+    }
+}
+
+/// Wallet SOL balance over RPC (off the runtime threads); None if unavailable
+async fn wallet_balance() -> Option<u64> {
+    let rpc = GLOBAL_RPC_CLIENT.get()?;
+    let wallet = get_wallet_keypair().pubkey();
+    tokio::task::spawn_blocking(move || rpc.get_balance(&wallet).ok())
+        .await
+        .ok()
+        .flatten()
+}
+
+/// Log a heartbeat line every `interval_secs`, regardless of activity
+pub async fn run_heartbeat(interval_secs: u64) {
+    let started_at = Instant::now();
+    let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+    interval.tick().await; // first tick fires immediately
+    loop {
+        interval.tick().await;
+        let (arpc_age, triton_age, monitoring_age) = get_last_message_ages();
+        let heartbeat = Heartbeat {
+            uptime: started_at.elapsed(),
+            arpc_age,
+            triton_age,
+            monitoring_age,
+            open_positions: get_open_positions(),
+            balance_lamports: wallet_balance().await,
+        };
+        println!("[{}] - [HEARTBEAT] {}", Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), heartbeat.line());
+        let reserve_sol = GLOBAL_CONFIG.get().map_or(0.0, |config| config.alert_min_balance_sol);
+        if let Some(lamports) = heartbeat.balance_lamports {
+            if (lamports as f64) < reserve_sol * 1_000_000_000.0 {
+                alert(AlertKind::LowBalance, format!("wallet balance {:.4} SOL below reserve {} SOL",
+                    lamports as f64 / 1_000_000_000.0, reserve_sol));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heartbeat_line() {
+        let heartbeat = Heartbeat {
+            uptime: Duration::from_secs(3600),
+            arpc_age: Some(Duration::from_millis(2500)),
+            triton_age: Some(Duration::ZERO),
+            monitoring_age: None,
+            open_positions: 1,
+            balance_lamports: Some(1_234_500_000),
+        };
+        assert_eq!(heartbeat.line(), "up=3600s arpc=2s triton=0s mon=- open=1 bal=1.2345SOL");
+    }
+}
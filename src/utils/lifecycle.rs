@@ -0,0 +1,187 @@
+use crate::utils::daily_summary::SellSale;
+use crate::utils::otel;
+use chrono::Utc;
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// Samples kept per stage for the percentile report
+const STAGE_SAMPLES: usize = 1000;
+// Sells not seen landing within this are dropped from the landing tracker
+const SELL_LAND_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Business-level stages of a position, each closed by the event named last
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    DetectToBuySend, // leader landed (Triton detection) -> a vendor accepted our buy
+    BuySendToLand,   // buy accepted -> our buy seen landed
+    SellTriggerToSend, // our buy landed (sell trigger, includes waitTime) -> a vendor accepted our sell
+    SellSendToLand,  // sell accepted -> our sell seen landed
+}
+
+const ALL_STAGES: [Stage; 4] = [Stage::DetectToBuySend, Stage::BuySendToLand, Stage::SellTriggerToSend, Stage::SellSendToLand];
+
+impl Stage {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Stage::DetectToBuySend => "detect->buy_send",
+            Stage::BuySendToLand => "buy_send->land",
+            Stage::SellTriggerToSend => "sell_trigger->send",
+            Stage::SellSendToLand => "sell_send->land",
+        }
+    }
+}
+
+/// Timestamps of one position's lifecycle, carried in TxWithPubkey
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TradeLifecycle {
+    pub detected: Option<Instant>,
+    pub buy_sent: Option<Instant>,
+    pub buy_landed: Option<Instant>,
+    pub sell_sent: Option<Instant>,
+    pub sell_landed: Option<Instant>,
+}
+
+impl TradeLifecycle {
+    /// Set the timestamp ending `stage`; returns its duration when the start is known
+    fn close_stage(&mut self, stage: Stage, at: Instant) -> Option<Duration> {
+        let (start, end) = match stage {
+            Stage::DetectToBuySend => (self.detected, &mut self.buy_sent),
+            Stage::BuySendToLand => (self.buy_sent, &mut self.buy_landed),
+            Stage::SellTriggerToSend => (self.buy_landed, &mut self.sell_sent),
+            Stage::SellSendToLand => (self.sell_sent, &mut self.sell_landed),
+        };
+        *end = Some(at);
+        start.map(|start| at.saturating_duration_since(start))
+    }
+
+    /// Close `stage` at `at` and add its duration to the latency report
+    pub fn advance(&mut self, stage: Stage, at: Instant) -> Option<Duration> {
+        let elapsed = self.close_stage(stage, at)?;
+        record_stage(stage, elapsed);
+        Some(elapsed)
+    }
+
+    /// Per-trade summary of every known stage
+    pub fn summary(&self) -> String {
+        let mut copy = TradeLifecycle { detected: self.detected, ..Default::default() };
+        ALL_STAGES
+            .iter()
+            .filter_map(|&stage| {
+                let end = match stage {
+                    Stage::DetectToBuySend => self.buy_sent,
+                    Stage::BuySendToLand => self.buy_landed,
+                    Stage::SellTriggerToSend => self.sell_sent,
+                    Stage::SellSendToLand => self.sell_landed,
+                }?;
+                copy.close_stage(stage, end).map(|d| format!("{}={:.2?}", stage.name(), d))
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+static STAGE_DURATIONS: Lazy<Mutex<Vec<VecDeque<Duration>>>> =
+    Lazy::new(|| Mutex::new(vec![VecDeque::new(); ALL_STAGES.len()]));
+
+fn record_stage(stage: Stage, elapsed: Duration) {
+    if let Ok(mut stages) = STAGE_DURATIONS.lock() {
+        let samples = &mut stages[stage as usize];
+        if samples.len() == STAGE_SAMPLES {
+            samples.pop_front();
+        }
+        samples.push_back(elapsed);
+    }
+}
+
+/// Nearest-rank percentile of `sorted` (ascending)
+fn percentile(sorted: &[Duration], pct: usize) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = (sorted.len() * pct + 99) / 100;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// (stage, samples, p50, p95) for each stage with data, over the last STAGE_SAMPLES trades
+pub fn latency_report() -> Vec<(Stage, usize, Duration, Duration)> {
+    let stages = match STAGE_DURATIONS.lock() {
+        Ok(stages) => stages,
+        Err(_) => return Vec::new(),
+    };
+    ALL_STAGES
+        .iter()
+        .filter(|&&stage| !stages[stage as usize].is_empty())
+        .map(|&stage| {
+            let mut sorted: Vec<Duration> = stages[stage as usize].iter().copied().collect();
+            sorted.sort();
+            (stage, sorted.len(), percentile(&sorted, 50), percentile(&sorted, 95))
+        })
+        .collect()
+}
+
+// Sent sells awaiting their landing, by signature
+static PENDING_SELL_LANDS: Lazy<DashMap<String, (TradeLifecycle, String, SellSale)>> = Lazy::new(DashMap::new);
+
+/// Watch for our sell `sig` landing so the last stage can be closed and the sale counted
+pub fn track_sell_landing(sig: String, lifecycle: TradeLifecycle, corr_id: String, sale: SellSale) {
+    PENDING_SELL_LANDS.retain(|_, (pending, _, _)| pending.sell_sent.map_or(false, |sent| sent.elapsed() < SELL_LAND_TIMEOUT));
+    PENDING_SELL_LANDS.insert(sig.trim_matches('"').to_string(), (lifecycle, corr_id, sale));
+}
+
+/// If `sig` is one of our tracked sells, close the lifecycle, log the per-trade latencies and
+/// return the sale for the daily summary
+pub fn record_sell_landing(sig: &str, landed_at: Instant) -> Option<SellSale> {
+    let (_, (mut lifecycle, corr_id, sale)) = PENDING_SELL_LANDS.remove(sig)?;
+    lifecycle.advance(Stage::SellSendToLand, landed_at);
+    if let Some(sell_sent) = lifecycle.sell_sent {
+        otel::record_span(&corr_id, "sell_confirm", sell_sent, landed_at, &[("sig", &sig)]);
+    }
+    println!("[{}] - [LIFECYCLE] [cid={}] Sell {} landed: {} ({})",
+        Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), corr_id, sig, lifecycle.summary(),
+        crate::config_load::network::network().explorer_tx_url(sig));
+    Some(sale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lifecycle_stage_bookkeeping() {
+        let t0 = Instant::now();
+        let ms = Duration::from_millis;
+        let mut lifecycle = TradeLifecycle { detected: Some(t0), ..Default::default() };
+
+        assert_eq!(lifecycle.close_stage(Stage::DetectToBuySend, t0 + ms(5)), Some(ms(5)));
+        assert_eq!(lifecycle.close_stage(Stage::BuySendToLand, t0 + ms(405)), Some(ms(400)));
+        assert_eq!(lifecycle.close_stage(Stage::SellTriggerToSend, t0 + ms(1_405)), Some(ms(1_000)));
+        assert_eq!(lifecycle.close_stage(Stage::SellSendToLand, t0 + ms(1_805)), Some(ms(400)));
+        assert_eq!(lifecycle.buy_landed, Some(t0 + ms(405)));
+        assert!(lifecycle.summary().contains("buy_send->land=400.00ms"));
+
+        // Unknown start (e.g. a reconciled position): timestamp kept, no duration
+        let mut reconciled = TradeLifecycle::default();
+        assert_eq!(reconciled.close_stage(Stage::SellSendToLand, t0), None);
+        assert_eq!(reconciled.sell_landed, Some(t0));
+    }
+
+    #[test]
+    fn test_sell_landing_tracking_and_percentiles() {
+        let t0 = Instant::now();
+        let lifecycle = TradeLifecycle { sell_sent: Some(t0), ..Default::default() };
+        let sale = SellSale { vendor: "zeroslot".to_string(), token_amount: 5, ..Default::default() };
+        track_sell_landing("sell-sig".to_string(), lifecycle, "cafe0001".to_string(), sale);
+        assert!(record_sell_landing("other-sig", t0).is_none());
+        assert_eq!(record_sell_landing("sell-sig", t0 + Duration::from_millis(300)).map(|s| s.token_amount), Some(5));
+        assert!(record_sell_landing("sell-sig", t0).is_none());
+        assert!(latency_report().iter().any(|(stage, n, _, _)| *stage == Stage::SellSendToLand && *n >= 1));
+
+        let sorted: Vec<Duration> = (1..=100).map(Duration::from_millis).collect();
+        assert_eq!(percentile(&sorted, 50), Duration::from_millis(50));
+        assert_eq!(percentile(&sorted, 95), Duration::from_millis(95));
+        assert_eq!(percentile(&[], 95), Duration::ZERO);
+    }
+}
@@ -10,10 +10,11 @@
 //!   setup_event_logger();
 //!   log_event(EventType::Grpc_Detection_Processing, "mysig", t0);
 
+use crate::utils::otel;
+use chrono::Utc;
+use once_cell::sync::OnceCell;
 use std::time::Instant;
 use tokio::sync::mpsc;
-use once_cell::sync::OnceCell;
-use chrono::Utc;
 
 #[derive(Debug)]
 pub enum EventType {
@@ -22,8 +23,6 @@ pub enum EventType {
     GrpcLanded,
     RaydiumLaunchpadBuy,
 
-
-
     // RaydiumBuy,
     RaydiumSell,
     SlotUpdate,
@@ -36,6 +35,7 @@ pub struct Event {
     pub sig: Vec<u8>,
     pub reference_time: Instant, // Now tracks the reference point
     pub blocks_to_land: Option<i64>,
+    pub corr_id: Option<String>, // Correlation ID of the trade this event belongs to
 }
 
 static EVENT_SENDER: OnceCell<mpsc::Sender<Event>> = OnceCell::new();
@@ -48,6 +48,11 @@ pub fn setup_event_logger() {
             let elapsed = event.reference_time.elapsed();
             let now = Utc::now();
             let now_str = now.format("%Y-%m-%d %H:%M:%S%.3f");
+            let cid = event
+                .corr_id
+                .as_deref()
+                .map(|c| format!(" | cid: {}", c))
+                .unwrap_or_default();
             match event.event_type {
                 EventType::GrpcDetectionProcessing => {
                     let sig_detect = if event.sig.is_empty() {
@@ -55,10 +60,10 @@ pub fn setup_event_logger() {
                     } else {
                         bs58::encode(&event.sig).into_string()
                     };
-                    
+
                     println!(
-                        "[{}] - [grpc] Detection event | elapsed: {:.2?} | sig: {}",
-                        now_str, elapsed, sig_detect
+                        "[{}] - [grpc] Detection event | elapsed: {:.2?} | sig: {}{}",
+                        now_str, elapsed, sig_detect, cid
                     );
                 }
                 EventType::GrpcLanded => {
@@ -68,8 +73,8 @@ pub fn setup_event_logger() {
                         bs58::encode(&event.sig).into_string()
                     };
                     println!(
-                        "[{}] - [grpc] Tranasction landed | sig: {} | blocks to land: {:?} | time to land: {:.2?} | Queueing sell tx, waiting 4 seconds",
-                        now_str, sig_detect, event.blocks_to_land.unwrap_or(-1), elapsed
+                        "[{}] - [grpc] Tranasction landed | sig: {} | blocks to land: {:?} | time to land: {:.2?} | Queueing sell tx, waiting 4 seconds{}",
+                        now_str, sig_detect, event.blocks_to_land.unwrap_or(-1), elapsed, cid
                     );
                 }
                 EventType::ArpcDetectionProcessing => {
@@ -78,40 +83,42 @@ pub fn setup_event_logger() {
                     } else {
                         bs58::encode(&event.sig).into_string()
                     };
-                    
+
                     println!(
-                        "[{}] - [arpc] Detection event | elapsed: {:.2?} | sig: {}",
-                        now_str, elapsed, sig_detect
+                        "[{}] - [arpc] Detection event | elapsed: {:.2?} | sig: {}{}",
+                        now_str, elapsed, sig_detect, cid
                     );
                 }
 
-
-
-
-
-
                 EventType::RaydiumLaunchpadBuy => {
                     println!(
-                        "[{}] - [arpc] Raydium Launchpad buy detected | elapsed: {:.2?} | sig: {}",
-                        now_str, elapsed, bs58::encode(&event.sig).into_string()
+                        "[{}] - [arpc] Raydium Launchpad buy detected | elapsed: {:.2?} | sig: {}{}",
+                        now_str, elapsed, bs58::encode(&event.sig).into_string(), cid
                     );
                 }
                 EventType::RaydiumSell => {
                     println!(
-                        "[{}] - [arpc] Raydium Launchpad sell detected | elapsed: {:.2?} | sig: {}",
-                        now_str, elapsed, bs58::encode(&event.sig).into_string()
+                        "[{}] - [arpc] Raydium Launchpad sell detected | elapsed: {:.2?} | sig: {}{}",
+                        now_str, elapsed, bs58::encode(&event.sig).into_string(), cid
                     );
                 }
                 EventType::SlotUpdate => {
                     println!(
-                        "[{}] - [arpc] Slot update | elapsed: {:.2?} | sig: {}",
-                        now_str, elapsed, bs58::encode(&event.sig).into_string()
+                        "[{}] - [arpc] Slot update | elapsed: {:.2?} | sig: {}{}",
+                        now_str,
+                        elapsed,
+                        bs58::encode(&event.sig).into_string(),
+                        cid
                     );
                 }
                 EventType::Custom(ref name) => {
                     println!(
-                        "[{}] - [arpc] {} | elapsed: {:.2?} | sig: {}",
-                        now_str, name, elapsed, bs58::encode(&event.sig).into_string()
+                        "[{}] - [arpc] {} | elapsed: {:.2?} | sig: {}{}",
+                        now_str,
+                        name,
+                        elapsed,
+                        bs58::encode(&event.sig).into_string(),
+                        cid
                     );
                 }
             }
@@ -119,14 +126,98 @@ pub fn setup_event_logger() {
     });
 }
 
-pub fn log_event(event_type: EventType, sig: &[u8], reference_time: Instant, blocks_to_land: Option<i64>) {
+pub fn log_event(
+    event_type: EventType,
+    sig: &[u8],
+    reference_time: Instant,
+    blocks_to_land: Option<i64>,
+) {
+    send_event(event_type, sig, reference_time, blocks_to_land, None);
+}
+
+/// Same as `log_event`, tagged with the trade's correlation ID
+pub fn log_event_cid(
+    event_type: EventType,
+    sig: &[u8],
+    reference_time: Instant,
+    blocks_to_land: Option<i64>,
+    corr_id: &str,
+) {
+    if otel::enabled() {
+        trace_event(&event_type, reference_time, blocks_to_land, corr_id);
+    }
+    send_event(
+        event_type,
+        sig,
+        reference_time,
+        blocks_to_land,
+        Some(corr_id.to_string()),
+    );
+}
+
+/// Mirror a trade event into its OpenTelemetry trace: detection opens the trade with a parse span,
+/// landing adds the confirm span
+fn trace_event(
+    event_type: &EventType,
+    reference_time: Instant,
+    blocks_to_land: Option<i64>,
+    corr_id: &str,
+) {
+    let now = Instant::now();
+    match event_type {
+        EventType::ArpcDetectionProcessing => {
+            otel::start_trade(corr_id, reference_time);
+            otel::record_span(corr_id, "parse", reference_time, now, &[]);
+        }
+        EventType::GrpcLanded => {
+            let slot_lag = blocks_to_land.unwrap_or(-1);
+            otel::record_span(
+                corr_id,
+                "confirm",
+                reference_time,
+                now,
+                &[("slot_lag", &slot_lag)],
+            );
+        }
+        _ => {}
+    }
+}
+
+fn send_event(
+    event_type: EventType,
+    sig: &[u8],
+    reference_time: Instant,
+    blocks_to_land: Option<i64>,
+    corr_id: Option<String>,
+) {
     if let Some(sender) = EVENT_SENDER.get() {
         let event = Event {
             event_type,
             sig: sig.to_vec(),
             reference_time,
             blocks_to_land,
+            corr_id,
         };
         let _ = sender.try_send(event);
     }
-} 
\ No newline at end of file
+}
+
+/// Short random ID assigned at detection time, carried through build, send and sell
+/// so one trade can be grepped across interleaved worker logs (`cid=...`)
+pub fn new_correlation_id() -> String {
+    format!("{:08x}", rand::random::<u32>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_correlation_ids_are_short_and_distinct() {
+        let a = new_correlation_id();
+        let b = new_correlation_id();
+        assert_eq!(a.len(), 8);
+        assert!(a.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(a, b);
+    }
+}
@@ -1,7 +1,22 @@
+pub mod affinity;
 pub mod ata;
+pub mod ata_sweep;
+pub mod control;
+pub mod daily_summary;
+pub mod heartbeat;
+pub mod kill_switch;
+pub mod lifecycle;
 pub mod logger;
+pub mod otel;
+pub mod price_feed;
+pub mod profiling;
+pub mod realized_slippage;
 pub mod rt_scheduler;
+pub mod slot_attempts;
+pub mod socket_tuning;
+pub mod state_dump;
 pub mod token_balance;
+pub mod watchdog;
 
 #[cfg(target_os = "linux")]
 pub fn get_memory_usage() -> Option<(usize, usize)> {
@@ -0,0 +1,191 @@
+//! Optional OpenTelemetry traces: one `trade` span per correlation ID with child spans for
+//! parse, build, per-vendor send and confirm, exported over OTLP/HTTP.
+//!
+//! Compiled in with `--features otel` and switched on by a non-empty `otel_endpoint`. Without the
+//! feature every call is an empty inline function; with it but unconfigured, each call returns
+//! after one `OnceCell` check.
+
+use std::fmt::Display;
+use std::time::Instant;
+
+// Span attributes, formatted only when tracing is on
+pub type Attrs<'a> = &'a [(&'static str, &'a dyn Display)];
+
+#[cfg(feature = "otel")]
+mod exporter {
+    use super::Attrs;
+    use dashmap::DashMap;
+    use once_cell::sync::{Lazy, OnceCell};
+    use opentelemetry::trace::{Span, TraceContextExt, Tracer, TracerProvider as _};
+    use opentelemetry::{Context, KeyValue};
+    use opentelemetry_otlp::WithExportConfig;
+    use opentelemetry_sdk::trace::{SdkTracer, SdkTracerProvider};
+    use opentelemetry_sdk::Resource;
+    use std::time::{Duration, Instant, SystemTime};
+
+    // Trades whose root span was never closed (buy never landed, sell never seen) are ended after this
+    const TRADE_SPAN_TIMEOUT: Duration = Duration::from_secs(600);
+
+    static TRACER: OnceCell<SdkTracer> = OnceCell::new();
+    // Open trade spans by correlation ID
+    static TRADES: Lazy<DashMap<String, (Context, Instant)>> = Lazy::new(DashMap::new);
+
+    fn system_time(at: Instant) -> SystemTime {
+        SystemTime::now() - at.elapsed()
+    }
+
+    fn key_values(attrs: Attrs) -> Vec<KeyValue> {
+        attrs.iter().map(|(key, value)| KeyValue::new(*key, value.to_string())).collect()
+    }
+
+    pub fn init(endpoint: &str, service_name: &str) -> Result<(), String> {
+        let exporter = opentelemetry_otlp::SpanExporter::builder()
+            .with_http()
+            .with_endpoint(endpoint)
+            .build()
+            .map_err(|e| e.to_string())?;
+        let provider = SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .with_resource(Resource::builder().with_service_name(service_name.to_string()).build())
+            .build();
+        let _ = TRACER.set(provider.tracer("copy_rust"));
+        Ok(())
+    }
+
+    pub fn enabled() -> bool {
+        TRACER.get().is_some()
+    }
+
+    pub fn start_trade(corr_id: &str, detected_at: Instant) {
+        let tracer = match TRACER.get() {
+            Some(tracer) => tracer,
+            None => return,
+        };
+        let expired: Vec<String> = TRADES
+            .iter()
+            .filter(|entry| entry.value().1.elapsed() > TRADE_SPAN_TIMEOUT)
+            .map(|entry| entry.key().clone())
+            .collect();
+        for corr_id in expired {
+            end_trade(&corr_id, "expired", Instant::now());
+        }
+        let span = tracer
+            .span_builder("trade")
+            .with_start_time(system_time(detected_at))
+            .with_attributes([KeyValue::new("corr_id", corr_id.to_string())])
+            .start(tracer);
+        TRADES.insert(corr_id.to_string(), (Context::current_with_span(span), detected_at));
+    }
+
+    pub fn set_trade_attributes(corr_id: &str, attrs: Attrs) {
+        if let Some(trade) = TRADES.get(corr_id) {
+            let span = trade.0.span();
+            for kv in key_values(attrs) {
+                span.set_attribute(kv);
+            }
+        }
+    }
+
+    pub fn record_span(corr_id: &str, name: &'static str, start: Instant, end: Instant, attrs: Attrs) {
+        let tracer = match TRACER.get() {
+            Some(tracer) => tracer,
+            None => return,
+        };
+        let mut kvs = key_values(attrs);
+        kvs.push(KeyValue::new("corr_id", corr_id.to_string()));
+        let builder = tracer.span_builder(name).with_start_time(system_time(start)).with_attributes(kvs);
+        let mut span = match TRADES.get(corr_id) {
+            Some(trade) => builder.start_with_context(tracer, &trade.0),
+            None => builder.start(tracer),
+        };
+        span.end_with_timestamp(system_time(end));
+    }
+
+    pub fn end_trade(corr_id: &str, outcome: &'static str, at: Instant) {
+        if let Some((_, (cx, _))) = TRADES.remove(corr_id) {
+            let span = cx.span();
+            span.set_attribute(KeyValue::new("outcome", outcome));
+            span.end_with_timestamp(system_time(at));
+        }
+    }
+}
+
+#[cfg(not(feature = "otel"))]
+mod exporter {
+    use super::Attrs;
+    use std::time::Instant;
+
+    pub fn init(_endpoint: &str, _service_name: &str) -> Result<(), String> {
+        Err("built without the `otel` feature".to_string())
+    }
+
+    #[inline(always)]
+    pub fn enabled() -> bool {
+        false
+    }
+
+    #[inline(always)]
+    pub fn start_trade(_corr_id: &str, _detected_at: Instant) {}
+
+    #[inline(always)]
+    pub fn set_trade_attributes(_corr_id: &str, _attrs: Attrs) {}
+
+    #[inline(always)]
+    pub fn record_span(_corr_id: &str, _name: &'static str, _start: Instant, _end: Instant, _attrs: Attrs) {}
+
+    #[inline(always)]
+    pub fn end_trade(_corr_id: &str, _outcome: &'static str, _at: Instant) {}
+}
+
+/// Start exporting to `endpoint` ("" leaves tracing off)
+pub fn init_tracing(endpoint: &str, service_name: &str) {
+    if endpoint.is_empty() {
+        return;
+    }
+    match exporter::init(endpoint, service_name) {
+        Ok(()) => println!("[OTEL] Exporting trade traces to {} as {}", endpoint, service_name),
+        Err(e) => eprintln!("[OTEL] Tracing disabled, otel_endpoint set but {}", e),
+    }
+}
+
+/// Whether spans are being exported
+pub fn enabled() -> bool {
+    exporter::enabled()
+}
+
+/// Open the root `trade` span for `corr_id`, starting at detection
+pub fn start_trade(corr_id: &str, detected_at: Instant) {
+    exporter::start_trade(corr_id, detected_at)
+}
+
+/// Add attributes (mint, tx_type, ...) to an open trade span
+pub fn set_trade_attributes(corr_id: &str, attrs: Attrs) {
+    exporter::set_trade_attributes(corr_id, attrs)
+}
+
+/// Record a finished child span of `corr_id`'s trade (a root span if the trade isn't open)
+pub fn record_span(corr_id: &str, name: &'static str, start: Instant, end: Instant, attrs: Attrs) {
+    exporter::record_span(corr_id, name, start, end, attrs)
+}
+
+/// Close `corr_id`'s trade span at `at`, tagged with how it ended
+pub fn end_trade(corr_id: &str, outcome: &'static str, at: Instant) {
+    exporter::end_trade(corr_id, outcome, at)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unconfigured_tracing_is_inert() {
+        init_tracing("", "copy_rust");
+        assert!(!enabled());
+        let t0 = Instant::now();
+        start_trade("otel-test", t0);
+        set_trade_attributes("otel-test", &[("mint", &"mint"), ("tx_type", &"pumpfun")]);
+        record_span("otel-test", "send", t0, Instant::now(), &[("vendor", &"jito")]);
+        end_trade("otel-test", "closed", Instant::now());
+        assert!(!enabled());
+    }
+}
@@ -0,0 +1,219 @@
+use crate::grpc::arpc_worker::GLOBAL_TX_MAP;
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use reqwest::Client;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+const BIRDEYE_MULTI_PRICE_URL: &str = "https://public-api.birdeye.so/defi/multi_price";
+// multi_price accepts at most 100 addresses per request
+const BIRDEYE_MULTI_PRICE_BATCH: usize = 100;
+
+#[derive(Deserialize, Debug)]
+struct BirdEyePrice {
+    value: f64,
+}
+
+#[derive(Deserialize, Debug)]
+struct BirdEyeMultiPriceResponse {
+    data: HashMap<String, Option<BirdEyePrice>>,
+}
+
+// mint -> (USD price, fetched at)
+static PRICE_CACHE: Lazy<DashMap<String, (f64, Instant)>> = Lazy::new(DashMap::new);
+// Mints refreshed by the background price task -> pinned (`mintsMonitor`, never pruned)
+static WATCHED_MINTS: Lazy<DashMap<String, bool>> = Lazy::new(DashMap::new);
+// Time of the last Birdeye request, shared so all callers respect the plan's rate limit
+static LAST_REQUEST: Lazy<tokio::sync::Mutex<Option<Instant>>> = Lazy::new(|| tokio::sync::Mutex::new(None));
+static PRICE_REQUESTS: AtomicUsize = AtomicUsize::new(0);
+static PRICE_ERRORS: AtomicUsize = AtomicUsize::new(0);
+
+/// Parse a multi_price response into mint -> USD price (mints without a price are left out)
+pub fn parse_multi_price(body: &str) -> Result<HashMap<String, f64>, Box<dyn Error>> {
+    let resp: BirdEyeMultiPriceResponse = serde_json::from_str(body)?;
+    Ok(resp
+        .data
+        .into_iter()
+        .filter_map(|(mint, price)| price.map(|p| (mint, p.value)))
+        .collect())
+}
+
+/// Cached USD price for a mint, if fetched within `ttl`
+pub fn cached_price_usd(mint: &str, ttl: Duration) -> Option<f64> {
+    PRICE_CACHE
+        .get(mint)
+        .filter(|entry| entry.1.elapsed() <= ttl)
+        .map(|entry| entry.0)
+}
+
+/// Add a bought mint to the set refreshed by the background price task; it is dropped again
+/// once no position in it is open
+pub fn watch_price(mint: &str) {
+    WATCHED_MINTS.entry(mint.to_string()).or_insert(false);
+}
+
+/// Stop refreshing (and forget the price of) unpinned mints not in `open_mints`; returns how many
+pub fn prune_watched_mints(open_mints: &HashSet<String>) -> usize {
+    let before = WATCHED_MINTS.len();
+    WATCHED_MINTS.retain(|mint, pinned| {
+        let keep = *pinned || open_mints.contains(mint);
+        if !keep {
+            PRICE_CACHE.remove(mint);
+        }
+        keep
+    });
+    before - WATCHED_MINTS.len()
+}
+
+pub fn get_price_stats() -> (usize, usize, usize) {
+    (
+        PRICE_CACHE.len(),
+        PRICE_REQUESTS.load(Ordering::Relaxed),
+        PRICE_ERRORS.load(Ordering::Relaxed),
+    )
+}
+
+/// Wait until at least `min_interval` has passed since the previous Birdeye request
+async fn wait_for_rate_limit(min_interval: Duration) {
+    let mut last = LAST_REQUEST.lock().await;
+    if let Some(prev) = *last {
+        let elapsed = prev.elapsed();
+        if elapsed < min_interval {
+            tokio::time::sleep(min_interval - elapsed).await;
+        }
+    }
+    *last = Some(Instant::now());
+}
+
+/// Fetch USD prices for `mints`, serving fresh entries from the cache and
+/// requesting the rest in rate-limited batches
+pub async fn get_token_prices(
+    client: &Client,
+    api_key: &str,
+    mints: &[String],
+    ttl: Duration,
+    min_interval: Duration,
+) -> HashMap<String, f64> {
+    let mut prices = HashMap::new();
+    let mut missing = Vec::new();
+    for mint in mints {
+        match cached_price_usd(mint, ttl) {
+            Some(price) => {
+                prices.insert(mint.clone(), price);
+            }
+            None => missing.push(mint.clone()),
+        }
+    }
+
+    for batch in missing.chunks(BIRDEYE_MULTI_PRICE_BATCH) {
+        wait_for_rate_limit(min_interval).await;
+        PRICE_REQUESTS.fetch_add(1, Ordering::Relaxed);
+        match fetch_multi_price(client, api_key, batch).await {
+            Ok(fetched) => {
+                let now = Instant::now();
+                for (mint, price) in fetched {
+                    PRICE_CACHE.insert(mint.clone(), (price, now));
+                    prices.insert(mint, price);
+                }
+            }
+            Err(e) => {
+                PRICE_ERRORS.fetch_add(1, Ordering::Relaxed);
+                eprintln!("[BIRDEYE] Price request for {} mints failed: {}", batch.len(), e);
+            }
+        }
+    }
+    prices
+}
+
+async fn fetch_multi_price(client: &Client, api_key: &str, mints: &[String]) -> Result<HashMap<String, f64>, Box<dyn Error>> {
+    let res = client
+        .get(BIRDEYE_MULTI_PRICE_URL)
+        .query(&[("list_address", mints.join(","))])
+        .header("accept", "application/json")
+        .header("x-chain", "solana")
+        .header("X-API-KEY", api_key)
+        .send()
+        .await?;
+
+    let status = res.status();
+    let body = res.text().await?;
+    if !status.is_success() {
+        return Err(format!("Birdeye API Error: {} - {}", status, body).into());
+    }
+    parse_multi_price(&body)
+}
+
+/// Background task keeping prices of `mintsMonitor` and watched (bought) mints fresh
+pub async fn run_price_refresh(api_key: String, initial_mints: Vec<String>, ttl: Duration, min_interval: Duration) {
+    for mint in &initial_mints {
+        WATCHED_MINTS.insert(mint.clone(), true);
+    }
+    let client = Client::new();
+    let mut interval = tokio::time::interval(ttl);
+    loop {
+        interval.tick().await;
+        let open_mints: HashSet<String> = GLOBAL_TX_MAP.iter().map(|entry| entry.mint.to_string()).collect();
+        prune_watched_mints(&open_mints);
+        let mints: Vec<String> = WATCHED_MINTS.iter().map(|entry| entry.key().clone()).collect();
+        if mints.is_empty() {
+            continue;
+        }
+        // Entries just past their TTL are due, so every watched mint is refetched each tick
+        let _prices = get_token_prices(&client, &api_key, &mints, Duration::ZERO, min_interval).await;
+        #[cfg(feature = "verbose_logging")]
+        println!("[BIRDEYE] Refreshed {}/{} prices", _prices.len(), mints.len());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_multi_price() {
+        let body = r#"{
+            "success": true,
+            "data": {
+                "So11111111111111111111111111111111111111112": {"value": 187.42, "updateUnixTime": 1760600000, "priceChange24h": -1.2},
+                "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v": {"value": 0.9998, "updateUnixTime": 1760600000},
+                "unknownMint1111111111111111111111111111111": null
+            }
+        }"#;
+        let prices = parse_multi_price(body).unwrap();
+        assert_eq!(prices.len(), 2);
+        assert_eq!(prices["So11111111111111111111111111111111111111112"], 187.42);
+        assert_eq!(prices["EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"], 0.9998);
+        assert!(!prices.contains_key("unknownMint1111111111111111111111111111111"));
+
+        assert!(parse_multi_price(r#"{"success": false, "message": "Unauthorized"}"#).is_err());
+    }
+
+    #[test]
+    fn test_closed_positions_pruned_from_watch_list() {
+        let (pinned, open, closed) = ("PinnedMint", "OpenMint", "ClosedMint");
+        WATCHED_MINTS.insert(pinned.to_string(), true);
+        watch_price(open);
+        watch_price(closed);
+        PRICE_CACHE.insert(closed.to_string(), (1.0, Instant::now()));
+
+        let open_mints: HashSet<String> = [open.to_string()].into_iter().collect();
+        prune_watched_mints(&open_mints);
+        assert!(WATCHED_MINTS.contains_key(pinned));
+        assert!(WATCHED_MINTS.contains_key(open));
+        assert!(!WATCHED_MINTS.contains_key(closed));
+        assert_eq!(cached_price_usd(closed, Duration::from_secs(10)), None);
+    }
+
+    #[test]
+    fn test_cached_price_ttl() {
+        let mint = "CachedMint111111111111111111111111111111111";
+        assert_eq!(cached_price_usd(mint, Duration::from_secs(10)), None);
+        PRICE_CACHE.insert(mint.to_string(), (0.5, Instant::now()));
+        assert_eq!(cached_price_usd(mint, Duration::from_secs(10)), Some(0.5));
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(cached_price_usd(mint, Duration::from_millis(1)), None);
+    }
+}
@@ -0,0 +1,53 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+// Runtime switch for per-step send-path profiling (config `send_profiling`)
+static PROFILING_ENABLED: AtomicBool = AtomicBool::new(false);
+
+pub fn set_profiling_enabled(enabled: bool) {
+    PROFILING_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+#[inline]
+pub fn profiling_enabled() -> bool {
+    PROFILING_ENABLED.load(Ordering::Relaxed)
+}
+
+/// Step timer that only reads the clock when profiling is enabled; otherwise `elapsed()` is zero
+#[derive(Clone, Copy)]
+pub struct StepTimer(Option<Instant>);
+
+impl StepTimer {
+    #[inline]
+    pub fn start() -> Self {
+        if profiling_enabled() {
+            StepTimer(Some(Instant::now()))
+        } else {
+            StepTimer(None)
+        }
+    }
+
+    #[inline]
+    pub fn elapsed(&self) -> Duration {
+        self.0.map(|start| start.elapsed()).unwrap_or(Duration::ZERO)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step_timer_disabled_reads_no_clock() {
+        set_profiling_enabled(false);
+        let timer = StepTimer::start();
+        std::thread::sleep(Duration::from_millis(2));
+        assert_eq!(timer.elapsed(), Duration::ZERO);
+
+        set_profiling_enabled(true);
+        let timer = StepTimer::start();
+        std::thread::sleep(Duration::from_millis(2));
+        assert!(timer.elapsed() >= Duration::from_millis(2));
+        set_profiling_enabled(false);
+    }
+}
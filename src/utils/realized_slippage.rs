@@ -0,0 +1,189 @@
+// realized_slippage.rs
+// Realized slippage per fill: the amount our pre-send estimate expected out of a swap against what the
+// confirmed transaction actually delivered, kept as a rolling average per side for the stats line.
+
+use crate::init::initialize::GLOBAL_RPC_CLIENT;
+use crate::init::wallet_loader::get_wallet_keypair;
+use crate::triton_grpc::sell_fraction::holding_of;
+use crate::utils::logger::{log_event_cid, EventType};
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Signature, Signer};
+use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::UiTransactionEncoding;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// Fills kept per side for the rolling average
+const SLIPPAGE_WINDOW: usize = 100;
+// The fill's transaction is read at confirmed; poll until it shows up or give up
+const FETCH_ATTEMPTS: u32 = 10;
+const FETCH_INTERVAL: Duration = Duration::from_secs(2);
+// Fills waiting for the worker; further fills are skipped while it's full
+const FILL_QUEUE: usize = 256;
+
+/// Slippage of a fill in basis points: positive when `actual` fell short of `expected`, negative when
+/// it beat it. None without an estimate.
+pub fn realized_slippage_bps(expected: u64, actual: u64) -> Option<f64> {
+    if expected == 0 {
+        return None;
+    }
+    Some((expected as f64 - actual as f64) / expected as f64 * 10_000.0)
+}
+
+/// Last SLIPPAGE_WINDOW realized slippages of one side
+#[derive(Debug, Default)]
+pub struct SlippageWindow {
+    samples: VecDeque<f64>,
+}
+
+impl SlippageWindow {
+    pub fn record(&mut self, bps: f64) {
+        if self.samples.len() == SLIPPAGE_WINDOW {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(bps);
+    }
+
+    pub fn average(&self) -> Option<f64> {
+        (!self.samples.is_empty()).then(|| self.samples.iter().sum::<f64>() / self.samples.len() as f64)
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+}
+
+// (buys, sells)
+static WINDOWS: Lazy<Mutex<(SlippageWindow, SlippageWindow)>> = Lazy::new(|| Mutex::new(Default::default()));
+
+/// (buy fills, average buy slippage bps, sell fills, average sell slippage bps) over the recent window
+pub fn get_realized_slippage_stats() -> (usize, Option<f64>, usize, Option<f64>) {
+    let windows = WINDOWS.lock().unwrap_or_else(|e| e.into_inner());
+    (windows.0.len(), windows.0.average(), windows.1.len(), windows.1.average())
+}
+
+/// What our fill `sig` actually delivered to the wallet: `mint` tokens received for a buy, SOL
+/// received for a sell (before the fee and `tip_lamports`, which the estimate doesn't cover)
+fn fetch_actual_output(sig: &Signature, side: &str, mint: &Pubkey, tip_lamports: u64) -> Option<u64> {
+    let rpc = GLOBAL_RPC_CLIENT.get()?;
+    let config = RpcTransactionConfig {
+        encoding: Some(UiTransactionEncoding::Base64),
+        commitment: Some(CommitmentConfig::confirmed()),
+        max_supported_transaction_version: Some(0),
+    };
+    let tx = (0..FETCH_ATTEMPTS).find_map(|attempt| {
+        if attempt > 0 {
+            std::thread::sleep(FETCH_INTERVAL);
+        }
+        rpc.get_transaction_with_config(sig, config).ok()
+    })?;
+    let meta = tx.transaction.meta?;
+    if meta.err.is_some() {
+        return None;
+    }
+    let wallet = get_wallet_keypair().pubkey();
+    if side == "buy" {
+        return match (&meta.pre_token_balances, &meta.post_token_balances) {
+            (OptionSerializer::Some(pre), OptionSerializer::Some(post)) => {
+                let (owner, mint) = (wallet.to_string(), mint.to_string());
+                holding_of(post, &owner, &mint).checked_sub(holding_of(pre, &owner, &mint))
+            }
+            _ => None,
+        };
+    }
+    let decoded = tx.transaction.transaction.decode()?;
+    let keys = decoded.message.static_account_keys();
+    let index = keys.iter().position(|key| *key == wallet)?;
+    let fee = if index == 0 { meta.fee } else { 0 };
+    let received = meta.post_balances.get(index)? + fee + tip_lamports;
+    received.checked_sub(*meta.pre_balances.get(index)?)
+}
+
+struct Fill {
+    side: &'static str,
+    signature: Signature,
+    mint: Pubkey,
+    expected: u64,
+    tip_lamports: u64,
+    corr_id: String,
+    started: Instant,
+}
+
+// One background worker reads the fills in order, so a burst of fills can't pile up threads
+static FILL_SENDER: Lazy<crossbeam::channel::Sender<Fill>> = Lazy::new(|| {
+    let (sender, receiver) = crossbeam::channel::bounded::<Fill>(FILL_QUEUE);
+    std::thread::Builder::new()
+        .name("realized-slippage".to_string())
+        .spawn(move || {
+            for fill in receiver {
+                measure_fill(fill);
+            }
+        })
+        .expect("Failed to spawn realized slippage worker");
+    sender
+});
+
+/// In the background, read our confirmed fill `sig` and record its realized slippage against
+/// `expected` (tokens for a buy, lamports for a sell), in the trade log and the rolling average
+pub fn record_fill(side: &'static str, sig: &str, mint: Pubkey, expected: u64, tip_lamports: u64, corr_id: String) {
+    let signature = match sig.trim_matches('"').parse::<Signature>() {
+        Ok(signature) => signature,
+        Err(_) => return,
+    };
+    let fill = Fill { side, signature, mint, expected, tip_lamports, corr_id, started: Instant::now() };
+    if FILL_SENDER.try_send(fill).is_err() {
+        println!("[{}] - [SLIPPAGE] {} fill {} skipped, {} fills already queued",
+            Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), side, signature, FILL_QUEUE);
+    }
+}
+
+fn measure_fill(fill: Fill) {
+    let Fill { side, signature, mint, expected, tip_lamports, corr_id, started } = fill;
+    let actual = match fetch_actual_output(&signature, side, &mint, tip_lamports) {
+        Some(actual) => actual,
+        None => return,
+    };
+    let bps = match realized_slippage_bps(expected, actual) {
+        Some(bps) => bps,
+        None => return,
+    };
+    {
+        let mut windows = WINDOWS.lock().unwrap_or_else(|e| e.into_inner());
+        let window = if side == "sell" { &mut windows.1 } else { &mut windows.0 };
+        window.record(bps);
+    }
+    println!("[{}] - [SLIPPAGE] [cid={}] {} {} expected {} got {}: realized slippage {:.1} bps",
+        Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), corr_id, side, mint, expected, actual, bps);
+    log_event_cid(EventType::Custom(format!("RealizedSlippage {} {:.1}bps", side, bps)), signature.as_ref(), started, None, &corr_id);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_realized_slippage() {
+        // Expected 1M tokens, got 985k: 1.5% short
+        assert!((realized_slippage_bps(1_000_000, 985_000).unwrap() - 150.0).abs() < 1e-9);
+        // Filled better than quoted
+        assert!((realized_slippage_bps(1_000_000, 1_010_000).unwrap() + 100.0).abs() < 1e-9);
+        assert_eq!(realized_slippage_bps(0, 5), None);
+
+        let mut window = SlippageWindow::default();
+        assert_eq!(window.average(), None);
+        window.record(150.0);
+        window.record(50.0);
+        assert_eq!(window.average(), Some(100.0));
+        for _ in 0..SLIPPAGE_WINDOW {
+            window.record(10.0);
+        }
+        // The early outliers rolled out
+        assert_eq!(window.len(), SLIPPAGE_WINDOW);
+        assert_eq!(window.average(), Some(10.0));
+    }
+}
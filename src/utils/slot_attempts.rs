@@ -0,0 +1,107 @@
+// slot_attempts.rs
+// Buy / sell send attempts per network slot over a short rolling window, to spot self-induced bursts
+// (many attempts in one slot contending for the same nonces and connections).
+
+use crate::config_load::GLOBAL_CONFIG;
+use crate::grpc::arpc_worker::get_current_slot;
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// Attempts made while the feeds were at `slot`
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SlotBucket {
+    pub slot: u64,
+    pub buys: usize,
+    pub sells: usize,
+}
+
+impl SlotBucket {
+    pub fn total(&self) -> usize {
+        self.buys + self.sells
+    }
+}
+
+/// Per-slot buckets for the last `capacity` slots that saw an attempt, oldest first
+#[derive(Debug, Default)]
+pub struct SlotAttempts {
+    buckets: VecDeque<SlotBucket>,
+    capacity: usize,
+}
+
+impl SlotAttempts {
+    pub fn new(capacity: usize) -> Self {
+        SlotAttempts { buckets: VecDeque::with_capacity(capacity), capacity }
+    }
+
+    /// Count a `side` ("buy" / "sell") attempt at `slot`. An attempt stamped with a slot older than the
+    /// newest bucket (a feed lagging behind) joins its bucket if still kept, else is dropped.
+    pub fn record(&mut self, slot: u64, side: &str) -> Option<SlotBucket> {
+        if self.capacity == 0 {
+            return None;
+        }
+        let newest = self.buckets.back().map_or(0, |bucket| bucket.slot);
+        if self.buckets.is_empty() || slot > newest {
+            self.buckets.push_back(SlotBucket { slot, ..Default::default() });
+            while self.buckets.len() > self.capacity {
+                self.buckets.pop_front();
+            }
+        }
+        let bucket = self.buckets.iter_mut().rev().find(|bucket| bucket.slot == slot)?;
+        match side {
+            "sell" => bucket.sells += 1,
+            _ => bucket.buys += 1,
+        }
+        Some(*bucket)
+    }
+
+    /// Kept buckets, oldest first
+    pub fn recent(&self) -> Vec<SlotBucket> {
+        self.buckets.iter().copied().collect()
+    }
+}
+
+static SLOT_ATTEMPTS: Lazy<Mutex<SlotAttempts>> = Lazy::new(|| {
+    Mutex::new(SlotAttempts::new(GLOBAL_CONFIG.get().map_or(0, |c| c.slot_attempt_history)))
+});
+
+/// Count one send attempt at the current feed slot (no-op when `slot_attempt_history` is 0)
+pub fn record_attempt(side: &str, corr_id: &str) {
+    let slot = get_current_slot();
+    let bucket = SLOT_ATTEMPTS.lock().unwrap_or_else(|e| e.into_inner()).record(slot, side);
+    if let Some(bucket) = bucket {
+        if GLOBAL_CONFIG.get().map_or(false, |c| c.log_slot_attempts) {
+            println!("[{}] - [SLOT_ATTEMPTS] [cid={}] {} attempt in slot {} (slot so far: {} buys, {} sells)",
+                Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), corr_id, side, slot, bucket.buys, bucket.sells);
+        }
+    }
+}
+
+/// Per-slot attempt counts for the recent slots that saw any, oldest first
+pub fn get_recent_slot_attempts() -> Vec<SlotBucket> {
+    SLOT_ATTEMPTS.lock().unwrap_or_else(|e| e.into_inner()).recent()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attempts_bucketed_per_slot() {
+        let mut attempts = SlotAttempts::new(3);
+        // (feed slot at the time of the attempt, side)
+        let sequence = [(100, "buy"), (100, "buy"), (100, "sell"), (101, "buy"), (103, "sell"), (101, "sell"), (104, "buy")];
+        for (slot, side) in sequence {
+            attempts.record(slot, side);
+        }
+        let bucket = |slot, buys, sells| SlotBucket { slot, buys, sells };
+        // Slot 100 rolled out; the late slot-101 sell still joined its bucket
+        assert_eq!(attempts.recent(), vec![bucket(101, 1, 1), bucket(103, 0, 1), bucket(104, 1, 0)]);
+        // Too old to be kept: dropped
+        assert_eq!(attempts.record(100, "buy"), None);
+        assert_eq!(attempts.recent().iter().map(SlotBucket::total).sum::<usize>(), 4);
+
+        assert_eq!(SlotAttempts::new(0).record(5, "buy"), None);
+    }
+}
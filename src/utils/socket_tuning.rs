@@ -0,0 +1,93 @@
+// Socket options for the vendor and feed connections. Disabling Nagle (TCP_NODELAY) keeps a
+// small send from waiting up to one RTT/ACK-delay (~40ms on Linux) behind an unacknowledged
+// segment; keepalives stop idle pooled connections from being silently dropped by NATs, which
+// would otherwise cost a fresh TCP+TLS handshake on the next send. tonic, reqwest and isahc
+// don't expose SO_SNDBUF/SO_RCVBUF, so buffer sizes stay at the kernel defaults.
+use std::time::Duration;
+use tonic::transport::Endpoint;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SocketTuning {
+    pub tcp_nodelay: bool,
+    pub tcp_keepalive: Option<Duration>,        // None keeps each client's own keepalive
+    pub http2_keepalive_interval: Option<Duration>, // gRPC channels only
+}
+
+impl SocketTuning {
+    fn resolve(tcp_nodelay: bool, tcp_keepalive_secs: u64, http2_keepalive_secs: u64) -> Self {
+        let secs = |s: u64| (s > 0).then(|| Duration::from_secs(s));
+        SocketTuning {
+            tcp_nodelay,
+            tcp_keepalive: secs(tcp_keepalive_secs),
+            http2_keepalive_interval: secs(http2_keepalive_secs),
+        }
+    }
+}
+
+/// Socket tuning from config (TCP_NODELAY on, client keepalives kept when unset)
+pub fn socket_tuning() -> SocketTuning {
+    crate::config_load::GLOBAL_CONFIG.get().map_or(SocketTuning::resolve(true, 0, 0), |c| {
+        SocketTuning::resolve(c.tcp_nodelay, c.tcp_keepalive_secs, c.http2_keepalive_interval_secs)
+    })
+}
+
+/// Apply socket tuning to a gRPC endpoint
+pub fn tune_endpoint(endpoint: Endpoint, tuning: SocketTuning) -> Endpoint {
+    let mut endpoint = endpoint.tcp_nodelay(tuning.tcp_nodelay);
+    if let Some(keepalive) = tuning.tcp_keepalive {
+        endpoint = endpoint.tcp_keepalive(Some(keepalive));
+    }
+    if let Some(interval) = tuning.http2_keepalive_interval {
+        endpoint = endpoint.http2_keep_alive_interval(interval).keep_alive_while_idle(true);
+    }
+    endpoint
+}
+
+/// gRPC endpoint for `address` with the configured socket tuning
+pub fn tuned_endpoint(address: &str) -> Result<Endpoint, tonic::transport::Error> {
+    Ok(tune_endpoint(Endpoint::from_shared(address.to_string())?, socket_tuning()))
+}
+
+/// Apply socket tuning to a reqwest client builder
+pub fn tune_reqwest(builder: reqwest::ClientBuilder, tuning: SocketTuning) -> reqwest::ClientBuilder {
+    let builder = builder.tcp_nodelay(tuning.tcp_nodelay);
+    match tuning.tcp_keepalive {
+        Some(keepalive) => builder.tcp_keepalive(Some(keepalive)),
+        None => builder,
+    }
+}
+
+/// Apply socket tuning to an isahc client builder
+pub fn tune_isahc(builder: isahc::HttpClientBuilder, tuning: SocketTuning) -> isahc::HttpClientBuilder {
+    use isahc::config::Configurable;
+    let builder = if tuning.tcp_nodelay { builder.tcp_nodelay() } else { builder };
+    match tuning.tcp_keepalive {
+        Some(keepalive) => builder.tcp_keepalive(keepalive),
+        None => builder,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_socket_tuning() {
+        let tuning = SocketTuning::resolve(true, 15, 0);
+        assert!(tuning.tcp_nodelay);
+        assert_eq!(tuning.tcp_keepalive, Some(Duration::from_secs(15)));
+        assert_eq!(tuning.http2_keepalive_interval, None);
+        assert_eq!(SocketTuning::resolve(false, 0, 10).tcp_keepalive, None);
+        // Defaults before config is loaded
+        assert_eq!(socket_tuning(), SocketTuning::resolve(true, 0, 0));
+    }
+
+    #[tokio::test]
+    async fn test_tuned_clients_build() {
+        let tuning = SocketTuning::resolve(true, 15, 10);
+        let endpoint = tune_endpoint(Endpoint::from_static("http://127.0.0.1:1"), tuning);
+        let _channel = endpoint.connect_lazy();
+        assert!(tune_reqwest(reqwest::Client::builder(), tuning).build().is_ok());
+        assert!(tune_isahc(isahc::HttpClient::builder(), tuning).build().is_ok());
+    }
+}
@@ -0,0 +1,92 @@
+// state_dump.rs
+// On-demand JSON snapshot of the in-memory maps (control command `dump [n]`), for debugging
+// stuck positions and dedup anomalies without attaching a debugger.
+
+use crate::grpc::arpc_parser::{dedup_snapshot, get_dedup_stats};
+use crate::grpc::arpc_worker::{TxWithPubkey, GLOBAL_TX_MAP};
+use crate::grpc::monitoring_client::GLOBAL_MONITORING_DATA;
+use crate::triton_grpc::crossbeam_worker::{feed_dedup_snapshot, get_feed_dedup_stats};
+use serde_json::{json, Value};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn tx_entry_json(key: &[u8], tx: &TxWithPubkey) -> Value {
+    json!({
+        "key": std::str::from_utf8(key)
+            .ok()
+            .filter(|k| k.starts_with("reconcile:"))
+            .map_or_else(|| bs58::encode(key).into_string(), str::to_string),
+        "corr_id": tx.corr_id,
+        "mint": tx.mint.to_string(),
+        "tx_type": tx.tx_type,
+        "token_amount": tx.token_amount,
+        "open_position": tx.open_position,
+        "send_sig": tx.send_sig,
+        "send_slot": tx.send_slot,
+        "age_ms": tx.created_at.elapsed().as_millis() as u64,
+        "since_send_ms": tx.send_time.elapsed().as_millis() as u64,
+        "vendors": tx.vendor_transactions.iter().map(|(vendor, _)| vendor.as_str()).collect::<Vec<_>>(),
+        "user_ata": tx.user_ata.map(|ata| json!({ "address": ata.address.to_string(), "exists": ata.exists })),
+        "entry_price_usd": tx.entry_price_usd,
+    })
+}
+
+/// Snapshot of GLOBAL_TX_MAP, GLOBAL_MONITORING_DATA and both dedup maps, at most `limit` entries
+/// each (with the full size alongside)
+pub fn dump_state(limit: usize) -> Value {
+    let now_unix = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let tx_map: Vec<Value> = GLOBAL_TX_MAP.iter().take(limit).map(|e| tx_entry_json(e.key(), e.value())).collect();
+    let monitoring: Vec<Value> = GLOBAL_MONITORING_DATA
+        .iter()
+        .take(limit)
+        .map(|e| json!({
+            "mint": e.value().mint_pubkey.to_string(),
+            "timestamp": e.value().timestamp,
+            "age_secs": now_unix.saturating_sub(e.value().timestamp),
+        }))
+        .collect();
+    let arpc_dedup: Vec<Value> = dedup_snapshot(limit)
+        .into_iter()
+        .map(|(sig, seen)| json!({ "sig": sig, "age_secs": now_unix.saturating_sub(seen) }))
+        .collect();
+    let feed_dedup: Vec<Value> = feed_dedup_snapshot(limit)
+        .into_iter()
+        .map(|(sig, feed, age)| json!({ "sig": sig, "feed": feed, "age_ms": age.as_millis() as u64 }))
+        .collect();
+    json!({
+        "tx_map": { "size": GLOBAL_TX_MAP.len(), "entries": tx_map },
+        "monitoring": { "size": GLOBAL_MONITORING_DATA.len(), "entries": monitoring },
+        "arpc_dedup": { "size": get_dedup_stats(), "entries": arpc_dedup },
+        "feed_dedup": { "size": get_feed_dedup_stats().0, "entries": feed_dedup },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::pubkey::Pubkey;
+
+    #[test]
+    fn test_dump_populated_map() {
+        let mut tx = TxWithPubkey::default();
+        tx.mint = Pubkey::new_unique();
+        tx.tx_type = "pump_swap".to_string();
+        tx.corr_id = "dump-test".to_string();
+        tx.open_position = true;
+        tx.vendor_transactions.push(("zeroslot".to_string(), Default::default()));
+        let key = vec![7u8; 64];
+        GLOBAL_TX_MAP.insert(key.clone(), tx.clone());
+        GLOBAL_TX_MAP.insert(b"reconcile:test".to_vec(), tx.clone());
+
+        let dump = dump_state(usize::MAX);
+        let text = serde_json::to_string(&dump).unwrap();
+        let entries = dump["tx_map"]["entries"].as_array().unwrap();
+        let entry = entries.iter().find(|e| e["key"] == bs58::encode(&key).into_string()).unwrap();
+        assert_eq!(entry["mint"], tx.mint.to_string());
+        assert_eq!(entry["vendors"][0], "zeroslot");
+        assert!(entries.iter().any(|e| e["key"] == "reconcile:test"));
+        assert!(text.contains("\"feed_dedup\""));
+
+        GLOBAL_TX_MAP.remove(&key);
+        GLOBAL_TX_MAP.remove(&b"reconcile:test".to_vec());
+    }
+}
@@ -0,0 +1,185 @@
+use chrono::Utc;
+use once_cell::sync::{Lazy, OnceCell};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+// Worker slots per pool, including replacements spawned for stuck workers
+const MAX_WORKERS: usize = 16;
+// Marks a worker blocked waiting for its next message
+const IDLE: u64 = 0;
+
+static STARTED: Lazy<Instant> = Lazy::new(Instant::now);
+
+fn now_ms() -> u64 {
+    // +1 so a busy mark is never IDLE
+    STARTED.elapsed().as_millis() as u64 + 1
+}
+
+/// Progress marks of one worker thread pool, checked by the watchdog
+pub struct WorkerPool {
+    pub name: &'static str,
+    busy_since_ms: [AtomicU64; MAX_WORKERS], // IDLE, or when the current message was picked up
+    alerted: [AtomicBool; MAX_WORKERS],
+    spawned: AtomicUsize,
+    respawn: OnceCell<Box<dyn Fn(usize) + Send + Sync>>,
+}
+
+pub static TRITON_WORKERS: WorkerPool = WorkerPool::new("triton");
+pub static ARPC_WORKERS: WorkerPool = WorkerPool::new("arpc");
+
+/// Marks its worker idle again when the loop iteration ends, however it ends
+pub struct BusyGuard<'a> {
+    pool: &'a WorkerPool,
+    worker_id: usize,
+}
+
+impl Drop for BusyGuard<'_> {
+    fn drop(&mut self) {
+        self.pool.busy_since_ms[self.worker_id].store(IDLE, Ordering::Relaxed);
+    }
+}
+
+impl WorkerPool {
+    pub const fn new(name: &'static str) -> Self {
+        WorkerPool {
+            name,
+            busy_since_ms: [const { AtomicU64::new(IDLE) }; MAX_WORKERS],
+            alerted: [const { AtomicBool::new(false) }; MAX_WORKERS],
+            spawned: AtomicUsize::new(0),
+            respawn: OnceCell::new(),
+        }
+    }
+
+    /// Spawn the pool's `workers` threads through `spawn`, which the watchdog reuses for replacements
+    pub fn start(&self, workers: usize, spawn: impl Fn(usize) + Send + Sync + 'static) {
+        for worker_id in 0..workers.min(MAX_WORKERS) {
+            spawn(worker_id);
+        }
+        self.spawned.store(workers.min(MAX_WORKERS), Ordering::Relaxed);
+        let _ = self.respawn.set(Box::new(spawn));
+    }
+
+    /// Called at the top of each loop iteration; the worker counts as making progress until the guard drops
+    pub fn busy(&self, worker_id: usize) -> BusyGuard<'_> {
+        let worker_id = worker_id.min(MAX_WORKERS - 1);
+        self.busy_since_ms[worker_id].store(now_ms(), Ordering::Relaxed);
+        BusyGuard { pool: self, worker_id }
+    }
+
+    /// How long each worker has been on its current message (None = idle)
+    pub fn progress_ages(&self) -> Vec<Option<Duration>> {
+        let now = now_ms();
+        self.busy_since_ms[..self.spawned.load(Ordering::Relaxed)]
+            .iter()
+            .map(|since| match since.load(Ordering::Relaxed) {
+                IDLE => None,
+                since => Some(Duration::from_millis(now.saturating_sub(since))),
+            })
+            .collect()
+    }
+
+    /// Workers on the same message for longer than `timeout`
+    pub fn stuck_workers(&self, timeout: Duration) -> Vec<(usize, Duration)> {
+        self.progress_ages()
+            .into_iter()
+            .enumerate()
+            .filter_map(|(worker_id, age)| age.filter(|age| *age > timeout).map(|age| (worker_id, age)))
+            .collect()
+    }
+
+    /// Alert once on each newly stuck worker (and spawn a replacement if `respawn`), note recovered ones
+    fn check(&self, timeout: Duration, respawn: bool) {
+        let stuck = self.stuck_workers(timeout);
+        for worker_id in 0..self.spawned.load(Ordering::Relaxed) {
+            let age = stuck.iter().find(|(id, _)| *id == worker_id).map(|(_, age)| *age);
+            let was_alerted = self.alerted[worker_id].swap(age.is_some(), Ordering::Relaxed);
+            match (age, was_alerted) {
+                (Some(age), false) => {
+                    eprintln!("[{}] - [WATCHDOG] !!! {} worker {} STUCK: no progress for {:.1?} (timeout {:?})",
+                        Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), self.name, worker_id, age, timeout);
+                    if respawn {
+                        self.spawn_replacement(worker_id);
+                    }
+                }
+                (None, true) => {
+                    println!("[{}] - [WATCHDOG] {} worker {} is making progress again",
+                        Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), self.name, worker_id);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn spawn_replacement(&self, stuck_id: usize) {
+        let spawn = match self.respawn.get() {
+            Some(spawn) => spawn,
+            None => return,
+        };
+        let worker_id = self.spawned.load(Ordering::Relaxed);
+        if worker_id >= MAX_WORKERS {
+            eprintln!("[WATCHDOG] {} pool at {} workers, not replacing stuck worker {}", self.name, MAX_WORKERS, stuck_id);
+            return;
+        }
+        spawn(worker_id);
+        self.spawned.store(worker_id + 1, Ordering::Relaxed);
+        println!("[{}] - [WATCHDOG] Spawned {} worker {} to replace stuck worker {}",
+            Utc::now().format("%Y-%m-%d %H:%M:%S%.3f"), self.name, worker_id, stuck_id);
+    }
+
+    /// e.g. `0=idle 1=0.2s 2=STUCK 45.1s`
+    pub fn progress_line(&self, timeout: Duration) -> String {
+        self.progress_ages()
+            .iter()
+            .enumerate()
+            .map(|(worker_id, age)| match age {
+                None => format!("{}=idle", worker_id),
+                Some(age) if !timeout.is_zero() && *age > timeout => format!("{}=STUCK {:.1?}", worker_id, age),
+                Some(age) => format!("{}={:.1?}", worker_id, age),
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Check both worker pools every quarter timeout (at least once a second)
+pub fn start_watchdog(timeout: Duration, respawn: bool) {
+    let interval = (timeout / 4).max(Duration::from_secs(1));
+    std::thread::spawn(move || loop {
+        std::thread::sleep(interval);
+        TRITON_WORKERS.check(timeout, respawn);
+        ARPC_WORKERS.check(timeout, respawn);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    #[test]
+    fn test_stuck_worker_detected() {
+        static POOL: WorkerPool = WorkerPool::new("test");
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        let release_rx = std::sync::Mutex::new(Some(release_rx));
+        POOL.start(2, move |worker_id| {
+            if worker_id == 1 {
+                // Worker 1 picks up a message and hangs on it until released
+                let release_rx = release_rx.lock().unwrap().take().unwrap();
+                std::thread::spawn(move || {
+                    let _busy = POOL.busy(1);
+                    let _ = release_rx.recv();
+                });
+            }
+        });
+        std::thread::sleep(Duration::from_millis(150));
+
+        let stuck = POOL.stuck_workers(Duration::from_millis(50));
+        assert_eq!(stuck.len(), 1);
+        assert_eq!(stuck[0].0, 1);
+        assert!(POOL.progress_line(Duration::from_millis(50)).starts_with("0=idle 1=STUCK"));
+
+        release_tx.send(()).unwrap();
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(POOL.stuck_workers(Duration::from_millis(50)).is_empty());
+    }
+}